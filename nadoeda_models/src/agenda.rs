@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+
+use crate::reminder::ReminderId;
+
+/// When a reminder is going to fire today.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgendaTime {
+    At(DateTime<Utc>),
+    Between(DateTime<Utc>, DateTime<Utc>),
+    /// Fires once another reminder is confirmed, so the time is not known in advance.
+    AfterPredecessor,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgendaItem {
+    pub reminder_id: ReminderId,
+    pub text: String,
+    pub time: AgendaTime,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SummaryOutcome {
+    Completed,
+    TimedOut,
+    Skipped,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SummaryItem {
+    pub reminder_id: ReminderId,
+    pub text: String,
+    pub outcome: SummaryOutcome,
+}
+
+/// Messages sent to the user once a day, not tied to a single reminder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DailyMessage {
+    /// Reminders due today, ordered by time.
+    Agenda(Vec<AgendaItem>),
+    /// What happened to today's reminders.
+    Summary(Vec<SummaryItem>),
+}
@@ -0,0 +1,32 @@
+use crate::{reminder::ReminderId, user::UserId};
+
+pub type ReminderBuddyId = i64;
+
+/// Who gets escalated to when a reminder times out.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BuddyTarget {
+    User(UserId),
+    TelegramChat(i64),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BuddyInvitationStatus {
+    Pending,
+    Accepted,
+    Declined,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReminderBuddy {
+    pub id: ReminderBuddyId,
+    pub reminder_id: ReminderId,
+    pub user_id: UserId,
+    pub target: BuddyTarget,
+    pub status: BuddyInvitationStatus,
+}
+
+impl ReminderBuddy {
+    pub fn is_accepted(&self) -> bool {
+        self.status == BuddyInvitationStatus::Accepted
+    }
+}
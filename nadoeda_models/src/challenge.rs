@@ -0,0 +1,12 @@
+use chrono::{DateTime, Utc};
+
+use crate::{reminder::ReminderId, user::UserId};
+
+/// A single answer given to a confirmation challenge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChallengeAttempt {
+    pub reminder_id: ReminderId,
+    pub user_id: UserId,
+    pub correct: bool,
+    pub answered_at: DateTime<Utc>,
+}
@@ -0,0 +1,18 @@
+use chrono::{DateTime, Utc};
+
+use crate::{occurrence::ReminderOccurrenceId, reminder::ReminderId, user::UserId};
+
+pub type ReminderEvidenceId = i64;
+
+/// Proof submitted by the user when confirming a reminder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReminderEvidence {
+    pub id: ReminderEvidenceId,
+    pub reminder_id: ReminderId,
+    /// The firing the proof confirmed, `None` for proof older than occurrence tracking.
+    pub occurrence_id: Option<ReminderOccurrenceId>,
+    pub user_id: UserId,
+    pub photo_file_id: Option<String>,
+    pub text: Option<String>,
+    pub submitted_at: DateTime<Utc>,
+}
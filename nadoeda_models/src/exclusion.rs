@@ -0,0 +1,15 @@
+use chrono::NaiveDate;
+
+use crate::user::UserId;
+
+pub type ExclusionDateId = i64;
+
+/// A day in the user's local calendar on which no daily reminder fires.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExclusionDate {
+    pub id: ExclusionDateId,
+    pub user_id: UserId,
+    pub date: NaiveDate,
+    /// Holiday or vacation name, if known.
+    pub label: Option<String>,
+}
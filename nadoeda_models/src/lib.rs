@@ -1,5 +1,13 @@
+pub mod agenda;
+pub mod buddy;
+pub mod challenge;
+pub mod evidence;
+pub mod exclusion;
+pub mod occurrence;
 pub mod reminder;
 pub mod settings;
+pub mod stats;
+pub mod template;
 pub mod user;
 
 pub use chrono;
@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+
+use crate::{reminder::ReminderId, user::UserId};
+
+pub type ReminderOccurrenceId = i64;
+
+/// How a single firing of a reminder ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OccurrenceOutcome {
+    Confirmed,
+    TimedOut,
+    Cancelled,
+    Snoozed,
+}
+
+/// A single firing of a reminder, from scheduling until its outcome is known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReminderOccurrence {
+    pub id: ReminderOccurrenceId,
+    pub reminder_id: ReminderId,
+    pub user_id: UserId,
+    pub scheduled_at: DateTime<Utc>,
+    pub fired_at: Option<DateTime<Utc>>,
+    pub acknowledged_at: Option<DateTime<Utc>>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    /// `None` while the occurrence is still in progress.
+    pub outcome: Option<OccurrenceOutcome>,
+}
+
+impl ReminderOccurrence {
+    pub fn is_finished(&self) -> bool {
+        self.outcome.is_some()
+    }
+
+    /// Acknowledged and still waiting for the user to confirm it.
+    pub fn is_confirming(&self) -> bool {
+        self.acknowledged_at.is_some() && !self.is_finished()
+    }
+}
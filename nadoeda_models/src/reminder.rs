@@ -1,4 +1,4 @@
-use chrono::{NaiveTime, Timelike, Utc};
+use chrono::{NaiveTime, TimeDelta, Timelike, Utc};
 
 use crate::user::UserId;
 
@@ -10,6 +10,68 @@ pub enum ReminderState {
     Confirming { attempts_left: u8 },
 }
 
+/// How many times the user is nagged after a reminder fires.
+pub const NAGGING_ATTEMPTS: u8 = 10;
+/// How many times the user is asked to confirm an acknowledged reminder.
+pub const CONFIRMATION_ATTEMPTS: u8 = 10;
+
+impl ReminderState {
+    /// Nags or confirmation requests already sent in the current state.
+    pub fn attempts_made(&self) -> u8 {
+        match self {
+            Self::Nagging { attempts_left } => NAGGING_ATTEMPTS.saturating_sub(*attempts_left),
+            Self::Confirming { attempts_left } => {
+                CONFIRMATION_ATTEMPTS.saturating_sub(*attempts_left)
+            }
+            Self::Pending | Self::Scheduled => 0,
+        }
+    }
+}
+
+/// What the user has to send to prove that the reminder was actually done.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ReminderVerification {
+    #[default]
+    None,
+    Photo,
+    Text,
+    Keyword(String),
+}
+
+impl ReminderVerification {
+    pub fn requires_evidence(&self) -> bool {
+        !matches!(self, Self::None)
+    }
+}
+
+/// Puzzle the user has to solve before a reminder can be confirmed.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum ConfirmationChallenge {
+    #[default]
+    None,
+    Arithmetic,
+    RetypeWord,
+    PickOption,
+}
+
+/// Makes a reminder wait for another one to be confirmed instead of firing at its own time.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ReminderDependency {
+    pub predecessor_id: ReminderId,
+    /// How long to wait after the predecessor is confirmed.
+    pub delay: TimeDelta,
+}
+
+pub type ChecklistItemId = i64;
+
+/// One step of a checklist reminder. Ticks are reset every time the reminder fires.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecklistItem {
+    pub id: ChecklistItemId,
+    pub text: String,
+    pub checked: bool,
+}
+
 pub enum ReminderFiringPeriod {
     OneOff,
     Daily,
@@ -17,7 +79,7 @@ pub enum ReminderFiringPeriod {
 
 pub type ReminderId = i64;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ReminderFireTime(chrono::NaiveTime);
 
 impl ReminderFireTime {
@@ -76,6 +138,36 @@ pub struct Reminder {
     pub id: ReminderId,
     pub state: ReminderState,
     pub fire_at: ReminderFireTime,
+    /// When set, the reminder fires at a random moment between `fire_at` and this time.
+    pub fire_window_end: Option<ReminderFireTime>,
     pub text: String,
     pub user_id: UserId,
+    pub verification: ReminderVerification,
+    pub challenge: ConfirmationChallenge,
+    pub dependency: Option<ReminderDependency>,
+    /// Ordered steps that all have to be ticked before the reminder counts as done.
+    pub checklist: Vec<ChecklistItem>,
+    /// Grows with every update, an update of an outdated copy is rejected.
+    pub version: i64,
+}
+
+impl Reminder {
+    /// Length of the random fire window, zero for reminders firing at a fixed time.
+    /// Windows that end before they start wrap around midnight.
+    pub fn fire_window(&self) -> TimeDelta {
+        let Some(end) = &self.fire_window_end else {
+            return TimeDelta::zero();
+        };
+
+        let width = *end.time() - *self.fire_at.time();
+        if width < TimeDelta::zero() {
+            width + TimeDelta::days(1)
+        } else {
+            width
+        }
+    }
+
+    pub fn is_checklist_done(&self) -> bool {
+        self.checklist.iter().all(|item| item.checked)
+    }
 }
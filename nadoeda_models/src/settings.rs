@@ -5,7 +5,67 @@ pub struct TelegramSettings {
     pub token: String,
 }
 
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct DatabaseSettings {
+    pub url: String,
+    pub max_connections: u32,
+    /// How long a connection waits for a locked database before giving up.
+    pub busy_timeout_ms: u64,
+    /// Use write-ahead logging so readers don't block the writer.
+    pub wal: bool,
+}
+
+impl Default for DatabaseSettings {
+    fn default() -> Self {
+        Self {
+            url: "sqlite://nadoeda.db".to_string(),
+            max_connections: 5,
+            busy_timeout_ms: 5000,
+            wal: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatabaseBackend {
+    Sqlite,
+    Postgres,
+    /// Keeps everything in process memory, nothing survives a restart.
+    Memory,
+}
+
+impl DatabaseSettings {
+    /// The backend is picked by the scheme of the URL, `None` for unknown schemes.
+    pub fn backend(&self) -> Option<DatabaseBackend> {
+        let (scheme, _) = self.url.split_once(':')?;
+        match scheme {
+            "sqlite" => Some(DatabaseBackend::Sqlite),
+            "postgres" | "postgresql" => Some(DatabaseBackend::Postgres),
+            "memory" => Some(DatabaseBackend::Memory),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct ArchiveSettings {
+    /// Archived reminders older than this are deleted for good.
+    pub retention_days: u32,
+}
+
+impl Default for ArchiveSettings {
+    fn default() -> Self {
+        Self { retention_days: 90 }
+    }
+}
+
 #[derive(Deserialize, Debug)]
 pub struct Settings {
     pub telegram: TelegramSettings,
+    #[serde(default)]
+    pub database: DatabaseSettings,
+    #[serde(default)]
+    pub archive: ArchiveSettings,
 }
@@ -0,0 +1,28 @@
+use chrono::TimeDelta;
+
+use crate::reminder::ReminderId;
+
+/// Share of finished occurrences that were confirmed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompletionRate {
+    pub confirmed: u32,
+    pub total: u32,
+}
+
+impl CompletionRate {
+    /// Returns the rate in whole percent, or `None` when nothing has finished yet.
+    pub fn percent(&self) -> Option<u32> {
+        (self.total > 0).then(|| self.confirmed * 100 / self.total)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReminderStats {
+    pub reminder_id: ReminderId,
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    pub last_7_days: CompletionRate,
+    pub last_30_days: CompletionRate,
+    pub average_time_to_acknowledge: Option<TimeDelta>,
+    pub wrong_challenge_answers: u32,
+}
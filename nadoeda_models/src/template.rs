@@ -0,0 +1,56 @@
+use crate::reminder::ReminderId;
+
+pub type MessageTemplateId = i64;
+
+/// Notifications whose text the user can replace with their own.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TemplateKind {
+    Fired,
+    Nag,
+    Confirmation,
+    Timeout,
+}
+
+impl TemplateKind {
+    pub const ALL: [Self; 4] = [Self::Fired, Self::Nag, Self::Confirmation, Self::Timeout];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Fired => "fired",
+            Self::Nag => "nag",
+            Self::Confirmation => "confirmation",
+            Self::Timeout => "timeout",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.code() == code)
+    }
+}
+
+/// User-written text for one kind of notification of a reminder.
+///
+/// Placeholders like `{text}` are filled in when the notification is sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageTemplate {
+    pub id: MessageTemplateId,
+    pub reminder_id: ReminderId,
+    pub kind: TemplateKind,
+    pub text: String,
+}
+
+/// Picks the variant for the given attempt, counting from 1. Variants are used in order and the
+/// last one repeats, so they can escalate.
+pub fn pick_template(
+    templates: &[MessageTemplate],
+    kind: TemplateKind,
+    attempt: u32,
+) -> Option<&str> {
+    let variants: Vec<&MessageTemplate> = templates
+        .iter()
+        .filter(|template| template.kind == kind)
+        .collect();
+
+    let index = (attempt.max(1) as usize - 1).min(variants.len().checked_sub(1)?);
+    Some(&variants[index].text)
+}
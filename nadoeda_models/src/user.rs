@@ -1,3 +1,5 @@
+use crate::reminder::ReminderFireTime;
+
 pub type UserId = i64;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -5,4 +7,39 @@ pub struct User {
     pub id: UserId,
     pub timezone: chrono_tz::Tz,
     pub tg_chat_id: Option<i64>,
+    /// Merges reminders firing at about the same time into one message.
+    pub digest_mode: bool,
+    /// When to send the list of today's reminders, if at all.
+    pub agenda_at: Option<ReminderFireTime>,
+    /// When to send what happened to today's reminders, if at all.
+    pub summary_at: Option<ReminderFireTime>,
+    pub language: Language,
+}
+
+/// Language the bot talks to the user in.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub enum Language {
+    #[default]
+    English,
+    Russian,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Russian];
+
+    /// ISO 639-1 code, also used for storage.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::Russian => "ru",
+        }
+    }
+
+    /// Accepts plain codes and IETF tags like `ru-RU`, as sent by Telegram.
+    pub fn from_code(code: &str) -> Option<Self> {
+        let primary = code.split(['-', '_']).next()?;
+        Self::ALL
+            .into_iter()
+            .find(|language| language.code().eq_ignore_ascii_case(primary))
+    }
 }
@@ -0,0 +1,271 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, DurationRound, NaiveDate, NaiveTime, TimeDelta, TimeZone, Timelike, Utc};
+use nadoeda_models::agenda::{AgendaItem, AgendaTime, DailyMessage, SummaryItem, SummaryOutcome};
+use nadoeda_models::occurrence::{OccurrenceOutcome, ReminderOccurrence};
+use nadoeda_models::reminder::{Reminder, ReminderFireTime};
+use nadoeda_models::user::{User, UserId};
+use nadoeda_scheduler::delivery::ReminderDeliveryChannel;
+use nadoeda_storage::{ReminderOccurrenceStorage, ReminderStorage, UserInfoStorage};
+use tokio::task::{self, JoinHandle};
+
+use crate::exclusion::{ExclusionCalendar, NoExclusions, skip_excluded_days};
+use crate::next_fire_at;
+
+/// Object safe view of the storages daily messages are generated from.
+#[async_trait]
+pub trait DailyMessageSource: Send + Sync {
+    async fn users(&self) -> anyhow::Result<Vec<User>>;
+    async fn reminders(&self, user_id: &UserId) -> anyhow::Result<Vec<Reminder>>;
+    async fn occurrences(
+        &self,
+        user_id: &UserId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<ReminderOccurrence>>;
+}
+
+/// Reads daily messages from the user, reminder and occurrence storages.
+pub struct StorageDailyMessageSource<U: ?Sized, R: ?Sized, O: ?Sized> {
+    users: Arc<U>,
+    reminders: Arc<R>,
+    occurrences: Arc<O>,
+}
+
+impl<U: ?Sized, R: ?Sized, O: ?Sized> StorageDailyMessageSource<U, R, O> {
+    pub fn new(users: Arc<U>, reminders: Arc<R>, occurrences: Arc<O>) -> Self {
+        Self {
+            users,
+            reminders,
+            occurrences,
+        }
+    }
+}
+
+#[async_trait]
+impl<U, R, O> DailyMessageSource for StorageDailyMessageSource<U, R, O>
+where
+    U: UserInfoStorage + ?Sized,
+    U::Error: Into<anyhow::Error>,
+    R: ReminderStorage + ?Sized,
+    O: ReminderOccurrenceStorage + ?Sized,
+{
+    async fn users(&self) -> anyhow::Result<Vec<User>> {
+        self.users.get_all().await.map_err(Into::into)
+    }
+
+    async fn reminders(&self, user_id: &UserId) -> anyhow::Result<Vec<Reminder>> {
+        Ok(self.reminders.get_all_user_reminders(user_id).await?)
+    }
+
+    async fn occurrences(
+        &self,
+        user_id: &UserId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<ReminderOccurrence>> {
+        Ok(self
+            .occurrences
+            .get_for_user_between(user_id, from, to)
+            .await?)
+    }
+}
+
+/// Sends the opt-in morning agenda and evening summary through the delivery channel.
+pub struct DailyMessageScheduler {
+    source: Arc<dyn DailyMessageSource>,
+    delivery_channel: Arc<dyn ReminderDeliveryChannel>,
+    exclusions: Arc<dyn ExclusionCalendar>,
+}
+
+impl DailyMessageScheduler {
+    pub fn new(
+        source: Arc<dyn DailyMessageSource>,
+        delivery_channel: Arc<dyn ReminderDeliveryChannel>,
+    ) -> Self {
+        Self {
+            source,
+            delivery_channel,
+            exclusions: Arc::new(NoExclusions),
+        }
+    }
+
+    /// The agenda of an excluded day is empty, as reminders do not fire on it.
+    pub fn with_exclusions(mut self, exclusions: Arc<dyn ExclusionCalendar>) -> Self {
+        self.exclusions = exclusions;
+        self
+    }
+
+    /// Checks for due messages at the start of every minute.
+    pub fn spawn(self) -> JoinHandle<()> {
+        task::spawn(async move {
+            loop {
+                let now = Utc::now();
+                let next_minute = now
+                    .duration_trunc(TimeDelta::minutes(1))
+                    .expect("Minutes are always in range")
+                    + TimeDelta::minutes(1);
+
+                tokio::time::sleep((next_minute - now).to_std().unwrap_or_default()).await;
+
+                self.send_due(next_minute).await;
+            }
+        })
+    }
+
+    pub(crate) async fn send_due(&self, now: DateTime<Utc>) {
+        let users = match self.source.users().await {
+            Ok(users) => users,
+            Err(err) => {
+                log::error!("Failed to load users for daily messages: {err}");
+                return;
+            }
+        };
+
+        for user in users {
+            if is_due(user.agenda_at, now) {
+                self.send(&user, self.agenda(&user, now).await).await;
+            }
+
+            if is_due(user.summary_at, now) {
+                self.send(&user, self.summary(&user, now).await).await;
+            }
+        }
+    }
+
+    async fn agenda(&self, user: &User, now: DateTime<Utc>) -> anyhow::Result<DailyMessage> {
+        let (day_start, day_end) = local_day(user, now);
+        // Same check the reminder tasks do before firing.
+        if skip_excluded_days(self.exclusions.as_ref(), &user.id, day_start).await != day_start {
+            return Ok(DailyMessage::Agenda(Vec::new()));
+        }
+        let reminders = self.source.reminders(&user.id).await?;
+
+        Ok(DailyMessage::Agenda(build_agenda(
+            &reminders, day_start, day_end,
+        )))
+    }
+
+    async fn summary(&self, user: &User, now: DateTime<Utc>) -> anyhow::Result<DailyMessage> {
+        let (day_start, _) = local_day(user, now);
+        let reminders = self.source.reminders(&user.id).await?;
+        let occurrences = self.source.occurrences(&user.id, day_start, now).await?;
+
+        Ok(DailyMessage::Summary(build_summary(
+            &reminders,
+            &occurrences,
+        )))
+    }
+
+    async fn send(&self, user: &User, message: anyhow::Result<DailyMessage>) {
+        let result = match message {
+            Ok(message) => self
+                .delivery_channel
+                .send_daily_message(user, &message)
+                .await
+                .map_err(|err| err.to_string()),
+            Err(err) => Err(err.to_string()),
+        };
+
+        if let Err(err) = result {
+            log::error!("Failed to send daily message. UserId {}: {}", user.id, err);
+        }
+    }
+}
+
+fn is_due(at: Option<ReminderFireTime>, now: DateTime<Utc>) -> bool {
+    at.is_some_and(|at| at.time().hour() == now.hour() && at.time().minute() == now.minute())
+}
+
+/// Start and end of the user's current local day.
+pub(crate) fn local_day(user: &User, now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+    let today = now.with_timezone(&user.timezone).date_naive();
+    let tomorrow = today.succ_opt().expect("Not realistic to overflow");
+
+    (local_midnight(user, today), local_midnight(user, tomorrow))
+}
+
+fn local_midnight(user: &User, date: NaiveDate) -> DateTime<Utc> {
+    let midnight = date.and_time(NaiveTime::MIN);
+
+    user.timezone
+        .from_local_datetime(&midnight)
+        .earliest()
+        // Midnight skipped by a daylight saving change.
+        .unwrap_or_else(|| user.timezone.from_utc_datetime(&midnight))
+        .with_timezone(&Utc)
+}
+
+/// Reminders firing between `day_start` and `day_end`, ordered by time.
+/// Reminders waiting for a predecessor go last since their time is not known in advance.
+pub fn build_agenda(
+    reminders: &[Reminder],
+    day_start: DateTime<Utc>,
+    day_end: DateTime<Utc>,
+) -> Vec<AgendaItem> {
+    let mut items = reminders
+        .iter()
+        .filter_map(|reminder| {
+            let time = if reminder.dependency.is_some() {
+                AgendaTime::AfterPredecessor
+            } else {
+                let start = next_fire_at(reminder, day_start);
+                if start >= day_end {
+                    return None;
+                }
+
+                match reminder.fire_window() {
+                    window if window > TimeDelta::zero() => {
+                        AgendaTime::Between(start, start + window)
+                    }
+                    _ => AgendaTime::At(start),
+                }
+            };
+
+            Some(AgendaItem {
+                reminder_id: reminder.id,
+                text: reminder.text.clone(),
+                time,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    items.sort_by_key(|item| match item.time {
+        AgendaTime::At(start) | AgendaTime::Between(start, _) => (false, Some(start)),
+        AgendaTime::AfterPredecessor => (true, None),
+    });
+
+    items
+}
+
+/// Best outcome of each reminder among the given occurrences.
+pub fn build_summary(
+    reminders: &[Reminder],
+    occurrences: &[ReminderOccurrence],
+) -> Vec<SummaryItem> {
+    reminders
+        .iter()
+        .map(|reminder| {
+            let outcomes = occurrences
+                .iter()
+                .filter(|occurrence| occurrence.reminder_id == reminder.id)
+                .filter_map(|occurrence| occurrence.outcome)
+                .collect::<Vec<_>>();
+
+            let outcome = if outcomes.contains(&OccurrenceOutcome::Confirmed) {
+                SummaryOutcome::Completed
+            } else if outcomes.contains(&OccurrenceOutcome::TimedOut) {
+                SummaryOutcome::TimedOut
+            } else {
+                SummaryOutcome::Skipped
+            };
+
+            SummaryItem {
+                reminder_id: reminder.id,
+                text: reminder.text.clone(),
+                outcome,
+            }
+        })
+        .collect()
+}
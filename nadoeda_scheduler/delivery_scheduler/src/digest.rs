@@ -0,0 +1,152 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use nadoeda_models::{
+    agenda::DailyMessage,
+    reminder::Reminder,
+    user::{User, UserId},
+};
+use nadoeda_scheduler::delivery::{ReminderDeliveryChannel, ReminderMessageType};
+use nadoeda_storage::UserInfoStorage;
+use tokio::task;
+
+/// How long to wait for other reminders of the same user before sending a digest.
+pub const DIGEST_WINDOW: Duration = Duration::from_secs(5);
+
+/// Object safe view of [`UserInfoStorage`], so the digest layer does not depend on the backend.
+#[async_trait]
+pub trait DigestPreferences: Send + Sync {
+    async fn is_digest_enabled(&self, user_id: &UserId) -> anyhow::Result<bool>;
+}
+
+/// Reads the digest mode stored for the user.
+pub struct StorageDigestPreferences<S: ?Sized> {
+    users: Arc<S>,
+}
+
+impl<S: ?Sized> StorageDigestPreferences<S> {
+    pub fn new(users: Arc<S>) -> Self {
+        Self { users }
+    }
+}
+
+#[async_trait]
+impl<S> DigestPreferences for StorageDigestPreferences<S>
+where
+    S: UserInfoStorage + ?Sized,
+    S::Error: Into<anyhow::Error>,
+{
+    async fn is_digest_enabled(&self, user_id: &UserId) -> anyhow::Result<bool> {
+        let user = self.users.get(user_id).await.map_err(Into::into)?;
+        Ok(user.is_some_and(|user| user.digest_mode))
+    }
+}
+
+type DigestKey = (UserId, ReminderMessageType);
+type Batches = Arc<Mutex<HashMap<DigestKey, Vec<Reminder>>>>;
+
+/// Batching layer in front of a [`ReminderDeliveryChannel`]. For users with digest mode on,
+/// fired reminders and nags arriving within [`DIGEST_WINDOW`] are merged into one digest.
+/// Everything else goes straight to the inner channel.
+pub struct DigestDeliveryChannel {
+    inner: Arc<dyn ReminderDeliveryChannel>,
+    preferences: Arc<dyn DigestPreferences>,
+    batches: Batches,
+}
+
+impl DigestDeliveryChannel {
+    pub fn new(
+        inner: Arc<dyn ReminderDeliveryChannel>,
+        preferences: Arc<dyn DigestPreferences>,
+    ) -> Self {
+        Self {
+            inner,
+            preferences,
+            batches: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    async fn is_digest_enabled(&self, user_id: &UserId) -> bool {
+        self.preferences
+            .is_digest_enabled(user_id)
+            .await
+            .unwrap_or_else(|err| {
+                log::error!("Failed to read digest mode. UserId {user_id}: {err}");
+                false
+            })
+    }
+
+    fn spawn_flush(&self, key: DigestKey) {
+        let inner = Arc::clone(&self.inner);
+        let batches = Arc::clone(&self.batches);
+
+        task::spawn(async move {
+            tokio::time::sleep(DIGEST_WINDOW).await;
+
+            let batch = batches.lock().unwrap().remove(&key).unwrap_or_default();
+            let (user_id, message) = key;
+
+            let result = match batch.as_slice() {
+                [reminder] => inner.send_reminder_notification(reminder, message).await,
+                reminders => inner.send_digest_notification(reminders, message).await,
+            };
+
+            if let Err(err) = result {
+                log::error!(
+                    "Failed to deliver {:?} digest. UserId {}: {}",
+                    message,
+                    user_id,
+                    err
+                );
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl ReminderDeliveryChannel for DigestDeliveryChannel {
+    async fn send_reminder_notification(
+        &self,
+        reminder: &Reminder,
+        message: ReminderMessageType,
+    ) -> Result<(), Box<dyn Error>> {
+        let batched = matches!(
+            message,
+            ReminderMessageType::Fired | ReminderMessageType::Nag
+        );
+
+        if !batched || !self.is_digest_enabled(&reminder.user_id).await {
+            return self
+                .inner
+                .send_reminder_notification(reminder, message)
+                .await;
+        }
+
+        let key = (reminder.user_id, message);
+        let opens_batch = {
+            let mut batches = self.batches.lock().unwrap();
+            let batch = batches.entry(key).or_default();
+            batch.push(reminder.clone());
+            batch.len() == 1
+        };
+
+        if opens_batch {
+            self.spawn_flush(key);
+        }
+
+        Ok(())
+    }
+
+    async fn send_daily_message(
+        &self,
+        user: &User,
+        message: &DailyMessage,
+    ) -> Result<(), Box<dyn Error>> {
+        self.inner.send_daily_message(user, message).await
+    }
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, TimeDelta, Utc};
+use nadoeda_models::user::UserId;
+use nadoeda_storage::{ExclusionDateStorage, UserInfoStorage};
+
+/// Longest run of excluded days skipped before firing anyway.
+const MAX_EXCLUDED_DAYS: i64 = 366;
+
+/// Object safe view of the users' exclusion dates.
+#[async_trait]
+pub trait ExclusionCalendar: Send + Sync {
+    /// Whether `at` falls on a day the user excluded, in their local calendar.
+    async fn is_excluded(&self, user_id: &UserId, at: DateTime<Utc>) -> anyhow::Result<bool>;
+}
+
+/// Calendar without any exclusion dates.
+pub struct NoExclusions;
+
+#[async_trait]
+impl ExclusionCalendar for NoExclusions {
+    async fn is_excluded(&self, _user_id: &UserId, _at: DateTime<Utc>) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+}
+
+/// Resolves exclusion dates in the timezone stored for the user.
+pub struct StorageExclusionCalendar<U: ?Sized, E: ?Sized> {
+    users: Arc<U>,
+    exclusions: Arc<E>,
+}
+
+impl<U: ?Sized, E: ?Sized> StorageExclusionCalendar<U, E> {
+    pub fn new(users: Arc<U>, exclusions: Arc<E>) -> Self {
+        Self { users, exclusions }
+    }
+}
+
+#[async_trait]
+impl<U, E> ExclusionCalendar for StorageExclusionCalendar<U, E>
+where
+    U: UserInfoStorage + ?Sized,
+    U::Error: Into<anyhow::Error>,
+    E: ExclusionDateStorage + ?Sized,
+{
+    async fn is_excluded(&self, user_id: &UserId, at: DateTime<Utc>) -> anyhow::Result<bool> {
+        let Some(user) = self.users.get(user_id).await.map_err(Into::into)? else {
+            return Ok(false);
+        };
+
+        let local_date = at.with_timezone(&user.timezone).date_naive();
+
+        Ok(self.exclusions.is_excluded(user_id, local_date).await?)
+    }
+}
+
+/// First of `first` and the same time on the following days that is not excluded.
+pub(crate) async fn skip_excluded_days(
+    exclusions: &dyn ExclusionCalendar,
+    user_id: &UserId,
+    first: DateTime<Utc>,
+) -> DateTime<Utc> {
+    for day in 0..MAX_EXCLUDED_DAYS {
+        let candidate = first + TimeDelta::days(day);
+
+        match exclusions.is_excluded(user_id, candidate).await {
+            Ok(true) => continue,
+            Ok(false) => return candidate,
+            Err(err) => {
+                log::error!("Failed to check exclusion dates. UserId {user_id}: {err}");
+                return candidate;
+            }
+        }
+    }
+
+    first + TimeDelta::days(MAX_EXCLUDED_DAYS)
+}
@@ -1,24 +1,44 @@
 use std::{
     collections::{HashMap, hash_map::Entry},
-    sync::Arc,
+    sync::{Arc, Mutex},
     time::Duration,
 };
 
+mod daily_messages;
+mod digest;
+mod exclusion;
+mod occurrence;
+mod retention;
+
 use async_trait::async_trait;
 use chrono::{DateTime, NaiveTime, TimeDelta, Utc};
 use nadoeda_scheduler::delivery::{ReminderDeliveryChannel, ReminderMessageType};
 use nadoeda_scheduler::{ReminderScheduler, ScheduleRequest, ScheduledReminder};
+use rand::{Rng, SeedableRng, rngs::StdRng};
 use tokio::{
     sync::{RwLock, mpsc, watch},
     task::{self, JoinHandle},
 };
 
-use nadoeda_models::reminder::{Reminder, ReminderId, ReminderState};
+use nadoeda_models::occurrence::OccurrenceOutcome;
+use nadoeda_models::reminder::{
+    CONFIRMATION_ATTEMPTS, NAGGING_ATTEMPTS, Reminder, ReminderId, ReminderState,
+};
 
-const NAGGING_ATTEMPTS: u8 = 10;
-const NAGGING_TIMEOUT: Duration = Duration::from_secs(30);
+pub use daily_messages::{
+    DailyMessageScheduler, DailyMessageSource, StorageDailyMessageSource, build_agenda,
+    build_summary,
+};
+pub use digest::{
+    DIGEST_WINDOW, DigestDeliveryChannel, DigestPreferences, StorageDigestPreferences,
+};
+use exclusion::skip_excluded_days;
+pub use exclusion::{ExclusionCalendar, NoExclusions, StorageExclusionCalendar};
+use occurrence::OccurrenceTracker;
+pub use occurrence::{OccurrenceLog, StorageOccurrenceLog};
+pub use retention::ArchiveRetentionJob;
 
-const CONFIRMATION_ATTEMPTS: u8 = 10;
+const NAGGING_TIMEOUT: Duration = Duration::from_secs(30);
 const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(120);
 
 #[derive(Debug)]
@@ -28,34 +48,83 @@ enum ReminderEvent {
     Acknowledge,
     Confirm,
     Cancel,
+    PredecessorFinished,
 }
 
 struct ScheduledReminderHandle {
     task: JoinHandle<()>,
     tx: mpsc::Sender<ReminderEvent>,
+    predecessor_id: Option<ReminderId>,
 }
 
 struct CleanupTask(watch::Sender<()>);
 
 type ReminderTaskStore = RwLock<HashMap<ReminderId, ScheduledReminderHandle>>;
 
+/// Collaborators a reminder task works with while going through the reminder's states.
+struct ReminderTaskContext {
+    delivery: Arc<dyn ReminderDeliveryChannel>,
+    exclusions: Arc<dyn ExclusionCalendar>,
+    occurrences: OccurrenceTracker,
+    rng: StdRng,
+    tasks: Arc<ReminderTaskStore>,
+    tx: mpsc::Sender<ReminderEvent>,
+}
+
 pub struct DeliveryReminderScheduler {
     tasks: Arc<ReminderTaskStore>,
     delivery_channel: Arc<dyn ReminderDeliveryChannel>,
+    occurrence_log: Arc<dyn OccurrenceLog>,
+    exclusions: Arc<dyn ExclusionCalendar>,
+    /// Picks the moment inside fire windows. Every reminder task gets its own RNG seeded from this one.
+    rng: Mutex<StdRng>,
     cleanup_task: CleanupTask,
 }
 
 impl DeliveryReminderScheduler {
-    pub fn new(delivery_channel: Arc<dyn ReminderDeliveryChannel>) -> Self {
+    pub fn new(
+        delivery_channel: Arc<dyn ReminderDeliveryChannel>,
+        occurrence_log: Arc<dyn OccurrenceLog>,
+    ) -> Self {
+        Self::with_rng(delivery_channel, occurrence_log, StdRng::from_os_rng())
+    }
+
+    /// Same as [`Self::new`], but fire windows are resolved deterministically.
+    pub fn with_seed(
+        delivery_channel: Arc<dyn ReminderDeliveryChannel>,
+        occurrence_log: Arc<dyn OccurrenceLog>,
+        seed: u64,
+    ) -> Self {
+        Self::with_rng(
+            delivery_channel,
+            occurrence_log,
+            StdRng::seed_from_u64(seed),
+        )
+    }
+
+    fn with_rng(
+        delivery_channel: Arc<dyn ReminderDeliveryChannel>,
+        occurrence_log: Arc<dyn OccurrenceLog>,
+        rng: StdRng,
+    ) -> Self {
         let tasks = Arc::new(RwLock::new(HashMap::new()));
         let cleanup_task = Self::spawn_cleanup_task(Arc::clone(&tasks));
 
         Self {
             tasks,
             delivery_channel,
+            occurrence_log,
+            exclusions: Arc::new(NoExclusions),
+            rng: Mutex::new(rng),
             cleanup_task,
         }
     }
+
+    /// Daily reminders skip the days excluded in the calendar.
+    pub fn with_exclusions(mut self, exclusions: Arc<dyn ExclusionCalendar>) -> Self {
+        self.exclusions = exclusions;
+        self
+    }
 }
 
 impl Drop for DeliveryReminderScheduler {
@@ -65,19 +134,40 @@ impl Drop for DeliveryReminderScheduler {
 }
 
 impl DeliveryReminderScheduler {
-    fn create_reminder_task(&self, reminder: Reminder) -> anyhow::Result<ScheduledReminderHandle> {
+    fn create_reminder_task(
+        &self,
+        request: ScheduleRequest,
+    ) -> anyhow::Result<ScheduledReminderHandle> {
+        let ScheduleRequest {
+            reminder,
+            not_before,
+        } = request;
         let reminder_id = reminder.id;
         log::info!("Starting task for reminder {reminder_id}");
         let (tx, rx) = mpsc::channel(10);
 
-        let tx_clone = tx.clone();
-        let delivery_channel = self.delivery_channel.clone();
+        let predecessor_id = reminder
+            .dependency
+            .map(|dependency| dependency.predecessor_id);
+
+        let context = ReminderTaskContext {
+            delivery: self.delivery_channel.clone(),
+            exclusions: self.exclusions.clone(),
+            occurrences: OccurrenceTracker::new(self.occurrence_log.clone(), reminder.user_id),
+            rng: StdRng::from_rng(&mut *self.rng.lock().unwrap()),
+            tasks: Arc::clone(&self.tasks),
+            tx: tx.clone(),
+        };
         let task = task::spawn(async move {
-            tx_clone.send(ReminderEvent::Schedule).await.unwrap();
-            run_reminder(reminder, delivery_channel.as_ref(), rx, tx_clone).await;
+            context.tx.send(ReminderEvent::Schedule).await.unwrap();
+            run_reminder(reminder, not_before, context, rx).await;
         });
 
-        let scheduled_reminder = ScheduledReminderHandle { task, tx };
+        let scheduled_reminder = ScheduledReminderHandle {
+            task,
+            tx,
+            predecessor_id,
+        };
 
         Ok(scheduled_reminder)
     }
@@ -121,9 +211,7 @@ impl ReminderScheduler for DeliveryReminderScheduler {
     ) -> anyhow::Result<ScheduledReminder> {
         let reminder_id = schedule_request.reminder.id;
         if let Entry::Vacant(e) = self.tasks.write().await.entry(reminder_id) {
-            let scheduled_reminder_handle = self
-                .create_reminder_task(schedule_request.reminder)
-                .unwrap();
+            let scheduled_reminder_handle = self.create_reminder_task(schedule_request).unwrap();
 
             e.insert(scheduled_reminder_handle);
 
@@ -169,38 +257,96 @@ impl ReminderScheduler for DeliveryReminderScheduler {
 
 async fn run_reminder(
     mut reminder: Reminder,
-    delivery: &dyn ReminderDeliveryChannel,
+    not_before: Option<DateTime<Utc>>,
+    mut context: ReminderTaskContext,
     mut rx: mpsc::Receiver<ReminderEvent>,
-    tx: mpsc::Sender<ReminderEvent>,
 ) {
     while let Some(event) = rx.recv().await {
+        let finished = matches!(
+            (&reminder.state, &event),
+            (ReminderState::Confirming { .. }, ReminderEvent::Confirm)
+        );
         let new_state =
-            handle_event(&reminder, &reminder.state, &event, delivery, tx.clone()).await;
+            handle_event(&reminder, &reminder.state, &event, not_before, &mut context).await;
         reminder.state = new_state;
+        if finished {
+            wake_dependents(&context.tasks, reminder.id).await;
+        }
         if matches!(event, ReminderEvent::Cancel) {
             break;
         }
     }
 }
 
+async fn wake_dependents(tasks: &ReminderTaskStore, predecessor_id: ReminderId) {
+    // Sending waits while a channel is full, so the task store must not stay locked meanwhile.
+    let dependents: Vec<_> = tasks
+        .read()
+        .await
+        .iter()
+        .filter(|(_, handle)| handle.predecessor_id == Some(predecessor_id))
+        .map(|(id, handle)| (*id, handle.tx.clone()))
+        .collect();
+
+    for (id, tx) in dependents {
+        if let Err(err) = tx.send(ReminderEvent::PredecessorFinished).await {
+            log::error!("Failed to wake dependent reminder {id}: {err}");
+        }
+    }
+}
+
 async fn handle_event(
     reminder: &Reminder,
     current_state: &ReminderState,
     event: &ReminderEvent,
-    delivery: &dyn ReminderDeliveryChannel,
-    tx: mpsc::Sender<ReminderEvent>,
+    not_before: Option<DateTime<Utc>>,
+    context: &mut ReminderTaskContext,
 ) -> ReminderState {
+    let delivery = context.delivery.as_ref();
+    let exclusions = context.exclusions.as_ref();
+    let occurrences = &mut context.occurrences;
+    let rng = &mut context.rng;
+    let tx = context.tx.clone();
     // println!("({current_state:?}, {event:?})");
     let id = reminder.id;
     match (current_state, event) {
+        (ReminderState::Pending, ReminderEvent::Schedule) if reminder.dependency.is_some() => {
+            notify(delivery, reminder, ReminderMessageType::Scheduled).await;
+
+            log::info!("[DORMANT] Waiting for the predecessor. ReminderId {}", id);
+
+            ReminderState::Scheduled
+        }
+        (ReminderState::Pending | ReminderState::Scheduled, ReminderEvent::PredecessorFinished) => {
+            let delay = reminder
+                .dependency
+                .map(|dependency| dependency.delay)
+                .unwrap_or_default()
+                .max(TimeDelta::zero());
+
+            occurrences.start(reminder, Utc::now() + delay).await;
+
+            log::info!(
+                "[PREDECESSOR FINISHED] Sleeping for {:?} delay. ReminderId {}",
+                delay,
+                id
+            );
+
+            send_after_delay(ReminderEvent::Trigger, tx, delay.to_std().unwrap());
+
+            ReminderState::Scheduled
+        }
         (ReminderState::Pending, ReminderEvent::Schedule) => {
-            let delay = get_target_delay(reminder.fire_at.time(), Utc::now())
-                .to_std()
-                .unwrap();
+            let now = Utc::now();
+            let from = not_before.map_or(now, |at| at.max(now));
+            let fire_at =
+                skip_excluded_days(exclusions, &reminder.user_id, next_fire_at(reminder, from))
+                    .await;
+            let target_delay = fire_at - now + get_fire_offset(reminder.fire_window(), rng);
+            let delay = target_delay.to_std().unwrap();
 
-            delivery
-                .send_reminder_notification(reminder, ReminderMessageType::Scheduled)
-                .await;
+            occurrences.start(reminder, now + target_delay).await;
+            notify(delivery, reminder, ReminderMessageType::Scheduled).await;
 
             log::info!(
                 "[SCHEDULE] Sleeping for {:?} delay. ReminderId {}",
@@ -213,9 +359,8 @@ async fn handle_event(
             ReminderState::Scheduled
         }
         (ReminderState::Scheduled, ReminderEvent::Trigger) => {
-            delivery
-                .send_reminder_notification(reminder, ReminderMessageType::Fired)
-                .await;
+            occurrences.fired().await;
+            notify(delivery, reminder, ReminderMessageType::Fired).await;
 
             log::info!(
                 "[NAGGING] Sleeping for {:?} delay. ReminderId {}",
@@ -231,15 +376,12 @@ async fn handle_event(
         }
         (ReminderState::Nagging { attempts_left }, ReminderEvent::Trigger) => {
             if *attempts_left == 0 {
-                delivery
-                    .send_reminder_notification(reminder, ReminderMessageType::Timeout)
-                    .await;
+                occurrences.finish(OccurrenceOutcome::TimedOut).await;
+                notify(delivery, reminder, ReminderMessageType::Timeout).await;
                 return ReminderState::Pending;
             }
 
-            delivery
-                .send_reminder_notification(reminder, ReminderMessageType::Nag)
-                .await;
+            notify(delivery, reminder, ReminderMessageType::Nag).await;
 
             log::info!(
                 "[NAGGING REPEAT] Sleeping for {:?} delay. ReminderId {}",
@@ -254,9 +396,8 @@ async fn handle_event(
             }
         }
         (ReminderState::Nagging { .. }, ReminderEvent::Acknowledge) => {
-            delivery
-                .send_reminder_notification(reminder, ReminderMessageType::Acknowledge)
-                .await;
+            occurrences.acknowledged().await;
+            notify(delivery, reminder, ReminderMessageType::Acknowledge).await;
 
             log::info!(
                 "[CONFIRMATION] Sleeping for {:?} delay. ReminderId {}",
@@ -272,15 +413,12 @@ async fn handle_event(
         }
         (ReminderState::Confirming { attempts_left }, ReminderEvent::Trigger) => {
             if *attempts_left == 0 {
-                delivery
-                    .send_reminder_notification(reminder, ReminderMessageType::Timeout)
-                    .await;
+                occurrences.finish(OccurrenceOutcome::TimedOut).await;
+                notify(delivery, reminder, ReminderMessageType::Timeout).await;
                 return ReminderState::Pending;
             }
 
-            delivery
-                .send_reminder_notification(reminder, ReminderMessageType::Confirmation)
-                .await;
+            notify(delivery, reminder, ReminderMessageType::Confirmation).await;
 
             log::info!(
                 "[CONFIRMATION REPEAT] Sleeping for {:?} delay. ReminderId {}",
@@ -295,15 +433,13 @@ async fn handle_event(
             }
         }
         (ReminderState::Confirming { .. }, ReminderEvent::Confirm) => {
-            delivery
-                .send_reminder_notification(reminder, ReminderMessageType::Finished)
-                .await;
+            occurrences.confirmed().await;
+            notify(delivery, reminder, ReminderMessageType::Finished).await;
             ReminderState::Pending
         }
         (_, ReminderEvent::Cancel) => {
-            delivery
-                .send_reminder_notification(reminder, ReminderMessageType::Cancelled)
-                .await;
+            occurrences.finish(OccurrenceOutcome::Cancelled).await;
+            notify(delivery, reminder, ReminderMessageType::Cancelled).await;
             ReminderState::Pending
         }
         (state, event) => {
@@ -319,6 +455,21 @@ async fn handle_event(
     }
 }
 
+async fn notify(
+    delivery: &dyn ReminderDeliveryChannel,
+    reminder: &Reminder,
+    message: ReminderMessageType,
+) {
+    if let Err(err) = delivery.send_reminder_notification(reminder, message).await {
+        log::error!(
+            "Failed to deliver {:?} notification. ReminderId {}: {}",
+            message,
+            reminder.id,
+            err
+        );
+    }
+}
+
 fn send_after_delay(ev: ReminderEvent, tx: mpsc::Sender<ReminderEvent>, delay: Duration) {
     task::spawn(async move {
         tokio::time::sleep(delay).await;
@@ -326,6 +477,11 @@ fn send_after_delay(ev: ReminderEvent, tx: mpsc::Sender<ReminderEvent>, delay: D
     });
 }
 
+/// Next time the reminder fires after `now`, or the start of its fire window.
+pub fn next_fire_at(reminder: &Reminder, now: DateTime<Utc>) -> DateTime<Utc> {
+    now + get_target_delay(reminder.fire_at.time(), now)
+}
+
 pub(crate) fn get_target_delay(fire_at: &NaiveTime, now: DateTime<Utc>) -> chrono::Duration {
     let max_delta = TimeDelta::new(10, 0).expect("This is always in bounds.");
     let delta = *fire_at - now.time();
@@ -344,5 +500,14 @@ pub(crate) fn get_target_delay(fire_at: &NaiveTime, now: DateTime<Utc>) -> chron
     target_datetime - now.naive_utc()
 }
 
+/// Uniformly random moment inside the fire window, as an offset from its start.
+pub(crate) fn get_fire_offset(window: TimeDelta, rng: &mut impl Rng) -> TimeDelta {
+    if window <= TimeDelta::zero() {
+        return TimeDelta::zero();
+    }
+
+    TimeDelta::seconds(rng.random_range(0..=window.num_seconds()))
+}
+
 #[cfg(test)]
 mod tests;
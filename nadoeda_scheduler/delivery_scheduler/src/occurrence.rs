@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use nadoeda_models::occurrence::{OccurrenceOutcome, ReminderOccurrence};
+use nadoeda_models::reminder::Reminder;
+use nadoeda_models::user::UserId;
+use nadoeda_storage::{NewReminderOccurrence, ReminderOccurrenceStorage};
+
+/// Object safe view of [`ReminderOccurrenceStorage`], so the scheduler does not depend on the backend.
+#[async_trait]
+pub trait OccurrenceLog: Send + Sync {
+    async fn start(&self, occurrence: NewReminderOccurrence) -> anyhow::Result<ReminderOccurrence>;
+    /// Saves the progress of an occurrence of one of the user's reminders.
+    async fn save(&self, occurrence: ReminderOccurrence, user_id: &UserId) -> anyhow::Result<()>;
+}
+
+/// Records occurrences in a [`ReminderOccurrenceStorage`].
+pub struct StorageOccurrenceLog<S: ?Sized> {
+    occurrences: Arc<S>,
+}
+
+impl<S: ?Sized> StorageOccurrenceLog<S> {
+    pub fn new(occurrences: Arc<S>) -> Self {
+        Self { occurrences }
+    }
+}
+
+#[async_trait]
+impl<S: ReminderOccurrenceStorage + ?Sized> OccurrenceLog for StorageOccurrenceLog<S> {
+    async fn start(&self, occurrence: NewReminderOccurrence) -> anyhow::Result<ReminderOccurrence> {
+        Ok(self.occurrences.insert(occurrence).await?)
+    }
+
+    async fn save(&self, occurrence: ReminderOccurrence, user_id: &UserId) -> anyhow::Result<()> {
+        self.occurrences.update(occurrence, user_id).await?;
+        Ok(())
+    }
+}
+
+/// Keeps track of the occurrence the reminder task is currently going through.
+pub(crate) struct OccurrenceTracker {
+    log: Arc<dyn OccurrenceLog>,
+    /// Owner of the scheduled reminder, the only user its occurrences are saved for.
+    owner: UserId,
+    current: Option<ReminderOccurrence>,
+}
+
+impl OccurrenceTracker {
+    pub fn new(log: Arc<dyn OccurrenceLog>, owner: UserId) -> Self {
+        Self {
+            log,
+            owner,
+            current: None,
+        }
+    }
+
+    pub async fn start(&mut self, reminder: &Reminder, scheduled_at: DateTime<Utc>) {
+        let new_occurrence = NewReminderOccurrence {
+            reminder_id: reminder.id,
+            user_id: self.owner,
+            scheduled_at,
+        };
+
+        match self.log.start(new_occurrence).await {
+            Ok(occurrence) => self.current = Some(occurrence),
+            Err(err) => log::error!(
+                "Failed to record occurrence. ReminderId {}: {}",
+                reminder.id,
+                err
+            ),
+        }
+    }
+
+    pub async fn fired(&mut self) {
+        self.record(|occurrence| occurrence.fired_at = Some(Utc::now()))
+            .await;
+    }
+
+    pub async fn acknowledged(&mut self) {
+        self.record(|occurrence| occurrence.acknowledged_at = Some(Utc::now()))
+            .await;
+    }
+
+    pub async fn confirmed(&mut self) {
+        self.record(|occurrence| {
+            occurrence.confirmed_at = Some(Utc::now());
+            occurrence.outcome = Some(OccurrenceOutcome::Confirmed);
+        })
+        .await;
+    }
+
+    pub async fn finish(&mut self, outcome: OccurrenceOutcome) {
+        self.record(|occurrence| occurrence.outcome = Some(outcome))
+            .await;
+    }
+
+    async fn record(&mut self, change: impl FnOnce(&mut ReminderOccurrence)) {
+        let Some(occurrence) = self.current.as_mut() else {
+            return;
+        };
+
+        change(occurrence);
+
+        if let Err(err) = self.log.save(occurrence.clone(), &self.owner).await {
+            log::error!(
+                "Failed to record occurrence {}. ReminderId {}: {}",
+                occurrence.id,
+                occurrence.reminder_id,
+                err
+            );
+        }
+
+        if occurrence.is_finished() {
+            self.current = None;
+        }
+    }
+}
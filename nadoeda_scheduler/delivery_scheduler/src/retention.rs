@@ -0,0 +1,42 @@
+use std::{sync::Arc, time::Duration};
+
+use chrono::{TimeDelta, Utc};
+use nadoeda_storage::ReminderStorage;
+use tokio::task::{self, JoinHandle};
+
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Periodically deletes reminders that stayed archived longer than the retention period.
+pub struct ArchiveRetentionJob<R: ?Sized> {
+    reminders: Arc<R>,
+    retention: TimeDelta,
+}
+
+impl<R: ReminderStorage + ?Sized + 'static> ArchiveRetentionJob<R> {
+    pub fn new(reminders: Arc<R>, retention: TimeDelta) -> Self {
+        Self {
+            reminders,
+            retention,
+        }
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        task::spawn(async move {
+            let mut interval = tokio::time::interval(PURGE_INTERVAL);
+
+            loop {
+                interval.tick().await;
+
+                match self
+                    .reminders
+                    .purge_archived(Utc::now() - self.retention)
+                    .await
+                {
+                    Ok(0) => {}
+                    Ok(purged) => log::info!("Purged {purged} archived reminder(s)"),
+                    Err(err) => log::error!("Failed to purge archived reminders: {err}"),
+                }
+            }
+        })
+    }
+}
@@ -1,3 +1,5 @@
 use super::*;
+mod daily_messages_tests;
 mod delivery_scheduler_tests;
+mod digest_tests;
 mod target_datetime_tests;
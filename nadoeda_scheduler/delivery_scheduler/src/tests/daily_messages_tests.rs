@@ -0,0 +1,270 @@
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveTime, TimeZone, Utc};
+use nadoeda_models::agenda::{AgendaItem, AgendaTime, DailyMessage, SummaryItem, SummaryOutcome};
+use nadoeda_models::chrono_tz;
+use nadoeda_models::occurrence::{OccurrenceOutcome, ReminderOccurrence};
+use nadoeda_models::reminder::{
+    ConfirmationChallenge, Reminder, ReminderDependency, ReminderFireTime, ReminderId,
+    ReminderState, ReminderVerification,
+};
+use nadoeda_models::user::{Language, User, UserId};
+
+use super::*;
+
+#[derive(Clone, Default)]
+struct RecordingDeliveryChannel {
+    sent: Arc<Mutex<Vec<(UserId, DailyMessage)>>>,
+}
+
+#[async_trait]
+impl ReminderDeliveryChannel for RecordingDeliveryChannel {
+    async fn send_reminder_notification(
+        &self,
+        _reminder: &Reminder,
+        _message: ReminderMessageType,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    async fn send_daily_message(
+        &self,
+        user: &User,
+        message: &DailyMessage,
+    ) -> Result<(), Box<dyn Error>> {
+        self.sent.lock().unwrap().push((user.id, message.clone()));
+        Ok(())
+    }
+}
+
+struct TestSource {
+    users: Vec<User>,
+    reminders: Vec<Reminder>,
+    occurrences: Vec<ReminderOccurrence>,
+}
+
+#[async_trait]
+impl DailyMessageSource for TestSource {
+    async fn users(&self) -> anyhow::Result<Vec<User>> {
+        Ok(self.users.clone())
+    }
+
+    async fn reminders(&self, user_id: &UserId) -> anyhow::Result<Vec<Reminder>> {
+        Ok(self
+            .reminders
+            .iter()
+            .filter(|reminder| reminder.user_id == *user_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn occurrences(
+        &self,
+        user_id: &UserId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<ReminderOccurrence>> {
+        Ok(self
+            .occurrences
+            .iter()
+            .filter(|occurrence| occurrence.user_id == *user_id)
+            .filter(|occurrence| (from..to).contains(&occurrence.scheduled_at))
+            .cloned()
+            .collect())
+    }
+}
+
+fn time(h: u32, m: u32) -> ReminderFireTime {
+    ReminderFireTime::new(NaiveTime::from_hms_opt(h, m, 0).unwrap())
+}
+
+fn utc(h: u32, m: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 6, 2, h, m, 0).unwrap()
+}
+
+fn reminder(id: ReminderId, fire_at: ReminderFireTime) -> Reminder {
+    Reminder {
+        id,
+        user_id: 1,
+        state: ReminderState::Pending,
+        fire_at,
+        fire_window_end: None,
+        text: format!("Reminder {id}"),
+        verification: ReminderVerification::None,
+        challenge: ConfirmationChallenge::None,
+        dependency: None,
+        checklist: Vec::new(),
+        version: 0,
+    }
+}
+
+fn user(agenda_at: Option<ReminderFireTime>, summary_at: Option<ReminderFireTime>) -> User {
+    User {
+        id: 1,
+        timezone: chrono_tz::UTC,
+        tg_chat_id: None,
+        language: Language::English,
+        digest_mode: false,
+        agenda_at,
+        summary_at,
+    }
+}
+
+fn occurrence(reminder_id: ReminderId, outcome: Option<OccurrenceOutcome>) -> ReminderOccurrence {
+    ReminderOccurrence {
+        id: reminder_id,
+        reminder_id,
+        user_id: 1,
+        scheduled_at: utc(9, 0),
+        fired_at: Some(utc(9, 0)),
+        acknowledged_at: None,
+        confirmed_at: None,
+        outcome,
+    }
+}
+
+#[test]
+fn agenda_should_be_ordered_by_fire_time() {
+    let mut windowed = reminder(2, time(12, 0));
+    windowed.fire_window_end = Some(time(14, 0));
+    let mut dependent = reminder(3, time(6, 0));
+    dependent.dependency = Some(ReminderDependency {
+        predecessor_id: 1,
+        delay: chrono::TimeDelta::zero(),
+    });
+
+    let agenda = build_agenda(
+        &[dependent, windowed, reminder(1, time(8, 30))],
+        utc(0, 0),
+        utc(0, 0) + chrono::TimeDelta::days(1),
+    );
+
+    assert_eq!(
+        agenda
+            .iter()
+            .map(|AgendaItem { time, .. }| *time)
+            .collect::<Vec<_>>(),
+        vec![
+            AgendaTime::At(utc(8, 30)),
+            AgendaTime::Between(utc(12, 0), utc(14, 0)),
+            AgendaTime::AfterPredecessor,
+        ]
+    );
+}
+
+#[test]
+fn agenda_should_follow_local_day() {
+    let mut user = user(None, None);
+    user.timezone = chrono_tz::Asia::Tokyo;
+    // 23:00 UTC is 08:00 of the next day in Tokyo.
+    let reminders = [reminder(1, time(23, 0))];
+
+    let (day_start, day_end) = daily_messages::local_day(&user, utc(22, 0));
+    let agenda = build_agenda(&reminders, day_start, day_end);
+
+    assert_eq!(day_start, utc(15, 0));
+    assert_eq!(agenda[0].time, AgendaTime::At(utc(23, 0)));
+}
+
+#[test]
+fn summary_should_report_best_outcome() {
+    let reminders = [
+        reminder(1, time(9, 0)),
+        reminder(2, time(9, 0)),
+        reminder(3, time(9, 0)),
+        reminder(4, time(9, 0)),
+    ];
+    let occurrences = [
+        occurrence(1, Some(OccurrenceOutcome::Snoozed)),
+        occurrence(1, Some(OccurrenceOutcome::Confirmed)),
+        occurrence(2, Some(OccurrenceOutcome::TimedOut)),
+        occurrence(3, Some(OccurrenceOutcome::Cancelled)),
+    ];
+
+    let summary = build_summary(&reminders, &occurrences);
+
+    assert_eq!(
+        summary
+            .iter()
+            .map(|SummaryItem { outcome, .. }| *outcome)
+            .collect::<Vec<_>>(),
+        vec![
+            SummaryOutcome::Completed,
+            SummaryOutcome::TimedOut,
+            SummaryOutcome::Skipped,
+            SummaryOutcome::Skipped,
+        ]
+    );
+}
+
+#[tokio::test]
+async fn messages_should_be_sent_at_chosen_time() {
+    let channel = RecordingDeliveryChannel::default();
+    let scheduler = DailyMessageScheduler::new(
+        Arc::new(TestSource {
+            users: vec![user(Some(time(7, 0)), Some(time(21, 30)))],
+            reminders: vec![reminder(1, time(9, 0))],
+            occurrences: vec![occurrence(1, Some(OccurrenceOutcome::Confirmed))],
+        }),
+        Arc::new(channel.clone()),
+    );
+
+    scheduler.send_due(utc(7, 0)).await;
+    scheduler.send_due(utc(7, 1)).await;
+    scheduler.send_due(utc(21, 30)).await;
+
+    let sent = channel.sent.lock().unwrap();
+    assert_eq!(sent.len(), 2);
+    assert!(matches!(&sent[0], (1, DailyMessage::Agenda(items)) if items.len() == 1));
+    assert!(matches!(
+        &sent[1],
+        (1, DailyMessage::Summary(items)) if items[0].outcome == SummaryOutcome::Completed
+    ));
+}
+
+#[tokio::test]
+async fn users_without_daily_messages_should_get_nothing() {
+    let channel = RecordingDeliveryChannel::default();
+    let scheduler = DailyMessageScheduler::new(
+        Arc::new(TestSource {
+            users: vec![user(None, None)],
+            reminders: vec![reminder(1, time(9, 0))],
+            occurrences: Vec::new(),
+        }),
+        Arc::new(channel.clone()),
+    );
+
+    scheduler.send_due(utc(7, 0)).await;
+
+    assert!(channel.sent.lock().unwrap().is_empty());
+}
+
+struct ExcludedDay(chrono::NaiveDate);
+
+#[async_trait]
+impl ExclusionCalendar for ExcludedDay {
+    async fn is_excluded(&self, _user_id: &UserId, at: DateTime<Utc>) -> anyhow::Result<bool> {
+        Ok(at.date_naive() == self.0)
+    }
+}
+
+#[tokio::test]
+async fn agenda_of_excluded_day_should_be_empty() {
+    let channel = RecordingDeliveryChannel::default();
+    let scheduler = DailyMessageScheduler::new(
+        Arc::new(TestSource {
+            users: vec![user(Some(time(7, 0)), None)],
+            reminders: vec![reminder(1, time(9, 0))],
+            occurrences: Vec::new(),
+        }),
+        Arc::new(channel.clone()),
+    )
+    .with_exclusions(Arc::new(ExcludedDay(utc(7, 0).date_naive())));
+
+    scheduler.send_due(utc(7, 0)).await;
+
+    let sent = channel.sent.lock().unwrap();
+    assert!(matches!(&sent[..], [(1, DailyMessage::Agenda(items))] if items.is_empty()));
+}
@@ -4,8 +4,15 @@ use std::time::Duration;
 
 use crate::ReminderMessageType;
 use async_trait::async_trait;
-use chrono::{NaiveTime, Utc};
-use nadoeda_models::reminder::{Reminder, ReminderFireTime, ReminderState};
+use chrono::{DateTime, NaiveTime, Timelike, Utc};
+use nadoeda_models::agenda::DailyMessage;
+use nadoeda_models::occurrence::{OccurrenceOutcome, ReminderOccurrence, ReminderOccurrenceId};
+use nadoeda_models::reminder::{
+    ConfirmationChallenge, Reminder, ReminderDependency, ReminderFireTime, ReminderId,
+    ReminderState, ReminderVerification,
+};
+use nadoeda_models::user::{User, UserId};
+use nadoeda_storage::{NewReminderOccurrence, ReminderOccurrenceStorage};
 use proptest::prelude::*;
 use test_strategy::proptest;
 
@@ -28,23 +35,124 @@ impl ReminderDeliveryChannel for TestDeliveryChannel {
         self.received_messages.lock().unwrap().push(message);
         Ok(())
     }
+
+    async fn send_daily_message(
+        &self,
+        _user: &User,
+        _message: &DailyMessage,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+}
+
+type RecordedOccurrences = Arc<Mutex<Vec<ReminderOccurrence>>>;
+
+#[derive(Clone, Default)]
+struct TestOccurrenceStorage {
+    occurrences: RecordedOccurrences,
+}
+
+#[async_trait]
+impl ReminderOccurrenceStorage for TestOccurrenceStorage {
+    type Error = std::convert::Infallible;
+
+    async fn insert(
+        &self,
+        occurrence: NewReminderOccurrence,
+    ) -> Result<ReminderOccurrence, Self::Error> {
+        let mut occurrences = self.occurrences.lock().unwrap();
+        let occurrence = ReminderOccurrence {
+            id: occurrences.len() as ReminderOccurrenceId + 1,
+            reminder_id: occurrence.reminder_id,
+            user_id: occurrence.user_id,
+            scheduled_at: occurrence.scheduled_at,
+            fired_at: None,
+            acknowledged_at: None,
+            confirmed_at: None,
+            outcome: None,
+        };
+        occurrences.push(occurrence.clone());
+
+        Ok(occurrence)
+    }
+
+    async fn update(
+        &self,
+        occurrence: ReminderOccurrence,
+        _user_id: &UserId,
+    ) -> Result<ReminderOccurrence, Self::Error> {
+        let mut occurrences = self.occurrences.lock().unwrap();
+        occurrences[occurrence.id as usize - 1] = occurrence.clone();
+
+        Ok(occurrence)
+    }
+
+    async fn get(
+        &self,
+        id: &ReminderOccurrenceId,
+    ) -> Result<Option<ReminderOccurrence>, Self::Error> {
+        let occurrences = self.occurrences.lock().unwrap();
+        Ok(occurrences.iter().find(|o| o.id == *id).cloned())
+    }
+
+    async fn get_for_reminder(
+        &self,
+        reminder_id: &ReminderId,
+        user_id: &UserId,
+        limit: u32,
+    ) -> Result<Vec<ReminderOccurrence>, Self::Error> {
+        let occurrences = self.occurrences.lock().unwrap();
+        Ok(occurrences
+            .iter()
+            .rev()
+            .filter(|o| o.reminder_id == *reminder_id && o.user_id == *user_id)
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_for_user_between(
+        &self,
+        user_id: &UserId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<ReminderOccurrence>, Self::Error> {
+        let occurrences = self.occurrences.lock().unwrap();
+        Ok(occurrences
+            .iter()
+            .filter(|o| o.user_id == *user_id && o.scheduled_at >= from && o.scheduled_at < to)
+            .cloned()
+            .collect())
+    }
 }
 
 struct TestContext {
     pub received_messages: ReceivedMessages,
+    pub occurrences: RecordedOccurrences,
     pub scheduler: DeliveryReminderScheduler,
 }
 
 impl TestContext {
     fn new() -> Self {
+        Self::with_seed(0)
+    }
+
+    fn with_seed(seed: u64) -> Self {
         let received_messages = Arc::new(Mutex::new(Vec::new()));
         let delivery_channel = TestDeliveryChannel {
             received_messages: received_messages.clone(),
         };
-        let scheduler = DeliveryReminderScheduler::new(Arc::new(delivery_channel.clone()));
+        let occurrence_storage = TestOccurrenceStorage::default();
+        let occurrences = occurrence_storage.occurrences.clone();
+        let scheduler = DeliveryReminderScheduler::with_seed(
+            Arc::new(delivery_channel.clone()),
+            Arc::new(StorageOccurrenceLog::new(Arc::new(occurrence_storage))),
+            seed,
+        );
 
         Self {
             received_messages,
+            occurrences,
             scheduler,
         }
     }
@@ -234,6 +342,256 @@ async fn confirmation_timeout_proptest(#[strategy(time_strategy())] time: NaiveT
     prop_assert_eq!(*msgs.last().unwrap(), ReminderMessageType::Timeout);
 }
 
+#[proptest(async = tokio_ct)]
+async fn confirmed_occurrence_proptest(#[strategy(time_strategy())] time: NaiveTime) {
+    let ctx = TestContext::new();
+    let req = schedule_request(time);
+    let expected_delay = expected_delay(&req.reminder);
+
+    let scheduled_reminder = ctx.scheduler.schedule_reminder(req).await.unwrap();
+
+    wait(expected_delay).await;
+
+    ctx.scheduler
+        .acknowledge_reminder(&scheduled_reminder)
+        .await
+        .unwrap();
+
+    wait(chrono::Duration::from_std(CONFIRMATION_TIMEOUT).unwrap()).await;
+
+    ctx.scheduler
+        .confirm_reminder(&scheduled_reminder)
+        .await
+        .unwrap();
+
+    wait(chrono::Duration::zero()).await;
+
+    let occurrences = ctx.occurrences.lock().unwrap();
+    prop_assert_eq!(occurrences.len(), 1);
+
+    let occurrence = &occurrences[0];
+    prop_assert_eq!(occurrence.scheduled_at.time().hour(), time.hour());
+    prop_assert_eq!(occurrence.scheduled_at.time().minute(), time.minute());
+    prop_assert!(occurrence.fired_at.is_some());
+    prop_assert!(occurrence.acknowledged_at.is_some());
+    prop_assert!(occurrence.confirmed_at.is_some());
+    prop_assert_eq!(occurrence.outcome, Some(OccurrenceOutcome::Confirmed));
+}
+
+#[proptest(async = tokio_ct)]
+async fn cancelled_occurrence_proptest(#[strategy(time_strategy())] time: NaiveTime) {
+    let ctx = TestContext::new();
+    let req = schedule_request(time);
+
+    let scheduled_reminder = ctx.scheduler.schedule_reminder(req).await.unwrap();
+
+    wait(chrono::Duration::zero()).await;
+
+    ctx.scheduler
+        .cancel_reminder(&scheduled_reminder)
+        .await
+        .unwrap();
+
+    wait(chrono::Duration::zero()).await;
+
+    let occurrences = ctx.occurrences.lock().unwrap();
+    prop_assert_eq!(occurrences.len(), 1);
+    prop_assert_eq!(occurrences[0].fired_at, None);
+    prop_assert_eq!(occurrences[0].outcome, Some(OccurrenceOutcome::Cancelled));
+}
+
+#[proptest(async = tokio_ct)]
+async fn timed_out_occurrence_proptest(#[strategy(time_strategy())] time: NaiveTime) {
+    let ctx = TestContext::new();
+    let req = schedule_request(time);
+    let expected_delay = expected_delay(&req.reminder);
+
+    ctx.scheduler.schedule_reminder(req).await.unwrap();
+
+    wait(expected_delay).await;
+
+    let total_nagging_time =
+        chrono::Duration::from_std(NAGGING_TIMEOUT * NAGGING_ATTEMPTS as u32).unwrap();
+
+    wait(total_nagging_time * 2).await;
+
+    let occurrences = ctx.occurrences.lock().unwrap();
+    prop_assert_eq!(occurrences.len(), 1);
+    prop_assert!(occurrences[0].fired_at.is_some());
+    prop_assert_eq!(occurrences[0].acknowledged_at, None);
+    prop_assert_eq!(occurrences[0].outcome, Some(OccurrenceOutcome::TimedOut));
+}
+
+#[proptest(async = tokio_ct)]
+async fn dependent_fires_after_predecessor_finished_proptest(
+    #[strategy(time_strategy())] time: NaiveTime,
+    #[strategy(0i64..3600)] delay_seconds: i64,
+) {
+    let ctx = TestContext::new();
+    let req = schedule_request(time);
+    let expected_delay = expected_delay(&req.reminder);
+    let dependency_delay = chrono::Duration::seconds(delay_seconds);
+
+    let predecessor = ctx.scheduler.schedule_reminder(req).await.unwrap();
+    ctx.scheduler
+        .schedule_reminder(dependent_request(dependency_delay))
+        .await
+        .unwrap();
+
+    wait(expected_delay).await;
+
+    ctx.scheduler
+        .acknowledge_reminder(&predecessor)
+        .await
+        .unwrap();
+    wait(chrono::Duration::from_std(CONFIRMATION_TIMEOUT).unwrap()).await;
+    ctx.scheduler.confirm_reminder(&predecessor).await.unwrap();
+
+    wait(dependency_delay).await;
+
+    let msgs = ctx.received_messages.lock().unwrap();
+    let fired_count = msgs
+        .iter()
+        .filter(|i| matches!(i, ReminderMessageType::Fired))
+        .count();
+
+    prop_assert_eq!(fired_count, 2);
+    prop_assert_eq!(
+        &msgs[msgs.len() - 2..],
+        &[ReminderMessageType::Finished, ReminderMessageType::Fired]
+    );
+}
+
+#[proptest(async = tokio_ct)]
+async fn dependent_stays_dormant_when_predecessor_times_out_proptest(
+    #[strategy(time_strategy())] time: NaiveTime,
+) {
+    let ctx = TestContext::new();
+    let req = schedule_request(time);
+    let expected_delay = expected_delay(&req.reminder);
+
+    ctx.scheduler.schedule_reminder(req).await.unwrap();
+    ctx.scheduler
+        .schedule_reminder(dependent_request(chrono::Duration::zero()))
+        .await
+        .unwrap();
+
+    wait(expected_delay).await;
+
+    let total_nagging_time =
+        chrono::Duration::from_std(NAGGING_TIMEOUT * NAGGING_ATTEMPTS as u32).unwrap();
+
+    wait(total_nagging_time * 2).await;
+
+    let msgs = ctx.received_messages.lock().unwrap();
+    let fired_count = msgs
+        .iter()
+        .filter(|i| matches!(i, ReminderMessageType::Fired))
+        .count();
+
+    prop_assert_eq!(fired_count, 1);
+    prop_assert_eq!(*msgs.last().unwrap(), ReminderMessageType::Timeout);
+}
+
+#[proptest(async = tokio_ct)]
+async fn windowed_occurrence_proptest(
+    #[strategy(time_strategy())] time: NaiveTime,
+    #[strategy(1i64..12 * 3600)] window_seconds: i64,
+    seed: u64,
+) {
+    let window = chrono::Duration::seconds(window_seconds);
+    let offsets = [
+        scheduled_window_offset(time, window, seed).await,
+        scheduled_window_offset(time, window, seed).await,
+    ];
+
+    prop_assert!(offsets[0] >= chrono::Duration::zero());
+    prop_assert!(offsets[0] <= window, "offset = {:?}", offsets[0]);
+    prop_assert_eq!(
+        offsets[0],
+        offsets[1],
+        "Same seed should pick the same moment"
+    );
+}
+
+struct TestExclusions(Vec<chrono::NaiveDate>);
+
+#[async_trait]
+impl ExclusionCalendar for TestExclusions {
+    async fn is_excluded(&self, _user_id: &UserId, at: DateTime<Utc>) -> anyhow::Result<bool> {
+        Ok(self.0.contains(&at.date_naive()))
+    }
+}
+
+#[proptest(async = tokio_ct)]
+async fn excluded_days_proptest(
+    #[strategy(time_strategy())] time: NaiveTime,
+    #[strategy(1i64..5)] excluded_days: i64,
+) {
+    let mut ctx = TestContext::new();
+    let req = schedule_request(time);
+    let first = next_fire_at(&req.reminder, Utc::now());
+    let excluded = (0..excluded_days)
+        .map(|day| (first + chrono::Duration::days(day)).date_naive())
+        .collect();
+    ctx.scheduler = ctx
+        .scheduler
+        .with_exclusions(Arc::new(TestExclusions(excluded)));
+
+    ctx.scheduler.schedule_reminder(req).await.unwrap();
+    wait(first - Utc::now()).await;
+
+    let msgs = ctx.received_messages.lock().unwrap();
+    prop_assert_eq!(&msgs[..], &[ReminderMessageType::Scheduled]);
+
+    let occurrences = ctx.occurrences.lock().unwrap();
+    prop_assert_eq!(
+        occurrences[0].scheduled_at.date_naive(),
+        (first + chrono::Duration::days(excluded_days)).date_naive()
+    );
+}
+
+#[proptest(async = tokio_ct)]
+async fn not_before_proptest(#[strategy(time_strategy())] time: NaiveTime) {
+    let ctx = TestContext::new();
+    let req = schedule_request(time);
+    let first = next_fire_at(&req.reminder, Utc::now());
+
+    ctx.scheduler
+        .schedule_reminder(req.not_before(first))
+        .await
+        .unwrap();
+    wait(chrono::Duration::zero()).await;
+
+    let occurrences = ctx.occurrences.lock().unwrap();
+    prop_assert_eq!(
+        occurrences[0].scheduled_at.date_naive(),
+        (first + chrono::Duration::days(1)).date_naive()
+    );
+}
+
+/// Schedules a windowed reminder and returns how far into the window the occurrence landed.
+async fn scheduled_window_offset(
+    time: NaiveTime,
+    window: chrono::Duration,
+    seed: u64,
+) -> chrono::Duration {
+    let ctx = TestContext::with_seed(seed);
+    let mut req = schedule_request(time);
+    req.reminder.fire_window_end = Some(ReminderFireTime::new(time + window));
+
+    ctx.scheduler.schedule_reminder(req).await.unwrap();
+    wait(chrono::Duration::zero()).await;
+
+    let occurrences = ctx.occurrences.lock().unwrap();
+    let offset = occurrences[0].scheduled_at.time() - time;
+    if offset < chrono::Duration::zero() {
+        offset + chrono::Duration::days(1)
+    } else {
+        offset
+    }
+}
+
 async fn wait(duration: chrono::Duration) {
     tokio::time::sleep(duration.to_std().unwrap() + std::time::Duration::from_secs(1)).await;
 }
@@ -248,12 +606,27 @@ fn reminder_at(time: NaiveTime) -> Reminder {
         user_id: 1,
         state: ReminderState::Pending,
         fire_at: ReminderFireTime::new(time),
+        fire_window_end: None,
         text: "Reminder Text".to_owned(),
+        verification: ReminderVerification::None,
+        challenge: ConfirmationChallenge::None,
+        dependency: None,
+        checklist: Vec::new(),
+        version: 0,
     }
 }
 
+fn dependent_request(delay: chrono::Duration) -> ScheduleRequest {
+    let mut reminder = reminder_at(NaiveTime::MIN);
+    reminder.id = 2;
+    reminder.dependency = Some(ReminderDependency {
+        predecessor_id: 1,
+        delay,
+    });
+
+    ScheduleRequest::new(reminder)
+}
+
 fn schedule_request(time: NaiveTime) -> ScheduleRequest {
-    ScheduleRequest {
-        reminder: reminder_at(time),
-    }
+    ScheduleRequest::new(reminder_at(time))
 }
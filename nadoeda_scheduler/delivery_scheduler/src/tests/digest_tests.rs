@@ -0,0 +1,222 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::NaiveTime;
+use nadoeda_models::agenda::DailyMessage;
+use nadoeda_models::reminder::{
+    ConfirmationChallenge, Reminder, ReminderFireTime, ReminderId, ReminderState,
+    ReminderVerification,
+};
+use nadoeda_models::user::{User, UserId};
+
+use super::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Sent {
+    Single(ReminderId, ReminderMessageType),
+    Digest(Vec<ReminderId>, ReminderMessageType),
+    Daily(UserId),
+}
+
+#[derive(Clone, Default)]
+struct RecordingDeliveryChannel {
+    sent: Arc<Mutex<Vec<Sent>>>,
+}
+
+#[async_trait]
+impl ReminderDeliveryChannel for RecordingDeliveryChannel {
+    async fn send_reminder_notification(
+        &self,
+        reminder: &Reminder,
+        message: ReminderMessageType,
+    ) -> Result<(), Box<dyn Error>> {
+        self.sent
+            .lock()
+            .unwrap()
+            .push(Sent::Single(reminder.id, message));
+        Ok(())
+    }
+
+    async fn send_digest_notification(
+        &self,
+        reminders: &[Reminder],
+        message: ReminderMessageType,
+    ) -> Result<(), Box<dyn Error>> {
+        let ids = reminders.iter().map(|reminder| reminder.id).collect();
+        self.sent.lock().unwrap().push(Sent::Digest(ids, message));
+        Ok(())
+    }
+
+    async fn send_daily_message(
+        &self,
+        user: &User,
+        _message: &DailyMessage,
+    ) -> Result<(), Box<dyn Error>> {
+        self.sent.lock().unwrap().push(Sent::Daily(user.id));
+        Ok(())
+    }
+}
+
+struct TestDigestPreferences(HashSet<UserId>);
+
+#[async_trait]
+impl DigestPreferences for TestDigestPreferences {
+    async fn is_digest_enabled(&self, user_id: &UserId) -> anyhow::Result<bool> {
+        Ok(self.0.contains(user_id))
+    }
+}
+
+const DIGEST_USER: UserId = 1;
+const REGULAR_USER: UserId = 2;
+
+fn digest_channel() -> (DigestDeliveryChannel, RecordingDeliveryChannel) {
+    let inner = RecordingDeliveryChannel::default();
+    let preferences = TestDigestPreferences(HashSet::from([DIGEST_USER]));
+    let channel = DigestDeliveryChannel::new(Arc::new(inner.clone()), Arc::new(preferences));
+
+    (channel, inner)
+}
+
+fn reminder(id: ReminderId, user_id: UserId) -> Reminder {
+    Reminder {
+        id,
+        user_id,
+        state: ReminderState::Pending,
+        fire_at: ReminderFireTime::new(NaiveTime::from_hms_opt(9, 0, 0).unwrap()),
+        fire_window_end: None,
+        text: format!("Reminder {id}"),
+        verification: ReminderVerification::None,
+        challenge: ConfirmationChallenge::None,
+        dependency: None,
+        checklist: Vec::new(),
+        version: 0,
+    }
+}
+
+async fn send(channel: &DigestDeliveryChannel, reminder: &Reminder, message: ReminderMessageType) {
+    channel
+        .send_reminder_notification(reminder, message)
+        .await
+        .unwrap();
+}
+
+async fn wait_for_digest() {
+    tokio::time::sleep(DIGEST_WINDOW + Duration::from_secs(1)).await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn fired_reminders_within_window_should_be_merged() {
+    let (channel, inner) = digest_channel();
+
+    for id in 1..=3 {
+        send(
+            &channel,
+            &reminder(id, DIGEST_USER),
+            ReminderMessageType::Fired,
+        )
+        .await;
+    }
+
+    assert!(inner.sent.lock().unwrap().is_empty());
+
+    wait_for_digest().await;
+
+    assert_eq!(
+        *inner.sent.lock().unwrap(),
+        vec![Sent::Digest(vec![1, 2, 3], ReminderMessageType::Fired)]
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn nags_should_be_merged_separately_from_fired_reminders() {
+    let (channel, inner) = digest_channel();
+
+    send(
+        &channel,
+        &reminder(1, DIGEST_USER),
+        ReminderMessageType::Fired,
+    )
+    .await;
+    send(
+        &channel,
+        &reminder(2, DIGEST_USER),
+        ReminderMessageType::Nag,
+    )
+    .await;
+    send(
+        &channel,
+        &reminder(3, DIGEST_USER),
+        ReminderMessageType::Nag,
+    )
+    .await;
+
+    wait_for_digest().await;
+
+    let sent = inner.sent.lock().unwrap();
+    assert_eq!(sent.len(), 2);
+    assert!(sent.contains(&Sent::Single(1, ReminderMessageType::Fired)));
+    assert!(sent.contains(&Sent::Digest(vec![2, 3], ReminderMessageType::Nag)));
+}
+
+#[tokio::test(start_paused = true)]
+async fn reminders_after_window_should_start_new_digest() {
+    let (channel, inner) = digest_channel();
+
+    send(
+        &channel,
+        &reminder(1, DIGEST_USER),
+        ReminderMessageType::Fired,
+    )
+    .await;
+    wait_for_digest().await;
+    send(
+        &channel,
+        &reminder(2, DIGEST_USER),
+        ReminderMessageType::Fired,
+    )
+    .await;
+    wait_for_digest().await;
+
+    assert_eq!(
+        *inner.sent.lock().unwrap(),
+        vec![
+            Sent::Single(1, ReminderMessageType::Fired),
+            Sent::Single(2, ReminderMessageType::Fired)
+        ]
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn regular_users_and_other_messages_should_pass_through() {
+    let (channel, inner) = digest_channel();
+
+    send(
+        &channel,
+        &reminder(1, REGULAR_USER),
+        ReminderMessageType::Fired,
+    )
+    .await;
+    send(
+        &channel,
+        &reminder(2, REGULAR_USER),
+        ReminderMessageType::Fired,
+    )
+    .await;
+    send(
+        &channel,
+        &reminder(3, DIGEST_USER),
+        ReminderMessageType::Scheduled,
+    )
+    .await;
+
+    assert_eq!(
+        *inner.sent.lock().unwrap(),
+        vec![
+            Sent::Single(1, ReminderMessageType::Fired),
+            Sent::Single(2, ReminderMessageType::Fired),
+            Sent::Single(3, ReminderMessageType::Scheduled)
+        ]
+    );
+}
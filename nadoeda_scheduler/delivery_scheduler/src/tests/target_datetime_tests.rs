@@ -6,11 +6,12 @@ use chrono::NaiveTime;
 use chrono::Timelike;
 use nadoeda_models::reminder::ReminderFireTime;
 use proptest_arbitrary_interop::arb;
+use rand::{SeedableRng, rngs::StdRng};
 
 #[test]
 pub fn when_firing_time_is_yet_to_come_target_delay_should_be_less_than_day() {
     let now_utc = NaiveDateTime::new(
-        NaiveDate::from_ymd_opt(2025, 05, 31).unwrap(),
+        NaiveDate::from_ymd_opt(2025, 5, 31).unwrap(),
         NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
     );
     let now = DateTime::from_naive_utc_and_offset(now_utc, Utc);
@@ -25,10 +26,34 @@ pub fn when_firing_time_is_yet_to_come_target_delay_should_be_less_than_day() {
     );
 }
 
+#[test]
+pub fn when_fire_window_is_empty_offset_should_be_zero() {
+    let mut rng = StdRng::seed_from_u64(7);
+
+    assert_eq!(
+        get_fire_offset(TimeDelta::zero(), &mut rng),
+        TimeDelta::zero()
+    );
+}
+
+#[test]
+pub fn when_seed_is_the_same_fire_offsets_should_repeat() {
+    let window = TimeDelta::hours(3);
+    let offsets = |seed| {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..10)
+            .map(|_| get_fire_offset(window, &mut rng))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(offsets(42), offsets(42));
+    assert_ne!(offsets(42), offsets(43));
+}
+
 #[test]
 pub fn when_firing_time_is_passed_target_delay_should_be_next_day() {
     let now_utc = NaiveDateTime::new(
-        NaiveDate::from_ymd_opt(2025, 05, 31).unwrap(),
+        NaiveDate::from_ymd_opt(2025, 5, 31).unwrap(),
         NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
     );
     let now = DateTime::from_naive_utc_and_offset(now_utc, Utc);
@@ -58,4 +83,16 @@ proptest::proptest! {
         assert!(target_datetime.time() == fire_at, "Target time should be equal to fire_at time specified in the reminder. fire_at = {:?}, target_datetime.time() = {:?}, target_datetime = {:?}", fire_at, target_datetime.time(), target_datetime);
         assert!(delay.num_days() <= 1, "Delay should be one day or less. delay.days = {}", delay.num_days())
     }
+
+    #[test]
+    fn test_fire_offset_stays_inside_window(
+        seed in proptest::prelude::any::<u64>(),
+        window_seconds in 0i64..86_400
+    ) {
+        let window = TimeDelta::seconds(window_seconds);
+        let offset = get_fire_offset(window, &mut StdRng::seed_from_u64(seed));
+
+        assert!(offset >= TimeDelta::zero(), "Offset should not be negative. offset = {:?}", offset);
+        assert!(offset <= window, "Offset should not leave the window. offset = {:?}, window = {:?}", offset, window);
+    }
 }
@@ -1,9 +1,9 @@
 use std::error::Error;
 
 use async_trait::async_trait;
-use nadoeda_models::reminder::Reminder;
+use nadoeda_models::{agenda::DailyMessage, reminder::Reminder, user::User};
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum ReminderMessageType {
     Scheduled,
     Fired,
@@ -22,4 +22,25 @@ pub trait ReminderDeliveryChannel: Send + Sync {
         reminder: &Reminder,
         message: ReminderMessageType,
     ) -> Result<(), Box<dyn Error>>;
+
+    /// Sends the daily agenda or summary to the user.
+    async fn send_daily_message(
+        &self,
+        user: &User,
+        message: &DailyMessage,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Sends the same message for several reminders of one user at once.
+    /// Channels that can't merge messages send them one by one.
+    async fn send_digest_notification(
+        &self,
+        reminders: &[Reminder],
+        message: ReminderMessageType,
+    ) -> Result<(), Box<dyn Error>> {
+        for reminder in reminders {
+            self.send_reminder_notification(reminder, message).await?;
+        }
+
+        Ok(())
+    }
 }
@@ -1,14 +1,25 @@
 use async_trait::async_trait;
 
+use nadoeda_models::chrono::{DateTime, Utc};
 use nadoeda_models::reminder::{Reminder, ReminderId};
 
 pub struct ScheduleRequest {
     pub reminder: Reminder,
+    /// The first firing happens after this moment, e.g. for reminders starting tomorrow.
+    pub not_before: Option<DateTime<Utc>>,
 }
 
 impl ScheduleRequest {
     pub fn new(reminder: Reminder) -> Self {
-        Self { reminder }
+        Self {
+            reminder,
+            not_before: None,
+        }
+    }
+
+    pub fn not_before(mut self, at: DateTime<Utc>) -> Self {
+        self.not_before = Some(at);
+        self
     }
 }
 
@@ -0,0 +1,27 @@
+CREATE TABLE IF NOT EXISTS reminder_buddies (
+       id                INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+       reminder_id       INTEGER NOT NULL UNIQUE,
+       user_id           INTEGER NOT NULL,
+       buddy_user_id     INTEGER NULL,
+       buddy_tg_chat_id  INTEGER NULL,
+       status            TEXT NOT NULL,
+
+       FOREIGN KEY (reminder_id)
+       REFERENCES reminders(id)
+       ON DELETE CASCADE
+       ON UPDATE CASCADE,
+
+       FOREIGN KEY (user_id)
+       REFERENCES users(id)
+       ON DELETE CASCADE
+       ON UPDATE CASCADE,
+
+       FOREIGN KEY (buddy_user_id)
+       REFERENCES users(id)
+       ON DELETE CASCADE
+       ON UPDATE CASCADE,
+
+       CHECK ((buddy_user_id IS NULL) <> (buddy_tg_chat_id IS NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_reminder_buddies_user_id ON reminder_buddies(user_id);
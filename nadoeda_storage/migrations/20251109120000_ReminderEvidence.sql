@@ -0,0 +1,23 @@
+ALTER TABLE reminders ADD COLUMN verification_kind TEXT NOT NULL DEFAULT 'None';
+ALTER TABLE reminders ADD COLUMN verification_keyword TEXT NULL;
+
+CREATE TABLE IF NOT EXISTS reminder_evidence (
+       id              INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+       reminder_id     INTEGER NOT NULL,
+       user_id         INTEGER NOT NULL,
+       photo_file_id   TEXT NULL,
+       text            TEXT NULL,
+       submitted_at    TEXT NOT NULL,
+
+       FOREIGN KEY (reminder_id)
+       REFERENCES reminders(id)
+       ON DELETE CASCADE
+       ON UPDATE CASCADE,
+
+       FOREIGN KEY (user_id)
+       REFERENCES users(id)
+       ON DELETE CASCADE
+       ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_reminder_evidence_reminder_id ON reminder_evidence(reminder_id);
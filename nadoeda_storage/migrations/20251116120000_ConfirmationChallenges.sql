@@ -0,0 +1,21 @@
+ALTER TABLE reminders ADD COLUMN challenge_kind TEXT NOT NULL DEFAULT 'None';
+
+CREATE TABLE IF NOT EXISTS reminder_challenge_attempts (
+       id              INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+       reminder_id     INTEGER NOT NULL,
+       user_id         INTEGER NOT NULL,
+       correct         BOOLEAN NOT NULL,
+       answered_at     TEXT NOT NULL,
+
+       FOREIGN KEY (reminder_id)
+       REFERENCES reminders(id)
+       ON DELETE CASCADE
+       ON UPDATE CASCADE,
+
+       FOREIGN KEY (user_id)
+       REFERENCES users(id)
+       ON DELETE CASCADE
+       ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_reminder_challenge_attempts_reminder_id ON reminder_challenge_attempts(reminder_id);
@@ -0,0 +1,23 @@
+CREATE TABLE IF NOT EXISTS reminder_occurrences (
+       id              INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+       reminder_id     INTEGER NOT NULL,
+       user_id         INTEGER NOT NULL,
+       scheduled_at    TEXT NOT NULL,
+       fired_at        TEXT NULL,
+       acknowledged_at TEXT NULL,
+       confirmed_at    TEXT NULL,
+       outcome         TEXT NULL,
+
+       FOREIGN KEY (reminder_id)
+       REFERENCES reminders(id)
+       ON DELETE CASCADE
+       ON UPDATE CASCADE,
+
+       FOREIGN KEY (user_id)
+       REFERENCES users(id)
+       ON DELETE CASCADE
+       ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_reminder_occurrences_reminder_id ON reminder_occurrences(reminder_id);
+CREATE INDEX IF NOT EXISTS idx_reminder_occurrences_user_id_scheduled_at ON reminder_occurrences(user_id, scheduled_at);
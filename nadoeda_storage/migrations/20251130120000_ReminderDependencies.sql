@@ -0,0 +1,4 @@
+ALTER TABLE reminders ADD COLUMN predecessor_id INTEGER NULL REFERENCES reminders(id) ON DELETE SET NULL;
+ALTER TABLE reminders ADD COLUMN predecessor_delay_seconds INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_reminders_predecessor_id ON reminders(predecessor_id);
@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS reminder_checklist_items (
+       id              INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+       reminder_id     INTEGER NOT NULL,
+       position        INTEGER NOT NULL,
+       text            TEXT NOT NULL,
+       checked         BOOLEAN NOT NULL DEFAULT FALSE,
+
+       FOREIGN KEY (reminder_id)
+       REFERENCES reminders(id)
+       ON DELETE CASCADE
+       ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_reminder_checklist_items_reminder_id ON reminder_checklist_items(reminder_id, position);
@@ -0,0 +1 @@
+ALTER TABLE reminders ADD COLUMN fire_window_end TEXT NULL;
@@ -0,0 +1,2 @@
+ALTER TABLE users ADD COLUMN agenda_at TEXT NULL;      -- stored as HH:MM:SS
+ALTER TABLE users ADD COLUMN summary_at TEXT NULL;     -- stored as HH:MM:SS
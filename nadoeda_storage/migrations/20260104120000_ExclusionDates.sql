@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS user_exclusion_dates (
+       id       INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+       user_id  INTEGER NOT NULL,
+       date     TEXT NOT NULL,
+       label    TEXT NULL,
+
+       UNIQUE (user_id, date),
+
+       FOREIGN KEY (user_id)
+       REFERENCES users(id)
+       ON DELETE CASCADE
+       ON UPDATE CASCADE
+);
@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS reminder_message_templates (
+       id              INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+       reminder_id     INTEGER NOT NULL,
+       kind            TEXT NOT NULL, -- fired, nag, confirmation or timeout
+       position        INTEGER NOT NULL,
+       text            TEXT NOT NULL,
+
+       FOREIGN KEY (reminder_id)
+       REFERENCES reminders(id)
+       ON DELETE CASCADE
+       ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_reminder_message_templates_reminder_id ON reminder_message_templates(reminder_id, kind, position);
@@ -0,0 +1,3 @@
+ALTER TABLE reminders ADD COLUMN archived_at TEXT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_reminders_archived_at ON reminders(archived_at);
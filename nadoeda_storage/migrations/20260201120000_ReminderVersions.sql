@@ -0,0 +1,2 @@
+-- Bumped on every update so that edits of an outdated copy can be rejected.
+ALTER TABLE reminders ADD COLUMN version INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,18 @@
+-- Evidence belongs to the occurrence it confirmed.
+ALTER TABLE reminder_evidence ADD COLUMN occurrence_id INTEGER NULL
+       REFERENCES reminder_occurrences(id)
+       ON DELETE CASCADE
+       ON UPDATE CASCADE;
+
+-- Older evidence goes to the last occurrence scheduled before it was submitted.
+UPDATE reminder_evidence
+SET occurrence_id = (
+       SELECT o.id
+       FROM reminder_occurrences o
+       WHERE o.reminder_id = reminder_evidence.reminder_id
+         AND o.scheduled_at <= reminder_evidence.submitted_at
+       ORDER BY o.scheduled_at DESC
+       LIMIT 1
+);
+
+CREATE INDEX IF NOT EXISTS idx_reminder_evidence_occurrence_id ON reminder_evidence(occurrence_id);
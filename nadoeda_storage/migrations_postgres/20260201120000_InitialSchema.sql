@@ -0,0 +1,42 @@
+-- Mirrors the SQLite schema for users and reminders. Times of day are stored as HH:MM:SS and
+-- moments as RFC 3339 text in UTC, so both backends share their row models.
+
+CREATE TABLE IF NOT EXISTS users (
+       id               BIGSERIAL PRIMARY KEY,
+       timezone         TEXT NOT NULL,
+       tg_chat_id       BIGINT UNIQUE,
+       digest_mode      BOOLEAN NOT NULL DEFAULT FALSE,
+       agenda_at        TEXT NULL,
+       summary_at       TEXT NULL,
+       language         TEXT NOT NULL DEFAULT 'en'
+);
+
+CREATE TABLE IF NOT EXISTS reminders (
+       id                         BIGSERIAL PRIMARY KEY,
+       user_id                    BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE ON UPDATE CASCADE,
+       state_kind                 TEXT NOT NULL,
+       attempts_left              BIGINT NULL,
+       fire_at                    TEXT NOT NULL,
+       fire_window_end            TEXT NULL,
+       text                       TEXT NOT NULL,
+       verification_kind          TEXT NOT NULL DEFAULT 'None',
+       verification_keyword       TEXT NULL,
+       challenge_kind             TEXT NOT NULL DEFAULT 'None',
+       predecessor_id             BIGINT NULL REFERENCES reminders(id) ON DELETE SET NULL,
+       predecessor_delay_seconds  BIGINT NOT NULL DEFAULT 0,
+       archived_at                TEXT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_reminders_user_id ON reminders(user_id);
+CREATE INDEX IF NOT EXISTS idx_reminders_predecessor_id ON reminders(predecessor_id);
+CREATE INDEX IF NOT EXISTS idx_reminders_archived_at ON reminders(archived_at);
+
+CREATE TABLE IF NOT EXISTS reminder_checklist_items (
+       id              BIGSERIAL PRIMARY KEY,
+       reminder_id     BIGINT NOT NULL REFERENCES reminders(id) ON DELETE CASCADE ON UPDATE CASCADE,
+       position        BIGINT NOT NULL,
+       text            TEXT NOT NULL,
+       checked         BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX IF NOT EXISTS idx_reminder_checklist_items_reminder_id ON reminder_checklist_items(reminder_id, position);
@@ -0,0 +1,72 @@
+-- Mirrors the SQLite tables around reminders: buddies, evidence, challenge attempts,
+-- occurrences, exclusion dates and message templates. Their row models hold chrono types, so
+-- moments are TIMESTAMPTZ and days are DATE here.
+
+CREATE TABLE IF NOT EXISTS reminder_buddies (
+       id                BIGSERIAL PRIMARY KEY,
+       reminder_id       BIGINT NOT NULL UNIQUE REFERENCES reminders(id) ON DELETE CASCADE ON UPDATE CASCADE,
+       user_id           BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE ON UPDATE CASCADE,
+       buddy_user_id     BIGINT NULL REFERENCES users(id) ON DELETE CASCADE ON UPDATE CASCADE,
+       buddy_tg_chat_id  BIGINT NULL,
+       status            TEXT NOT NULL,
+
+       CHECK ((buddy_user_id IS NULL) <> (buddy_tg_chat_id IS NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_reminder_buddies_user_id ON reminder_buddies(user_id);
+
+CREATE TABLE IF NOT EXISTS reminder_occurrences (
+       id              BIGSERIAL PRIMARY KEY,
+       reminder_id     BIGINT NOT NULL REFERENCES reminders(id) ON DELETE CASCADE ON UPDATE CASCADE,
+       user_id         BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE ON UPDATE CASCADE,
+       scheduled_at    TIMESTAMPTZ NOT NULL,
+       fired_at        TIMESTAMPTZ NULL,
+       acknowledged_at TIMESTAMPTZ NULL,
+       confirmed_at    TIMESTAMPTZ NULL,
+       outcome         TEXT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_reminder_occurrences_reminder_id ON reminder_occurrences(reminder_id);
+CREATE INDEX IF NOT EXISTS idx_reminder_occurrences_user_id_scheduled_at ON reminder_occurrences(user_id, scheduled_at);
+
+CREATE TABLE IF NOT EXISTS reminder_evidence (
+       id              BIGSERIAL PRIMARY KEY,
+       reminder_id     BIGINT NOT NULL REFERENCES reminders(id) ON DELETE CASCADE ON UPDATE CASCADE,
+       user_id         BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE ON UPDATE CASCADE,
+       occurrence_id   BIGINT NULL REFERENCES reminder_occurrences(id) ON DELETE CASCADE ON UPDATE CASCADE,
+       photo_file_id   TEXT NULL,
+       text            TEXT NULL,
+       submitted_at    TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_reminder_evidence_reminder_id ON reminder_evidence(reminder_id);
+CREATE INDEX IF NOT EXISTS idx_reminder_evidence_occurrence_id ON reminder_evidence(occurrence_id);
+
+CREATE TABLE IF NOT EXISTS reminder_challenge_attempts (
+       id              BIGSERIAL PRIMARY KEY,
+       reminder_id     BIGINT NOT NULL REFERENCES reminders(id) ON DELETE CASCADE ON UPDATE CASCADE,
+       user_id         BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE ON UPDATE CASCADE,
+       correct         BOOLEAN NOT NULL,
+       answered_at     TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_reminder_challenge_attempts_reminder_id ON reminder_challenge_attempts(reminder_id);
+
+CREATE TABLE IF NOT EXISTS user_exclusion_dates (
+       id       BIGSERIAL PRIMARY KEY,
+       user_id  BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE ON UPDATE CASCADE,
+       date     DATE NOT NULL,
+       label    TEXT NULL,
+
+       UNIQUE (user_id, date)
+);
+
+CREATE TABLE IF NOT EXISTS reminder_message_templates (
+       id              BIGSERIAL PRIMARY KEY,
+       reminder_id     BIGINT NOT NULL REFERENCES reminders(id) ON DELETE CASCADE ON UPDATE CASCADE,
+       kind            TEXT NOT NULL, -- fired, nag, confirmation or timeout
+       position        BIGINT NOT NULL,
+       text            TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_reminder_message_templates_reminder_id ON reminder_message_templates(reminder_id, kind, position);
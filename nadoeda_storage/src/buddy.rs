@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use nadoeda_models::{
+    buddy::{BuddyInvitationStatus, BuddyTarget, ReminderBuddy, ReminderBuddyId},
+    reminder::ReminderId,
+    user::UserId,
+};
+
+pub struct NewReminderBuddy {
+    pub reminder_id: ReminderId,
+    pub user_id: UserId,
+    pub target: BuddyTarget,
+}
+
+#[async_trait]
+pub trait ReminderBuddyStorage: Send + Sync {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    async fn get(&self, id: &ReminderBuddyId) -> Result<Option<ReminderBuddy>, Self::Error>;
+    async fn get_for_reminder(
+        &self,
+        reminder_id: &ReminderId,
+    ) -> Result<Option<ReminderBuddy>, Self::Error>;
+
+    /// Creates a pending invitation, replacing any buddy previously assigned to the reminder.
+    /// Fails with `NotFound` unless the reminder is an active one of the user.
+    async fn invite(&self, new_buddy: NewReminderBuddy) -> Result<ReminderBuddy, Self::Error>;
+    /// Answers the invitation from the Telegram chat it was sent to: the invited chat itself or
+    /// the chat of the invited user.
+    async fn set_status(
+        &self,
+        id: &ReminderBuddyId,
+        tg_chat_id: i64,
+        status: BuddyInvitationStatus,
+    ) -> Result<ReminderBuddy, Self::Error>;
+}
@@ -0,0 +1,20 @@
+use async_trait::async_trait;
+use nadoeda_models::{
+    challenge::ChallengeAttempt,
+    chrono::{DateTime, Utc},
+    reminder::ReminderId,
+    user::UserId,
+};
+
+#[async_trait]
+pub trait ChallengeAttemptStorage: Send + Sync {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    async fn record(&self, attempt: ChallengeAttempt) -> Result<(), Self::Error>;
+    async fn count_wrong_answers(
+        &self,
+        reminder_id: &ReminderId,
+        user_id: &UserId,
+        since: DateTime<Utc>,
+    ) -> Result<u32, Self::Error>;
+}
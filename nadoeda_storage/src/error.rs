@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+/// Error of the storages that have no failures of their own besides a missing record.
+#[derive(Debug, Error)]
+pub enum StorageError {
+    #[error(transparent)]
+    Sqlx(#[from] sqlx::Error),
+    #[error("Record does not exist")]
+    NotFound,
+}
@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+use nadoeda_models::{
+    chrono::{DateTime, Utc},
+    evidence::ReminderEvidence,
+    occurrence::ReminderOccurrenceId,
+    reminder::ReminderId,
+    user::UserId,
+};
+
+pub struct NewReminderEvidence {
+    pub reminder_id: ReminderId,
+    pub occurrence_id: ReminderOccurrenceId,
+    pub user_id: UserId,
+    pub photo_file_id: Option<String>,
+    pub text: Option<String>,
+    pub submitted_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait ReminderEvidenceStorage: Send + Sync {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Stores proof for an occurrence of one of the user's reminders, fails with `NotFound`
+    /// otherwise.
+    async fn insert(&self, evidence: NewReminderEvidence) -> Result<ReminderEvidence, Self::Error>;
+
+    /// Returns the evidence submitted for the occurrence, oldest first.
+    async fn get_for_occurrence(
+        &self,
+        occurrence_id: &ReminderOccurrenceId,
+        user_id: &UserId,
+    ) -> Result<Vec<ReminderEvidence>, Self::Error>;
+}
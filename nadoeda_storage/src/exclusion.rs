@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+use nadoeda_models::{chrono::NaiveDate, exclusion::ExclusionDate, user::UserId};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewExclusionDate {
+    pub date: NaiveDate,
+    pub label: Option<String>,
+}
+
+#[async_trait]
+pub trait ExclusionDateStorage: Send + Sync {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Adds the dates, skipping the ones already excluded. Returns how many were added.
+    async fn add(&self, user_id: &UserId, dates: Vec<NewExclusionDate>)
+    -> Result<u64, Self::Error>;
+
+    /// Returns `false` when the date was not excluded.
+    async fn remove(&self, user_id: &UserId, date: NaiveDate) -> Result<bool, Self::Error>;
+
+    async fn is_excluded(&self, user_id: &UserId, date: NaiveDate) -> Result<bool, Self::Error>;
+
+    /// Returns excluded dates from `from` onwards, earliest first.
+    async fn get_upcoming(
+        &self,
+        user_id: &UserId,
+        from: NaiveDate,
+        limit: u32,
+    ) -> Result<Vec<ExclusionDate>, Self::Error>;
+}
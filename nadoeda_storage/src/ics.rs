@@ -0,0 +1,566 @@
+//! Just enough iCalendar (RFC 5545) to exchange calendars with other apps.
+
+use nadoeda_models::chrono::{
+    DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone, Utc,
+};
+use nadoeda_models::chrono_tz::Tz;
+use nadoeda_models::reminder::{Reminder, ReminderFireTime};
+use nadoeda_models::user::UserId;
+use thiserror::Error;
+
+use crate::exclusion::NewExclusionDate;
+use crate::reminder::NewReminder;
+
+const PRODUCT_ID: &str = "-//nadoeda//reminders//EN";
+
+/// Content lines longer than this many octets are folded.
+const MAX_LINE_LENGTH: usize = 75;
+
+/// Longest event accepted as exclusion dates, so a broken file can't exclude years at once.
+const MAX_EXCLUDED_DAYS: i64 = 366;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum IcsError {
+    #[error("Line {0} is not a valid iCalendar line")]
+    InvalidLine(usize),
+    #[error("Component {0} is not closed properly")]
+    UnbalancedComponent(String),
+    #[error("Missing {0} in {1}")]
+    MissingProperty(&'static str, String),
+    #[error("Invalid {0} value: {1}")]
+    InvalidValue(&'static str, String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IcsProperty {
+    pub name: String,
+    pub params: Vec<(String, String)>,
+    pub value: String,
+}
+
+impl IcsProperty {
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param, _)| param.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Value with the TEXT escapes resolved.
+    pub fn text(&self) -> String {
+        let mut text = String::with_capacity(self.value.len());
+        let mut chars = self.value.chars();
+
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                text.push(c);
+                continue;
+            }
+
+            match chars.next() {
+                Some('n' | 'N') => text.push('\n'),
+                Some(escaped) => text.push(escaped),
+                None => text.push('\\'),
+            }
+        }
+
+        text
+    }
+
+    /// Time of a DATE-TIME value in UTC. Floating times are read in `timezone` unless TZID says otherwise.
+    /// Returns `None` for DATE values.
+    pub fn utc_time(&self, timezone: Tz) -> Result<Option<NaiveTime>, IcsError> {
+        let invalid = || IcsError::InvalidValue("date-time", self.value.clone());
+
+        let Some(local) = self.value.get(..15) else {
+            return self.date().map(|_| None);
+        };
+        let local = NaiveDateTime::parse_from_str(local, "%Y%m%dT%H%M%S").map_err(|_| invalid())?;
+
+        if self.value.ends_with('Z') {
+            return Ok(Some(local.time()));
+        }
+
+        let timezone = match self.param("TZID") {
+            Some(tzid) => tzid.parse::<Tz>().map_err(|_| invalid())?,
+            None => timezone,
+        };
+
+        let utc = timezone
+            .from_local_datetime(&local)
+            .earliest()
+            .ok_or_else(invalid)?
+            .with_timezone(&Utc);
+
+        Ok(Some(utc.time()))
+    }
+
+    /// Date part of a DATE or DATE-TIME value.
+    pub fn date(&self) -> Result<NaiveDate, IcsError> {
+        self.value
+            .get(..8)
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+            .ok_or_else(|| IcsError::InvalidValue("date", self.value.clone()))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IcsComponent {
+    pub name: String,
+    pub properties: Vec<IcsProperty>,
+    pub components: Vec<IcsComponent>,
+}
+
+impl IcsComponent {
+    pub fn property(&self, name: &str) -> Option<&IcsProperty> {
+        self.properties
+            .iter()
+            .find(|property| property.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Nested components with the given name, at any depth.
+    pub fn find_all<'a>(&'a self, name: &str, found: &mut Vec<&'a IcsComponent>) {
+        for component in &self.components {
+            if component.name.eq_ignore_ascii_case(name) {
+                found.push(component);
+            }
+            component.find_all(name, found);
+        }
+    }
+}
+
+/// Parses the top level components, usually a single VCALENDAR.
+pub fn parse(text: &str) -> Result<Vec<IcsComponent>, IcsError> {
+    let mut stack = vec![IcsComponent::default()];
+
+    for (number, line) in unfold(text) {
+        let property = parse_line(&line).ok_or(IcsError::InvalidLine(number))?;
+
+        if property.name.eq_ignore_ascii_case("BEGIN") {
+            stack.push(IcsComponent {
+                name: property.value.to_ascii_uppercase(),
+                ..Default::default()
+            });
+        } else if property.name.eq_ignore_ascii_case("END") {
+            let component = stack
+                .pop()
+                .filter(|component| component.name.eq_ignore_ascii_case(&property.value))
+                .ok_or_else(|| IcsError::UnbalancedComponent(property.value.clone()))?;
+            stack
+                .last_mut()
+                .ok_or_else(|| IcsError::UnbalancedComponent(property.value.clone()))?
+                .components
+                .push(component);
+        } else {
+            let current = stack.last_mut().expect("The root is never popped");
+            current.properties.push(property);
+        }
+    }
+
+    match stack.pop() {
+        Some(root) if stack.is_empty() => Ok(root.components),
+        Some(unclosed) => Err(IcsError::UnbalancedComponent(unclosed.name)),
+        None => unreachable!("The root is never popped"),
+    }
+}
+
+/// Joins folded lines, keeping the number of the line each one starts on.
+fn unfold(text: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+
+    for (index, line) in text.lines().enumerate() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some((_, previous))) => previous.push_str(continuation),
+            _ if line.trim().is_empty() => {}
+            _ => lines.push((index + 1, line.to_string())),
+        }
+    }
+
+    lines
+}
+
+fn parse_line(line: &str) -> Option<IcsProperty> {
+    let mut quoted = false;
+    let colon = line.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            quoted = !quoted;
+            None
+        }
+        ':' if !quoted => Some(i),
+        _ => None,
+    })?;
+
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+    let mut parts = head.split(';');
+    let name = parts.next().filter(|name| !name.is_empty())?;
+
+    let params = parts
+        .map(|param| {
+            let (key, value) = param.split_once('=')?;
+            Some((
+                key.to_ascii_uppercase(),
+                value.trim_matches('"').to_string(),
+            ))
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(IcsProperty {
+        name: name.to_ascii_uppercase(),
+        params,
+        value: value.to_string(),
+    })
+}
+
+/// Every day covered by the calendar's events, labeled with the event summary.
+pub fn parse_exclusion_dates(text: &str) -> Result<Vec<NewExclusionDate>, IcsError> {
+    let calendars = parse(text)?;
+    let mut events = Vec::new();
+    for component in &calendars {
+        component.find_all("VEVENT", &mut events);
+    }
+
+    let mut dates = Vec::new();
+    for event in events {
+        let start = event
+            .property("DTSTART")
+            .ok_or_else(|| IcsError::MissingProperty("DTSTART", event.name.clone()))?
+            .date()?;
+
+        // All-day events end on the next day, timed ones only cover the starting day.
+        let end = match event.property("DTEND") {
+            Some(end) if end.param("VALUE") == Some("DATE") => end.date()?,
+            _ => start + TimeDelta::days(1),
+        };
+
+        let days = (end - start).num_days().clamp(1, MAX_EXCLUDED_DAYS);
+        let label = event.property("SUMMARY").map(IcsProperty::text);
+
+        dates.extend((0..days).map(|day| NewExclusionDate {
+            date: start + TimeDelta::days(day),
+            label: label.clone(),
+        }));
+    }
+
+    Ok(dates)
+}
+
+/// Daily reminders found in a calendar.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReminderImport {
+    pub reminders: Vec<NewReminder>,
+    /// Entries that can't become a daily reminder, like all-day or weekly events.
+    pub skipped: usize,
+}
+
+/// Turns timed events and to-dos repeating daily, or not at all, into reminders for the user.
+pub fn parse_reminders(
+    text: &str,
+    user_id: UserId,
+    timezone: Tz,
+) -> Result<ReminderImport, IcsError> {
+    let calendars = parse(text)?;
+    let mut entries = Vec::new();
+    for component in &calendars {
+        component.find_all("VEVENT", &mut entries);
+        component.find_all("VTODO", &mut entries);
+    }
+
+    let mut import = ReminderImport::default();
+    for entry in entries {
+        let text = entry
+            .property("SUMMARY")
+            .map(|summary| summary.text().trim().to_string())
+            .filter(|text| !text.is_empty());
+        let is_daily = entry
+            .property("RRULE")
+            .is_none_or(|rule| is_daily_rule(&rule.value));
+        let start = entry.property("DTSTART").or_else(|| entry.property("DUE"));
+
+        let fire_at = match start {
+            Some(start) => start.utc_time(timezone)?,
+            None => None,
+        };
+
+        match (text, fire_at) {
+            (Some(text), Some(fire_at)) if is_daily => import.reminders.push(NewReminder {
+                text,
+                fire_at: ReminderFireTime::new(fire_at),
+                user_id,
+            }),
+            _ => import.skipped += 1,
+        }
+    }
+
+    Ok(import)
+}
+
+fn is_daily_rule(rule: &str) -> bool {
+    let mut daily = false;
+    for part in rule.split(';') {
+        match part.split_once('=') {
+            Some(("FREQ", frequency)) => daily = frequency == "DAILY",
+            Some(("INTERVAL", interval)) if interval != "1" => return false,
+            Some(("BYDAY" | "COUNT" | "UNTIL", _)) => return false,
+            _ => {}
+        }
+    }
+
+    daily
+}
+
+/// Calendar with a daily repeating event and alarm for every reminder.
+pub fn write_reminders(reminders: &[Reminder], now: DateTime<Utc>) -> String {
+    let mut out = String::new();
+    let stamp = format_utc(now);
+
+    write_line(&mut out, "BEGIN:VCALENDAR");
+    write_line(&mut out, "VERSION:2.0");
+    write_line(&mut out, &format!("PRODID:{PRODUCT_ID}"));
+
+    for reminder in reminders {
+        let start = now
+            .date_naive()
+            .and_time(*reminder.fire_at.time())
+            .and_utc();
+        let text = escape_text(&reminder.text);
+
+        write_line(&mut out, "BEGIN:VEVENT");
+        write_line(&mut out, &format!("UID:reminder-{}@nadoeda", reminder.id));
+        write_line(&mut out, &format!("DTSTAMP:{stamp}"));
+        write_line(&mut out, &format!("DTSTART:{}", format_utc(start)));
+        write_line(
+            &mut out,
+            &format!("DTEND:{}", format_utc(start + reminder.fire_window())),
+        );
+        write_line(&mut out, "RRULE:FREQ=DAILY");
+        write_line(&mut out, &format!("SUMMARY:{text}"));
+        write_line(&mut out, "BEGIN:VALARM");
+        write_line(&mut out, "ACTION:DISPLAY");
+        write_line(&mut out, "TRIGGER:PT0S");
+        write_line(&mut out, &format!("DESCRIPTION:{text}"));
+        write_line(&mut out, "END:VALARM");
+        write_line(&mut out, "END:VEVENT");
+    }
+
+    write_line(&mut out, "END:VCALENDAR");
+
+    out
+}
+
+fn format_utc(at: DateTime<Utc>) -> String {
+    at.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Writes a CRLF terminated line, folded without splitting characters.
+fn write_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE_LENGTH {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    const HOLIDAYS: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Holidays//EN\r
+BEGIN:VEVENT\r
+DTSTART;VALUE=DATE:20251224\r
+DTEND;VALUE=DATE:20251227\r
+SUMMARY:Christmas\\, finally\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+DTSTART:20260101T000000Z\r
+DTEND:20260101T235900Z\r
+SUMMARY:New Year's\r
+  Day\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+    #[test]
+    fn should_parse_nested_components() {
+        let calendars = parse(HOLIDAYS).unwrap();
+
+        assert_eq!(calendars.len(), 1);
+        assert_eq!(calendars[0].name, "VCALENDAR");
+        assert_eq!(calendars[0].components.len(), 2);
+        assert_eq!(
+            calendars[0].components[1]
+                .property("SUMMARY")
+                .map(IcsProperty::text),
+            Some("New Year's Day".to_string())
+        );
+    }
+
+    #[test]
+    fn should_expand_all_day_events() {
+        let dates = parse_exclusion_dates(HOLIDAYS).unwrap();
+
+        assert_eq!(
+            dates.iter().map(|date| date.date).collect::<Vec<_>>(),
+            vec![
+                date(2025, 12, 24),
+                date(2025, 12, 25),
+                date(2025, 12, 26),
+                date(2026, 1, 1)
+            ]
+        );
+        assert_eq!(dates[0].label.as_deref(), Some("Christmas, finally"));
+    }
+
+    #[test]
+    fn should_keep_quoted_colons_in_params() {
+        let property = parse_line("ATTENDEE;CN=\"Doe: John\":mailto:john@example.com").unwrap();
+
+        assert_eq!(property.param("cn"), Some("Doe: John"));
+        assert_eq!(property.value, "mailto:john@example.com");
+    }
+
+    #[test]
+    fn should_reject_unbalanced_components() {
+        assert_eq!(
+            parse("BEGIN:VCALENDAR\nBEGIN:VEVENT\nEND:VCALENDAR\n"),
+            Err(IcsError::UnbalancedComponent("VCALENDAR".to_string()))
+        );
+        assert_eq!(
+            parse("BEGIN:VCALENDAR\n"),
+            Err(IcsError::UnbalancedComponent("VCALENDAR".to_string()))
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_lines() {
+        assert_eq!(
+            parse("BEGIN:VCALENDAR\nnot a property\nEND:VCALENDAR\n"),
+            Err(IcsError::InvalidLine(2))
+        );
+    }
+
+    fn reminder(id: i64, text: &str, h: u32, m: u32) -> Reminder {
+        use nadoeda_models::reminder::{
+            ConfirmationChallenge, ReminderState, ReminderVerification,
+        };
+
+        Reminder {
+            id,
+            user_id: 1,
+            state: ReminderState::Pending,
+            fire_at: ReminderFireTime::new(NaiveTime::from_hms_opt(h, m, 0).unwrap()),
+            fire_window_end: None,
+            text: text.to_string(),
+            verification: ReminderVerification::None,
+            challenge: ConfirmationChallenge::None,
+            dependency: None,
+            checklist: Vec::new(),
+            version: 0,
+        }
+    }
+
+    #[test]
+    fn exported_reminders_should_import_back() {
+        let now = Utc.with_ymd_and_hms(2025, 6, 2, 12, 0, 0).unwrap();
+        let long_text = "Water the plants; the big ones, the small ones and the ones on the balcony 🌱\nAll of them";
+        let reminders = [reminder(1, "Stretch", 9, 30), reminder(2, long_text, 21, 0)];
+
+        let exported = write_reminders(&reminders, now);
+        let imported = parse_reminders(&exported, 7, Tz::Asia__Tokyo).unwrap();
+
+        assert!(
+            exported
+                .lines()
+                .all(|line| line.len() <= MAX_LINE_LENGTH + 1)
+        );
+        assert_eq!(imported.skipped, 0);
+        assert_eq!(
+            imported.reminders,
+            reminders
+                .iter()
+                .map(|reminder| NewReminder {
+                    text: reminder.text.clone(),
+                    fire_at: reminder.fire_at,
+                    user_id: 7,
+                })
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn should_import_local_times_in_utc() {
+        let calendar = "BEGIN:VCALENDAR
+BEGIN:VEVENT
+DTSTART;TZID=Europe/Prague:20250602T080000
+RRULE:FREQ=DAILY
+SUMMARY:Vitamins
+END:VEVENT
+BEGIN:VTODO
+DUE:20250602T070000
+SUMMARY:Plan the day
+END:VTODO
+END:VCALENDAR
+";
+
+        let imported = parse_reminders(calendar, 1, Tz::Asia__Tokyo).unwrap();
+        let times = imported
+            .reminders
+            .iter()
+            .map(|reminder| *reminder.fire_at.time())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            times,
+            vec![
+                NaiveTime::from_hms_opt(6, 0, 0).unwrap(),
+                NaiveTime::from_hms_opt(22, 0, 0).unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn should_skip_entries_that_are_not_daily() {
+        let calendar = "BEGIN:VCALENDAR
+BEGIN:VEVENT
+DTSTART:20250602T080000Z
+RRULE:FREQ=WEEKLY;BYDAY=MO
+SUMMARY:Team meeting
+END:VEVENT
+BEGIN:VEVENT
+DTSTART:20250602T080000Z
+RRULE:FREQ=DAILY;INTERVAL=2
+SUMMARY:Every other day
+END:VEVENT
+BEGIN:VEVENT
+DTSTART;VALUE=DATE:20250602
+SUMMARY:Birthday
+END:VEVENT
+BEGIN:VEVENT
+DTSTART:20250602T080000Z
+END:VEVENT
+END:VCALENDAR
+";
+
+        let imported = parse_reminders(calendar, 1, Tz::UTC).unwrap();
+
+        assert!(imported.reminders.is_empty());
+        assert_eq!(imported.skipped, 4);
+    }
+}
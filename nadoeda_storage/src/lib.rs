@@ -1,6 +1,32 @@
+pub mod buddy;
+pub mod challenge;
+mod error;
+pub mod evidence;
+pub mod exclusion;
+pub mod ics;
+pub mod memory;
+mod model;
+pub mod occurrence;
+#[cfg(feature = "postgres")]
+pub mod postgres;
 pub mod reminder;
+#[cfg(feature = "sqlite")]
 pub mod sqlite;
+pub mod stats;
+mod storages;
+pub mod template;
+#[cfg(feature = "test-suite")]
+pub mod test_suite;
 pub mod user;
 
+pub use buddy::*;
+pub use challenge::*;
+pub use error::StorageError;
+pub use evidence::*;
+pub use exclusion::*;
+pub use occurrence::*;
 pub use reminder::*;
+pub use stats::*;
+pub use storages::*;
+pub use template::*;
 pub use user::*;
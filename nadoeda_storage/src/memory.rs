@@ -0,0 +1,9 @@
+pub mod buddy_storage;
+pub mod challenge_storage;
+pub mod evidence_storage;
+pub mod exclusion_storage;
+pub mod occurrence_storage;
+pub mod reminder_storage;
+pub mod stats_storage;
+pub mod template_storage;
+pub mod user_storage;
@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use nadoeda_models::{
+    buddy::{BuddyInvitationStatus, BuddyTarget, ReminderBuddy, ReminderBuddyId},
+    reminder::ReminderId,
+};
+use tokio::sync::RwLock;
+
+use super::{reminder_storage::InMemoryReminderStorage, user_storage::InMemoryUserInfoStorage};
+use crate::StorageError;
+use crate::buddy::{NewReminderBuddy, ReminderBuddyStorage};
+
+#[derive(Default)]
+struct InMemoryBuddyStore {
+    last_id: ReminderBuddyId,
+    buddies: BTreeMap<ReminderBuddyId, ReminderBuddy>,
+}
+
+/// Keeps buddies in memory only, checking ownership against the given reminders and resolving
+/// invited users through the given users.
+pub struct InMemoryReminderBuddyStorage {
+    store: RwLock<InMemoryBuddyStore>,
+    reminders: Arc<InMemoryReminderStorage>,
+    users: Arc<InMemoryUserInfoStorage>,
+}
+
+impl InMemoryReminderBuddyStorage {
+    pub fn new(
+        reminders: Arc<InMemoryReminderStorage>,
+        users: Arc<InMemoryUserInfoStorage>,
+    ) -> Self {
+        Self {
+            store: RwLock::default(),
+            reminders,
+            users,
+        }
+    }
+}
+
+#[async_trait]
+impl ReminderBuddyStorage for InMemoryReminderBuddyStorage {
+    type Error = StorageError;
+
+    async fn get(&self, id: &ReminderBuddyId) -> Result<Option<ReminderBuddy>, Self::Error> {
+        Ok(self.store.read().await.buddies.get(id).cloned())
+    }
+
+    async fn get_for_reminder(
+        &self,
+        reminder_id: &ReminderId,
+    ) -> Result<Option<ReminderBuddy>, Self::Error> {
+        let store = self.store.read().await;
+        Ok(store
+            .buddies
+            .values()
+            .find(|buddy| buddy.reminder_id == *reminder_id)
+            .cloned())
+    }
+
+    async fn invite(&self, new_buddy: NewReminderBuddy) -> Result<ReminderBuddy, Self::Error> {
+        let NewReminderBuddy {
+            reminder_id,
+            user_id,
+            target,
+        } = new_buddy;
+        if !self.reminders.is_active_for(&reminder_id, &user_id).await {
+            return Err(StorageError::NotFound);
+        }
+
+        let mut store = self.store.write().await;
+        // Like the database backends, a new invitation keeps the id of the buddy it replaces.
+        let existing_id = store
+            .buddies
+            .values()
+            .find(|buddy| buddy.reminder_id == reminder_id)
+            .map(|buddy| buddy.id);
+        let id = match existing_id {
+            Some(id) => id,
+            None => {
+                store.last_id += 1;
+                store.last_id
+            }
+        };
+
+        let buddy = ReminderBuddy {
+            id,
+            reminder_id,
+            user_id,
+            target,
+            status: BuddyInvitationStatus::Pending,
+        };
+        store.buddies.insert(id, buddy.clone());
+
+        Ok(buddy)
+    }
+
+    async fn set_status(
+        &self,
+        id: &ReminderBuddyId,
+        tg_chat_id: i64,
+        status: BuddyInvitationStatus,
+    ) -> Result<ReminderBuddy, Self::Error> {
+        let mut store = self.store.write().await;
+        let buddy = store.buddies.get_mut(id).ok_or(StorageError::NotFound)?;
+        let buddy_chat_id = match buddy.target {
+            BuddyTarget::User(user_id) => self.users.chat_of(&user_id).await,
+            BuddyTarget::TelegramChat(chat_id) => Some(chat_id),
+        };
+        if buddy_chat_id != Some(tg_chat_id) {
+            return Err(StorageError::NotFound);
+        }
+        buddy.status = status;
+
+        Ok(buddy.clone())
+    }
+}
@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+use nadoeda_models::{
+    challenge::ChallengeAttempt,
+    chrono::{DateTime, Utc},
+    reminder::ReminderId,
+    user::UserId,
+};
+use tokio::sync::RwLock;
+
+use crate::StorageError;
+use crate::challenge::ChallengeAttemptStorage;
+
+/// Keeps challenge attempts in memory only, everything is lost when the storage is dropped.
+#[derive(Default)]
+pub struct InMemoryChallengeAttemptStorage {
+    attempts: RwLock<Vec<ChallengeAttempt>>,
+}
+
+impl InMemoryChallengeAttemptStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ChallengeAttemptStorage for InMemoryChallengeAttemptStorage {
+    type Error = StorageError;
+
+    async fn record(&self, attempt: ChallengeAttempt) -> Result<(), Self::Error> {
+        self.attempts.write().await.push(attempt);
+        Ok(())
+    }
+
+    async fn count_wrong_answers(
+        &self,
+        reminder_id: &ReminderId,
+        user_id: &UserId,
+        since: DateTime<Utc>,
+    ) -> Result<u32, Self::Error> {
+        let attempts = self.attempts.read().await;
+        let count = attempts
+            .iter()
+            .filter(|attempt| {
+                attempt.reminder_id == *reminder_id
+                    && attempt.user_id == *user_id
+                    && !attempt.correct
+                    && attempt.answered_at >= since
+            })
+            .count();
+
+        Ok(count as u32)
+    }
+}
@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use nadoeda_models::{
+    evidence::{ReminderEvidence, ReminderEvidenceId},
+    occurrence::ReminderOccurrenceId,
+    user::UserId,
+};
+use tokio::sync::RwLock;
+
+use super::occurrence_storage::InMemoryReminderOccurrenceStorage;
+use crate::StorageError;
+use crate::evidence::{NewReminderEvidence, ReminderEvidenceStorage};
+use crate::occurrence::ReminderOccurrenceStorage;
+
+#[derive(Default)]
+struct InMemoryEvidenceStore {
+    last_id: ReminderEvidenceId,
+    evidence: Vec<ReminderEvidence>,
+}
+
+/// Keeps proof in memory only, checking ownership against the given occurrences.
+pub struct InMemoryReminderEvidenceStorage {
+    store: RwLock<InMemoryEvidenceStore>,
+    occurrences: Arc<InMemoryReminderOccurrenceStorage>,
+}
+
+impl InMemoryReminderEvidenceStorage {
+    pub fn new(occurrences: Arc<InMemoryReminderOccurrenceStorage>) -> Self {
+        Self {
+            store: RwLock::default(),
+            occurrences,
+        }
+    }
+}
+
+#[async_trait]
+impl ReminderEvidenceStorage for InMemoryReminderEvidenceStorage {
+    type Error = StorageError;
+
+    async fn insert(&self, evidence: NewReminderEvidence) -> Result<ReminderEvidence, Self::Error> {
+        let NewReminderEvidence {
+            reminder_id,
+            occurrence_id,
+            user_id,
+            photo_file_id,
+            text,
+            submitted_at,
+        } = evidence;
+        // Occurrences are only ever started for the owner of their reminder.
+        let occurrence = self.occurrences.get(&occurrence_id).await?;
+        if !occurrence.is_some_and(|occurrence| {
+            occurrence.reminder_id == reminder_id && occurrence.user_id == user_id
+        }) {
+            return Err(StorageError::NotFound);
+        }
+
+        let mut store = self.store.write().await;
+        store.last_id += 1;
+        let evidence = ReminderEvidence {
+            id: store.last_id,
+            reminder_id,
+            occurrence_id: Some(occurrence_id),
+            user_id,
+            photo_file_id,
+            text,
+            submitted_at,
+        };
+        store.evidence.push(evidence.clone());
+
+        Ok(evidence)
+    }
+
+    async fn get_for_occurrence(
+        &self,
+        occurrence_id: &ReminderOccurrenceId,
+        user_id: &UserId,
+    ) -> Result<Vec<ReminderEvidence>, Self::Error> {
+        let store = self.store.read().await;
+        let mut evidence: Vec<_> = store
+            .evidence
+            .iter()
+            .filter(|evidence| {
+                evidence.occurrence_id == Some(*occurrence_id) && evidence.user_id == *user_id
+            })
+            .cloned()
+            .collect();
+        evidence.sort_by_key(|evidence| (evidence.submitted_at, evidence.id));
+
+        Ok(evidence)
+    }
+}
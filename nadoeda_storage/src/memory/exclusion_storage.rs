@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use nadoeda_models::{
+    chrono::NaiveDate,
+    exclusion::{ExclusionDate, ExclusionDateId},
+    user::UserId,
+};
+use tokio::sync::RwLock;
+
+use crate::StorageError;
+use crate::exclusion::{ExclusionDateStorage, NewExclusionDate};
+
+#[derive(Default)]
+struct InMemoryExclusionStore {
+    last_id: ExclusionDateId,
+    dates: BTreeMap<(UserId, NaiveDate), ExclusionDate>,
+}
+
+/// Keeps exclusion dates in memory only, everything is lost when the storage is dropped.
+#[derive(Default)]
+pub struct InMemoryExclusionDateStorage {
+    store: RwLock<InMemoryExclusionStore>,
+}
+
+impl InMemoryExclusionDateStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ExclusionDateStorage for InMemoryExclusionDateStorage {
+    type Error = StorageError;
+
+    async fn add(
+        &self,
+        user_id: &UserId,
+        dates: Vec<NewExclusionDate>,
+    ) -> Result<u64, Self::Error> {
+        let mut store = self.store.write().await;
+        let mut added = 0;
+
+        for NewExclusionDate { date, label } in dates {
+            if store.dates.contains_key(&(*user_id, date)) {
+                continue;
+            }
+            store.last_id += 1;
+            let excluded = ExclusionDate {
+                id: store.last_id,
+                user_id: *user_id,
+                date,
+                label,
+            };
+            store.dates.insert((*user_id, date), excluded);
+            added += 1;
+        }
+
+        Ok(added)
+    }
+
+    async fn remove(&self, user_id: &UserId, date: NaiveDate) -> Result<bool, Self::Error> {
+        let mut store = self.store.write().await;
+        Ok(store.dates.remove(&(*user_id, date)).is_some())
+    }
+
+    async fn is_excluded(&self, user_id: &UserId, date: NaiveDate) -> Result<bool, Self::Error> {
+        let store = self.store.read().await;
+        Ok(store.dates.contains_key(&(*user_id, date)))
+    }
+
+    async fn get_upcoming(
+        &self,
+        user_id: &UserId,
+        from: NaiveDate,
+        limit: u32,
+    ) -> Result<Vec<ExclusionDate>, Self::Error> {
+        let store = self.store.read().await;
+        Ok(store
+            .dates
+            .range((*user_id, from)..=(*user_id, NaiveDate::MAX))
+            .map(|(_, excluded)| excluded.clone())
+            .take(limit as usize)
+            .collect())
+    }
+}
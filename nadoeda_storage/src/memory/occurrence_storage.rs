@@ -0,0 +1,165 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use nadoeda_models::{
+    chrono::{DateTime, Utc},
+    occurrence::{ReminderOccurrence, ReminderOccurrenceId},
+    reminder::ReminderId,
+    user::UserId,
+};
+use tokio::sync::RwLock;
+
+use super::reminder_storage::InMemoryReminderStorage;
+use crate::StorageError;
+use crate::occurrence::{NewReminderOccurrence, ReminderOccurrenceStorage};
+
+#[derive(Default)]
+struct InMemoryOccurrenceStore {
+    last_id: ReminderOccurrenceId,
+    occurrences: BTreeMap<ReminderOccurrenceId, ReminderOccurrence>,
+}
+
+/// Keeps occurrences in memory only, checking ownership against the given reminders.
+pub struct InMemoryReminderOccurrenceStorage {
+    store: RwLock<InMemoryOccurrenceStore>,
+    reminders: Arc<InMemoryReminderStorage>,
+}
+
+impl InMemoryReminderOccurrenceStorage {
+    pub fn new(reminders: Arc<InMemoryReminderStorage>) -> Self {
+        Self {
+            store: RwLock::default(),
+            reminders,
+        }
+    }
+
+    /// Occurrences of the reminder that finished up to `until`, oldest first.
+    pub(super) async fn finished_until(
+        &self,
+        reminder_id: &ReminderId,
+        user_id: &UserId,
+        until: DateTime<Utc>,
+    ) -> Vec<ReminderOccurrence> {
+        let store = self.store.read().await;
+        let mut occurrences: Vec<_> = store
+            .occurrences
+            .values()
+            .filter(|occurrence| {
+                occurrence.reminder_id == *reminder_id
+                    && occurrence.user_id == *user_id
+                    && occurrence.is_finished()
+                    && occurrence.scheduled_at <= until
+            })
+            .cloned()
+            .collect();
+        occurrences.sort_by_key(|occurrence| (occurrence.scheduled_at, occurrence.id));
+
+        occurrences
+    }
+}
+
+#[async_trait]
+impl ReminderOccurrenceStorage for InMemoryReminderOccurrenceStorage {
+    type Error = StorageError;
+
+    async fn insert(
+        &self,
+        occurrence: NewReminderOccurrence,
+    ) -> Result<ReminderOccurrence, Self::Error> {
+        let NewReminderOccurrence {
+            reminder_id,
+            user_id,
+            scheduled_at,
+        } = occurrence;
+        if !self.reminders.is_owned_by(&reminder_id, &user_id).await {
+            return Err(StorageError::NotFound);
+        }
+
+        let mut store = self.store.write().await;
+        store.last_id += 1;
+        let occurrence = ReminderOccurrence {
+            id: store.last_id,
+            reminder_id,
+            user_id,
+            scheduled_at,
+            fired_at: None,
+            acknowledged_at: None,
+            confirmed_at: None,
+            outcome: None,
+        };
+        store.occurrences.insert(occurrence.id, occurrence.clone());
+
+        Ok(occurrence)
+    }
+
+    async fn update(
+        &self,
+        occurrence: ReminderOccurrence,
+        user_id: &UserId,
+    ) -> Result<ReminderOccurrence, Self::Error> {
+        let mut store = self.store.write().await;
+        let stored = store
+            .occurrences
+            .get_mut(&occurrence.id)
+            .filter(|stored| stored.user_id == *user_id)
+            .ok_or(StorageError::NotFound)?;
+        stored.fired_at = occurrence.fired_at;
+        stored.acknowledged_at = occurrence.acknowledged_at;
+        stored.confirmed_at = occurrence.confirmed_at;
+        stored.outcome = occurrence.outcome;
+
+        Ok(stored.clone())
+    }
+
+    async fn get(
+        &self,
+        id: &ReminderOccurrenceId,
+    ) -> Result<Option<ReminderOccurrence>, Self::Error> {
+        Ok(self.store.read().await.occurrences.get(id).cloned())
+    }
+
+    async fn get_for_reminder(
+        &self,
+        reminder_id: &ReminderId,
+        user_id: &UserId,
+        limit: u32,
+    ) -> Result<Vec<ReminderOccurrence>, Self::Error> {
+        let store = self.store.read().await;
+        let mut occurrences: Vec<_> = store
+            .occurrences
+            .values()
+            .filter(|occurrence| {
+                occurrence.reminder_id == *reminder_id && occurrence.user_id == *user_id
+            })
+            .cloned()
+            .collect();
+        occurrences.sort_by_key(|occurrence| (occurrence.scheduled_at, occurrence.id));
+        occurrences.reverse();
+        occurrences.truncate(limit as usize);
+
+        Ok(occurrences)
+    }
+
+    async fn get_for_user_between(
+        &self,
+        user_id: &UserId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<ReminderOccurrence>, Self::Error> {
+        let store = self.store.read().await;
+        let mut occurrences: Vec<_> = store
+            .occurrences
+            .values()
+            .filter(|occurrence| {
+                occurrence.user_id == *user_id
+                    && occurrence.scheduled_at >= from
+                    && occurrence.scheduled_at < to
+            })
+            .cloned()
+            .collect();
+        occurrences.sort_by_key(|occurrence| (occurrence.scheduled_at, occurrence.id));
+
+        Ok(occurrences)
+    }
+}
@@ -0,0 +1,510 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use nadoeda_models::{
+    chrono::{DateTime, Utc},
+    reminder::{
+        ChecklistItem, ChecklistItemId, ConfirmationChallenge, Reminder, ReminderDependency,
+        ReminderId, ReminderState, ReminderVerification,
+    },
+    user::UserId,
+};
+use tokio::sync::RwLock;
+
+use crate::reminder::{NewReminder, ReminderStorage, ReminderStorageError};
+
+struct StoredReminder {
+    reminder: Reminder,
+    archived_at: Option<DateTime<Utc>>,
+}
+
+impl StoredReminder {
+    fn is_active_for(&self, user_id: &UserId) -> bool {
+        self.reminder.user_id == *user_id && self.archived_at.is_none()
+    }
+}
+
+#[derive(Default)]
+struct InMemoryReminderStore {
+    last_reminder_id: ReminderId,
+    last_item_id: ChecklistItemId,
+    reminders: BTreeMap<ReminderId, StoredReminder>,
+}
+
+impl InMemoryReminderStore {
+    fn active(&self, id: &ReminderId, user_id: &UserId) -> Option<&StoredReminder> {
+        self.reminders
+            .get(id)
+            .filter(|stored| stored.is_active_for(user_id))
+    }
+
+    fn active_mut(
+        &mut self,
+        id: &ReminderId,
+        user_id: &UserId,
+    ) -> Result<&mut StoredReminder, ReminderStorageError> {
+        self.reminders
+            .get_mut(id)
+            .filter(|stored| stored.is_active_for(user_id))
+            .ok_or(ReminderStorageError::NotFound(*id))
+    }
+
+    /// Whether `id` is `start` or one of the reminders `start` waits for, directly or not.
+    fn is_in_chain(&self, start: ReminderId, id: ReminderId) -> bool {
+        let mut current = Some(start);
+        while let Some(current_id) = current {
+            if current_id == id {
+                return true;
+            }
+            current = self
+                .reminders
+                .get(&current_id)
+                .and_then(|stored| stored.reminder.dependency)
+                .map(|dependency| dependency.predecessor_id);
+        }
+        false
+    }
+
+    fn detach_dependents(&mut self, predecessor_id: &ReminderId) {
+        for stored in self.reminders.values_mut() {
+            if stored
+                .reminder
+                .dependency
+                .is_some_and(|dependency| dependency.predecessor_id == *predecessor_id)
+            {
+                stored.reminder.dependency = None;
+            }
+        }
+    }
+}
+
+/// Keeps reminders in memory only, everything is lost when the storage is dropped.
+#[derive(Default)]
+pub struct InMemoryReminderStorage {
+    store: RwLock<InMemoryReminderStore>,
+}
+
+impl InMemoryReminderStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the reminder belongs to the user, archived or not.
+    pub(super) async fn is_owned_by(&self, id: &ReminderId, user_id: &UserId) -> bool {
+        self.store
+            .read()
+            .await
+            .reminders
+            .get(id)
+            .is_some_and(|stored| stored.reminder.user_id == *user_id)
+    }
+
+    /// Whether the reminder belongs to the user and is not archived.
+    pub(super) async fn is_active_for(&self, id: &ReminderId, user_id: &UserId) -> bool {
+        self.store.read().await.active(id, user_id).is_some()
+    }
+
+    pub(super) async fn delete_user_reminders(&self, user_id: &UserId) {
+        self.store
+            .write()
+            .await
+            .reminders
+            .retain(|_, stored| stored.reminder.user_id != *user_id);
+    }
+}
+
+#[async_trait]
+impl ReminderStorage for InMemoryReminderStorage {
+    type Error = ReminderStorageError;
+
+    async fn get(
+        &self,
+        id: &ReminderId,
+        user_id: &UserId,
+    ) -> Result<Option<Reminder>, Self::Error> {
+        let store = self.store.read().await;
+        Ok(store
+            .active(id, user_id)
+            .map(|stored| stored.reminder.clone()))
+    }
+    async fn get_all_user_reminders(&self, user_id: &UserId) -> Result<Vec<Reminder>, Self::Error> {
+        let store = self.store.read().await;
+        Ok(store
+            .reminders
+            .values()
+            .rev()
+            .filter(|stored| stored.is_active_for(user_id))
+            .map(|stored| stored.reminder.clone())
+            .collect())
+    }
+    async fn get_archived_user_reminders(
+        &self,
+        user_id: &UserId,
+    ) -> Result<Vec<Reminder>, Self::Error> {
+        let store = self.store.read().await;
+        let mut archived: Vec<_> = store
+            .reminders
+            .values()
+            .filter(|stored| stored.reminder.user_id == *user_id)
+            .filter_map(|stored| Some((stored.archived_at?, stored.reminder.clone())))
+            .collect();
+        archived.sort_by(|(left, _), (right, _)| right.cmp(left));
+
+        Ok(archived.into_iter().map(|(_, reminder)| reminder).collect())
+    }
+    async fn insert(&self, reminder: NewReminder) -> Result<Reminder, Self::Error> {
+        let NewReminder {
+            text,
+            fire_at,
+            user_id,
+        } = reminder;
+        let mut store = self.store.write().await;
+        store.last_reminder_id += 1;
+        let reminder = Reminder {
+            id: store.last_reminder_id,
+            state: ReminderState::Pending,
+            fire_at,
+            fire_window_end: None,
+            text,
+            user_id,
+            verification: ReminderVerification::None,
+            challenge: ConfirmationChallenge::None,
+            dependency: None,
+            checklist: Vec::new(),
+            version: 0,
+        };
+        store.reminders.insert(
+            reminder.id,
+            StoredReminder {
+                reminder: reminder.clone(),
+                archived_at: None,
+            },
+        );
+
+        Ok(reminder)
+    }
+
+    async fn update(&self, reminder: Reminder, user_id: &UserId) -> Result<Reminder, Self::Error> {
+        let mut store = self.store.write().await;
+        let stored = store.active_mut(&reminder.id, user_id)?;
+        if stored.reminder.version != reminder.version {
+            return Err(ReminderStorageError::Conflict(reminder.id));
+        }
+        stored.reminder = Reminder {
+            id: stored.reminder.id,
+            user_id: stored.reminder.user_id,
+            dependency: stored.reminder.dependency,
+            checklist: std::mem::take(&mut stored.reminder.checklist),
+            version: reminder.version + 1,
+            ..reminder
+        };
+
+        Ok(stored.reminder.clone())
+    }
+
+    async fn delete(&self, id: &ReminderId, user_id: &UserId) -> Result<bool, Self::Error> {
+        let mut store = self.store.write().await;
+        if store
+            .reminders
+            .get(id)
+            .is_none_or(|stored| stored.reminder.user_id != *user_id)
+        {
+            return Ok(false);
+        }
+
+        store.reminders.remove(id);
+        store.detach_dependents(id);
+
+        Ok(true)
+    }
+
+    async fn archive(&self, id: &ReminderId, user_id: &UserId) -> Result<bool, Self::Error> {
+        let mut store = self.store.write().await;
+        let Ok(stored) = store.active_mut(id, user_id) else {
+            return Ok(false);
+        };
+        stored.archived_at = Some(Utc::now());
+
+        store.detach_dependents(id);
+
+        Ok(true)
+    }
+
+    async fn restore(
+        &self,
+        id: &ReminderId,
+        user_id: &UserId,
+    ) -> Result<Option<Reminder>, Self::Error> {
+        let mut store = self.store.write().await;
+        let restored = store
+            .reminders
+            .get_mut(id)
+            .filter(|stored| stored.reminder.user_id == *user_id)
+            .filter(|stored| stored.archived_at.is_some())
+            .map(|stored| {
+                stored.archived_at = None;
+                stored.reminder.clone()
+            });
+
+        Ok(restored)
+    }
+
+    async fn purge_archived(&self, archived_before: DateTime<Utc>) -> Result<u64, Self::Error> {
+        let mut store = self.store.write().await;
+        let count = store.reminders.len();
+        store.reminders.retain(|_, stored| {
+            stored
+                .archived_at
+                .is_none_or(|archived_at| archived_at >= archived_before)
+        });
+
+        Ok((count - store.reminders.len()) as u64)
+    }
+
+    async fn set_dependency(
+        &self,
+        id: &ReminderId,
+        user_id: &UserId,
+        dependency: Option<ReminderDependency>,
+    ) -> Result<Reminder, Self::Error> {
+        let mut store = self.store.write().await;
+        if let Some(dependency) = dependency {
+            let predecessor_id = dependency.predecessor_id;
+            if store.active(&predecessor_id, user_id).is_none() {
+                return Err(ReminderStorageError::NotFound(predecessor_id));
+            }
+            if store.is_in_chain(predecessor_id, *id) {
+                return Err(ReminderStorageError::DependencyCycle(*id, predecessor_id));
+            }
+        }
+
+        let stored = store.active_mut(id, user_id)?;
+        stored.reminder.dependency = dependency;
+
+        Ok(stored.reminder.clone())
+    }
+
+    async fn set_checklist(
+        &self,
+        id: &ReminderId,
+        user_id: &UserId,
+        items: Vec<String>,
+    ) -> Result<Reminder, Self::Error> {
+        let mut store = self.store.write().await;
+        let first_item_id = store.last_item_id + 1;
+        store.last_item_id += items.len() as ChecklistItemId;
+
+        let stored = store.active_mut(id, user_id)?;
+        stored.reminder.checklist = (first_item_id..)
+            .zip(items)
+            .map(|(id, text)| ChecklistItem {
+                id,
+                text,
+                checked: false,
+            })
+            .collect();
+
+        Ok(stored.reminder.clone())
+    }
+
+    async fn toggle_checklist_item(
+        &self,
+        item_id: &ChecklistItemId,
+        user_id: &UserId,
+    ) -> Result<Reminder, Self::Error> {
+        let mut store = self.store.write().await;
+        let (stored, position) = store
+            .reminders
+            .values_mut()
+            .filter(|stored| stored.reminder.user_id == *user_id)
+            .find_map(|stored| {
+                let position = stored
+                    .reminder
+                    .checklist
+                    .iter()
+                    .position(|item| item.id == *item_id)?;
+                Some((stored, position))
+            })
+            .ok_or(ReminderStorageError::ChecklistItemNotFound(*item_id))?;
+        let item = &mut stored.reminder.checklist[position];
+        item.checked = !item.checked;
+
+        if stored.archived_at.is_some() {
+            return Err(ReminderStorageError::NotFound(stored.reminder.id));
+        }
+
+        Ok(stored.reminder.clone())
+    }
+
+    async fn reset_checklist(
+        &self,
+        id: &ReminderId,
+        user_id: &UserId,
+    ) -> Result<Reminder, Self::Error> {
+        let mut store = self.store.write().await;
+        let stored = store.active_mut(id, user_id)?;
+        for item in &mut stored.reminder.checklist {
+            item.checked = false;
+        }
+
+        Ok(stored.reminder.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nadoeda_models::{
+        chrono::{NaiveTime, TimeDelta},
+        reminder::ReminderFireTime,
+    };
+
+    use super::*;
+
+    const USER: UserId = 1;
+
+    async fn insert(storage: &InMemoryReminderStorage, text: &str) -> Reminder {
+        storage
+            .insert(NewReminder {
+                text: text.to_string(),
+                fire_at: ReminderFireTime::new(NaiveTime::from_hms_opt(9, 30, 0).unwrap()),
+                user_id: USER,
+            })
+            .await
+            .unwrap()
+    }
+
+    fn after(predecessor_id: ReminderId) -> Option<ReminderDependency> {
+        Some(ReminderDependency {
+            predecessor_id,
+            delay: TimeDelta::minutes(5),
+        })
+    }
+
+    #[tokio::test]
+    async fn keeps_reminders_per_user() {
+        let storage = InMemoryReminderStorage::new();
+        let first = insert(&storage, "First").await;
+        let second = insert(&storage, "Second").await;
+
+        assert_eq!(storage.get(&first.id, &2).await.unwrap(), None);
+        assert_eq!(
+            storage.get_all_user_reminders(&USER).await.unwrap(),
+            vec![second, first]
+        );
+    }
+
+    #[tokio::test]
+    async fn update_leaves_dependency_and_checklist_alone() {
+        let storage = InMemoryReminderStorage::new();
+        let first = insert(&storage, "First").await;
+        let second = insert(&storage, "Second").await;
+        storage
+            .set_dependency(&second.id, &USER, after(first.id))
+            .await
+            .unwrap();
+        let with_checklist = storage
+            .set_checklist(&second.id, &USER, vec!["Step".into()])
+            .await
+            .unwrap();
+
+        let updated = storage
+            .update(
+                Reminder {
+                    text: "Renamed".into(),
+                    dependency: None,
+                    checklist: Vec::new(),
+                    ..second
+                },
+                &USER,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(updated.text, "Renamed");
+        assert_eq!(updated.dependency, after(first.id));
+        assert_eq!(updated.checklist, with_checklist.checklist);
+    }
+
+    #[tokio::test]
+    async fn rejects_dependency_cycles() {
+        let storage = InMemoryReminderStorage::new();
+        let first = insert(&storage, "First").await;
+        let second = insert(&storage, "Second").await;
+        let third = insert(&storage, "Third").await;
+        storage
+            .set_dependency(&second.id, &USER, after(first.id))
+            .await
+            .unwrap();
+        storage
+            .set_dependency(&third.id, &USER, after(second.id))
+            .await
+            .unwrap();
+
+        let err = storage
+            .set_dependency(&first.id, &USER, after(third.id))
+            .await
+            .unwrap_err();
+
+        assert!(
+            matches!(err, ReminderStorageError::DependencyCycle(..)),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn archiving_detaches_dependents_until_purged() {
+        let storage = InMemoryReminderStorage::new();
+        let first = insert(&storage, "First").await;
+        let second = insert(&storage, "Second").await;
+        storage
+            .set_dependency(&second.id, &USER, after(first.id))
+            .await
+            .unwrap();
+
+        assert!(storage.archive(&first.id, &USER).await.unwrap());
+        assert!(!storage.archive(&first.id, &USER).await.unwrap());
+        let dependent = storage.get(&second.id, &USER).await.unwrap().unwrap();
+        assert_eq!(dependent.dependency, None);
+        assert_eq!(
+            storage.get_archived_user_reminders(&USER).await.unwrap(),
+            vec![first.clone()]
+        );
+
+        assert_eq!(
+            storage.restore(&first.id, &USER).await.unwrap(),
+            Some(first.clone())
+        );
+        storage.archive(&first.id, &USER).await.unwrap();
+        let purged = storage
+            .purge_archived(Utc::now() + TimeDelta::minutes(1))
+            .await
+            .unwrap();
+        assert_eq!(purged, 1);
+        assert_eq!(storage.restore(&first.id, &USER).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn toggles_and_resets_checklist_items() {
+        let storage = InMemoryReminderStorage::new();
+        let reminder = insert(&storage, "Pack").await;
+        let reminder = storage
+            .set_checklist(&reminder.id, &USER, vec!["Passport".into(), "Keys".into()])
+            .await
+            .unwrap();
+
+        let toggled = storage
+            .toggle_checklist_item(&reminder.checklist[1].id, &USER)
+            .await
+            .unwrap();
+        assert!(toggled.checklist[1].checked);
+        assert!(
+            storage
+                .toggle_checklist_item(&reminder.checklist[1].id, &2)
+                .await
+                .is_err()
+        );
+
+        let reset = storage.reset_checklist(&reminder.id, &USER).await.unwrap();
+        assert_eq!(reset.checklist, reminder.checklist);
+    }
+}
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use nadoeda_models::{
+    chrono::{DateTime, TimeDelta, Utc},
+    reminder::ReminderId,
+    stats::ReminderStats,
+    user::UserId,
+};
+
+use super::{
+    challenge_storage::InMemoryChallengeAttemptStorage,
+    occurrence_storage::InMemoryReminderOccurrenceStorage,
+};
+use crate::StorageError;
+use crate::challenge::ChallengeAttemptStorage;
+use crate::stats::{ReminderStatsStorage, compute_reminder_stats};
+
+/// Computes statistics from the occurrences and challenge attempts kept by the given storages.
+pub struct InMemoryReminderStatsStorage {
+    occurrences: Arc<InMemoryReminderOccurrenceStorage>,
+    challenges: Arc<InMemoryChallengeAttemptStorage>,
+}
+
+impl InMemoryReminderStatsStorage {
+    pub fn new(
+        occurrences: Arc<InMemoryReminderOccurrenceStorage>,
+        challenges: Arc<InMemoryChallengeAttemptStorage>,
+    ) -> Self {
+        Self {
+            occurrences,
+            challenges,
+        }
+    }
+}
+
+#[async_trait]
+impl ReminderStatsStorage for InMemoryReminderStatsStorage {
+    type Error = StorageError;
+
+    async fn get_for_reminder(
+        &self,
+        reminder_id: &ReminderId,
+        user_id: &UserId,
+        now: DateTime<Utc>,
+    ) -> Result<ReminderStats, Self::Error> {
+        let occurrences = self
+            .occurrences
+            .finished_until(reminder_id, user_id, now)
+            .await;
+        let wrong_answers = self
+            .challenges
+            .count_wrong_answers(reminder_id, user_id, now - TimeDelta::days(30))
+            .await?;
+
+        Ok(compute_reminder_stats(
+            *reminder_id,
+            &occurrences,
+            wrong_answers,
+            now,
+        ))
+    }
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use nadoeda_models::{
+    reminder::ReminderId,
+    template::{MessageTemplate, MessageTemplateId, TemplateKind},
+    user::UserId,
+};
+use tokio::sync::RwLock;
+
+use super::reminder_storage::InMemoryReminderStorage;
+use crate::StorageError;
+use crate::template::MessageTemplateStorage;
+
+#[derive(Default)]
+struct InMemoryTemplateStore {
+    last_id: MessageTemplateId,
+    templates: Vec<MessageTemplate>,
+}
+
+/// Keeps message templates in memory only, checking ownership against the given reminders.
+pub struct InMemoryMessageTemplateStorage {
+    store: RwLock<InMemoryTemplateStore>,
+    reminders: Arc<InMemoryReminderStorage>,
+}
+
+impl InMemoryMessageTemplateStorage {
+    pub fn new(reminders: Arc<InMemoryReminderStorage>) -> Self {
+        Self {
+            store: RwLock::default(),
+            reminders,
+        }
+    }
+}
+
+#[async_trait]
+impl MessageTemplateStorage for InMemoryMessageTemplateStorage {
+    type Error = StorageError;
+
+    async fn get_for_reminder(
+        &self,
+        reminder_id: &ReminderId,
+    ) -> Result<Vec<MessageTemplate>, Self::Error> {
+        let store = self.store.read().await;
+        let mut templates: Vec<_> = store
+            .templates
+            .iter()
+            .filter(|template| template.reminder_id == *reminder_id)
+            .cloned()
+            .collect();
+        // Variants are kept in order, the database backends sort kinds by their code too.
+        templates.sort_by_key(|template| template.kind.code());
+
+        Ok(templates)
+    }
+
+    async fn set(
+        &self,
+        reminder_id: &ReminderId,
+        user_id: &UserId,
+        kind: TemplateKind,
+        texts: Vec<String>,
+    ) -> Result<bool, Self::Error> {
+        if !self.reminders.is_owned_by(reminder_id, user_id).await {
+            return Ok(false);
+        }
+
+        let mut store = self.store.write().await;
+        store
+            .templates
+            .retain(|template| template.reminder_id != *reminder_id || template.kind != kind);
+        for text in texts {
+            store.last_id += 1;
+            let template = MessageTemplate {
+                id: store.last_id,
+                reminder_id: *reminder_id,
+                kind,
+                text,
+            };
+            store.templates.push(template);
+        }
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use nadoeda_models::user::{User, UserId};
+use tokio::sync::RwLock;
+
+use super::reminder_storage::InMemoryReminderStorage;
+use crate::user::{NewUser, UserInfoStorage};
+
+#[derive(Default)]
+struct InMemoryUserStore {
+    last_id: UserId,
+    users: BTreeMap<UserId, User>,
+}
+
+/// Keeps users in memory only, everything is lost when the storage is dropped.
+#[derive(Default)]
+pub struct InMemoryUserInfoStorage {
+    store: RwLock<InMemoryUserStore>,
+    reminders: Option<Arc<InMemoryReminderStorage>>,
+}
+
+impl InMemoryUserInfoStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Deleting a user also deletes their reminders from the given storage, like the
+    /// database backends do.
+    pub fn with_reminders(reminders: Arc<InMemoryReminderStorage>) -> Self {
+        Self {
+            store: RwLock::default(),
+            reminders: Some(reminders),
+        }
+    }
+
+    /// The Telegram chat of the user, if they have one.
+    pub(super) async fn chat_of(&self, id: &UserId) -> Option<i64> {
+        self.store.read().await.users.get(id)?.tg_chat_id
+    }
+}
+
+#[async_trait]
+impl UserInfoStorage for InMemoryUserInfoStorage {
+    type Error = anyhow::Error;
+
+    async fn get(&self, id: &UserId) -> Result<Option<User>, Self::Error> {
+        Ok(self.store.read().await.users.get(id).copied())
+    }
+    async fn get_all(&self) -> Result<Vec<User>, Self::Error> {
+        Ok(self.store.read().await.users.values().copied().collect())
+    }
+    async fn get_by_tg_chat(&self, chat_id: i64) -> Result<Option<User>, Self::Error> {
+        let store = self.store.read().await;
+        Ok(store
+            .users
+            .values()
+            .find(|user| user.tg_chat_id == Some(chat_id))
+            .copied())
+    }
+    async fn create(&self, new_user: NewUser) -> Result<User, Self::Error> {
+        let NewUser {
+            tg_chat_id,
+            timezone,
+            language,
+        } = new_user;
+        let mut store = self.store.write().await;
+        if tg_chat_id.is_some()
+            && store
+                .users
+                .values()
+                .any(|user| user.tg_chat_id == tg_chat_id)
+        {
+            anyhow::bail!("User with chat {tg_chat_id:?} already exists");
+        }
+
+        store.last_id += 1;
+        let user = User {
+            id: store.last_id,
+            timezone,
+            tg_chat_id,
+            digest_mode: false,
+            agenda_at: None,
+            summary_at: None,
+            language,
+        };
+        store.users.insert(user.id, user);
+
+        Ok(user)
+    }
+    async fn update(&self, update_user: User) -> Result<User, Self::Error> {
+        let mut store = self.store.write().await;
+        let Some(user) = store.users.get_mut(&update_user.id) else {
+            anyhow::bail!("User {} does not exist", update_user.id);
+        };
+        *user = update_user;
+
+        Ok(update_user)
+    }
+    async fn delete(&self, id: &UserId) -> Result<(), Self::Error> {
+        self.store.write().await.users.remove(id);
+        if let Some(reminders) = &self.reminders {
+            reminders.delete_user_reminders(id).await;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,6 @@
+//! Row models shared by the SQLite and PostgreSQL backends.
+
+pub(crate) mod buddy;
+pub(crate) mod occurrence;
+pub(crate) mod reminder;
+pub(crate) mod user;
@@ -0,0 +1,127 @@
+use nadoeda_models::buddy::{BuddyInvitationStatus, BuddyTarget, ReminderBuddy};
+
+pub struct BuddyStorageModel {
+    pub id: i64,
+    pub reminder_id: i64,
+    pub user_id: i64,
+    pub buddy_user_id: Option<i64>,
+    pub buddy_tg_chat_id: Option<i64>,
+    pub status: String,
+}
+
+impl From<ReminderBuddy> for BuddyStorageModel {
+    fn from(value: ReminderBuddy) -> Self {
+        let (buddy_user_id, buddy_tg_chat_id) = convert_target(value.target);
+        Self {
+            id: value.id,
+            reminder_id: value.reminder_id,
+            user_id: value.user_id,
+            buddy_user_id,
+            buddy_tg_chat_id,
+            status: convert_status(value.status),
+        }
+    }
+}
+
+impl From<BuddyStorageModel> for ReminderBuddy {
+    fn from(value: BuddyStorageModel) -> Self {
+        let target = match (value.buddy_user_id, value.buddy_tg_chat_id) {
+            (Some(user_id), _) => BuddyTarget::User(user_id),
+            (None, chat_id) => BuddyTarget::TelegramChat(chat_id.unwrap_or_default()),
+        };
+        Self {
+            id: value.id,
+            reminder_id: value.reminder_id,
+            user_id: value.user_id,
+            target,
+            status: parse_status(&value.status),
+        }
+    }
+}
+
+pub fn convert_target(target: BuddyTarget) -> (Option<i64>, Option<i64>) {
+    match target {
+        BuddyTarget::User(user_id) => (Some(user_id), None),
+        BuddyTarget::TelegramChat(chat_id) => (None, Some(chat_id)),
+    }
+}
+
+pub fn convert_status(status: BuddyInvitationStatus) -> String {
+    match status {
+        BuddyInvitationStatus::Pending => "Pending",
+        BuddyInvitationStatus::Accepted => "Accepted",
+        BuddyInvitationStatus::Declined => "Declined",
+    }
+    .to_string()
+}
+
+pub fn parse_status(status: &str) -> BuddyInvitationStatus {
+    match status {
+        "Pending" => BuddyInvitationStatus::Pending,
+        "Accepted" => BuddyInvitationStatus::Accepted,
+        "Declined" => BuddyInvitationStatus::Declined,
+        other => {
+            log::warn!(
+                "Warning: Unknown buddy status {}, defaulting to Pending",
+                other
+            );
+            BuddyInvitationStatus::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn arb_status() -> impl Strategy<Value = BuddyInvitationStatus> {
+        prop_oneof![
+            Just(BuddyInvitationStatus::Pending),
+            Just(BuddyInvitationStatus::Accepted),
+            Just(BuddyInvitationStatus::Declined),
+        ]
+    }
+
+    fn arb_target() -> impl Strategy<Value = BuddyTarget> {
+        prop_oneof![
+            any::<i64>().prop_map(BuddyTarget::User),
+            any::<i64>().prop_map(BuddyTarget::TelegramChat),
+        ]
+    }
+
+    fn arb_buddy() -> impl Strategy<Value = ReminderBuddy> {
+        (
+            any::<i64>(), // id
+            any::<i64>(), // reminder_id
+            any::<i64>(), // user_id
+            arb_target(),
+            arb_status(),
+        )
+            .prop_map(|(id, reminder_id, user_id, target, status)| ReminderBuddy {
+                id,
+                reminder_id,
+                user_id,
+                target,
+                status,
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn test_buddy_roundtrip(buddy in arb_buddy()) {
+            let storage: BuddyStorageModel = buddy.clone().into();
+            let restored: ReminderBuddy = storage.into();
+
+            prop_assert_eq!(buddy, restored);
+        }
+
+        #[test]
+        fn test_parse_status_handles_unknown_strings(s in ".*") {
+            let parsed = parse_status(&s);
+            if s != "Accepted" && s != "Declined" {
+                prop_assert_eq!(parsed, BuddyInvitationStatus::Pending);
+            }
+        }
+    }
+}
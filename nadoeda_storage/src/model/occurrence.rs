@@ -0,0 +1,144 @@
+use nadoeda_models::{
+    chrono::{DateTime, Utc},
+    occurrence::{OccurrenceOutcome, ReminderOccurrence},
+};
+
+pub struct OccurrenceStorageModel {
+    pub id: i64,
+    pub reminder_id: i64,
+    pub user_id: i64,
+    pub scheduled_at: DateTime<Utc>,
+    pub fired_at: Option<DateTime<Utc>>,
+    pub acknowledged_at: Option<DateTime<Utc>>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub outcome: Option<String>,
+}
+
+impl From<ReminderOccurrence> for OccurrenceStorageModel {
+    fn from(value: ReminderOccurrence) -> Self {
+        Self {
+            id: value.id,
+            reminder_id: value.reminder_id,
+            user_id: value.user_id,
+            scheduled_at: value.scheduled_at,
+            fired_at: value.fired_at,
+            acknowledged_at: value.acknowledged_at,
+            confirmed_at: value.confirmed_at,
+            outcome: value.outcome.map(convert_outcome),
+        }
+    }
+}
+
+impl From<OccurrenceStorageModel> for ReminderOccurrence {
+    fn from(value: OccurrenceStorageModel) -> Self {
+        Self {
+            id: value.id,
+            reminder_id: value.reminder_id,
+            user_id: value.user_id,
+            scheduled_at: value.scheduled_at,
+            fired_at: value.fired_at,
+            acknowledged_at: value.acknowledged_at,
+            confirmed_at: value.confirmed_at,
+            outcome: value.outcome.as_deref().and_then(parse_outcome),
+        }
+    }
+}
+
+pub fn convert_outcome(outcome: OccurrenceOutcome) -> String {
+    match outcome {
+        OccurrenceOutcome::Confirmed => "Confirmed",
+        OccurrenceOutcome::TimedOut => "TimedOut",
+        OccurrenceOutcome::Cancelled => "Cancelled",
+        OccurrenceOutcome::Snoozed => "Snoozed",
+    }
+    .to_string()
+}
+
+pub fn parse_outcome(outcome: &str) -> Option<OccurrenceOutcome> {
+    match outcome {
+        "Confirmed" => Some(OccurrenceOutcome::Confirmed),
+        "TimedOut" => Some(OccurrenceOutcome::TimedOut),
+        "Cancelled" => Some(OccurrenceOutcome::Cancelled),
+        "Snoozed" => Some(OccurrenceOutcome::Snoozed),
+        other => {
+            log::warn!(
+                "Warning: Unknown occurrence outcome {}, treating as in progress",
+                other
+            );
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nadoeda_models::chrono::TimeZone;
+    use proptest::prelude::*;
+
+    fn arb_outcome() -> impl Strategy<Value = Option<OccurrenceOutcome>> {
+        prop_oneof![
+            Just(None),
+            Just(Some(OccurrenceOutcome::Confirmed)),
+            Just(Some(OccurrenceOutcome::TimedOut)),
+            Just(Some(OccurrenceOutcome::Cancelled)),
+            Just(Some(OccurrenceOutcome::Snoozed)),
+        ]
+    }
+
+    fn arb_datetime() -> impl Strategy<Value = DateTime<Utc>> {
+        (0i64..4_102_444_800).prop_map(|secs| Utc.timestamp_opt(secs, 0).unwrap())
+    }
+
+    fn arb_occurrence() -> impl Strategy<Value = ReminderOccurrence> {
+        (
+            any::<i64>(), // id
+            any::<i64>(), // reminder_id
+            any::<i64>(), // user_id
+            arb_datetime(),
+            proptest::option::of(arb_datetime()),
+            proptest::option::of(arb_datetime()),
+            proptest::option::of(arb_datetime()),
+            arb_outcome(),
+        )
+            .prop_map(
+                |(
+                    id,
+                    reminder_id,
+                    user_id,
+                    scheduled_at,
+                    fired_at,
+                    acknowledged_at,
+                    confirmed_at,
+                    outcome,
+                )| ReminderOccurrence {
+                    id,
+                    reminder_id,
+                    user_id,
+                    scheduled_at,
+                    fired_at,
+                    acknowledged_at,
+                    confirmed_at,
+                    outcome,
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn test_occurrence_roundtrip(occurrence in arb_occurrence()) {
+            let storage: OccurrenceStorageModel = occurrence.clone().into();
+            let restored: ReminderOccurrence = storage.into();
+
+            prop_assert_eq!(occurrence, restored);
+        }
+
+        #[test]
+        fn test_parse_outcome_handles_unknown_strings(s in ".*") {
+            let parsed = parse_outcome(&s);
+            if !["Confirmed", "TimedOut", "Cancelled", "Snoozed"].contains(&s.as_str()) {
+                prop_assert_eq!(parsed, None);
+            }
+        }
+    }
+}
@@ -0,0 +1,339 @@
+use nadoeda_models::chrono::TimeDelta;
+use nadoeda_models::reminder::{
+    ChecklistItem, ConfirmationChallenge, Reminder, ReminderDependency, ReminderFireTime,
+    ReminderState, ReminderVerification,
+};
+
+pub struct ReminderStorageModel {
+    pub id: i64,
+    pub user_id: i64,
+    pub state_kind: String,
+    pub attempts_left: Option<i64>,
+    pub fire_at: String,
+    pub text: String,
+    pub verification_kind: String,
+    pub verification_keyword: Option<String>,
+    pub challenge_kind: String,
+    pub predecessor_id: Option<i64>,
+    pub predecessor_delay_seconds: i64,
+    pub fire_window_end: Option<String>,
+    pub archived_at: Option<String>,
+    pub version: i64,
+}
+
+pub struct ChecklistItemStorageModel {
+    pub id: i64,
+    pub reminder_id: i64,
+    pub position: i64,
+    pub text: String,
+    pub checked: bool,
+}
+
+impl From<ChecklistItemStorageModel> for ChecklistItem {
+    fn from(value: ChecklistItemStorageModel) -> Self {
+        Self {
+            id: value.id,
+            text: value.text,
+            checked: value.checked,
+        }
+    }
+}
+
+impl From<Reminder> for ReminderStorageModel {
+    fn from(value: Reminder) -> Self {
+        let (state, attempts_left) = convert_state(value.state);
+        let (verification_kind, verification_keyword) = convert_verification(value.verification);
+        let (predecessor_id, predecessor_delay_seconds) = convert_dependency(value.dependency);
+        Self {
+            id: value.id,
+            user_id: value.user_id,
+            text: value.text,
+            fire_at: value.fire_at.into_string(),
+            state_kind: state,
+            attempts_left,
+            verification_kind,
+            verification_keyword,
+            challenge_kind: convert_challenge(value.challenge),
+            predecessor_id,
+            predecessor_delay_seconds,
+            fire_window_end: value.fire_window_end.map(ReminderFireTime::into_string),
+            archived_at: None,
+            version: value.version,
+        }
+    }
+}
+
+impl From<ReminderStorageModel> for Reminder {
+    fn from(value: ReminderStorageModel) -> Self {
+        let state = parse_state(&value.state_kind, value.attempts_left);
+        let fire_at = ReminderFireTime::from_string(&value.fire_at).unwrap();
+        let verification = parse_verification(&value.verification_kind, value.verification_keyword);
+        Self {
+            id: value.id,
+            user_id: value.user_id,
+            fire_at,
+            fire_window_end: value
+                .fire_window_end
+                .as_deref()
+                .and_then(ReminderFireTime::from_string),
+            text: value.text,
+            state,
+            verification,
+            challenge: parse_challenge(&value.challenge_kind),
+            dependency: parse_dependency(value.predecessor_id, value.predecessor_delay_seconds),
+            checklist: Vec::new(),
+            version: value.version,
+        }
+    }
+}
+
+pub fn convert_state(state: ReminderState) -> (String, Option<i64>) {
+    match state {
+        ReminderState::Pending => ("Pending".to_string(), None),
+        ReminderState::Scheduled => ("Scheduled".to_string(), None),
+        ReminderState::Nagging { attempts_left } => {
+            ("Nagging".to_string(), Some(attempts_left as i64))
+        }
+        ReminderState::Confirming { attempts_left } => {
+            ("Confirming".to_string(), Some(attempts_left as i64))
+        }
+    }
+}
+
+pub fn parse_state(state: &str, attempts_left: Option<i64>) -> ReminderState {
+    match state {
+        "Pending" => ReminderState::Pending,
+        "Scheduled" => ReminderState::Scheduled,
+        "Nagging" => ReminderState::Nagging {
+            attempts_left: attempts_left.unwrap_or(3) as u8,
+        },
+        "Confirming" => ReminderState::Confirming {
+            attempts_left: attempts_left.unwrap_or(3) as u8,
+        },
+        other => {
+            log::warn!("Warning: Unknown state {}, defaulting to Pending", other);
+            ReminderState::Pending
+        }
+    }
+}
+
+pub fn convert_verification(verification: ReminderVerification) -> (String, Option<String>) {
+    match verification {
+        ReminderVerification::None => ("None".to_string(), None),
+        ReminderVerification::Photo => ("Photo".to_string(), None),
+        ReminderVerification::Text => ("Text".to_string(), None),
+        ReminderVerification::Keyword(keyword) => ("Keyword".to_string(), Some(keyword)),
+    }
+}
+
+pub fn parse_verification(kind: &str, keyword: Option<String>) -> ReminderVerification {
+    match (kind, keyword) {
+        ("None", _) => ReminderVerification::None,
+        ("Photo", _) => ReminderVerification::Photo,
+        ("Text", _) => ReminderVerification::Text,
+        ("Keyword", Some(keyword)) => ReminderVerification::Keyword(keyword),
+        (other, _) => {
+            log::warn!(
+                "Warning: Unknown verification {}, defaulting to None",
+                other
+            );
+            ReminderVerification::None
+        }
+    }
+}
+
+pub fn convert_challenge(challenge: ConfirmationChallenge) -> String {
+    match challenge {
+        ConfirmationChallenge::None => "None",
+        ConfirmationChallenge::Arithmetic => "Arithmetic",
+        ConfirmationChallenge::RetypeWord => "RetypeWord",
+        ConfirmationChallenge::PickOption => "PickOption",
+    }
+    .to_string()
+}
+
+pub fn parse_challenge(kind: &str) -> ConfirmationChallenge {
+    match kind {
+        "None" => ConfirmationChallenge::None,
+        "Arithmetic" => ConfirmationChallenge::Arithmetic,
+        "RetypeWord" => ConfirmationChallenge::RetypeWord,
+        "PickOption" => ConfirmationChallenge::PickOption,
+        other => {
+            log::warn!("Warning: Unknown challenge {}, defaulting to None", other);
+            ConfirmationChallenge::None
+        }
+    }
+}
+
+pub fn convert_dependency(dependency: Option<ReminderDependency>) -> (Option<i64>, i64) {
+    match dependency {
+        Some(dependency) => (
+            Some(dependency.predecessor_id),
+            dependency.delay.num_seconds(),
+        ),
+        None => (None, 0),
+    }
+}
+
+pub fn parse_dependency(
+    predecessor_id: Option<i64>,
+    delay_seconds: i64,
+) -> Option<ReminderDependency> {
+    predecessor_id.map(|predecessor_id| ReminderDependency {
+        predecessor_id,
+        delay: TimeDelta::seconds(delay_seconds),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nadoeda_models::reminder::{
+        ConfirmationChallenge, Reminder, ReminderDependency, ReminderFireTime, ReminderState,
+        ReminderVerification,
+    };
+    use proptest::prelude::*;
+
+    fn arb_reminder_state() -> impl Strategy<Value = ReminderState> {
+        prop_oneof![
+            Just(ReminderState::Pending),
+            Just(ReminderState::Scheduled),
+            (1u8..=10u8).prop_map(|a| ReminderState::Nagging { attempts_left: a }),
+            (1u8..=10u8).prop_map(|a| ReminderState::Confirming { attempts_left: a }),
+        ]
+    }
+
+    fn arb_verification() -> impl Strategy<Value = ReminderVerification> {
+        prop_oneof![
+            Just(ReminderVerification::None),
+            Just(ReminderVerification::Photo),
+            Just(ReminderVerification::Text),
+            ".*".prop_map(ReminderVerification::Keyword),
+        ]
+    }
+
+    fn arb_challenge() -> impl Strategy<Value = ConfirmationChallenge> {
+        prop_oneof![
+            Just(ConfirmationChallenge::None),
+            Just(ConfirmationChallenge::Arithmetic),
+            Just(ConfirmationChallenge::RetypeWord),
+            Just(ConfirmationChallenge::PickOption),
+        ]
+    }
+
+    fn arb_dependency() -> impl Strategy<Value = Option<ReminderDependency>> {
+        proptest::option::of(
+            (any::<i64>(), 0i64..86_400).prop_map(|(predecessor_id, delay)| ReminderDependency {
+                predecessor_id,
+                delay: TimeDelta::seconds(delay),
+            }),
+        )
+    }
+
+    fn arb_fire_time() -> impl Strategy<Value = ReminderFireTime> {
+        "12:30:00".prop_map(|_| ReminderFireTime::from_string("12:30:00").unwrap())
+    }
+
+    fn arb_reminder() -> impl Strategy<Value = Reminder> {
+        (
+            any::<i64>(),                          // id
+            any::<i64>(),                          // user_id
+            arb_fire_time(),                       // fire_at
+            ".*",                                  // text
+            arb_reminder_state(),                  // state
+            arb_verification(),                    // verification
+            arb_challenge(),                       // challenge
+            arb_dependency(),                      // dependency
+            proptest::option::of(arb_fire_time()), // fire_window_end
+        )
+            .prop_map(
+                |(
+                    id,
+                    user_id,
+                    fire_at,
+                    text,
+                    state,
+                    verification,
+                    challenge,
+                    dependency,
+                    fire_window_end,
+                )| {
+                    Reminder {
+                        id,
+                        user_id,
+                        fire_at,
+                        fire_window_end,
+                        text,
+                        state,
+                        verification,
+                        challenge,
+                        dependency,
+                        checklist: Vec::new(),
+                        version: 0,
+                    }
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn test_convert_and_parse_state_roundtrip(state in arb_reminder_state()) {
+            let (kind, attempts) = convert_state(state);
+            let parsed = parse_state(&kind, attempts);
+            match (state, parsed) {
+                (ReminderState::Pending, ReminderState::Pending)
+                | (ReminderState::Scheduled, ReminderState::Scheduled) => {},
+                (ReminderState::Nagging { attempts_left: a1 }, ReminderState::Nagging { attempts_left: a2 })
+                | (ReminderState::Confirming { attempts_left: a1 }, ReminderState::Confirming { attempts_left: a2 }) => {
+                    prop_assert_eq!(a1 as i64, a2 as i64);
+                },
+                (s1, s2) => prop_assert_eq!(s1, s2, "State mismatch after roundtrip")
+            }
+        }
+
+        #[test]
+        fn test_reminder_roundtrip(reminder in arb_reminder()) {
+            let storage: ReminderStorageModel = reminder.clone().into();
+            let restored: Reminder = storage.into();
+
+            prop_assert_eq!(reminder.id, restored.id);
+            prop_assert_eq!(reminder.user_id, restored.user_id);
+            prop_assert_eq!(reminder.text, restored.text);
+            prop_assert_eq!(reminder.fire_at.into_string(), restored.fire_at.into_string());
+            prop_assert_eq!(reminder.verification, restored.verification);
+            prop_assert_eq!(reminder.challenge, restored.challenge);
+            prop_assert_eq!(reminder.dependency, restored.dependency);
+            prop_assert_eq!(reminder.fire_window_end, restored.fire_window_end);
+
+            let (kind, attempts) = convert_state(reminder.state);
+            let (kind2, attempts2) = convert_state(restored.state);
+
+            prop_assert_eq!(kind, kind2, "State kind mismatch after roundtrip");
+            prop_assert_eq!(attempts, attempts2, "Attempts mismatch after roundtrip");
+        }
+
+        #[test]
+        fn test_parse_state_handles_unknown_strings(s in ".*") {
+            // Any non-matching state string should default to Pending
+            let parsed = parse_state(&s, Some(5));
+            if s != "Pending" && s != "Scheduled" && s != "Nagging" && s != "Confirming" {
+                match parsed {
+                    ReminderState::Pending => {},
+                    _ => prop_assert!(false, "Unexpected state for unknown string: {}", s),
+                }
+            }
+        }
+
+        #[test]
+        fn test_convert_state_consistency(state in arb_reminder_state()) {
+            let (kind, attempts) = convert_state(state);
+            match (kind.as_str(), attempts, state) {
+                ("Pending", None, ReminderState::Pending) => {},
+                ("Scheduled", None, ReminderState::Scheduled) => {},
+                ("Nagging", Some(a), ReminderState::Nagging { attempts_left }) => prop_assert_eq!(a, attempts_left as i64),
+                ("Confirming", Some(a), ReminderState::Confirming { attempts_left }) => prop_assert_eq!(a, attempts_left as i64),
+                (k, _, s) => prop_assert!(false, "Invalid conversion: kind={}, state={:?}", k, s),
+            }
+        }
+    }
+}
@@ -1,10 +1,15 @@
-use nadoeda_models::user::User;
+use nadoeda_models::reminder::ReminderFireTime;
+use nadoeda_models::user::{Language, User};
 
 #[derive(Debug, Clone)]
 pub struct UserStorageModel {
     pub id: i64,
     pub timezone: String,
     pub tg_chat_id: Option<i64>,
+    pub digest_mode: bool,
+    pub agenda_at: Option<String>,
+    pub summary_at: Option<String>,
+    pub language: String,
 }
 
 impl From<User> for UserStorageModel {
@@ -13,6 +18,10 @@ impl From<User> for UserStorageModel {
             id: value.id,
             timezone: value.timezone.to_string(),
             tg_chat_id: value.tg_chat_id,
+            digest_mode: value.digest_mode,
+            agenda_at: value.agenda_at.map(ReminderFireTime::into_string),
+            summary_at: value.summary_at.map(ReminderFireTime::into_string),
+            language: value.language.code().to_string(),
         }
     }
 }
@@ -23,6 +32,16 @@ impl From<UserStorageModel> for User {
             id: value.id,
             tg_chat_id: value.tg_chat_id,
             timezone: value.timezone.parse().unwrap_or_default(),
+            digest_mode: value.digest_mode,
+            agenda_at: value
+                .agenda_at
+                .as_deref()
+                .and_then(ReminderFireTime::from_string),
+            summary_at: value
+                .summary_at
+                .as_deref()
+                .and_then(ReminderFireTime::from_string),
+            language: Language::from_code(&value.language).unwrap_or_default(),
         }
     }
 }
@@ -30,13 +49,29 @@ impl From<UserStorageModel> for User {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use nadoeda_models::{chrono_tz, user::User};
+    use nadoeda_models::{
+        chrono::NaiveTime,
+        chrono_tz,
+        user::{Language, User},
+    };
     use proptest::prelude::*;
 
+    fn arb_daily_time() -> impl Strategy<Value = Option<ReminderFireTime>> {
+        proptest::option::of(
+            (0u32..24, 0u32..60).prop_map(|(h, m)| {
+                ReminderFireTime::new(NaiveTime::from_hms_opt(h, m, 0).unwrap())
+            }),
+        )
+    }
+
     fn arb_user() -> impl Strategy<Value = User> {
         (
             any::<i64>(),         // id
             any::<Option<i64>>(), // tg_chat_id
+            any::<bool>(),        // digest_mode
+            arb_daily_time(),     // agenda_at
+            arb_daily_time(),     // summary_at
+            proptest::sample::select(&Language::ALL[..]),
             prop_oneof![
                 Just("UTC".to_string()),
                 Just("Europe/Moscow".to_string()),
@@ -45,14 +80,20 @@ mod tests {
                 ".*".prop_map(|s| s),
             ],
         )
-            .prop_map(|(id, tg_chat_id, tz)| {
-                let timezone = tz.parse().unwrap_or_default();
-                User {
-                    id,
-                    tg_chat_id,
-                    timezone,
-                }
-            })
+            .prop_map(
+                |(id, tg_chat_id, digest_mode, agenda_at, summary_at, language, tz)| {
+                    let timezone = tz.parse().unwrap_or_default();
+                    User {
+                        id,
+                        tg_chat_id,
+                        timezone,
+                        digest_mode,
+                        agenda_at,
+                        summary_at,
+                        language,
+                    }
+                },
+            )
     }
 
     // --- Property tests ---
@@ -65,6 +106,10 @@ mod tests {
 
             prop_assert_eq!(user.id, restored.id);
             prop_assert_eq!(user.tg_chat_id, restored.tg_chat_id);
+            prop_assert_eq!(user.digest_mode, restored.digest_mode);
+            prop_assert_eq!(user.agenda_at, restored.agenda_at);
+            prop_assert_eq!(user.summary_at, restored.summary_at);
+            prop_assert_eq!(user.language, restored.language);
 
             prop_assert_eq!(
                 user.timezone.to_string(),
@@ -79,6 +124,10 @@ mod tests {
                 id,
                 timezone: tz.clone(),
                 tg_chat_id,
+                digest_mode: false,
+                agenda_at: None,
+                summary_at: None,
+                language: "en".to_string(),
             };
 
             let restored: User = storage.clone().into();
@@ -101,6 +150,10 @@ mod tests {
                 id: 1,
                 timezone: tz.clone(),
                 tg_chat_id: Some(42),
+                digest_mode: false,
+                agenda_at: None,
+                summary_at: None,
+                language: "en".to_string(),
             };
 
             let user: User = storage.into();
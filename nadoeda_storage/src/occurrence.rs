@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+use nadoeda_models::{
+    chrono::{DateTime, Utc},
+    occurrence::{ReminderOccurrence, ReminderOccurrenceId},
+    reminder::ReminderId,
+    user::UserId,
+};
+
+pub struct NewReminderOccurrence {
+    pub reminder_id: ReminderId,
+    pub user_id: UserId,
+    pub scheduled_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait ReminderOccurrenceStorage: Send + Sync {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Starts an occurrence of one of the user's reminders, fails with `NotFound` otherwise.
+    async fn insert(
+        &self,
+        occurrence: NewReminderOccurrence,
+    ) -> Result<ReminderOccurrence, Self::Error>;
+    /// Stores the progress of an occurrence of one of the user's reminders.
+    async fn update(
+        &self,
+        occurrence: ReminderOccurrence,
+        user_id: &UserId,
+    ) -> Result<ReminderOccurrence, Self::Error>;
+    async fn get(
+        &self,
+        id: &ReminderOccurrenceId,
+    ) -> Result<Option<ReminderOccurrence>, Self::Error>;
+
+    /// Returns the latest occurrences of the reminder, newest first.
+    async fn get_for_reminder(
+        &self,
+        reminder_id: &ReminderId,
+        user_id: &UserId,
+        limit: u32,
+    ) -> Result<Vec<ReminderOccurrence>, Self::Error>;
+
+    /// Returns all occurrences of the user scheduled in `[from, to)`, oldest first.
+    async fn get_for_user_between(
+        &self,
+        user_id: &UserId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<ReminderOccurrence>, Self::Error>;
+}
@@ -0,0 +1,114 @@
+use async_trait::async_trait;
+use nadoeda_models::{
+    buddy::{BuddyInvitationStatus, ReminderBuddy, ReminderBuddyId},
+    reminder::ReminderId,
+};
+
+use crate::StorageError;
+use crate::buddy::{NewReminderBuddy, ReminderBuddyStorage};
+use crate::model::buddy::{BuddyStorageModel, convert_status, convert_target};
+
+pub struct PostgresReminderBuddyStorage {
+    pool: sqlx::PgPool,
+}
+
+impl PostgresReminderBuddyStorage {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ReminderBuddyStorage for PostgresReminderBuddyStorage {
+    type Error = StorageError;
+
+    async fn get(&self, id: &ReminderBuddyId) -> Result<Option<ReminderBuddy>, Self::Error> {
+        let buddy = sqlx::query_as!(
+            BuddyStorageModel,
+            "SELECT * FROM reminder_buddies WHERE id = $1",
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(buddy.map(Into::into))
+    }
+
+    async fn get_for_reminder(
+        &self,
+        reminder_id: &ReminderId,
+    ) -> Result<Option<ReminderBuddy>, Self::Error> {
+        let buddy = sqlx::query_as!(
+            BuddyStorageModel,
+            "SELECT * FROM reminder_buddies WHERE reminder_id = $1",
+            reminder_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(buddy.map(Into::into))
+    }
+
+    async fn invite(&self, new_buddy: NewReminderBuddy) -> Result<ReminderBuddy, Self::Error> {
+        let NewReminderBuddy {
+            reminder_id,
+            user_id,
+            target,
+        } = new_buddy;
+        let (buddy_user_id, buddy_tg_chat_id) = convert_target(target);
+        let status = convert_status(BuddyInvitationStatus::Pending);
+
+        let buddy = sqlx::query_as!(
+            BuddyStorageModel,
+            "
+INSERT INTO reminder_buddies (reminder_id, user_id, buddy_user_id, buddy_tg_chat_id, status)
+SELECT id, user_id, $3::BIGINT, $4::BIGINT, $5::TEXT
+FROM reminders
+WHERE id = $1 AND user_id = $2 AND archived_at IS NULL
+ON CONFLICT (reminder_id) DO UPDATE
+SET user_id = excluded.user_id,
+    buddy_user_id = excluded.buddy_user_id,
+    buddy_tg_chat_id = excluded.buddy_tg_chat_id,
+    status = excluded.status
+RETURNING *
+",
+            reminder_id,
+            user_id,
+            buddy_user_id,
+            buddy_tg_chat_id,
+            status
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(StorageError::NotFound)?;
+
+        Ok(buddy.into())
+    }
+
+    async fn set_status(
+        &self,
+        id: &ReminderBuddyId,
+        tg_chat_id: i64,
+        status: BuddyInvitationStatus,
+    ) -> Result<ReminderBuddy, Self::Error> {
+        let status = convert_status(status);
+        let buddy = sqlx::query_as!(
+            BuddyStorageModel,
+            "
+UPDATE reminder_buddies
+SET status = $1
+WHERE id = $2
+  AND (buddy_tg_chat_id = $3 OR buddy_user_id IN (SELECT id FROM users WHERE tg_chat_id = $3))
+RETURNING *
+",
+            status,
+            id,
+            tg_chat_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(StorageError::NotFound)?;
+
+        Ok(buddy.into())
+    }
+}
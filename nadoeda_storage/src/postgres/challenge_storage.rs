@@ -0,0 +1,68 @@
+use async_trait::async_trait;
+use nadoeda_models::{
+    challenge::ChallengeAttempt,
+    chrono::{DateTime, Utc},
+    reminder::ReminderId,
+    user::UserId,
+};
+
+use crate::StorageError;
+use crate::challenge::ChallengeAttemptStorage;
+
+pub struct PostgresChallengeAttemptStorage {
+    pool: sqlx::PgPool,
+}
+
+impl PostgresChallengeAttemptStorage {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ChallengeAttemptStorage for PostgresChallengeAttemptStorage {
+    type Error = StorageError;
+
+    async fn record(&self, attempt: ChallengeAttempt) -> Result<(), Self::Error> {
+        let ChallengeAttempt {
+            reminder_id,
+            user_id,
+            correct,
+            answered_at,
+        } = attempt;
+
+        sqlx::query!(
+            "INSERT INTO reminder_challenge_attempts (reminder_id, user_id, correct, answered_at)
+VALUES ($1, $2, $3, $4)",
+            reminder_id,
+            user_id,
+            correct,
+            answered_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn count_wrong_answers(
+        &self,
+        reminder_id: &ReminderId,
+        user_id: &UserId,
+        since: DateTime<Utc>,
+    ) -> Result<u32, Self::Error> {
+        let count = sqlx::query_scalar!(
+            r#"
+SELECT COUNT(*) as "count!" FROM reminder_challenge_attempts
+WHERE reminder_id = $1 AND user_id = $2 AND correct = FALSE AND answered_at >= $3
+"#,
+            reminder_id,
+            user_id,
+            since
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count as u32)
+    }
+}
@@ -0,0 +1,47 @@
+use std::str::FromStr;
+
+use nadoeda_models::settings::DatabaseSettings;
+use sqlx::{
+    PgPool,
+    migrate::MigrateError,
+    postgres::{PgConnectOptions, PgPoolOptions},
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PostgresConnectError {
+    #[error("Invalid database URL {url:?}: {source}")]
+    InvalidUrl { url: String, source: sqlx::Error },
+    #[error("Could not connect to database {url:?}: {source}")]
+    Connect { url: String, source: sqlx::Error },
+    #[error("Could not apply database migrations: {0}")]
+    Migrate(#[from] MigrateError),
+}
+
+/// Connects to the database described by the settings and brings its schema up to date with the
+/// PostgreSQL migrations embedded into this crate.
+///
+/// The busy timeout becomes the lock timeout of every connection, WAL mode does not apply.
+pub async fn connect_and_migrate(
+    settings: &DatabaseSettings,
+) -> Result<PgPool, PostgresConnectError> {
+    let options = PgConnectOptions::from_str(&settings.url)
+        .map_err(|source| PostgresConnectError::InvalidUrl {
+            url: settings.url.clone(),
+            source,
+        })?
+        .options([("lock_timeout", settings.busy_timeout_ms.to_string())]);
+
+    let pool = PgPoolOptions::new()
+        .max_connections(settings.max_connections)
+        .connect_with(options)
+        .await
+        .map_err(|source| PostgresConnectError::Connect {
+            url: settings.url.clone(),
+            source,
+        })?;
+
+    sqlx::migrate!("./migrations_postgres").run(&pool).await?;
+
+    Ok(pool)
+}
@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use nadoeda_models::{evidence::ReminderEvidence, occurrence::ReminderOccurrenceId, user::UserId};
+
+use crate::StorageError;
+use crate::evidence::{NewReminderEvidence, ReminderEvidenceStorage};
+
+pub struct PostgresReminderEvidenceStorage {
+    pool: sqlx::PgPool,
+}
+
+impl PostgresReminderEvidenceStorage {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ReminderEvidenceStorage for PostgresReminderEvidenceStorage {
+    type Error = StorageError;
+
+    async fn insert(&self, evidence: NewReminderEvidence) -> Result<ReminderEvidence, Self::Error> {
+        let NewReminderEvidence {
+            reminder_id,
+            occurrence_id,
+            user_id,
+            photo_file_id,
+            text,
+            submitted_at,
+        } = evidence;
+
+        let evidence = sqlx::query_as!(
+            ReminderEvidence,
+            "
+INSERT INTO reminder_evidence
+    (reminder_id, occurrence_id, user_id, photo_file_id, text, submitted_at)
+SELECT r.id, o.id, r.user_id, $4::TEXT, $5::TEXT, $6::TIMESTAMPTZ
+FROM reminders r
+JOIN reminder_occurrences o ON o.reminder_id = r.id
+WHERE r.id = $1 AND o.id = $2 AND r.user_id = $3
+RETURNING id, reminder_id, occurrence_id, user_id, photo_file_id, text, submitted_at
+",
+            reminder_id,
+            occurrence_id,
+            user_id,
+            photo_file_id,
+            text,
+            submitted_at
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(StorageError::NotFound)?;
+
+        Ok(evidence)
+    }
+
+    async fn get_for_occurrence(
+        &self,
+        occurrence_id: &ReminderOccurrenceId,
+        user_id: &UserId,
+    ) -> Result<Vec<ReminderEvidence>, Self::Error> {
+        let evidence = sqlx::query_as!(
+            ReminderEvidence,
+            "
+SELECT id, reminder_id, occurrence_id, user_id, photo_file_id, text, submitted_at
+FROM reminder_evidence
+WHERE occurrence_id = $1 AND user_id = $2
+ORDER BY submitted_at ASC, id ASC
+",
+            occurrence_id,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(evidence)
+    }
+}
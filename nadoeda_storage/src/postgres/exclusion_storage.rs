@@ -0,0 +1,100 @@
+use async_trait::async_trait;
+use nadoeda_models::{chrono::NaiveDate, exclusion::ExclusionDate, user::UserId};
+
+use crate::StorageError;
+use crate::exclusion::{ExclusionDateStorage, NewExclusionDate};
+
+pub struct PostgresExclusionDateStorage {
+    pool: sqlx::PgPool,
+}
+
+impl PostgresExclusionDateStorage {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ExclusionDateStorage for PostgresExclusionDateStorage {
+    type Error = StorageError;
+
+    async fn add(
+        &self,
+        user_id: &UserId,
+        dates: Vec<NewExclusionDate>,
+    ) -> Result<u64, Self::Error> {
+        let mut transaction = self.pool.begin().await?;
+        let mut added = 0;
+
+        for NewExclusionDate { date, label } in dates {
+            added += sqlx::query!(
+                "
+INSERT INTO user_exclusion_dates (user_id, date, label)
+VALUES ($1, $2, $3)
+ON CONFLICT (user_id, date) DO NOTHING
+",
+                user_id,
+                date,
+                label
+            )
+            .execute(&mut *transaction)
+            .await?
+            .rows_affected();
+        }
+
+        transaction.commit().await?;
+
+        Ok(added)
+    }
+
+    async fn remove(&self, user_id: &UserId, date: NaiveDate) -> Result<bool, Self::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM user_exclusion_dates WHERE user_id = $1 AND date = $2",
+            user_id,
+            date
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn is_excluded(&self, user_id: &UserId, date: NaiveDate) -> Result<bool, Self::Error> {
+        let excluded = sqlx::query_scalar!(
+            r#"
+SELECT EXISTS (SELECT 1 FROM user_exclusion_dates WHERE user_id = $1 AND date = $2) as "excluded!"
+"#,
+            user_id,
+            date
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(excluded)
+    }
+
+    async fn get_upcoming(
+        &self,
+        user_id: &UserId,
+        from: NaiveDate,
+        limit: u32,
+    ) -> Result<Vec<ExclusionDate>, Self::Error> {
+        let dates = sqlx::query_as!(
+            ExclusionDate,
+            "
+SELECT id, user_id, date, label
+FROM user_exclusion_dates
+WHERE user_id = $1 AND date >= $2
+ORDER BY date
+LIMIT $3
+",
+            user_id,
+            from,
+            limit as i64
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(dates)
+    }
+}
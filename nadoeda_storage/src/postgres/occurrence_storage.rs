@@ -0,0 +1,147 @@
+use async_trait::async_trait;
+use nadoeda_models::{
+    chrono::{DateTime, Utc},
+    occurrence::{ReminderOccurrence, ReminderOccurrenceId},
+    reminder::ReminderId,
+    user::UserId,
+};
+
+use crate::StorageError;
+use crate::model::occurrence::{OccurrenceStorageModel, convert_outcome};
+use crate::occurrence::{NewReminderOccurrence, ReminderOccurrenceStorage};
+
+pub struct PostgresReminderOccurrenceStorage {
+    pool: sqlx::PgPool,
+}
+
+impl PostgresReminderOccurrenceStorage {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ReminderOccurrenceStorage for PostgresReminderOccurrenceStorage {
+    type Error = StorageError;
+
+    async fn insert(
+        &self,
+        occurrence: NewReminderOccurrence,
+    ) -> Result<ReminderOccurrence, Self::Error> {
+        let NewReminderOccurrence {
+            reminder_id,
+            user_id,
+            scheduled_at,
+        } = occurrence;
+
+        let occurrence = sqlx::query_as!(
+            OccurrenceStorageModel,
+            "
+INSERT INTO reminder_occurrences (reminder_id, user_id, scheduled_at)
+SELECT id, user_id, $3::TIMESTAMPTZ
+FROM reminders
+WHERE id = $1 AND user_id = $2
+RETURNING *
+",
+            reminder_id,
+            user_id,
+            scheduled_at
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(StorageError::NotFound)?;
+
+        Ok(occurrence.into())
+    }
+
+    async fn update(
+        &self,
+        occurrence: ReminderOccurrence,
+        user_id: &UserId,
+    ) -> Result<ReminderOccurrence, Self::Error> {
+        let outcome = occurrence.outcome.map(convert_outcome);
+
+        let occurrence = sqlx::query_as!(
+            OccurrenceStorageModel,
+            "
+UPDATE reminder_occurrences
+SET fired_at = $1, acknowledged_at = $2, confirmed_at = $3, outcome = $4
+WHERE id = $5 AND user_id = $6
+RETURNING *
+",
+            occurrence.fired_at,
+            occurrence.acknowledged_at,
+            occurrence.confirmed_at,
+            outcome,
+            occurrence.id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(StorageError::NotFound)?;
+
+        Ok(occurrence.into())
+    }
+
+    async fn get(
+        &self,
+        id: &ReminderOccurrenceId,
+    ) -> Result<Option<ReminderOccurrence>, Self::Error> {
+        let occurrence = sqlx::query_as!(
+            OccurrenceStorageModel,
+            "SELECT * FROM reminder_occurrences WHERE id = $1",
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(occurrence.map(Into::into))
+    }
+
+    async fn get_for_reminder(
+        &self,
+        reminder_id: &ReminderId,
+        user_id: &UserId,
+        limit: u32,
+    ) -> Result<Vec<ReminderOccurrence>, Self::Error> {
+        let occurrences = sqlx::query_as!(
+            OccurrenceStorageModel,
+            "
+SELECT * FROM reminder_occurrences
+WHERE reminder_id = $1 AND user_id = $2
+ORDER BY scheduled_at DESC, id DESC
+LIMIT $3
+",
+            reminder_id,
+            user_id,
+            limit as i64
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(occurrences.into_iter().map(Into::into).collect())
+    }
+
+    async fn get_for_user_between(
+        &self,
+        user_id: &UserId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<ReminderOccurrence>, Self::Error> {
+        let occurrences = sqlx::query_as!(
+            OccurrenceStorageModel,
+            "
+SELECT * FROM reminder_occurrences
+WHERE user_id = $1 AND scheduled_at >= $2 AND scheduled_at < $3
+ORDER BY scheduled_at ASC, id ASC
+",
+            user_id,
+            from,
+            to
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(occurrences.into_iter().map(Into::into).collect())
+    }
+}
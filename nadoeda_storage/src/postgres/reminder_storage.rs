@@ -0,0 +1,463 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use nadoeda_models::{
+    chrono::{DateTime, SecondsFormat, Utc},
+    reminder::{ChecklistItemId, Reminder, ReminderDependency, ReminderId, ReminderState},
+    user::UserId,
+};
+
+use crate::model::reminder::{
+    ChecklistItemStorageModel, ReminderStorageModel, convert_dependency, convert_state,
+};
+use crate::reminder::{NewReminder, ReminderStorage, ReminderStorageError};
+
+/// Archive moments are stored as text with a fixed width so that they compare in order.
+fn archived_at_text(moment: DateTime<Utc>) -> String {
+    moment.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+pub struct PostgresReminderStorage {
+    pool: sqlx::PgPool,
+}
+
+impl PostgresReminderStorage {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn with_checklist(
+        &self,
+        reminder: ReminderStorageModel,
+    ) -> Result<Reminder, ReminderStorageError> {
+        let items = sqlx::query_as!(
+            ChecklistItemStorageModel,
+            "SELECT * FROM reminder_checklist_items WHERE reminder_id = $1 ORDER BY position",
+            reminder.id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut reminder: Reminder = reminder.into();
+        reminder.checklist = items.into_iter().map(Into::into).collect();
+
+        Ok(reminder)
+    }
+
+    /// Attaches checklists to reminders of one user with a single query.
+    async fn with_checklists(
+        &self,
+        user_id: &UserId,
+        reminders: Vec<ReminderStorageModel>,
+    ) -> Result<Vec<Reminder>, ReminderStorageError> {
+        let items = sqlx::query_as!(
+            ChecklistItemStorageModel,
+            "
+SELECT reminder_checklist_items.* FROM reminder_checklist_items
+JOIN reminders ON reminders.id = reminder_checklist_items.reminder_id
+WHERE reminders.user_id = $1
+ORDER BY reminder_checklist_items.position
+",
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut checklists = HashMap::<ReminderId, Vec<_>>::new();
+        for item in items {
+            checklists
+                .entry(item.reminder_id)
+                .or_default()
+                .push(item.into());
+        }
+
+        Ok(reminders
+            .into_iter()
+            .map(|reminder| {
+                let checklist = checklists.remove(&reminder.id).unwrap_or_default();
+                Reminder {
+                    checklist,
+                    ..reminder.into()
+                }
+            })
+            .collect())
+    }
+
+    async fn get_existing(
+        &self,
+        id: &ReminderId,
+        user_id: &UserId,
+    ) -> Result<Reminder, ReminderStorageError> {
+        self.get(id, user_id)
+            .await?
+            .ok_or(ReminderStorageError::NotFound(*id))
+    }
+}
+
+#[async_trait]
+impl ReminderStorage for PostgresReminderStorage {
+    type Error = ReminderStorageError;
+
+    async fn get(
+        &self,
+        id: &ReminderId,
+        user_id: &UserId,
+    ) -> Result<Option<Reminder>, Self::Error> {
+        let reminder = sqlx::query_as!(
+            ReminderStorageModel,
+            "SELECT * FROM reminders WHERE id = $1 AND user_id = $2 AND archived_at IS NULL",
+            id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match reminder {
+            Some(reminder) => Ok(Some(self.with_checklist(reminder).await?)),
+            None => Ok(None),
+        }
+    }
+    async fn get_all_user_reminders(&self, user_id: &UserId) -> Result<Vec<Reminder>, Self::Error> {
+        let reminders = sqlx::query_as!(
+            ReminderStorageModel,
+            "SELECT * FROM reminders WHERE user_id = $1 AND archived_at IS NULL ORDER BY id DESC",
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        self.with_checklists(user_id, reminders).await
+    }
+    async fn get_archived_user_reminders(
+        &self,
+        user_id: &UserId,
+    ) -> Result<Vec<Reminder>, Self::Error> {
+        let reminders = sqlx::query_as!(
+            ReminderStorageModel,
+            "SELECT * FROM reminders WHERE user_id = $1 AND archived_at IS NOT NULL ORDER BY archived_at DESC",
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        self.with_checklists(user_id, reminders).await
+    }
+    async fn insert(&self, reminder: NewReminder) -> Result<Reminder, Self::Error> {
+        let NewReminder {
+            text,
+            fire_at,
+            user_id,
+        } = reminder;
+        let (state_kind, attempts_left) = convert_state(ReminderState::Pending);
+        let fire_at = fire_at.into_string();
+
+        let created_reminder = sqlx::query_as!(
+            ReminderStorageModel,
+            "INSERT INTO reminders (user_id, state_kind, attempts_left, fire_at, text)
+VALUES ($1, $2, $3, $4, $5) RETURNING *",
+            user_id,
+            state_kind,
+            attempts_left,
+            fire_at,
+            text
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created_reminder.into())
+    }
+
+    async fn update(&self, reminder: Reminder, user_id: &UserId) -> Result<Reminder, Self::Error> {
+        let ReminderStorageModel {
+            id,
+            user_id: _,
+            state_kind,
+            attempts_left,
+            fire_at,
+            fire_window_end,
+            text,
+            verification_kind,
+            verification_keyword,
+            challenge_kind,
+            predecessor_id: _,
+            predecessor_delay_seconds: _,
+            archived_at: _,
+            version,
+        } = reminder.into();
+        let updated_reminder = sqlx::query_as!(
+            ReminderStorageModel,
+            "
+UPDATE reminders
+SET state_kind = $1,
+    attempts_left = $2,
+    fire_at = $3,
+    fire_window_end = $4,
+    text = $5,
+    verification_kind = $6,
+    verification_keyword = $7,
+    challenge_kind = $8,
+    version = version + 1
+WHERE id = $9 AND user_id = $10 AND version = $11 AND archived_at IS NULL
+RETURNING *
+",
+            state_kind,
+            attempts_left,
+            fire_at,
+            fire_window_end,
+            text,
+            verification_kind,
+            verification_keyword,
+            challenge_kind,
+            id,
+            user_id,
+            version
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match updated_reminder {
+            Some(reminder) => self.with_checklist(reminder).await,
+            None => {
+                let exists = sqlx::query_scalar!(
+                    r#"SELECT COUNT(*) as "count!" FROM reminders WHERE id = $1 AND user_id = $2 AND archived_at IS NULL"#,
+                    id,
+                    user_id
+                )
+                .fetch_one(&self.pool)
+                .await?;
+                Err(if exists > 0 {
+                    ReminderStorageError::Conflict(id)
+                } else {
+                    ReminderStorageError::NotFound(id)
+                })
+            }
+        }
+    }
+
+    async fn delete(&self, id: &ReminderId, user_id: &UserId) -> Result<bool, Self::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM reminders WHERE id = $1 AND user_id = $2",
+            id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn archive(&self, id: &ReminderId, user_id: &UserId) -> Result<bool, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+        let now = archived_at_text(Utc::now());
+
+        let result = sqlx::query!(
+            "UPDATE reminders SET archived_at = $1 WHERE id = $2 AND user_id = $3 AND archived_at IS NULL",
+            now,
+            id,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            "UPDATE reminders SET predecessor_id = NULL WHERE predecessor_id = $1",
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    async fn restore(
+        &self,
+        id: &ReminderId,
+        user_id: &UserId,
+    ) -> Result<Option<Reminder>, Self::Error> {
+        let restored = sqlx::query_as!(
+            ReminderStorageModel,
+            "
+UPDATE reminders
+SET archived_at = NULL
+WHERE id = $1 AND user_id = $2 AND archived_at IS NOT NULL
+RETURNING *
+",
+            id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match restored {
+            Some(reminder) => Ok(Some(self.with_checklist(reminder).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn purge_archived(&self, archived_before: DateTime<Utc>) -> Result<u64, Self::Error> {
+        let archived_before = archived_at_text(archived_before);
+        let result = sqlx::query!(
+            "DELETE FROM reminders WHERE archived_at IS NOT NULL AND archived_at < $1",
+            archived_before
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn set_dependency(
+        &self,
+        id: &ReminderId,
+        user_id: &UserId,
+        dependency: Option<ReminderDependency>,
+    ) -> Result<Reminder, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        // Dependencies never cross users, so locking the owner serializes concurrent changes to
+        // one chain: otherwise two transactions could each link a pair of reminders the other way
+        // round without seeing the other link and commit a cycle together.
+        sqlx::query!("SELECT id FROM users WHERE id = $1 FOR UPDATE", user_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        let (predecessor_id, predecessor_delay_seconds) = convert_dependency(dependency);
+        let updated_reminder = sqlx::query_as!(
+            ReminderStorageModel,
+            "
+UPDATE reminders
+SET predecessor_id = $1,
+    predecessor_delay_seconds = $2
+WHERE id = $3 AND user_id = $4 AND archived_at IS NULL
+RETURNING *
+",
+            predecessor_id,
+            predecessor_delay_seconds,
+            id,
+            user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(ReminderStorageError::NotFound(*id))?;
+
+        if let Some(dependency) = dependency {
+            let predecessor_id = dependency.predecessor_id;
+            let predecessor_exists = sqlx::query_scalar!(
+                r#"SELECT COUNT(*) as "count!" FROM reminders WHERE id = $1 AND user_id = $2 AND archived_at IS NULL"#,
+                predecessor_id,
+                user_id
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+            if predecessor_exists == 0 {
+                return Err(ReminderStorageError::NotFound(predecessor_id));
+            }
+
+            let creates_cycle = sqlx::query_scalar!(
+                r#"
+WITH RECURSIVE chain(id) AS (
+    SELECT $1::BIGINT
+    UNION
+    SELECT reminders.predecessor_id FROM reminders
+    JOIN chain ON reminders.id = chain.id
+    WHERE reminders.predecessor_id IS NOT NULL
+)
+SELECT COUNT(*) as "count!" FROM chain WHERE id = $2
+"#,
+                predecessor_id,
+                id
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+            if creates_cycle > 0 {
+                return Err(ReminderStorageError::DependencyCycle(*id, predecessor_id));
+            }
+        }
+
+        tx.commit().await?;
+
+        self.with_checklist(updated_reminder).await
+    }
+
+    async fn set_checklist(
+        &self,
+        id: &ReminderId,
+        user_id: &UserId,
+        items: Vec<String>,
+    ) -> Result<Reminder, Self::Error> {
+        self.get_existing(id, user_id).await?;
+
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            "DELETE FROM reminder_checklist_items WHERE reminder_id = $1",
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        for (position, text) in items.into_iter().enumerate() {
+            let position = position as i64;
+            sqlx::query!(
+                "INSERT INTO reminder_checklist_items (reminder_id, position, text) VALUES ($1, $2, $3)",
+                id,
+                position,
+                text
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        self.get_existing(id, user_id).await
+    }
+
+    async fn toggle_checklist_item(
+        &self,
+        item_id: &ChecklistItemId,
+        user_id: &UserId,
+    ) -> Result<Reminder, Self::Error> {
+        let reminder_id = sqlx::query_scalar!(
+            "
+UPDATE reminder_checklist_items
+SET checked = NOT checked
+WHERE id = $1 AND reminder_id IN (SELECT id FROM reminders WHERE user_id = $2)
+RETURNING reminder_id
+",
+            item_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(ReminderStorageError::ChecklistItemNotFound(*item_id))?;
+
+        self.get_existing(&reminder_id, user_id).await
+    }
+
+    async fn reset_checklist(
+        &self,
+        id: &ReminderId,
+        user_id: &UserId,
+    ) -> Result<Reminder, Self::Error> {
+        sqlx::query!(
+            "
+UPDATE reminder_checklist_items
+SET checked = FALSE
+WHERE reminder_id = $1 AND reminder_id IN (SELECT id FROM reminders WHERE user_id = $2)
+",
+            id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        self.get_existing(id, user_id).await
+    }
+}
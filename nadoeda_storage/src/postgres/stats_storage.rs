@@ -0,0 +1,71 @@
+use async_trait::async_trait;
+use nadoeda_models::{
+    chrono::{DateTime, TimeDelta, Utc},
+    occurrence::ReminderOccurrence,
+    reminder::ReminderId,
+    stats::ReminderStats,
+    user::UserId,
+};
+
+use crate::StorageError;
+use crate::model::occurrence::OccurrenceStorageModel;
+use crate::stats::{ReminderStatsStorage, compute_reminder_stats};
+
+pub struct PostgresReminderStatsStorage {
+    pool: sqlx::PgPool,
+}
+
+impl PostgresReminderStatsStorage {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ReminderStatsStorage for PostgresReminderStatsStorage {
+    type Error = StorageError;
+
+    async fn get_for_reminder(
+        &self,
+        reminder_id: &ReminderId,
+        user_id: &UserId,
+        now: DateTime<Utc>,
+    ) -> Result<ReminderStats, Self::Error> {
+        let occurrences = sqlx::query_as!(
+            OccurrenceStorageModel,
+            "
+SELECT * FROM reminder_occurrences
+WHERE reminder_id = $1 AND user_id = $2 AND outcome IS NOT NULL AND scheduled_at <= $3
+ORDER BY scheduled_at ASC, id ASC
+",
+            reminder_id,
+            user_id,
+            now
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let month_ago = now - TimeDelta::days(30);
+        let wrong_answers = sqlx::query_scalar!(
+            r#"
+SELECT COUNT(*) as "count!" FROM reminder_challenge_attempts
+WHERE reminder_id = $1 AND user_id = $2 AND correct = FALSE AND answered_at >= $3
+"#,
+            reminder_id,
+            user_id,
+            month_ago
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let occurrences: Vec<ReminderOccurrence> =
+            occurrences.into_iter().map(Into::into).collect();
+
+        Ok(compute_reminder_stats(
+            *reminder_id,
+            &occurrences,
+            wrong_answers as u32,
+            now,
+        ))
+    }
+}
@@ -0,0 +1,116 @@
+use async_trait::async_trait;
+use nadoeda_models::reminder::ReminderFireTime;
+use nadoeda_models::user::{User, UserId};
+
+use crate::model::user::UserStorageModel;
+use crate::user::{NewUser, UserInfoStorage};
+
+pub struct PostgresUserInfoStorage {
+    pool: sqlx::PgPool,
+}
+
+impl PostgresUserInfoStorage {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UserInfoStorage for PostgresUserInfoStorage {
+    type Error = anyhow::Error;
+
+    async fn get(&self, id: &UserId) -> Result<Option<User>, Self::Error> {
+        let user = sqlx::query_as!(UserStorageModel, "SELECT * FROM users WHERE id = $1", id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(user.map(Into::into))
+    }
+    async fn get_all(&self) -> Result<Vec<User>, Self::Error> {
+        // Both backends share the offline query cache, which is keyed by the query text,
+        // so this one must not be identical to its SQLite counterpart.
+        let users = sqlx::query_as!(UserStorageModel, "SELECT * FROM users ORDER BY id ASC")
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(users.into_iter().map(Into::into).collect())
+    }
+    async fn get_by_tg_chat(&self, chat_id: i64) -> Result<Option<User>, Self::Error> {
+        let user = sqlx::query_as!(
+            UserStorageModel,
+            "SELECT * FROM users WHERE tg_chat_id = $1",
+            chat_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(user.map(Into::into))
+    }
+    async fn create(&self, new_user: NewUser) -> Result<User, Self::Error> {
+        let NewUser {
+            tg_chat_id,
+            timezone,
+            language,
+        } = new_user;
+        let timezone = timezone.to_string();
+        let language = language.code();
+        let user = sqlx::query_as!(
+            UserStorageModel,
+            "INSERT INTO users (tg_chat_id, timezone, language)
+                 VALUES ($1, $2, $3)
+                 RETURNING *",
+            tg_chat_id,
+            timezone,
+            language
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(user.into())
+    }
+    async fn update(&self, update_user: User) -> Result<User, Self::Error> {
+        let User {
+            id,
+            tg_chat_id,
+            timezone,
+            digest_mode,
+            agenda_at,
+            summary_at,
+            language,
+        } = update_user;
+        let timezone = timezone.to_string();
+        let agenda_at = agenda_at.map(ReminderFireTime::into_string);
+        let summary_at = summary_at.map(ReminderFireTime::into_string);
+        let language = language.code();
+        let user = sqlx::query_as!(
+            UserStorageModel,
+            "UPDATE users
+                 SET tg_chat_id = $1,
+                     timezone = $2,
+                     digest_mode = $3,
+                     agenda_at = $4,
+                     summary_at = $5,
+                     language = $6
+                 WHERE id = $7
+                 RETURNING *",
+            tg_chat_id,
+            timezone,
+            digest_mode,
+            agenda_at,
+            summary_at,
+            language,
+            id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(user.into())
+    }
+    async fn delete(&self, id: &UserId) -> Result<(), Self::Error> {
+        sqlx::query!("DELETE FROM users WHERE id = $1", id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
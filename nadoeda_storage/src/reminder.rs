@@ -1,91 +1,90 @@
 use async_trait::async_trait;
 
 use nadoeda_models::{
-    reminder::{Reminder, ReminderFireTime, ReminderId},
+    chrono::{DateTime, Utc},
+    reminder::{ChecklistItemId, Reminder, ReminderDependency, ReminderFireTime, ReminderId},
     user::UserId,
 };
+use thiserror::Error;
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NewReminder {
     pub text: String,
     pub fire_at: ReminderFireTime,
     pub user_id: UserId,
 }
 
+/// Failures of [`ReminderStorage`] shared by every backend, so callers can tell them apart
+/// without knowing which backend they talk to.
+#[derive(Debug, Error)]
+pub enum ReminderStorageError {
+    #[error(transparent)]
+    Sqlx(#[from] sqlx::Error),
+    #[error("Reminder {0} does not exist")]
+    NotFound(ReminderId),
+    #[error("Reminder {0} can't depend on reminder {1}, it would create a cycle")]
+    DependencyCycle(ReminderId, ReminderId),
+    #[error("Checklist item {0} does not exist")]
+    ChecklistItemNotFound(ChecklistItemId),
+    #[error("Reminder {0} was changed by someone else in the meantime")]
+    Conflict(ReminderId),
+}
+
 #[async_trait]
 pub trait ReminderStorage: Send + Sync {
     type Error: std::error::Error + Send + Sync + 'static;
 
+    /// Archived reminders are left out, see [`ReminderStorage::get_archived_user_reminders`].
     async fn get(&self, id: &ReminderId, user_id: &UserId)
     -> Result<Option<Reminder>, Self::Error>;
     async fn get_all_user_reminders(&self, user_id: &UserId) -> Result<Vec<Reminder>, Self::Error>;
+    async fn get_archived_user_reminders(
+        &self,
+        user_id: &UserId,
+    ) -> Result<Vec<Reminder>, Self::Error>;
     async fn insert(&self, reminder: NewReminder) -> Result<Reminder, Self::Error>;
-    async fn update(&self, reminder: Reminder) -> Result<Reminder, Self::Error>;
+    /// Updates everything but the owner, the dependency and the checklist, which have their own
+    /// methods. Fails when the user has no such reminder, or with a conflict when the reminder was
+    /// updated since this copy was read.
+    async fn update(&self, reminder: Reminder, user_id: &UserId) -> Result<Reminder, Self::Error>;
+    /// Removes the reminder together with its occurrences, checklist and buddy.
+    /// Returns `false` when the user has no such reminder.
+    async fn delete(&self, id: &ReminderId, user_id: &UserId) -> Result<bool, Self::Error>;
+    /// Hides the reminder but keeps its history. Reminders waiting for it no longer do.
+    /// Returns `false` when the user has no such active reminder.
+    async fn archive(&self, id: &ReminderId, user_id: &UserId) -> Result<bool, Self::Error>;
+    /// Brings an archived reminder back. Returns `None` when the user has no such archived reminder.
+    async fn restore(
+        &self,
+        id: &ReminderId,
+        user_id: &UserId,
+    ) -> Result<Option<Reminder>, Self::Error>;
+    /// Deletes reminders archived before the given moment and returns how many were removed.
+    async fn purge_archived(&self, archived_before: DateTime<Utc>) -> Result<u64, Self::Error>;
+    /// Links the reminder to a predecessor of the same user. Fails if this would create a cycle.
+    async fn set_dependency(
+        &self,
+        id: &ReminderId,
+        user_id: &UserId,
+        dependency: Option<ReminderDependency>,
+    ) -> Result<Reminder, Self::Error>;
+    /// Replaces the checklist with the given items, in order. An empty list removes the checklist.
+    async fn set_checklist(
+        &self,
+        id: &ReminderId,
+        user_id: &UserId,
+        items: Vec<String>,
+    ) -> Result<Reminder, Self::Error>;
+    /// Ticks or unticks one checklist item and returns the reminder it belongs to.
+    async fn toggle_checklist_item(
+        &self,
+        item_id: &ChecklistItemId,
+        user_id: &UserId,
+    ) -> Result<Reminder, Self::Error>;
+    /// Unticks every checklist item of the reminder.
+    async fn reset_checklist(
+        &self,
+        id: &ReminderId,
+        user_id: &UserId,
+    ) -> Result<Reminder, Self::Error>;
 }
-
-// struct InMemoryReminderStore {
-//     current_id: ReminderId,
-//     storage: HashMap<ReminderId, Reminder>,
-// }
-
-// pub struct InMemoryReminderStorage {
-//     store: RwLock<InMemoryReminderStore>,
-// }
-
-// impl InMemoryReminderStorage {
-
-//     pub fn new() -> Self {
-//         InMemoryReminderStorage {
-//             store: RwLock::new(InMemoryReminderStore {
-//                 current_id: 0,
-//                 storage: HashMap::new(),
-//             }),
-//         }
-//     }
-// }
-
-// #[async_trait]
-// impl ReminderStorage for InMemoryReminderStorage {
-//     type Error = anyhow::Error;
-
-//     async fn insert(&self, reminder: NewReminder) -> Result<ReminderId, Self::Error> {
-//         let mut store = self.store.write().await;
-//         let current_id = store.current_id;
-//         let storage = &mut store.storage;
-//         let reminder_insert = Reminder {
-//             fire_at: reminder.fire_at,
-//             text: reminder.text,
-//             state: ReminderState::Pending,
-//             id: current_id,
-//         };
-
-//         storage.insert(current_id, reminder_insert);
-
-//         store.current_id += 1;
-//         log::info!("Returning current id {}", current_id);
-//         Ok(current_id)
-//     }
-
-//     async fn update(&self, update_reminder: UpdateReminder) -> Result<ReminderId, Self::Error> {
-//         let mut store = self.store.write().await;
-//         let storage = &mut store.storage;
-//         let id = update_reminder.id;
-//         if let Some(mut reminder) = storage.remove(&id) {
-//             reminder.text = update_reminder.text.unwrap_or(reminder.text);
-//             reminder.fire_at = update_reminder.fire_at.unwrap_or(reminder.fire_at);
-//             storage.insert(id, reminder);
-//             Ok(id)
-//         } else {
-//             anyhow::bail!("Does not exist");
-//         }
-//     }
-
-//     async fn get(&self, id: ReminderId) -> Result<Option<Reminder>, Self::Error> {
-//         let store = self.store.read().await;
-//         store.storage.get(&id).cloned()
-//     }
-
-//     async fn get_all(&self) -> Result<Option<Reminder>, Self::Error> {
-//         let store = self.store.read().await;
-//         store.storage.values().cloned().collect()
-//     }
-// }
@@ -1,4 +1,13 @@
+pub mod buddy_storage;
+pub mod challenge_storage;
+mod connection;
+pub mod evidence_storage;
+pub mod exclusion_storage;
+pub mod occurrence_storage;
 pub mod reminder_storage;
+pub mod stats_storage;
+pub mod template_storage;
 pub mod user_storage;
 
+pub use connection::{SqliteConnectError, connect_and_migrate};
 pub use sqlx;
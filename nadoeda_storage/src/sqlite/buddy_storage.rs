@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+use nadoeda_models::{
+    buddy::{BuddyInvitationStatus, ReminderBuddy, ReminderBuddyId},
+    reminder::ReminderId,
+};
+
+use crate::StorageError;
+use crate::buddy::{NewReminderBuddy, ReminderBuddyStorage};
+use crate::model::buddy::{BuddyStorageModel, convert_status, convert_target};
+
+pub struct SqliteReminderBuddyStorage {
+    pool: sqlx::SqlitePool,
+}
+
+impl SqliteReminderBuddyStorage {
+    pub fn new(pool: sqlx::SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ReminderBuddyStorage for SqliteReminderBuddyStorage {
+    type Error = StorageError;
+
+    async fn get(&self, id: &ReminderBuddyId) -> Result<Option<ReminderBuddy>, Self::Error> {
+        let buddy = sqlx::query_as!(
+            BuddyStorageModel,
+            "SELECT * FROM reminder_buddies WHERE id = ?",
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(buddy.map(Into::into))
+    }
+
+    async fn get_for_reminder(
+        &self,
+        reminder_id: &ReminderId,
+    ) -> Result<Option<ReminderBuddy>, Self::Error> {
+        let buddy = sqlx::query_as!(
+            BuddyStorageModel,
+            "SELECT * FROM reminder_buddies WHERE reminder_id = ?",
+            reminder_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(buddy.map(Into::into))
+    }
+
+    async fn invite(&self, new_buddy: NewReminderBuddy) -> Result<ReminderBuddy, Self::Error> {
+        let NewReminderBuddy {
+            reminder_id,
+            user_id,
+            target,
+        } = new_buddy;
+        let (buddy_user_id, buddy_tg_chat_id) = convert_target(target);
+        let status = convert_status(BuddyInvitationStatus::Pending);
+
+        let buddy = sqlx::query_as!(
+            BuddyStorageModel,
+            "
+INSERT INTO reminder_buddies (reminder_id, user_id, buddy_user_id, buddy_tg_chat_id, status)
+SELECT id, user_id, ?, ?, ?
+FROM reminders
+WHERE id = ? AND user_id = ? AND archived_at IS NULL
+ON CONFLICT (reminder_id) DO UPDATE
+SET user_id = excluded.user_id,
+    buddy_user_id = excluded.buddy_user_id,
+    buddy_tg_chat_id = excluded.buddy_tg_chat_id,
+    status = excluded.status
+RETURNING *
+",
+            buddy_user_id,
+            buddy_tg_chat_id,
+            status,
+            reminder_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(StorageError::NotFound)?;
+
+        Ok(buddy.into())
+    }
+
+    async fn set_status(
+        &self,
+        id: &ReminderBuddyId,
+        tg_chat_id: i64,
+        status: BuddyInvitationStatus,
+    ) -> Result<ReminderBuddy, Self::Error> {
+        let status = convert_status(status);
+        let buddy = sqlx::query_as!(
+            BuddyStorageModel,
+            "
+UPDATE reminder_buddies
+SET status = ?
+WHERE id = ?
+  AND (buddy_tg_chat_id = ? OR buddy_user_id IN (SELECT id FROM users WHERE tg_chat_id = ?))
+RETURNING *
+",
+            status,
+            id,
+            tg_chat_id,
+            tg_chat_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(StorageError::NotFound)?;
+
+        Ok(buddy.into())
+    }
+}
@@ -0,0 +1,92 @@
+use std::{str::FromStr, time::Duration};
+
+use nadoeda_models::settings::DatabaseSettings;
+use sqlx::{
+    SqlitePool,
+    migrate::MigrateError,
+    sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions},
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SqliteConnectError {
+    #[error("Invalid database URL {url:?}: {source}")]
+    InvalidUrl { url: String, source: sqlx::Error },
+    #[error("Could not open database {url:?}: {source}")]
+    Connect { url: String, source: sqlx::Error },
+    #[error("Could not apply database migrations: {0}")]
+    Migrate(#[from] MigrateError),
+}
+
+/// Opens the database described by the settings, creating it if needed, and brings its schema
+/// up to date with the migrations embedded into this crate.
+pub async fn connect_and_migrate(
+    settings: &DatabaseSettings,
+) -> Result<SqlitePool, SqliteConnectError> {
+    let journal_mode = if settings.wal {
+        SqliteJournalMode::Wal
+    } else {
+        SqliteJournalMode::Delete
+    };
+
+    let options = SqliteConnectOptions::from_str(&settings.url)
+        .map_err(|source| SqliteConnectError::InvalidUrl {
+            url: settings.url.clone(),
+            source,
+        })?
+        .create_if_missing(true)
+        .foreign_keys(true)
+        .busy_timeout(Duration::from_millis(settings.busy_timeout_ms))
+        .journal_mode(journal_mode);
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(settings.max_connections)
+        .connect_with(options)
+        .await
+        .map_err(|source| SqliteConnectError::Connect {
+            url: settings.url.clone(),
+            source,
+        })?;
+
+    sqlx::migrate!().run(&pool).await?;
+
+    Ok(pool)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(url: &str) -> DatabaseSettings {
+        DatabaseSettings {
+            url: url.to_string(),
+            max_connections: 1,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn applies_migrations_to_a_new_database() {
+        let pool = connect_and_migrate(&settings("sqlite::memory:"))
+            .await
+            .unwrap();
+
+        let reminders: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM reminders")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(reminders, 0);
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_url() {
+        let err = connect_and_migrate(&settings("sqlite://nadoeda.db?mode=sometimes"))
+            .await
+            .unwrap_err();
+
+        assert!(
+            matches!(err, SqliteConnectError::InvalidUrl { .. }),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use async_trait::async_trait;
+use nadoeda_models::{
+    chrono::{DateTime, Utc},
+    evidence::ReminderEvidence,
+    occurrence::ReminderOccurrenceId,
+    user::UserId,
+};
+
+use crate::StorageError;
+use crate::evidence::{NewReminderEvidence, ReminderEvidenceStorage};
+
+pub struct SqliteReminderEvidenceStorage {
+    pool: sqlx::SqlitePool,
+}
+
+impl SqliteReminderEvidenceStorage {
+    pub fn new(pool: sqlx::SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ReminderEvidenceStorage for SqliteReminderEvidenceStorage {
+    type Error = StorageError;
+
+    async fn insert(&self, evidence: NewReminderEvidence) -> Result<ReminderEvidence, Self::Error> {
+        let NewReminderEvidence {
+            reminder_id,
+            occurrence_id,
+            user_id,
+            photo_file_id,
+            text,
+            submitted_at,
+        } = evidence;
+
+        let evidence = sqlx::query_as!(
+            ReminderEvidence,
+            r#"
+INSERT INTO reminder_evidence
+    (reminder_id, occurrence_id, user_id, photo_file_id, text, submitted_at)
+SELECT r.id, o.id, r.user_id, ?, ?, ?
+FROM reminders r
+JOIN reminder_occurrences o ON o.reminder_id = r.id
+WHERE r.id = ? AND r.user_id = ? AND o.id = ?
+RETURNING id, reminder_id, occurrence_id, user_id, photo_file_id, text,
+          submitted_at as "submitted_at: DateTime<Utc>"
+"#,
+            photo_file_id,
+            text,
+            submitted_at,
+            reminder_id,
+            user_id,
+            occurrence_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(StorageError::NotFound)?;
+
+        Ok(evidence)
+    }
+
+    async fn get_for_occurrence(
+        &self,
+        occurrence_id: &ReminderOccurrenceId,
+        user_id: &UserId,
+    ) -> Result<Vec<ReminderEvidence>, Self::Error> {
+        let evidence = sqlx::query_as!(
+            ReminderEvidence,
+            r#"
+SELECT id, reminder_id, occurrence_id, user_id, photo_file_id, text,
+       submitted_at as "submitted_at: DateTime<Utc>"
+FROM reminder_evidence
+WHERE occurrence_id = ? AND user_id = ?
+ORDER BY submitted_at ASC, id ASC
+"#,
+            occurrence_id,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(evidence)
+    }
+}
@@ -0,0 +1,100 @@
+use async_trait::async_trait;
+use nadoeda_models::{chrono::NaiveDate, exclusion::ExclusionDate, user::UserId};
+
+use crate::StorageError;
+use crate::exclusion::{ExclusionDateStorage, NewExclusionDate};
+
+pub struct SqliteExclusionDateStorage {
+    pool: sqlx::SqlitePool,
+}
+
+impl SqliteExclusionDateStorage {
+    pub fn new(pool: sqlx::SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ExclusionDateStorage for SqliteExclusionDateStorage {
+    type Error = StorageError;
+
+    async fn add(
+        &self,
+        user_id: &UserId,
+        dates: Vec<NewExclusionDate>,
+    ) -> Result<u64, Self::Error> {
+        let mut transaction = self.pool.begin().await?;
+        let mut added = 0;
+
+        for NewExclusionDate { date, label } in dates {
+            added += sqlx::query!(
+                "
+INSERT INTO user_exclusion_dates (user_id, date, label)
+VALUES (?, ?, ?)
+ON CONFLICT (user_id, date) DO NOTHING
+",
+                user_id,
+                date,
+                label
+            )
+            .execute(&mut *transaction)
+            .await?
+            .rows_affected();
+        }
+
+        transaction.commit().await?;
+
+        Ok(added)
+    }
+
+    async fn remove(&self, user_id: &UserId, date: NaiveDate) -> Result<bool, Self::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM user_exclusion_dates WHERE user_id = ? AND date = ?",
+            user_id,
+            date
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn is_excluded(&self, user_id: &UserId, date: NaiveDate) -> Result<bool, Self::Error> {
+        let excluded = sqlx::query_scalar!(
+            r#"
+SELECT EXISTS (SELECT 1 FROM user_exclusion_dates WHERE user_id = ? AND date = ?) as "excluded: bool"
+"#,
+            user_id,
+            date
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(excluded)
+    }
+
+    async fn get_upcoming(
+        &self,
+        user_id: &UserId,
+        from: NaiveDate,
+        limit: u32,
+    ) -> Result<Vec<ExclusionDate>, Self::Error> {
+        let dates = sqlx::query_as!(
+            ExclusionDate,
+            r#"
+SELECT id, user_id, date as "date: NaiveDate", label
+FROM user_exclusion_dates
+WHERE user_id = ? AND date >= ?
+ORDER BY date
+LIMIT ?
+"#,
+            user_id,
+            from,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(dates)
+    }
+}
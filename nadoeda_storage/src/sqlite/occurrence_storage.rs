@@ -0,0 +1,178 @@
+use async_trait::async_trait;
+use nadoeda_models::{
+    chrono::{DateTime, Utc},
+    occurrence::{ReminderOccurrence, ReminderOccurrenceId},
+    reminder::ReminderId,
+    user::UserId,
+};
+
+use crate::StorageError;
+use crate::model::occurrence::{OccurrenceStorageModel, convert_outcome};
+use crate::occurrence::{NewReminderOccurrence, ReminderOccurrenceStorage};
+
+pub struct SqliteReminderOccurrenceStorage {
+    pool: sqlx::SqlitePool,
+}
+
+impl SqliteReminderOccurrenceStorage {
+    pub fn new(pool: sqlx::SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ReminderOccurrenceStorage for SqliteReminderOccurrenceStorage {
+    type Error = StorageError;
+
+    async fn insert(
+        &self,
+        occurrence: NewReminderOccurrence,
+    ) -> Result<ReminderOccurrence, Self::Error> {
+        let NewReminderOccurrence {
+            reminder_id,
+            user_id,
+            scheduled_at,
+        } = occurrence;
+
+        let occurrence = sqlx::query_as!(
+            OccurrenceStorageModel,
+            r#"
+INSERT INTO reminder_occurrences (reminder_id, user_id, scheduled_at)
+SELECT id, user_id, ?
+FROM reminders
+WHERE id = ? AND user_id = ?
+RETURNING id, reminder_id, user_id,
+          scheduled_at as "scheduled_at: DateTime<Utc>",
+          fired_at as "fired_at: DateTime<Utc>",
+          acknowledged_at as "acknowledged_at: DateTime<Utc>",
+          confirmed_at as "confirmed_at: DateTime<Utc>",
+          outcome
+"#,
+            scheduled_at,
+            reminder_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(StorageError::NotFound)?;
+
+        Ok(occurrence.into())
+    }
+
+    async fn update(
+        &self,
+        occurrence: ReminderOccurrence,
+        user_id: &UserId,
+    ) -> Result<ReminderOccurrence, Self::Error> {
+        let outcome = occurrence.outcome.map(convert_outcome);
+
+        let occurrence = sqlx::query_as!(
+            OccurrenceStorageModel,
+            r#"
+UPDATE reminder_occurrences
+SET fired_at = ?, acknowledged_at = ?, confirmed_at = ?, outcome = ?
+WHERE id = ? AND user_id = ?
+RETURNING id, reminder_id, user_id,
+          scheduled_at as "scheduled_at: DateTime<Utc>",
+          fired_at as "fired_at: DateTime<Utc>",
+          acknowledged_at as "acknowledged_at: DateTime<Utc>",
+          confirmed_at as "confirmed_at: DateTime<Utc>",
+          outcome
+"#,
+            occurrence.fired_at,
+            occurrence.acknowledged_at,
+            occurrence.confirmed_at,
+            outcome,
+            occurrence.id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(StorageError::NotFound)?;
+
+        Ok(occurrence.into())
+    }
+
+    async fn get(
+        &self,
+        id: &ReminderOccurrenceId,
+    ) -> Result<Option<ReminderOccurrence>, Self::Error> {
+        let occurrence = sqlx::query_as!(
+            OccurrenceStorageModel,
+            r#"
+SELECT id, reminder_id, user_id,
+       scheduled_at as "scheduled_at: DateTime<Utc>",
+       fired_at as "fired_at: DateTime<Utc>",
+       acknowledged_at as "acknowledged_at: DateTime<Utc>",
+       confirmed_at as "confirmed_at: DateTime<Utc>",
+       outcome
+FROM reminder_occurrences
+WHERE id = ?
+"#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(occurrence.map(Into::into))
+    }
+
+    async fn get_for_reminder(
+        &self,
+        reminder_id: &ReminderId,
+        user_id: &UserId,
+        limit: u32,
+    ) -> Result<Vec<ReminderOccurrence>, Self::Error> {
+        let occurrences = sqlx::query_as!(
+            OccurrenceStorageModel,
+            r#"
+SELECT id, reminder_id, user_id,
+       scheduled_at as "scheduled_at: DateTime<Utc>",
+       fired_at as "fired_at: DateTime<Utc>",
+       acknowledged_at as "acknowledged_at: DateTime<Utc>",
+       confirmed_at as "confirmed_at: DateTime<Utc>",
+       outcome
+FROM reminder_occurrences
+WHERE reminder_id = ? AND user_id = ?
+ORDER BY scheduled_at DESC, id DESC
+LIMIT ?
+"#,
+            reminder_id,
+            user_id,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(occurrences.into_iter().map(Into::into).collect())
+    }
+
+    async fn get_for_user_between(
+        &self,
+        user_id: &UserId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<ReminderOccurrence>, Self::Error> {
+        let occurrences = sqlx::query_as!(
+            OccurrenceStorageModel,
+            r#"
+SELECT id, reminder_id, user_id,
+       scheduled_at as "scheduled_at: DateTime<Utc>",
+       fired_at as "fired_at: DateTime<Utc>",
+       acknowledged_at as "acknowledged_at: DateTime<Utc>",
+       confirmed_at as "confirmed_at: DateTime<Utc>",
+       outcome
+FROM reminder_occurrences
+WHERE user_id = ? AND scheduled_at >= ? AND scheduled_at < ?
+ORDER BY scheduled_at ASC, id ASC
+"#,
+            user_id,
+            from,
+            to
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(occurrences.into_iter().map(Into::into).collect())
+    }
+}
@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use nadoeda_models::{
+    chrono::{DateTime, TimeDelta, Utc},
+    occurrence::ReminderOccurrence,
+    reminder::ReminderId,
+    stats::ReminderStats,
+    user::UserId,
+};
+
+use crate::StorageError;
+use crate::model::occurrence::OccurrenceStorageModel;
+use crate::stats::{ReminderStatsStorage, compute_reminder_stats};
+
+pub struct SqliteReminderStatsStorage {
+    pool: sqlx::SqlitePool,
+}
+
+impl SqliteReminderStatsStorage {
+    pub fn new(pool: sqlx::SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ReminderStatsStorage for SqliteReminderStatsStorage {
+    type Error = StorageError;
+
+    async fn get_for_reminder(
+        &self,
+        reminder_id: &ReminderId,
+        user_id: &UserId,
+        now: DateTime<Utc>,
+    ) -> Result<ReminderStats, Self::Error> {
+        let occurrences = sqlx::query_as!(
+            OccurrenceStorageModel,
+            r#"
+SELECT id, reminder_id, user_id,
+       scheduled_at as "scheduled_at: DateTime<Utc>",
+       fired_at as "fired_at: DateTime<Utc>",
+       acknowledged_at as "acknowledged_at: DateTime<Utc>",
+       confirmed_at as "confirmed_at: DateTime<Utc>",
+       outcome
+FROM reminder_occurrences
+WHERE reminder_id = ? AND user_id = ? AND outcome IS NOT NULL AND scheduled_at <= ?
+ORDER BY scheduled_at ASC, id ASC
+"#,
+            reminder_id,
+            user_id,
+            now
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let month_ago = now - TimeDelta::days(30);
+        let wrong_answers = sqlx::query_scalar!(
+            "
+SELECT COUNT(*) FROM reminder_challenge_attempts
+WHERE reminder_id = ? AND user_id = ? AND correct = FALSE AND answered_at >= ?
+",
+            reminder_id,
+            user_id,
+            month_ago
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let occurrences: Vec<ReminderOccurrence> =
+            occurrences.into_iter().map(Into::into).collect();
+
+        Ok(compute_reminder_stats(
+            *reminder_id,
+            &occurrences,
+            wrong_answers as u32,
+            now,
+        ))
+    }
+}
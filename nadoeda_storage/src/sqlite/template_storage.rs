@@ -0,0 +1,105 @@
+use async_trait::async_trait;
+use nadoeda_models::{
+    reminder::ReminderId,
+    template::{MessageTemplate, TemplateKind},
+    user::UserId,
+};
+
+use crate::StorageError;
+use crate::template::MessageTemplateStorage;
+
+pub struct SqliteMessageTemplateStorage {
+    pool: sqlx::SqlitePool,
+}
+
+impl SqliteMessageTemplateStorage {
+    pub fn new(pool: sqlx::SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl MessageTemplateStorage for SqliteMessageTemplateStorage {
+    type Error = StorageError;
+
+    async fn get_for_reminder(
+        &self,
+        reminder_id: &ReminderId,
+    ) -> Result<Vec<MessageTemplate>, Self::Error> {
+        let rows = sqlx::query!(
+            "
+SELECT id, reminder_id, kind, text
+FROM reminder_message_templates
+WHERE reminder_id = ?
+ORDER BY kind, position
+",
+            reminder_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let templates = rows
+            .into_iter()
+            .filter_map(|row| {
+                Some(MessageTemplate {
+                    id: row.id,
+                    reminder_id: row.reminder_id,
+                    kind: TemplateKind::from_code(&row.kind)?,
+                    text: row.text,
+                })
+            })
+            .collect();
+
+        Ok(templates)
+    }
+
+    async fn set(
+        &self,
+        reminder_id: &ReminderId,
+        user_id: &UserId,
+        kind: TemplateKind,
+        texts: Vec<String>,
+    ) -> Result<bool, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let owned = sqlx::query_scalar!(
+            r#"
+SELECT EXISTS (SELECT 1 FROM reminders WHERE id = ? AND user_id = ?) as "owned: bool"
+"#,
+            reminder_id,
+            user_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if !owned {
+            return Ok(false);
+        }
+
+        let kind = kind.code();
+        sqlx::query!(
+            "DELETE FROM reminder_message_templates WHERE reminder_id = ? AND kind = ?",
+            reminder_id,
+            kind
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        for (position, text) in texts.into_iter().enumerate() {
+            let position = position as i64;
+            sqlx::query!(
+                "INSERT INTO reminder_message_templates (reminder_id, kind, position, text) VALUES (?, ?, ?, ?)",
+                reminder_id,
+                kind,
+                position,
+                text
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+}
@@ -1,9 +1,8 @@
-mod model;
-
 use async_trait::async_trait;
-use model::UserStorageModel;
+use nadoeda_models::reminder::ReminderFireTime;
 use nadoeda_models::user::{User, UserId};
 
+use crate::model::user::UserStorageModel;
 use crate::user::{NewUser, UserInfoStorage};
 
 pub struct SqliteUserInfoStorage {
@@ -27,6 +26,13 @@ impl UserInfoStorage for SqliteUserInfoStorage {
 
         Ok(user.map(Into::into))
     }
+    async fn get_all(&self) -> Result<Vec<User>, Self::Error> {
+        let users = sqlx::query_as!(UserStorageModel, "SELECT * FROM users ORDER BY id")
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(users.into_iter().map(Into::into).collect())
+    }
     async fn get_by_tg_chat(&self, chat_id: i64) -> Result<Option<User>, Self::Error> {
         let user = sqlx::query_as!(
             UserStorageModel,
@@ -42,15 +48,18 @@ impl UserInfoStorage for SqliteUserInfoStorage {
         let NewUser {
             tg_chat_id,
             timezone,
+            language,
         } = new_user;
         let timezone = timezone.to_string();
+        let language = language.code();
         let user = sqlx::query_as!(
             UserStorageModel,
-            "INSERT INTO users (tg_chat_id, timezone)
-                 VALUES (?, ?)
+            "INSERT INTO users (tg_chat_id, timezone, language)
+                 VALUES (?, ?, ?)
                  RETURNING *",
             tg_chat_id,
-            timezone
+            timezone,
+            language
         )
         .fetch_one(&self.pool)
         .await?;
@@ -62,17 +71,32 @@ impl UserInfoStorage for SqliteUserInfoStorage {
             id,
             tg_chat_id,
             timezone,
+            digest_mode,
+            agenda_at,
+            summary_at,
+            language,
         } = update_user;
         let timezone = timezone.to_string();
+        let agenda_at = agenda_at.map(ReminderFireTime::into_string);
+        let summary_at = summary_at.map(ReminderFireTime::into_string);
+        let language = language.code();
         let user = sqlx::query_as!(
             UserStorageModel,
             "UPDATE users
                  SET tg_chat_id = ?,
-                     timezone = ?
+                     timezone = ?,
+                     digest_mode = ?,
+                     agenda_at = ?,
+                     summary_at = ?,
+                     language = ?
                  WHERE id = ?
                  RETURNING *",
             tg_chat_id,
             timezone,
+            digest_mode,
+            agenda_at,
+            summary_at,
+            language,
             id
         )
         .fetch_one(&self.pool)
@@ -0,0 +1,194 @@
+use async_trait::async_trait;
+use nadoeda_models::{
+    chrono::{DateTime, TimeDelta, Utc},
+    occurrence::{OccurrenceOutcome, ReminderOccurrence},
+    reminder::ReminderId,
+    stats::{CompletionRate, ReminderStats},
+    user::UserId,
+};
+
+#[async_trait]
+pub trait ReminderStatsStorage: Send + Sync {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    async fn get_for_reminder(
+        &self,
+        reminder_id: &ReminderId,
+        user_id: &UserId,
+        now: DateTime<Utc>,
+    ) -> Result<ReminderStats, Self::Error>;
+}
+
+/// Builds the statistics from the finished occurrences of a reminder, sorted oldest first.
+///
+/// Cancelled and snoozed occurrences neither extend nor break a streak. Rates and the average
+/// time to acknowledge only consider the last 30 days.
+pub fn compute_reminder_stats(
+    reminder_id: ReminderId,
+    occurrences: &[ReminderOccurrence],
+    wrong_challenge_answers: u32,
+    now: DateTime<Utc>,
+) -> ReminderStats {
+    let mut current_streak = 0;
+    let mut longest_streak = 0;
+
+    for occurrence in occurrences {
+        match occurrence.outcome {
+            Some(OccurrenceOutcome::Confirmed) => {
+                current_streak += 1;
+                longest_streak = longest_streak.max(current_streak);
+            }
+            Some(OccurrenceOutcome::TimedOut) => current_streak = 0,
+            _ => {}
+        }
+    }
+
+    let month_ago = now - TimeDelta::days(30);
+    let ack_times: Vec<TimeDelta> = occurrences
+        .iter()
+        .filter(|occurrence| occurrence.scheduled_at >= month_ago)
+        .filter_map(|occurrence| Some(occurrence.acknowledged_at? - occurrence.fired_at?))
+        .collect();
+
+    let average_time_to_acknowledge = (!ack_times.is_empty())
+        .then(|| ack_times.iter().sum::<TimeDelta>() / ack_times.len() as i32);
+
+    ReminderStats {
+        reminder_id,
+        current_streak,
+        longest_streak,
+        last_7_days: completion_rate(occurrences, now - TimeDelta::days(7)),
+        last_30_days: completion_rate(occurrences, month_ago),
+        average_time_to_acknowledge,
+        wrong_challenge_answers,
+    }
+}
+
+fn completion_rate(occurrences: &[ReminderOccurrence], since: DateTime<Utc>) -> CompletionRate {
+    occurrences
+        .iter()
+        .filter(|occurrence| occurrence.scheduled_at >= since)
+        .fold(CompletionRate::default(), |mut rate, occurrence| {
+            match occurrence.outcome {
+                Some(OccurrenceOutcome::Confirmed) => {
+                    rate.confirmed += 1;
+                    rate.total += 1;
+                }
+                Some(OccurrenceOutcome::TimedOut) => rate.total += 1,
+                _ => {}
+            }
+            rate
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nadoeda_models::chrono::TimeZone;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 11, 30, 12, 0, 0).unwrap()
+    }
+
+    fn occurrence(days_ago: i64, outcome: OccurrenceOutcome) -> ReminderOccurrence {
+        let scheduled_at = now() - TimeDelta::days(days_ago);
+        ReminderOccurrence {
+            id: days_ago,
+            reminder_id: 1,
+            user_id: 1,
+            scheduled_at,
+            fired_at: Some(scheduled_at),
+            acknowledged_at: None,
+            confirmed_at: None,
+            outcome: Some(outcome),
+        }
+    }
+
+    fn history(outcomes: &[(i64, OccurrenceOutcome)]) -> Vec<ReminderOccurrence> {
+        outcomes
+            .iter()
+            .map(|(days_ago, outcome)| occurrence(*days_ago, *outcome))
+            .collect()
+    }
+
+    #[test]
+    fn empty_history_has_no_stats() {
+        let stats = compute_reminder_stats(1, &[], 0, now());
+
+        assert_eq!(stats.current_streak, 0);
+        assert_eq!(stats.longest_streak, 0);
+        assert_eq!(stats.last_7_days.percent(), None);
+        assert_eq!(stats.last_30_days.percent(), None);
+        assert_eq!(stats.average_time_to_acknowledge, None);
+    }
+
+    #[test]
+    fn timeout_breaks_the_streak() {
+        use OccurrenceOutcome::*;
+        let occurrences = history(&[
+            (6, Confirmed),
+            (5, Confirmed),
+            (4, Confirmed),
+            (3, TimedOut),
+            (2, Confirmed),
+            (1, Confirmed),
+        ]);
+
+        let stats = compute_reminder_stats(1, &occurrences, 0, now());
+
+        assert_eq!(stats.current_streak, 2);
+        assert_eq!(stats.longest_streak, 3);
+    }
+
+    #[test]
+    fn cancelled_occurrences_are_ignored() {
+        use OccurrenceOutcome::*;
+        let occurrences = history(&[(3, Confirmed), (2, Cancelled), (1, Confirmed)]);
+
+        let stats = compute_reminder_stats(1, &occurrences, 0, now());
+
+        assert_eq!(stats.current_streak, 2);
+        assert_eq!(
+            stats.last_7_days,
+            CompletionRate {
+                confirmed: 2,
+                total: 2
+            }
+        );
+    }
+
+    #[test]
+    fn completion_rate_respects_window() {
+        use OccurrenceOutcome::*;
+        let occurrences = history(&[
+            (20, TimedOut),
+            (10, TimedOut),
+            (2, Confirmed),
+            (1, TimedOut),
+        ]);
+
+        let stats = compute_reminder_stats(1, &occurrences, 0, now());
+
+        assert_eq!(stats.last_7_days.percent(), Some(50));
+        assert_eq!(stats.last_30_days.percent(), Some(25));
+    }
+
+    #[test]
+    fn average_time_to_acknowledge_uses_acknowledged_occurrences() {
+        use OccurrenceOutcome::*;
+        let mut occurrences = history(&[(3, Confirmed), (2, Confirmed), (1, TimedOut)]);
+        occurrences[0].acknowledged_at = occurrences[0]
+            .fired_at
+            .map(|fired_at| fired_at + TimeDelta::minutes(2));
+        occurrences[1].acknowledged_at = occurrences[1]
+            .fired_at
+            .map(|fired_at| fired_at + TimeDelta::minutes(4));
+
+        let stats = compute_reminder_stats(1, &occurrences, 0, now());
+
+        assert_eq!(
+            stats.average_time_to_acknowledge,
+            Some(TimeDelta::minutes(3))
+        );
+    }
+}
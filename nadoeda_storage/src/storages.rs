@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use crate::memory::{
+    buddy_storage::InMemoryReminderBuddyStorage,
+    challenge_storage::InMemoryChallengeAttemptStorage,
+    evidence_storage::InMemoryReminderEvidenceStorage,
+    exclusion_storage::InMemoryExclusionDateStorage,
+    occurrence_storage::InMemoryReminderOccurrenceStorage,
+    reminder_storage::InMemoryReminderStorage, stats_storage::InMemoryReminderStatsStorage,
+    template_storage::InMemoryMessageTemplateStorage, user_storage::InMemoryUserInfoStorage,
+};
+use crate::{
+    ChallengeAttemptStorage, ExclusionDateStorage, MessageTemplateStorage, ReminderBuddyStorage,
+    ReminderEvidenceStorage, ReminderOccurrenceStorage, ReminderStatsStorage, ReminderStorage,
+    ReminderStorageError, StorageError, UserInfoStorage,
+};
+
+pub type DynReminderStorage = dyn ReminderStorage<Error = ReminderStorageError>;
+pub type DynUserInfoStorage = dyn UserInfoStorage<Error = anyhow::Error>;
+pub type DynReminderBuddyStorage = dyn ReminderBuddyStorage<Error = StorageError>;
+pub type DynReminderEvidenceStorage = dyn ReminderEvidenceStorage<Error = StorageError>;
+pub type DynChallengeAttemptStorage = dyn ChallengeAttemptStorage<Error = StorageError>;
+pub type DynReminderOccurrenceStorage = dyn ReminderOccurrenceStorage<Error = StorageError>;
+pub type DynReminderStatsStorage = dyn ReminderStatsStorage<Error = StorageError>;
+pub type DynExclusionDateStorage = dyn ExclusionDateStorage<Error = StorageError>;
+pub type DynMessageTemplateStorage = dyn MessageTemplateStorage<Error = StorageError>;
+
+/// Every storage of one backend, behind trait objects so the backend can be picked at runtime.
+#[derive(Clone)]
+pub struct Storages {
+    pub reminders: Arc<DynReminderStorage>,
+    pub users: Arc<DynUserInfoStorage>,
+    pub buddies: Arc<DynReminderBuddyStorage>,
+    pub evidence: Arc<DynReminderEvidenceStorage>,
+    pub challenges: Arc<DynChallengeAttemptStorage>,
+    pub occurrences: Arc<DynReminderOccurrenceStorage>,
+    pub stats: Arc<DynReminderStatsStorage>,
+    pub exclusions: Arc<DynExclusionDateStorage>,
+    pub templates: Arc<DynMessageTemplateStorage>,
+}
+
+impl Storages {
+    /// Storages that keep everything in memory and lose it when dropped.
+    pub fn in_memory() -> Self {
+        let reminders = Arc::new(InMemoryReminderStorage::new());
+        let occurrences = Arc::new(InMemoryReminderOccurrenceStorage::new(reminders.clone()));
+        let challenges = Arc::new(InMemoryChallengeAttemptStorage::new());
+        let users = Arc::new(InMemoryUserInfoStorage::with_reminders(reminders.clone()));
+
+        Self {
+            buddies: Arc::new(InMemoryReminderBuddyStorage::new(
+                reminders.clone(),
+                users.clone(),
+            )),
+            evidence: Arc::new(InMemoryReminderEvidenceStorage::new(occurrences.clone())),
+            stats: Arc::new(InMemoryReminderStatsStorage::new(
+                occurrences.clone(),
+                challenges.clone(),
+            )),
+            exclusions: Arc::new(InMemoryExclusionDateStorage::new()),
+            templates: Arc::new(InMemoryMessageTemplateStorage::new(reminders.clone())),
+            reminders,
+            users,
+            occurrences,
+            challenges,
+        }
+    }
+
+    #[cfg(feature = "sqlite")]
+    pub fn sqlite(pool: sqlx::SqlitePool) -> Self {
+        use crate::sqlite::{
+            buddy_storage::SqliteReminderBuddyStorage,
+            challenge_storage::SqliteChallengeAttemptStorage,
+            evidence_storage::SqliteReminderEvidenceStorage,
+            exclusion_storage::SqliteExclusionDateStorage,
+            occurrence_storage::SqliteReminderOccurrenceStorage,
+            reminder_storage::SqliteReminderStorage, stats_storage::SqliteReminderStatsStorage,
+            template_storage::SqliteMessageTemplateStorage, user_storage::SqliteUserInfoStorage,
+        };
+
+        Self {
+            reminders: Arc::new(SqliteReminderStorage::new(pool.clone())),
+            users: Arc::new(SqliteUserInfoStorage::new(pool.clone())),
+            buddies: Arc::new(SqliteReminderBuddyStorage::new(pool.clone())),
+            evidence: Arc::new(SqliteReminderEvidenceStorage::new(pool.clone())),
+            challenges: Arc::new(SqliteChallengeAttemptStorage::new(pool.clone())),
+            occurrences: Arc::new(SqliteReminderOccurrenceStorage::new(pool.clone())),
+            stats: Arc::new(SqliteReminderStatsStorage::new(pool.clone())),
+            exclusions: Arc::new(SqliteExclusionDateStorage::new(pool.clone())),
+            templates: Arc::new(SqliteMessageTemplateStorage::new(pool)),
+        }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn postgres(pool: sqlx::PgPool) -> Self {
+        use crate::postgres::{
+            buddy_storage::PostgresReminderBuddyStorage,
+            challenge_storage::PostgresChallengeAttemptStorage,
+            evidence_storage::PostgresReminderEvidenceStorage,
+            exclusion_storage::PostgresExclusionDateStorage,
+            occurrence_storage::PostgresReminderOccurrenceStorage,
+            reminder_storage::PostgresReminderStorage, stats_storage::PostgresReminderStatsStorage,
+            template_storage::PostgresMessageTemplateStorage,
+            user_storage::PostgresUserInfoStorage,
+        };
+
+        Self {
+            reminders: Arc::new(PostgresReminderStorage::new(pool.clone())),
+            users: Arc::new(PostgresUserInfoStorage::new(pool.clone())),
+            buddies: Arc::new(PostgresReminderBuddyStorage::new(pool.clone())),
+            evidence: Arc::new(PostgresReminderEvidenceStorage::new(pool.clone())),
+            challenges: Arc::new(PostgresChallengeAttemptStorage::new(pool.clone())),
+            occurrences: Arc::new(PostgresReminderOccurrenceStorage::new(pool.clone())),
+            stats: Arc::new(PostgresReminderStatsStorage::new(pool.clone())),
+            exclusions: Arc::new(PostgresExclusionDateStorage::new(pool.clone())),
+            templates: Arc::new(PostgresMessageTemplateStorage::new(pool)),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use nadoeda_models::{
+    reminder::ReminderId,
+    template::{MessageTemplate, TemplateKind},
+    user::UserId,
+};
+
+#[async_trait]
+pub trait MessageTemplateStorage: Send + Sync {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Returns the templates of the reminder, variants of each kind in the order they are used.
+    async fn get_for_reminder(
+        &self,
+        reminder_id: &ReminderId,
+    ) -> Result<Vec<MessageTemplate>, Self::Error>;
+
+    /// Replaces the variants of one kind, an empty list brings the default text back.
+    /// Returns `false` when the reminder does not belong to the user.
+    async fn set(
+        &self,
+        reminder_id: &ReminderId,
+        user_id: &UserId,
+        kind: TemplateKind,
+        texts: Vec<String>,
+    ) -> Result<bool, Self::Error>;
+}
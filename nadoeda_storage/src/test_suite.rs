@@ -0,0 +1,701 @@
+//! Behaviour every [`ReminderStorage`] and [`UserInfoStorage`] backend has to share.
+//!
+//! A backend runs the whole suite with [`storage_test_suite!`], giving it an async expression
+//! that prepares a fresh, empty [`StorageFixture`] for every test, or `None` to skip the tests
+//! when the backend is not available. The generated tests use `#[tokio::test]`.
+//!
+//! A backend that may be missing names an environment variable with `required_by`; when it is
+//! set, an unavailable backend fails the tests instead of skipping them.
+//!
+//! ```ignore
+//! async fn setup() -> Option<(SqliteUserInfoStorage, SqliteReminderStorage)> { ... }
+//!
+//! nadoeda_storage::storage_test_suite!(setup());
+//! nadoeda_storage::storage_test_suite!(test_database(), required_by = "NADOEDA_REQUIRE_POSTGRES");
+//! ```
+//!
+//! The cases about the storages around reminders take the whole [`Storages`] of a backend and are
+//! called from a test of their own.
+use std::fmt::Debug;
+
+use nadoeda_models::{
+    buddy::{BuddyInvitationStatus, BuddyTarget},
+    chrono::{NaiveTime, TimeDelta, Utc},
+    chrono_tz,
+    occurrence::ReminderOccurrence,
+    reminder::{Reminder, ReminderDependency, ReminderFireTime},
+    user::{Language, UserId},
+};
+
+use crate::StorageError;
+use crate::buddy::NewReminderBuddy;
+use crate::evidence::NewReminderEvidence;
+use crate::occurrence::NewReminderOccurrence;
+use crate::reminder::{NewReminder, ReminderStorage};
+use crate::storages::Storages;
+use crate::user::{NewUser, UserInfoStorage};
+
+/// Storages sharing one backend, so that deleting a user affects their reminders.
+pub trait StorageFixture {
+    type Users: UserInfoStorage<Error: Debug>;
+    type Reminders: ReminderStorage;
+
+    fn users(&self) -> &Self::Users;
+    fn reminders(&self) -> &Self::Reminders;
+}
+
+impl<U, R> StorageFixture for (U, R)
+where
+    U: UserInfoStorage<Error: Debug>,
+    R: ReminderStorage,
+{
+    type Users = U;
+    type Reminders = R;
+
+    fn users(&self) -> &U {
+        &self.0
+    }
+    fn reminders(&self) -> &R {
+        &self.1
+    }
+}
+
+/// Skips a test whose backend is not available, or fails it when `required_by` is set.
+pub fn backend_unavailable(required_by: Option<&str>) {
+    if let Some(variable) = required_by
+        && std::env::var_os(variable).is_some()
+    {
+        panic!("Storage backend is not available, but {variable} requires it");
+    }
+    println!("Storage backend is not available, skipping");
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __storage_test_suite_cases {
+    ($setup:expr, $required_by:expr; $($name:ident),* $(,)?) => {
+        $(
+            #[tokio::test]
+            async fn $name() {
+                let Some(fixture) = $setup.await else {
+                    $crate::test_suite::backend_unavailable($required_by);
+                    return;
+                };
+                $crate::test_suite::$name(&fixture).await;
+            }
+        )*
+    };
+}
+
+/// Generates one test per case of the suite, see the [module docs](crate::test_suite).
+#[macro_export]
+macro_rules! storage_test_suite {
+    (@cases $setup:expr, $required_by:expr) => {
+        $crate::__storage_test_suite_cases!(
+            $setup, $required_by;
+            get_is_limited_to_the_owner,
+            writes_by_another_user_are_rejected,
+            user_reminders_are_newest_first,
+            deleting_a_user_deletes_their_reminders,
+            archived_reminders_are_hidden_until_restored,
+            purging_keeps_recently_archived_reminders,
+            archiving_detaches_dependents,
+            archived_reminders_can_not_be_changed,
+            dependencies_can_not_form_cycles,
+            update_keeps_dependency_and_checklist,
+            updating_an_outdated_copy_conflicts,
+            checklist_items_are_toggled_by_their_owner,
+            users_are_found_by_chat,
+            updated_users_are_stored,
+        );
+    };
+    ($setup:expr) => {
+        $crate::storage_test_suite!(@cases $setup, None);
+    };
+    ($setup:expr, required_by = $variable:literal) => {
+        $crate::storage_test_suite!(@cases $setup, Some($variable));
+    };
+}
+
+async fn create_user(fixture: &impl StorageFixture, chat_id: i64) -> UserId {
+    fixture
+        .users()
+        .create(NewUser {
+            timezone: chrono_tz::UTC,
+            tg_chat_id: Some(chat_id),
+            language: Language::English,
+        })
+        .await
+        .unwrap()
+        .id
+}
+
+async fn insert(fixture: &impl StorageFixture, user_id: UserId, text: &str) -> Reminder {
+    fixture
+        .reminders()
+        .insert(NewReminder {
+            text: text.to_string(),
+            fire_at: ReminderFireTime::new(NaiveTime::from_hms_opt(9, 30, 0).unwrap()),
+            user_id,
+        })
+        .await
+        .unwrap()
+}
+
+fn after(predecessor_id: i64) -> Option<ReminderDependency> {
+    Some(ReminderDependency {
+        predecessor_id,
+        delay: TimeDelta::minutes(5),
+    })
+}
+
+pub async fn get_is_limited_to_the_owner(fixture: &impl StorageFixture) {
+    let owner = create_user(fixture, 1).await;
+    let stranger = create_user(fixture, 2).await;
+    let reminder = insert(fixture, owner, "Water the plants").await;
+    let reminders = fixture.reminders();
+
+    assert_eq!(
+        reminders.get(&reminder.id, &owner).await.unwrap(),
+        Some(reminder.clone())
+    );
+    assert_eq!(reminders.get(&reminder.id, &stranger).await.unwrap(), None);
+    assert_eq!(
+        reminders.get_all_user_reminders(&stranger).await.unwrap(),
+        vec![]
+    );
+}
+
+pub async fn writes_by_another_user_are_rejected(fixture: &impl StorageFixture) {
+    let owner = create_user(fixture, 1).await;
+    let stranger = create_user(fixture, 2).await;
+    let predecessor = insert(fixture, owner, "Wake up").await;
+    let reminder = insert(fixture, owner, "Water the plants").await;
+    let strangers_own = insert(fixture, stranger, "Feed the cat").await;
+    let reminders = fixture.reminders();
+    let reminder = reminders
+        .set_checklist(&reminder.id, &owner, vec!["Step".into()])
+        .await
+        .unwrap();
+    let item_id = reminder.checklist[0].id;
+
+    let hijacked = Reminder {
+        text: "Hijacked".into(),
+        user_id: stranger,
+        ..reminder.clone()
+    };
+    assert!(reminders.update(hijacked, &stranger).await.is_err());
+    assert!(!reminders.delete(&reminder.id, &stranger).await.unwrap());
+    assert!(!reminders.archive(&reminder.id, &stranger).await.unwrap());
+    assert!(
+        reminders
+            .set_dependency(&reminder.id, &stranger, after(predecessor.id))
+            .await
+            .is_err()
+    );
+    assert!(
+        reminders
+            .set_dependency(&strangers_own.id, &stranger, after(reminder.id))
+            .await
+            .is_err()
+    );
+    assert!(
+        reminders
+            .set_checklist(&reminder.id, &stranger, vec!["Other".into()])
+            .await
+            .is_err()
+    );
+    assert!(
+        reminders
+            .toggle_checklist_item(&item_id, &stranger)
+            .await
+            .is_err()
+    );
+    assert!(
+        reminders
+            .reset_checklist(&reminder.id, &stranger)
+            .await
+            .is_err()
+    );
+    assert_eq!(
+        reminders.get(&reminder.id, &owner).await.unwrap(),
+        Some(reminder.clone())
+    );
+
+    reminders.archive(&reminder.id, &owner).await.unwrap();
+    assert_eq!(
+        reminders.restore(&reminder.id, &stranger).await.unwrap(),
+        None
+    );
+    assert_eq!(
+        reminders.restore(&reminder.id, &owner).await.unwrap(),
+        Some(reminder)
+    );
+}
+
+pub async fn user_reminders_are_newest_first(fixture: &impl StorageFixture) {
+    let owner = create_user(fixture, 1).await;
+    let stranger = create_user(fixture, 2).await;
+    let first = insert(fixture, owner, "First").await;
+    insert(fixture, stranger, "Someone else's").await;
+    let second = insert(fixture, owner, "Second").await;
+    let third = insert(fixture, owner, "Third").await;
+
+    assert_eq!(
+        fixture
+            .reminders()
+            .get_all_user_reminders(&owner)
+            .await
+            .unwrap(),
+        vec![third, second, first]
+    );
+}
+
+pub async fn deleting_a_user_deletes_their_reminders(fixture: &impl StorageFixture) {
+    let deleted = create_user(fixture, 1).await;
+    let kept = create_user(fixture, 2).await;
+    let deleted_reminder = insert(fixture, deleted, "Gone").await;
+    let kept_reminder = insert(fixture, kept, "Still here").await;
+
+    fixture.users().delete(&deleted).await.unwrap();
+
+    let reminders = fixture.reminders();
+    assert_eq!(fixture.users().get(&deleted).await.unwrap(), None);
+    assert_eq!(
+        reminders.get(&deleted_reminder.id, &deleted).await.unwrap(),
+        None
+    );
+    assert_eq!(
+        reminders.get_all_user_reminders(&deleted).await.unwrap(),
+        vec![]
+    );
+    assert_eq!(
+        reminders.get_all_user_reminders(&kept).await.unwrap(),
+        vec![kept_reminder]
+    );
+}
+
+pub async fn archived_reminders_are_hidden_until_restored(fixture: &impl StorageFixture) {
+    let owner = create_user(fixture, 1).await;
+    let archived = insert(fixture, owner, "Archived").await;
+    let active = insert(fixture, owner, "Active").await;
+    let reminders = fixture.reminders();
+
+    assert!(reminders.archive(&archived.id, &owner).await.unwrap());
+    assert!(!reminders.archive(&archived.id, &owner).await.unwrap());
+    assert_eq!(reminders.get(&archived.id, &owner).await.unwrap(), None);
+    assert_eq!(
+        reminders.get_all_user_reminders(&owner).await.unwrap(),
+        vec![active.clone()]
+    );
+    assert_eq!(
+        reminders.get_archived_user_reminders(&owner).await.unwrap(),
+        vec![archived.clone()]
+    );
+
+    assert_eq!(reminders.restore(&active.id, &owner).await.unwrap(), None);
+    assert_eq!(
+        reminders.restore(&archived.id, &owner).await.unwrap(),
+        Some(archived.clone())
+    );
+    assert_eq!(
+        reminders.get_all_user_reminders(&owner).await.unwrap(),
+        vec![active, archived]
+    );
+}
+
+pub async fn purging_keeps_recently_archived_reminders(fixture: &impl StorageFixture) {
+    let owner = create_user(fixture, 1).await;
+    let archived = insert(fixture, owner, "Archived").await;
+    let active = insert(fixture, owner, "Active").await;
+    let reminders = fixture.reminders();
+    reminders.archive(&archived.id, &owner).await.unwrap();
+
+    let purged = reminders
+        .purge_archived(Utc::now() - TimeDelta::hours(1))
+        .await
+        .unwrap();
+    assert_eq!(purged, 0);
+
+    let purged = reminders
+        .purge_archived(Utc::now() + TimeDelta::hours(1))
+        .await
+        .unwrap();
+    assert_eq!(purged, 1);
+    assert_eq!(reminders.restore(&archived.id, &owner).await.unwrap(), None);
+    assert_eq!(
+        reminders.get_all_user_reminders(&owner).await.unwrap(),
+        vec![active]
+    );
+}
+
+pub async fn archiving_detaches_dependents(fixture: &impl StorageFixture) {
+    let owner = create_user(fixture, 1).await;
+    let first = insert(fixture, owner, "First").await;
+    let second = insert(fixture, owner, "Second").await;
+    let reminders = fixture.reminders();
+    reminders
+        .set_dependency(&second.id, &owner, after(first.id))
+        .await
+        .unwrap();
+
+    reminders.archive(&first.id, &owner).await.unwrap();
+
+    let second = reminders.get(&second.id, &owner).await.unwrap().unwrap();
+    assert_eq!(second.dependency, None);
+}
+
+pub async fn archived_reminders_can_not_be_changed(fixture: &impl StorageFixture) {
+    let owner = create_user(fixture, 1).await;
+    let archived = insert(fixture, owner, "Archived").await;
+    let active = insert(fixture, owner, "Active").await;
+    let reminders = fixture.reminders();
+    reminders.archive(&archived.id, &owner).await.unwrap();
+
+    let renamed = Reminder {
+        text: "Renamed".to_string(),
+        ..archived.clone()
+    };
+    assert!(reminders.update(renamed, &owner).await.is_err());
+    assert!(
+        reminders
+            .set_dependency(&archived.id, &owner, after(active.id))
+            .await
+            .is_err()
+    );
+    assert!(
+        reminders
+            .set_dependency(&active.id, &owner, after(archived.id))
+            .await
+            .is_err()
+    );
+
+    assert_eq!(
+        reminders.restore(&archived.id, &owner).await.unwrap(),
+        Some(archived)
+    );
+    let active = reminders.get(&active.id, &owner).await.unwrap().unwrap();
+    assert_eq!(active.dependency, None);
+}
+
+pub async fn dependencies_can_not_form_cycles(fixture: &impl StorageFixture) {
+    let owner = create_user(fixture, 1).await;
+    let first = insert(fixture, owner, "First").await;
+    let second = insert(fixture, owner, "Second").await;
+    let third = insert(fixture, owner, "Third").await;
+    let reminders = fixture.reminders();
+
+    reminders
+        .set_dependency(&second.id, &owner, after(first.id))
+        .await
+        .unwrap();
+    let third = reminders
+        .set_dependency(&third.id, &owner, after(second.id))
+        .await
+        .unwrap();
+    assert_eq!(third.dependency, after(second.id));
+
+    assert!(
+        reminders
+            .set_dependency(&first.id, &owner, after(third.id))
+            .await
+            .is_err()
+    );
+    assert!(
+        reminders
+            .set_dependency(&first.id, &owner, after(first.id))
+            .await
+            .is_err()
+    );
+    let first = reminders.get(&first.id, &owner).await.unwrap().unwrap();
+    assert_eq!(first.dependency, None);
+}
+
+pub async fn update_keeps_dependency_and_checklist(fixture: &impl StorageFixture) {
+    let owner = create_user(fixture, 1).await;
+    let first = insert(fixture, owner, "First").await;
+    let second = insert(fixture, owner, "Second").await;
+    let reminders = fixture.reminders();
+    reminders
+        .set_dependency(&second.id, &owner, after(first.id))
+        .await
+        .unwrap();
+    let second = reminders
+        .set_checklist(&second.id, &owner, vec!["Step".into()])
+        .await
+        .unwrap();
+
+    let fire_window_end = Some(ReminderFireTime::new(
+        NaiveTime::from_hms_opt(10, 0, 0).unwrap(),
+    ));
+    let updated = reminders
+        .update(
+            Reminder {
+                text: "Renamed".into(),
+                fire_window_end,
+                dependency: None,
+                checklist: Vec::new(),
+                ..second.clone()
+            },
+            &owner,
+        )
+        .await
+        .unwrap();
+
+    let expected = Reminder {
+        text: "Renamed".into(),
+        fire_window_end,
+        version: second.version + 1,
+        ..second
+    };
+    assert_eq!(updated, expected);
+    assert_eq!(
+        reminders.get(&updated.id, &owner).await.unwrap(),
+        Some(expected)
+    );
+}
+
+pub async fn updating_an_outdated_copy_conflicts(fixture: &impl StorageFixture) {
+    let owner = create_user(fixture, 1).await;
+    let outdated = insert(fixture, owner, "Original").await;
+    let reminders = fixture.reminders();
+    let current = reminders
+        .update(
+            Reminder {
+                text: "First edit".into(),
+                ..outdated.clone()
+            },
+            &owner,
+        )
+        .await
+        .unwrap();
+    assert!(current.version > outdated.version);
+
+    let result = reminders
+        .update(
+            Reminder {
+                text: "Second edit".into(),
+                ..outdated
+            },
+            &owner,
+        )
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(
+        reminders.get(&current.id, &owner).await.unwrap(),
+        Some(current.clone())
+    );
+    let again = reminders
+        .update(
+            Reminder {
+                text: "Second edit".into(),
+                ..current
+            },
+            &owner,
+        )
+        .await
+        .unwrap();
+    assert_eq!(again.text, "Second edit");
+}
+
+pub async fn checklist_items_are_toggled_by_their_owner(fixture: &impl StorageFixture) {
+    let owner = create_user(fixture, 1).await;
+    let stranger = create_user(fixture, 2).await;
+    let reminder = insert(fixture, owner, "Pack").await;
+    let reminders = fixture.reminders();
+    let reminder = reminders
+        .set_checklist(&reminder.id, &owner, vec!["Passport".into(), "Keys".into()])
+        .await
+        .unwrap();
+    let texts: Vec<_> = reminder
+        .checklist
+        .iter()
+        .map(|item| item.text.as_str())
+        .collect();
+    assert_eq!(texts, ["Passport", "Keys"]);
+
+    let item_id = reminder.checklist[1].id;
+    assert!(
+        reminders
+            .toggle_checklist_item(&item_id, &stranger)
+            .await
+            .is_err()
+    );
+    let toggled = reminders
+        .toggle_checklist_item(&item_id, &owner)
+        .await
+        .unwrap();
+    assert!(!toggled.checklist[0].checked);
+    assert!(toggled.checklist[1].checked);
+
+    let reset = reminders
+        .reset_checklist(&reminder.id, &owner)
+        .await
+        .unwrap();
+    assert_eq!(reset.checklist, reminder.checklist);
+
+    let cleared = reminders
+        .set_checklist(&reminder.id, &owner, Vec::new())
+        .await
+        .unwrap();
+    assert_eq!(cleared.checklist, vec![]);
+}
+
+pub async fn users_are_found_by_chat(fixture: &impl StorageFixture) {
+    let first = create_user(fixture, 1).await;
+    let second = create_user(fixture, 2).await;
+    let users = fixture.users();
+
+    let found = users.get_by_tg_chat(2).await.unwrap().unwrap();
+    assert_eq!(found.id, second);
+    assert_eq!(users.get_by_tg_chat(3).await.unwrap(), None);
+
+    let ids: Vec<_> = users
+        .get_all()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|user| user.id)
+        .collect();
+    assert_eq!(ids, [first, second]);
+}
+
+pub async fn updated_users_are_stored(fixture: &impl StorageFixture) {
+    let id = create_user(fixture, 1).await;
+    let users = fixture.users();
+    let mut user = users.get(&id).await.unwrap().unwrap();
+    assert!(!user.digest_mode);
+    assert_eq!(user.agenda_at, None);
+
+    user.timezone = chrono_tz::Europe::Moscow;
+    user.language = Language::Russian;
+    user.digest_mode = true;
+    user.agenda_at = Some(ReminderFireTime::new(
+        NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+    ));
+    let updated = users.update(user).await.unwrap();
+
+    assert_eq!(updated, user);
+    assert_eq!(users.get(&id).await.unwrap(), Some(user));
+}
+
+pub async fn writes_around_reminders_by_another_user_are_rejected(storages: &Storages) {
+    let mut users = Vec::new();
+    for chat_id in [1, 2] {
+        let user = storages
+            .users
+            .create(NewUser {
+                timezone: chrono_tz::UTC,
+                tg_chat_id: Some(chat_id),
+                language: Language::English,
+            })
+            .await
+            .unwrap();
+        users.push(user.id);
+    }
+    let (owner, stranger) = (users[0], users[1]);
+    let reminder = storages
+        .reminders
+        .insert(NewReminder {
+            text: "Water the plants".to_string(),
+            fire_at: ReminderFireTime::new(NaiveTime::from_hms_opt(9, 30, 0).unwrap()),
+            user_id: owner,
+        })
+        .await
+        .unwrap();
+
+    let invited = storages
+        .buddies
+        .invite(NewReminderBuddy {
+            reminder_id: reminder.id,
+            user_id: stranger,
+            target: BuddyTarget::TelegramChat(2),
+        })
+        .await;
+    assert!(matches!(invited, Err(StorageError::NotFound)));
+    assert_eq!(
+        storages
+            .buddies
+            .get_for_reminder(&reminder.id)
+            .await
+            .unwrap(),
+        None
+    );
+
+    let buddy = storages
+        .buddies
+        .invite(NewReminderBuddy {
+            reminder_id: reminder.id,
+            user_id: owner,
+            target: BuddyTarget::TelegramChat(42),
+        })
+        .await
+        .unwrap();
+    let answered = storages
+        .buddies
+        .set_status(&buddy.id, 2, BuddyInvitationStatus::Accepted)
+        .await;
+    assert!(matches!(answered, Err(StorageError::NotFound)));
+    assert_eq!(
+        storages.buddies.get(&buddy.id).await.unwrap(),
+        Some(buddy.clone())
+    );
+    let answered = storages
+        .buddies
+        .set_status(&buddy.id, 42, BuddyInvitationStatus::Accepted)
+        .await
+        .unwrap();
+    assert_eq!(answered.status, BuddyInvitationStatus::Accepted);
+
+    let started = storages
+        .occurrences
+        .insert(NewReminderOccurrence {
+            reminder_id: reminder.id,
+            user_id: stranger,
+            scheduled_at: Utc::now(),
+        })
+        .await;
+    assert!(matches!(started, Err(StorageError::NotFound)));
+
+    let occurrence = storages
+        .occurrences
+        .insert(NewReminderOccurrence {
+            reminder_id: reminder.id,
+            user_id: owner,
+            scheduled_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+    let fired = ReminderOccurrence {
+        fired_at: Some(occurrence.scheduled_at),
+        ..occurrence.clone()
+    };
+    let updated = storages.occurrences.update(fired, &stranger).await;
+    assert!(matches!(updated, Err(StorageError::NotFound)));
+    assert_eq!(
+        storages.occurrences.get(&occurrence.id).await.unwrap(),
+        Some(occurrence.clone())
+    );
+
+    let submitted = storages
+        .evidence
+        .insert(NewReminderEvidence {
+            reminder_id: reminder.id,
+            occurrence_id: occurrence.id,
+            user_id: stranger,
+            photo_file_id: None,
+            text: Some("Done".to_string()),
+            submitted_at: Utc::now(),
+        })
+        .await;
+    assert!(matches!(submitted, Err(StorageError::NotFound)));
+    assert_eq!(
+        storages
+            .evidence
+            .get_for_occurrence(&occurrence.id, &stranger)
+            .await
+            .unwrap(),
+        vec![]
+    );
+}
@@ -1,19 +1,21 @@
 use async_trait::async_trait;
 use nadoeda_models::{
     chrono_tz,
-    user::{User, UserId},
+    user::{Language, User, UserId},
 };
 
 pub struct NewUser {
     pub timezone: chrono_tz::Tz,
     pub tg_chat_id: Option<i64>,
+    pub language: Language,
 }
 
 #[async_trait]
-pub trait UserInfoStorage {
+pub trait UserInfoStorage: Send + Sync {
     type Error: Send + Sync + 'static;
 
     async fn get(&self, id: &UserId) -> Result<Option<User>, Self::Error>;
+    async fn get_all(&self) -> Result<Vec<User>, Self::Error>;
     async fn get_by_tg_chat(&self, chat_id: i64) -> Result<Option<User>, Self::Error>;
     async fn create(&self, new_user: NewUser) -> Result<User, Self::Error>;
     async fn update(&self, update_user: User) -> Result<User, Self::Error>;
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use nadoeda_storage::Storages;
+use nadoeda_storage::memory::{
+    reminder_storage::InMemoryReminderStorage, user_storage::InMemoryUserInfoStorage,
+};
+use nadoeda_storage::test_suite::{
+    StorageFixture, writes_around_reminders_by_another_user_are_rejected,
+};
+
+struct InMemoryStorages {
+    users: InMemoryUserInfoStorage,
+    reminders: Arc<InMemoryReminderStorage>,
+}
+
+impl StorageFixture for InMemoryStorages {
+    type Users = InMemoryUserInfoStorage;
+    type Reminders = InMemoryReminderStorage;
+
+    fn users(&self) -> &InMemoryUserInfoStorage {
+        &self.users
+    }
+    fn reminders(&self) -> &InMemoryReminderStorage {
+        &self.reminders
+    }
+}
+
+async fn setup() -> Option<InMemoryStorages> {
+    let reminders = Arc::new(InMemoryReminderStorage::new());
+    Some(InMemoryStorages {
+        users: InMemoryUserInfoStorage::with_reminders(reminders.clone()),
+        reminders,
+    })
+}
+
+nadoeda_storage::storage_test_suite!(setup());
+
+#[tokio::test]
+async fn writes_around_reminders_are_owner_only() {
+    writes_around_reminders_by_another_user_are_rejected(&Storages::in_memory()).await;
+}
@@ -0,0 +1,350 @@
+//! Runs the storage test suite against a throwaway cluster spawned with `initdb` and `pg_ctl`,
+//! or against the server in `NADOEDA_TEST_POSTGRES_URL` when it is set. The tests are skipped
+//! when neither is available unless `NADOEDA_REQUIRE_POSTGRES` is set, a configured server that
+//! can't be used fails them.
+#![cfg(feature = "postgres")]
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use nadoeda_models::{
+    buddy::{BuddyInvitationStatus, BuddyTarget},
+    challenge::ChallengeAttempt,
+    chrono::{NaiveDate, NaiveTime, TimeDelta, TimeZone, Utc},
+    chrono_tz,
+    occurrence::OccurrenceOutcome,
+    reminder::ReminderFireTime,
+    settings::DatabaseSettings,
+    template::TemplateKind,
+    user::Language,
+};
+use nadoeda_storage::postgres::{
+    connect_and_migrate, reminder_storage::PostgresReminderStorage,
+    user_storage::PostgresUserInfoStorage,
+};
+use nadoeda_storage::test_suite::{
+    StorageFixture, backend_unavailable, writes_around_reminders_by_another_user_are_rejected,
+};
+use nadoeda_storage::{
+    NewExclusionDate, NewReminder, NewReminderBuddy, NewReminderEvidence, NewReminderOccurrence,
+    NewUser, Storages,
+};
+use sqlx::{ConnectOptions, PgPool, postgres::PgConnectOptions};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A database for a single test, the spawned cluster is stopped and removed on drop.
+struct TestDatabase {
+    users: PostgresUserInfoStorage,
+    reminders: PostgresReminderStorage,
+    storages: Storages,
+    _cluster: Option<Cluster>,
+}
+
+struct Cluster {
+    pg_ctl: PathBuf,
+    dir: PathBuf,
+}
+
+impl Drop for Cluster {
+    fn drop(&mut self) {
+        let _ = Command::new(&self.pg_ctl)
+            .args(["stop", "-m", "immediate", "-D"])
+            .arg(self.dir.join("data"))
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn find_pg_binary(name: &str) -> Option<PathBuf> {
+    let in_path = std::env::var_os("PATH").and_then(|paths| {
+        std::env::split_paths(&paths)
+            .map(|dir| dir.join(name))
+            .find(|path| path.is_file())
+    });
+    in_path.or_else(|| {
+        let output = Command::new("pg_config").arg("--bindir").output().ok()?;
+        let bindir = String::from_utf8(output.stdout).ok()?;
+        Some(Path::new(bindir.trim()).join(name)).filter(|path| path.is_file())
+    })
+}
+
+fn run_quietly(command: &mut Command) -> Option<()> {
+    command
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .ok()?
+        .success()
+        .then_some(())
+}
+
+/// Spawns a cluster that only listens on a unix socket in its own directory.
+fn spawn_cluster(id: usize) -> Option<(String, Cluster)> {
+    let initdb = find_pg_binary("initdb")?;
+    let pg_ctl = find_pg_binary("pg_ctl")?;
+    let dir = std::env::temp_dir().join(format!("nadoeda-pg-{}-{id}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).ok()?;
+    let cluster = Cluster { pg_ctl, dir };
+    let data = cluster.dir.join("data");
+
+    run_quietly(
+        Command::new(initdb)
+            .args(["-A", "trust", "-U", "postgres", "-D"])
+            .arg(&data),
+    )?;
+    run_quietly(
+        Command::new(&cluster.pg_ctl)
+            .args(["start", "-w", "-D"])
+            .arg(&data)
+            .arg("-o")
+            .arg(format!(
+                "-c listen_addresses='' -k {}",
+                cluster.dir.display()
+            )),
+    )?;
+
+    let url = format!(
+        "postgres://postgres@localhost/postgres?host={}",
+        cluster.dir.display()
+    );
+    Some((url, cluster))
+}
+
+async fn test_database() -> Option<TestDatabase> {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let (url, cluster) = match std::env::var("NADOEDA_TEST_POSTGRES_URL") {
+        Ok(url) => {
+            // Every test gets its own database on the shared server.
+            let name = format!("nadoeda_test_{}_{id}", std::process::id());
+            let options = PgConnectOptions::from_str(&url)
+                .expect("NADOEDA_TEST_POSTGRES_URL is not a PostgreSQL URL");
+            let admin = PgPool::connect_with(options.clone())
+                .await
+                .expect("NADOEDA_TEST_POSTGRES_URL is not reachable");
+            sqlx::query(&format!("DROP DATABASE IF EXISTS {name}"))
+                .execute(&admin)
+                .await
+                .unwrap();
+            sqlx::query(&format!("CREATE DATABASE {name}"))
+                .execute(&admin)
+                .await
+                .unwrap();
+            let url = options.database(&name).to_url_lossy().to_string();
+            (url, None)
+        }
+        Err(_) => {
+            let (url, cluster) = spawn_cluster(id)?;
+            (url, Some(cluster))
+        }
+    };
+
+    let settings = DatabaseSettings {
+        url,
+        max_connections: 2,
+        ..Default::default()
+    };
+    let pool = connect_and_migrate(&settings).await.unwrap();
+
+    Some(TestDatabase {
+        users: PostgresUserInfoStorage::new(pool.clone()),
+        reminders: PostgresReminderStorage::new(pool.clone()),
+        storages: Storages::postgres(pool),
+        _cluster: cluster,
+    })
+}
+
+impl StorageFixture for TestDatabase {
+    type Users = PostgresUserInfoStorage;
+    type Reminders = PostgresReminderStorage;
+
+    fn users(&self) -> &PostgresUserInfoStorage {
+        &self.users
+    }
+    fn reminders(&self) -> &PostgresReminderStorage {
+        &self.reminders
+    }
+}
+
+nadoeda_storage::storage_test_suite!(test_database(), required_by = "NADOEDA_REQUIRE_POSTGRES");
+
+/// The storages around reminders map their chrono types onto the PostgreSQL columns.
+#[tokio::test]
+async fn auxiliary_storages_round_trip() {
+    let Some(database) = test_database().await else {
+        backend_unavailable(Some("NADOEDA_REQUIRE_POSTGRES"));
+        return;
+    };
+    let storages = &database.storages;
+    let user = storages
+        .users
+        .create(NewUser {
+            timezone: chrono_tz::UTC,
+            tg_chat_id: Some(1),
+            language: Language::English,
+        })
+        .await
+        .unwrap();
+    let reminder = storages
+        .reminders
+        .insert(NewReminder {
+            text: "Water the plants".to_string(),
+            fire_at: ReminderFireTime::new(NaiveTime::from_hms_opt(9, 30, 0).unwrap()),
+            user_id: user.id,
+        })
+        .await
+        .unwrap();
+    let scheduled_at = Utc.with_ymd_and_hms(2026, 3, 1, 9, 30, 0).unwrap();
+
+    let mut occurrence = storages
+        .occurrences
+        .insert(NewReminderOccurrence {
+            reminder_id: reminder.id,
+            user_id: user.id,
+            scheduled_at,
+        })
+        .await
+        .unwrap();
+    occurrence.fired_at = Some(scheduled_at);
+    occurrence.acknowledged_at = Some(scheduled_at + TimeDelta::minutes(5));
+    occurrence.outcome = Some(OccurrenceOutcome::Confirmed);
+    let occurrence = storages
+        .occurrences
+        .update(occurrence, &user.id)
+        .await
+        .unwrap();
+    let between = storages
+        .occurrences
+        .get_for_user_between(&user.id, scheduled_at, scheduled_at + TimeDelta::days(1))
+        .await
+        .unwrap();
+    assert_eq!(between, vec![occurrence.clone()]);
+
+    let evidence = storages
+        .evidence
+        .insert(NewReminderEvidence {
+            reminder_id: reminder.id,
+            occurrence_id: occurrence.id,
+            user_id: user.id,
+            photo_file_id: None,
+            text: Some("Done".to_string()),
+            submitted_at: scheduled_at,
+        })
+        .await
+        .unwrap();
+    let stored = storages
+        .evidence
+        .get_for_occurrence(&occurrence.id, &user.id)
+        .await
+        .unwrap();
+    assert_eq!(stored, vec![evidence]);
+
+    storages
+        .challenges
+        .record(ChallengeAttempt {
+            reminder_id: reminder.id,
+            user_id: user.id,
+            correct: false,
+            answered_at: scheduled_at,
+        })
+        .await
+        .unwrap();
+    let wrong = storages
+        .challenges
+        .count_wrong_answers(&reminder.id, &user.id, scheduled_at)
+        .await
+        .unwrap();
+    assert_eq!(wrong, 1);
+
+    let stats = storages
+        .stats
+        .get_for_reminder(&reminder.id, &user.id, scheduled_at + TimeDelta::days(1))
+        .await
+        .unwrap();
+    assert_eq!(stats.current_streak, 1);
+    assert_eq!(stats.wrong_challenge_answers, 1);
+
+    let date = NaiveDate::from_ymd_opt(2026, 3, 8).unwrap();
+    let added = storages
+        .exclusions
+        .add(
+            &user.id,
+            vec![NewExclusionDate {
+                date,
+                label: Some("Holiday".to_string()),
+            }],
+        )
+        .await
+        .unwrap();
+    assert_eq!(added, 1);
+    assert!(
+        storages
+            .exclusions
+            .is_excluded(&user.id, date)
+            .await
+            .unwrap()
+    );
+    let upcoming = storages
+        .exclusions
+        .get_upcoming(&user.id, scheduled_at.date_naive(), 10)
+        .await
+        .unwrap();
+    assert_eq!(upcoming.len(), 1);
+    assert_eq!(upcoming[0].date, date);
+
+    let texts = vec![
+        "Time to water".to_string(),
+        "Plants are thirsty".to_string(),
+    ];
+    let set = storages
+        .templates
+        .set(&reminder.id, &user.id, TemplateKind::Fired, texts.clone())
+        .await
+        .unwrap();
+    assert!(set);
+    let templates = storages
+        .templates
+        .get_for_reminder(&reminder.id)
+        .await
+        .unwrap();
+    let stored: Vec<_> = templates
+        .into_iter()
+        .map(|template| template.text)
+        .collect();
+    assert_eq!(stored, texts);
+
+    let buddy = storages
+        .buddies
+        .invite(NewReminderBuddy {
+            reminder_id: reminder.id,
+            user_id: user.id,
+            target: BuddyTarget::TelegramChat(42),
+        })
+        .await
+        .unwrap();
+    let buddy = storages
+        .buddies
+        .set_status(&buddy.id, 42, BuddyInvitationStatus::Accepted)
+        .await
+        .unwrap();
+    let stored = storages
+        .buddies
+        .get_for_reminder(&reminder.id)
+        .await
+        .unwrap();
+    assert_eq!(stored, Some(buddy));
+}
+
+#[tokio::test]
+async fn writes_around_reminders_are_owner_only() {
+    let Some(database) = test_database().await else {
+        backend_unavailable(Some("NADOEDA_REQUIRE_POSTGRES"));
+        return;
+    };
+    writes_around_reminders_by_another_user_are_rejected(&database.storages).await;
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "sqlite")]
+
+use nadoeda_models::{
+    chrono::NaiveTime, chrono_tz, reminder::ReminderFireTime, settings::DatabaseSettings,
+    user::Language,
+};
+use nadoeda_storage::sqlite::{
+    connect_and_migrate, reminder_storage::SqliteReminderStorage, sqlx::SqlitePool,
+    user_storage::SqliteUserInfoStorage,
+};
+use nadoeda_storage::test_suite::writes_around_reminders_by_another_user_are_rejected;
+use nadoeda_storage::{
+    NewReminder, NewUser, ReminderStorage, ReminderStorageError, Storages, UserInfoStorage,
+};
+
+async fn pool() -> SqlitePool {
+    // Every connection to an in-memory database gets its own, so the pool keeps just one.
+    let settings = DatabaseSettings {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..Default::default()
+    };
+    connect_and_migrate(&settings).await.unwrap()
+}
+
+async fn setup() -> Option<(SqliteUserInfoStorage, SqliteReminderStorage)> {
+    let pool = pool().await;
+
+    Some((
+        SqliteUserInfoStorage::new(pool.clone()),
+        SqliteReminderStorage::new(pool),
+    ))
+}
+
+nadoeda_storage::storage_test_suite!(setup());
+
+#[tokio::test]
+async fn updating_a_reminder_of_another_user_is_not_found() {
+    let (users, reminders) = setup().await.unwrap();
+    let mut user_ids = Vec::new();
+    for chat_id in [1, 2] {
+        let user = users
+            .create(NewUser {
+                timezone: chrono_tz::UTC,
+                tg_chat_id: Some(chat_id),
+                language: Language::English,
+            })
+            .await
+            .unwrap();
+        user_ids.push(user.id);
+    }
+    let reminder = reminders
+        .insert(NewReminder {
+            text: "Water the plants".to_string(),
+            fire_at: ReminderFireTime::new(NaiveTime::from_hms_opt(9, 30, 0).unwrap()),
+            user_id: user_ids[0],
+        })
+        .await
+        .unwrap();
+
+    let err = reminders
+        .update(reminder.clone(), &user_ids[1])
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(err, ReminderStorageError::NotFound(id) if id == reminder.id),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn writes_around_reminders_are_owner_only() {
+    let storages = Storages::sqlite(pool().await);
+    writes_around_reminders_by_another_user_are_rejected(&storages).await;
+}
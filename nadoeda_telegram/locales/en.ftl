@@ -0,0 +1,315 @@
+## Registration
+
+auth-welcome-back = I've seen you before. Proceed.
+auth-ask-timezone = Not seen before. Send me your timezone.
+auth-registered = Timezone received. Welcome aboard.
+auth-invalid-timezone = Invalid timezone. Please try again.
+auth-invalid = Invalid.
+auth-register-first = Please register first.
+
+## Language
+
+language-changed = I will speak English from now on.
+language-usage = Please use /language en or /language ru.
+
+## Creating reminders
+
+create-start = Creating a new daily reminder! Please input reminder text. If you want to cancel, use the /cancel command.
+create-ask-time =
+    Great! You will be reminded about "{ $text }"
+    Now, please enter time when reminder is going to be fired (e.g. 13:00 or 7:30pm)
+create-ask-text = Please send me reminder text.
+create-ask-time-again = Please send me the time.
+create-confirm =
+    You will be reminded *"{ $schedule }"*
+    Reminder text is *"{ $text }"*
+    If it's okay, please press *Confirm*
+    If you want to change something, please type /cancel and start over
+create-confirm-button = Confirm
+create-saved = Reminder saved and scheduled.
+
+## Quick reminders
+
+remind-usage = Please use /remind TIME TEXT, for example /remind 08:30 take pills or /remind tomorrow 9:00 call mom.
+remind-set = Reminder *{ $text }* is set for { $schedule }\.
+remind-undo-button = Undo
+remind-removed = Reminder removed.
+remind-gone = This reminder is already gone.
+
+## Parsing times, MarkdownV2
+
+time-examples = Please send a time of day, for example *13:00*, *7:30pm* or *every day at 8*
+time-unrecognized = Could not parse time\. { time-examples }
+time-missing = Please tell me the time of day too\. { time-examples }
+time-in-past = This time has already passed\. { time-examples }
+time-not-daily = I understood *{ $schedule }*, but only daily reminders are supported yet\. { time-examples }
+time-empty-window = The window must not be empty\.
+time-skipped = *{ $time }* is skipped or repeated by a daylight saving change today\. { time-examples }
+
+## Describing schedules, not escaped
+
+schedule-daily = every day at { $time }
+schedule-weekdays = every weekday at { $time }
+schedule-weekend = every weekend at { $time }
+schedule-weekly = every { $days } at { $time }
+schedule-days = { $first } and { $last }
+schedule-today = today at { $time }
+schedule-tomorrow = tomorrow at { $time }
+schedule-on = on { $date } at { $time }
+schedule-then-daily = { $first }, then every day
+schedule-date-format = %a, %-d %b
+schedule-date-with-year-format = %a, %-d %b %Y
+schedule-monday = Monday
+schedule-tuesday = Tuesday
+schedule-wednesday = Wednesday
+schedule-thursday = Thursday
+schedule-friday = Friday
+schedule-saturday = Saturday
+schedule-sunday = Sunday
+
+## Listing and editing reminders
+
+edit-no-reminders = You have to create at least one reminder\!
+edit-reminder-at = remind every day at *{ $time }*
+edit-reminder-between = remind every day between *{ $start }* and *{ $end }*
+edit-reminder =
+    { $order }: *{ $text }* \({ $schedule }\)
+    Edit \- /edit\_{ $id }
+    Buddy \- /buddy\_{ $id }
+    Proof \- /proof\_{ $id }
+    After \- /after\_{ $id }
+    Texts \- /texts\_{ $id }
+    Delete \- /delete\_{ $id }
+edit-what = What do you want to update?
+edit-text-button = Text
+edit-time-button = Time
+edit-proof-button = Proof
+edit-challenge-button = Challenge
+edit-checklist-button = Checklist
+edit-delete-button = Delete
+edit-invalid-command = Invalid Edit Reminder command. Please try again.
+edit-ask-text = Please enter reminder text.
+edit-ask-time = Please enter the time. Example: 13:00, 7:30pm, or 14:00-17:00 to fire at a random moment in between
+edit-ask-proof = What should I ask for when you confirm this reminder?
+edit-proof-none-button = Nothing
+edit-proof-photo-button = Photo
+edit-proof-text-button = Text answer
+edit-proof-keyword-button = Keyword
+edit-ask-challenge = What should you solve before confirming this reminder?
+edit-challenge-none-button = Nothing
+edit-challenge-math-button = Math
+edit-challenge-word-button = Retype a word
+edit-challenge-pick-button = Pick a button
+edit-ask-checklist = Send me the checklist, one item per line. Send - to remove it.
+edit-ask-keyword = Please enter the keyword you will have to type to confirm the reminder.
+edit-ask-keyword-again = Please send me the keyword.
+edit-ask-checklist-again = Please send me the checklist items.
+edit-updated = Reminder updated.
+edit-updated-keyword = Reminder updated, new keyword: *"{ $keyword }"*
+edit-updated-text = Reminder updated, new text: *"{ $text }"*
+edit-updated-time = Reminder updated, new time: every day at *{ $time }*
+edit-updated-window = Reminder updated, new window: *{ $start }* \- *{ $end }*
+edit-checklist-removed = Checklist removed\.
+edit-updated-checklist =
+    Reminder updated, new checklist:
+    { $items }
+edit-conflict = This reminder was changed while you were editing it, so nothing was saved. What do you want to update?
+edit-gone = This reminder no longer exists.
+
+## Deleting reminders
+
+delete-invalid-command = Invalid Delete command. Please try again.
+delete-ask = Delete *{ $text }*? This cannot be undone, archive it instead to keep its history\.
+delete-archive-button = Archive
+delete-confirm-button = Delete
+delete-keep-button = Keep it
+delete-done = Reminder deleted.
+delete-kept = Reminder kept.
+delete-archived = Reminder archived. Use /archive to bring it back.
+delete-gone = This reminder is already gone.
+
+## Archive
+
+archive-empty = Nothing is archived.
+archive-title = Archived reminders, tap one to bring it back:
+archive-restore-button = Restore: { $text }
+archive-restored = Reminder *{ $text }* is back\.
+archive-gone = This reminder is not in the archive anymore.
+
+## Notification texts, MarkdownV2
+
+texts-invalid-command = Invalid Texts command. Please try again.
+texts-which = Which notification of *{ $text }* do you want to reword?
+texts-fired-button = When it fires
+texts-nag-button = Reminding again
+texts-confirmation-button = Asking for confirmation
+texts-timeout-button = Giving up
+texts-ask =
+    Send me the new text\. Every line is a variant: they are used in turn and the last one repeats\.
+    You can use { $placeholders }\.
+    Send \- to go back to the default text\.
+texts-current =
+    Current texts:
+    { $texts }
+texts-empty = Please send me the text.
+texts-saved = Saved { $count } text(s).
+texts-reset = The default text is back.
+texts-gone = This reminder does not exist anymore.
+
+## Reminder notifications, MarkdownV2
+
+reminder-scheduled = ⏱️ Scheduled *{ $text }*
+reminder-fired = 🚨 { $text }
+reminder-confirmation = ⁉️ { $text }
+reminder-acknowledged = ☑️ { $text }
+reminder-timeout = No reaction\! Stopping\.
+reminder-finished = ✅ { $text }
+reminder-cancelled = ❌ Cancelled { $text }
+reminder-acknowledge-button = Acknowledge
+reminder-confirm-button = Confirm
+reminder-proof-button = Send proof
+digest-fired = 🚨 Time for:
+digest-nag = 🚨 Still waiting for:
+digest-acknowledge-button = Acknowledge: { $text }
+buddy-no-reaction = 👀 Your buddy did not react to *{ $text }*\. Maybe give them a nudge?
+proof-photo = Did you *really* do it? Send me a photo as proof\.
+proof-text = Did you *really* do it? Describe what you did\.
+proof-keyword = Did you *really* do it? Type the keyword to confirm\.
+proof-invalid = This does not look like proof\. { $prompt }
+proof-expired = This reminder is not waiting for confirmation anymore, the proof was not saved.
+
+## Daily messages, MarkdownV2
+
+agenda-empty = 📋 Nothing planned for today\.
+agenda-title = 📋 *Today's agenda*
+agenda-after-predecessor = after another one
+summary-empty = 🌙 Nothing to sum up today\.
+summary-title = 🌙 *Today's summary*
+
+## Confirming reminders
+
+confirm-gone = This reminder does not exist anymore.
+confirm-checklist-first = Tick every item on the checklist first.
+challenge-arithmetic = How much is { $a } { $symbol } { $b }?
+challenge-retype = Type this word backwards: { $word }
+challenge-pick = Pick the word "{ $word }"
+challenge-wrong = Wrong answer. Try this one.
+challenge-ask-answer = Please answer the question first.
+proof-saved = Proof saved. Well done!
+proof-invalid-command = Invalid Proof command. Please try again.
+proof-none = No proof submitted yet.
+proof-latest = Latest proof for *{ $text }*:
+
+## Buddies
+
+buddy-invalid-command = Invalid Buddy command. Please try again.
+buddy-ask =
+    Who should I tell when you ignore *{ $text }*? Share their contact or send me their Telegram chat id\.
+    They have to start a conversation with me first\.
+buddy-ask-chat = Please share a contact or send me a Telegram chat id.
+buddy-self = You can't be your own buddy. Please try again.
+buddy-gone = This reminder does not exist anymore.
+buddy-someone = Someone
+buddy-invitation =
+    🤝 *{ $inviter }* wants you to be their accountability buddy for *{ $text }*\.
+    If they ignore the reminder, I will let you know\.
+buddy-accept-button = Accept
+buddy-decline-button = Decline
+buddy-invited = Invitation sent. I will let you know when they answer.
+buddy-unreachable = I could not reach that chat. Ask them to start a conversation with me and try again.
+buddy-invitation-invalid = This invitation is no longer valid.
+buddy-accepted = You are an accountability buddy now. I will tell you when they give up.
+buddy-declined = Invitation declined.
+buddy-owner-accepted = Your buddy accepted the invitation for *{ $text }*\.
+buddy-owner-declined = Your buddy declined the invitation for *{ $text }*\.
+
+## Linking reminders
+
+link-invalid-command = Invalid After command. Please try again.
+link-none-button = Nothing, use its own time
+link-ask-predecessor = Which reminder has to be confirmed before *{ $text }*?
+link-ask-delay = How many minutes should I wait after it is confirmed? Send 0 to fire right away.
+link-ask-delay-again = Please send the delay in minutes. Example: 15
+link-removed = Reminder will fire at its own time again.
+link-cycle = These reminders already wait for each other. Pick another one.
+link-gone = This reminder does not exist anymore.
+link-saved = *{ $text }* will fire { $minutes ->
+        [0] right
+        [one] { $minutes } minute
+       *[other] { $minutes } minutes
+    } after *{ $predecessor }* is confirmed\.
+
+## Statistics, MarkdownV2
+
+stats-no-reminders = You have to create at least one reminder\!
+stats-streak = Streak: *{ $current }* \(best *{ $longest }*\)
+stats-done = Done: *{ $week }* last 7 days, *{ $month }* last 30 days
+stats-time-to-acknowledge = Average time to acknowledge: *{ $duration }*
+stats-wrong-answers = Wrong challenge answers: *{ $count }*
+stats-seconds = { $seconds }s
+stats-minutes = { $minutes }m { $seconds }s
+stats-hours = { $hours }h { $minutes }m
+
+## Charts
+
+chart-all-caption = Completion of all reminders
+chart-caption = Completion of *{ $text }*
+chart-invalid-command = Invalid Chart command. Please try again.
+chart-no-acknowledgements = No acknowledgements to chart yet.
+chart-ack-caption = Time to acknowledge, oldest first
+
+## Calendars
+
+calendar-export-empty = You have no reminders to export.
+calendar-import-start = Upload an .ics calendar file. Timed events repeating daily, or not at all, will become daily reminders.
+calendar-ask-file = Please upload an .ics calendar file.
+calendar-too-large = This calendar is too large.
+calendar-not-utf8 = This calendar is not valid UTF-8.
+calendar-unreadable = I could not read this calendar: { $error }.
+calendar-no-reminders = There are no daily reminders in this calendar. Please try another one.
+calendar-preview =
+    These reminders will be created:
+    { $preview }
+calendar-skipped = { $count ->
+        [one] { $count } entry is not a daily reminder and will be skipped\.
+       *[other] { $count } entries are not daily reminders and will be skipped\.
+    }
+calendar-import-button = Import
+calendar-cancel-button = Cancel
+calendar-import-cancelled = Import cancelled.
+calendar-imported = Imported { $count } reminder(s).
+
+## Skipped days
+
+exclude-start = Which days should reminders skip? Send me dates as YYYY-MM-DD, one per line, optionally followed by a name. Or upload an .ics calendar file with holidays.
+exclude-invalid-date = I don't understand "{ $line }". Please use YYYY-MM-DD, for example 2025-12-24.
+exclude-ask-dates = Please send me the dates or an .ics file.
+exclude-no-dates = There are no dates in there. Please try again.
+exclude-added = Reminders will skip { $added } more day(s).
+exclude-added-known = Reminders will skip { $added } more day(s), { $known } were already skipped.
+exclude-upcoming =
+    Skipped days:
+    { $days }
+include-usage = Please use /include YYYY-MM-DD to stop skipping a day.
+include-done = Reminders will fire on { $date } again.
+include-not-skipped = { $date } was not skipped.
+
+## Daily message settings, MarkdownV2 except usage
+
+agenda-usage = Please use /agenda HH:MM to get it every day at that time or /agenda off to stop it.
+agenda-on = You will get the daily agenda every day at *{ $time }*\.
+agenda-off = You will not get the daily agenda anymore\.
+summary-usage = Please use /summary HH:MM to get it every day at that time or /summary off to stop it.
+summary-on = You will get the daily summary every day at *{ $time }*\.
+summary-off = You will not get the daily summary anymore\.
+
+## Digest mode
+
+digest-on = Digest mode is on. Reminders firing together will arrive in one message.
+digest-off = Digest mode is off. Every reminder will arrive in its own message.
+
+## Dialogues
+
+cancelled = Cancelled current operation.
+invalid-message = Unable to handle the message. Please try again or use /cancel to stop current operation.
+invalid-query = Unable to handle the query result. Please try again or use /cancel to stop current operation.
@@ -0,0 +1,317 @@
+## Registration
+
+auth-welcome-back = Я вас помню. Продолжаем.
+auth-ask-timezone = Мы ещё не знакомы. Пришлите мне ваш часовой пояс, например Europe/Moscow.
+auth-registered = Часовой пояс сохранён. Добро пожаловать!
+auth-invalid-timezone = Не знаю такого часового пояса. Попробуйте ещё раз.
+auth-invalid = Не понимаю.
+auth-register-first = Сначала зарегистрируйтесь.
+
+## Language
+
+language-changed = Теперь я говорю по-русски.
+language-usage = Используйте /language en или /language ru.
+
+## Creating reminders
+
+create-start = Создаём новое ежедневное напоминание! Пришлите текст напоминания. Чтобы отменить, используйте команду /cancel.
+create-ask-time =
+    Отлично! Я напомню вам о "{ $text }"
+    Теперь пришлите время, когда сработает напоминание (например, 13:00 или в 7 вечера)
+create-ask-text = Пришлите мне текст напоминания.
+create-ask-time-again = Пришлите мне время.
+create-confirm =
+    Я буду напоминать *"{ $schedule }"*
+    Текст напоминания: *"{ $text }"*
+    Если всё верно, нажмите *Подтвердить*
+    Если хотите что\-то изменить, отправьте /cancel и начните заново
+create-confirm-button = Подтвердить
+create-saved = Напоминание сохранено и запланировано.
+
+## Quick reminders
+
+remind-usage = Используйте /remind ВРЕМЯ ТЕКСТ, например /remind 08:30 выпить таблетки или /remind завтра в 9:00 позвонить маме.
+remind-set = Напоминание *{ $text }* установлено: { $schedule }\.
+remind-undo-button = Отменить
+remind-removed = Напоминание удалено.
+remind-gone = Этого напоминания уже нет.
+
+## Parsing times, MarkdownV2
+
+time-examples = Пришлите время, например *13:00*, *в 7 вечера* или *каждый день в 8*
+time-unrecognized = Не удалось разобрать время\. { time-examples }
+time-missing = Укажите ещё и время суток\. { time-examples }
+time-in-past = Это время уже прошло\. { time-examples }
+time-not-daily = Я понял *{ $schedule }*, но пока поддерживаются только ежедневные напоминания\. { time-examples }
+time-empty-window = Интервал не может быть пустым\.
+time-skipped = Время *{ $time }* сегодня пропускается или повторяется из\-за перехода на летнее или зимнее время\. { time-examples }
+
+## Describing schedules, not escaped
+
+schedule-daily = каждый день в { $time }
+schedule-weekdays = по будням в { $time }
+schedule-weekend = по выходным в { $time }
+schedule-weekly = по { $days } в { $time }
+schedule-days = { $first } и { $last }
+schedule-today = сегодня в { $time }
+schedule-tomorrow = завтра в { $time }
+schedule-on = { $date } в { $time }
+schedule-then-daily = { $first }, затем каждый день
+schedule-date-format = %d.%m
+schedule-date-with-year-format = %d.%m.%Y
+schedule-monday = понедельникам
+schedule-tuesday = вторникам
+schedule-wednesday = средам
+schedule-thursday = четвергам
+schedule-friday = пятницам
+schedule-saturday = субботам
+schedule-sunday = воскресеньям
+
+## Listing and editing reminders
+
+edit-no-reminders = Сначала создайте хотя бы одно напоминание\!
+edit-reminder-at = каждый день в *{ $time }*
+edit-reminder-between = каждый день между *{ $start }* и *{ $end }*
+edit-reminder =
+    { $order }: *{ $text }* \({ $schedule }\)
+    Изменить \- /edit\_{ $id }
+    Напарник \- /buddy\_{ $id }
+    Доказательства \- /proof\_{ $id }
+    После другого \- /after\_{ $id }
+    Тексты \- /texts\_{ $id }
+    Удалить \- /delete\_{ $id }
+edit-what = Что вы хотите изменить?
+edit-text-button = Текст
+edit-time-button = Время
+edit-proof-button = Доказательство
+edit-challenge-button = Задание
+edit-checklist-button = Чек-лист
+edit-delete-button = Удалить
+edit-invalid-command = Неверная команда редактирования. Попробуйте ещё раз.
+edit-ask-text = Пришлите новый текст напоминания.
+edit-ask-time = Пришлите время. Например: 13:00, в 7 вечера или 14:00-17:00, чтобы напоминание сработало в случайный момент между ними
+edit-ask-proof = Что спрашивать, когда вы подтверждаете это напоминание?
+edit-proof-none-button = Ничего
+edit-proof-photo-button = Фото
+edit-proof-text-button = Текстовый ответ
+edit-proof-keyword-button = Ключевое слово
+edit-ask-challenge = Что нужно решить перед подтверждением напоминания?
+edit-challenge-none-button = Ничего
+edit-challenge-math-button = Пример
+edit-challenge-word-button = Повторить слово
+edit-challenge-pick-button = Выбрать кнопку
+edit-ask-checklist = Пришлите чек-лист, по одному пункту в строке. Пришлите -, чтобы удалить его.
+edit-ask-keyword = Пришлите ключевое слово, которое нужно будет ввести для подтверждения.
+edit-ask-keyword-again = Пришлите мне ключевое слово.
+edit-ask-checklist-again = Пришлите мне пункты чек-листа.
+edit-updated = Напоминание обновлено.
+edit-updated-keyword = Напоминание обновлено, новое ключевое слово: *"{ $keyword }"*
+edit-updated-text = Напоминание обновлено, новый текст: *"{ $text }"*
+edit-updated-time = Напоминание обновлено, новое время: каждый день в *{ $time }*
+edit-updated-window = Напоминание обновлено, новый интервал: *{ $start }* \- *{ $end }*
+edit-checklist-removed = Чек\-лист удалён\.
+edit-updated-checklist =
+    Напоминание обновлено, новый чек\-лист:
+    { $items }
+edit-conflict = Напоминание изменилось, пока вы его редактировали, поэтому ничего не сохранено. Что вы хотите изменить?
+edit-gone = Этого напоминания больше нет.
+
+## Deleting reminders
+
+delete-invalid-command = Неверная команда Delete. Попробуйте ещё раз.
+delete-ask = Удалить *{ $text }*? Это нельзя отменить, заархивируйте его, чтобы сохранить историю\.
+delete-archive-button = В архив
+delete-confirm-button = Удалить
+delete-keep-button = Оставить
+delete-done = Напоминание удалено.
+delete-kept = Напоминание оставлено.
+delete-archived = Напоминание в архиве. Используйте /archive, чтобы вернуть его.
+delete-gone = Этого напоминания уже нет.
+
+## Archive
+
+archive-empty = В архиве ничего нет.
+archive-title = Архив напоминаний, нажмите на напоминание, чтобы вернуть его:
+archive-restore-button = Вернуть: { $text }
+archive-restored = Напоминание *{ $text }* снова активно\.
+archive-gone = Этого напоминания больше нет в архиве.
+
+## Notification texts, MarkdownV2
+
+texts-invalid-command = Неверная команда Texts. Попробуйте ещё раз.
+texts-which = Какое уведомление *{ $text }* вы хотите переписать?
+texts-fired-button = Срабатывание
+texts-nag-button = Повторное напоминание
+texts-confirmation-button = Запрос подтверждения
+texts-timeout-button = Отказ от ожидания
+texts-ask =
+    Отправьте мне новый текст\. Каждая строка — отдельный вариант: они используются по очереди, а последний повторяется\.
+    Можно использовать { $placeholders }\.
+    Отправьте \- чтобы вернуть текст по умолчанию\.
+texts-current =
+    Текущие тексты:
+    { $texts }
+texts-empty = Отправьте мне текст.
+texts-saved = Сохранено текстов: { $count }.
+texts-reset = Текст по умолчанию возвращён.
+texts-gone = Этого напоминания больше нет.
+
+## Reminder notifications, MarkdownV2
+
+reminder-scheduled = ⏱️ Запланировано *{ $text }*
+reminder-fired = 🚨 { $text }
+reminder-confirmation = ⁉️ { $text }
+reminder-acknowledged = ☑️ { $text }
+reminder-timeout = Нет реакции\! Останавливаюсь\.
+reminder-finished = ✅ { $text }
+reminder-cancelled = ❌ Отменено { $text }
+reminder-acknowledge-button = Принято
+reminder-confirm-button = Подтвердить
+reminder-proof-button = Отправить доказательство
+digest-fired = 🚨 Пора:
+digest-nag = 🚨 Всё ещё жду:
+digest-acknowledge-button = Принято: { $text }
+buddy-no-reaction = 👀 Ваш напарник не отреагировал на *{ $text }*\. Может, напомните ему?
+proof-photo = Вы *правда* это сделали? Пришлите фото в доказательство\.
+proof-text = Вы *правда* это сделали? Опишите, что вы сделали\.
+proof-keyword = Вы *правда* это сделали? Введите ключевое слово для подтверждения\.
+proof-invalid = Это не похоже на доказательство\. { $prompt }
+proof-expired = Это напоминание больше не ждёт подтверждения, доказательство не сохранено.
+
+## Daily messages, MarkdownV2
+
+agenda-empty = 📋 На сегодня ничего не запланировано\.
+agenda-title = 📋 *План на сегодня*
+agenda-after-predecessor = после другого
+summary-empty = 🌙 Сегодня нечего подводить\.
+summary-title = 🌙 *Итоги дня*
+
+## Confirming reminders
+
+confirm-gone = Этого напоминания больше нет.
+confirm-checklist-first = Сначала отметьте все пункты чек-листа.
+challenge-arithmetic = Сколько будет { $a } { $symbol } { $b }?
+challenge-retype = Напишите это слово задом наперёд: { $word }
+challenge-pick = Выберите слово "{ $word }"
+challenge-wrong = Неверный ответ. Попробуйте этот.
+challenge-ask-answer = Сначала ответьте на вопрос.
+proof-saved = Доказательство сохранено. Отлично!
+proof-invalid-command = Неверная команда Proof. Попробуйте ещё раз.
+proof-none = Доказательств пока нет.
+proof-latest = Последние доказательства для *{ $text }*:
+
+## Buddies
+
+buddy-invalid-command = Неверная команда Buddy. Попробуйте ещё раз.
+buddy-ask =
+    Кому сообщить, если вы проигнорируете *{ $text }*? Поделитесь контактом или пришлите id чата в Telegram\.
+    Этот человек должен сначала начать разговор со мной\.
+buddy-ask-chat = Поделитесь контактом или пришлите id чата в Telegram.
+buddy-self = Нельзя быть напарником самому себе. Попробуйте ещё раз.
+buddy-gone = Этого напоминания больше нет.
+buddy-someone = Кто-то
+buddy-invitation =
+    🤝 *{ $inviter }* просит вас стать напарником для *{ $text }*\.
+    Если напоминание будет проигнорировано, я дам вам знать\.
+buddy-accept-button = Принять
+buddy-decline-button = Отклонить
+buddy-invited = Приглашение отправлено. Я сообщу, когда на него ответят.
+buddy-unreachable = Не удалось написать в этот чат. Попросите начать разговор со мной и попробуйте ещё раз.
+buddy-invitation-invalid = Это приглашение больше не действует.
+buddy-accepted = Теперь вы напарник. Я сообщу, если напоминание будет проигнорировано.
+buddy-declined = Приглашение отклонено.
+buddy-owner-accepted = Напарник принял приглашение для *{ $text }*\.
+buddy-owner-declined = Напарник отклонил приглашение для *{ $text }*\.
+
+## Linking reminders
+
+link-invalid-command = Неверная команда After. Попробуйте ещё раз.
+link-none-button = Ничего, по своему времени
+link-ask-predecessor = Какое напоминание нужно подтвердить перед *{ $text }*?
+link-ask-delay = Сколько минут подождать после его подтверждения? Отправьте 0, чтобы срабатывать сразу.
+link-ask-delay-again = Отправьте задержку в минутах. Например: 15
+link-removed = Напоминание снова будет срабатывать в своё время.
+link-cycle = Эти напоминания уже ждут друг друга. Выберите другое.
+link-gone = Этого напоминания больше нет.
+link-saved = *{ $text }* сработает { $minutes ->
+        [0] сразу
+        [one] через { $minutes } минуту
+        [few] через { $minutes } минуты
+       *[other] через { $minutes } минут
+    } после подтверждения *{ $predecessor }*\.
+
+## Statistics, MarkdownV2
+
+stats-no-reminders = Сначала создайте хотя бы одно напоминание\!
+stats-streak = Серия: *{ $current }* \(рекорд *{ $longest }*\)
+stats-done = Выполнено: *{ $week }* за 7 дней, *{ $month }* за 30 дней
+stats-time-to-acknowledge = Среднее время до подтверждения: *{ $duration }*
+stats-wrong-answers = Неверных ответов на задания: *{ $count }*
+stats-seconds = { $seconds } с
+stats-minutes = { $minutes } мин { $seconds } с
+stats-hours = { $hours } ч { $minutes } мин
+
+## Charts
+
+chart-all-caption = Выполнение всех напоминаний
+chart-caption = Выполнение *{ $text }*
+chart-invalid-command = Неверная команда Chart. Попробуйте ещё раз.
+chart-no-acknowledgements = Пока нечего показать на графике подтверждений.
+chart-ack-caption = Время до подтверждения, сначала старые
+
+## Calendars
+
+calendar-export-empty = Нет напоминаний для экспорта.
+calendar-import-start = Загрузите календарь в формате .ics. События со временем, которые повторяются каждый день или не повторяются вовсе, станут ежедневными напоминаниями.
+calendar-ask-file = Загрузите календарь в формате .ics.
+calendar-too-large = Этот календарь слишком большой.
+calendar-not-utf8 = Этот календарь не в кодировке UTF-8.
+calendar-unreadable = Не удалось прочитать этот календарь: { $error }.
+calendar-no-reminders = В этом календаре нет ежедневных напоминаний. Попробуйте другой.
+calendar-preview =
+    Будут созданы такие напоминания:
+    { $preview }
+calendar-skipped = { $count ->
+        [one] { $count } запись не является ежедневным напоминанием и будет пропущена\.
+        [few] { $count } записи не являются ежедневными напоминаниями и будут пропущены\.
+       *[other] { $count } записей не являются ежедневными напоминаниями и будут пропущены\.
+    }
+calendar-import-button = Импортировать
+calendar-cancel-button = Отмена
+calendar-import-cancelled = Импорт отменён.
+calendar-imported = Импортировано напоминаний: { $count }.
+
+## Skipped days
+
+exclude-start = Какие дни пропускать? Пришлите даты в формате ГГГГ-ММ-ДД, по одной в строке, можно с названием. Или загрузите календарь праздников в формате .ics.
+exclude-invalid-date = Не понимаю "{ $line }". Используйте формат ГГГГ-ММ-ДД, например 2025-12-24.
+exclude-ask-dates = Пришлите даты или файл .ics.
+exclude-no-dates = Здесь нет дат. Попробуйте ещё раз.
+exclude-added = Дней, которые будут пропущены дополнительно: { $added }.
+exclude-added-known = Дней, которые будут пропущены дополнительно: { $added }, ещё { $known } уже пропускались.
+exclude-upcoming =
+    Пропускаемые дни:
+    { $days }
+include-usage = Используйте /include ГГГГ-ММ-ДД, чтобы перестать пропускать день.
+include-done = Напоминания снова будут срабатывать { $date }.
+include-not-skipped = { $date } не пропускался.
+
+## Daily message settings, MarkdownV2 except usage
+
+agenda-usage = Используйте /agenda ЧЧ:ММ, чтобы получать план каждый день в это время, или /agenda off, чтобы отключить его.
+agenda-on = План на день будет приходить каждый день в *{ $time }*\.
+agenda-off = План на день больше не будет приходить\.
+summary-usage = Используйте /summary ЧЧ:ММ, чтобы получать итоги каждый день в это время, или /summary off, чтобы отключить их.
+summary-on = Итоги дня будут приходить каждый день в *{ $time }*\.
+summary-off = Итоги дня больше не будут приходить\.
+
+## Digest mode
+
+digest-on = Режим дайджеста включён. Напоминания, сработавшие одновременно, придут одним сообщением.
+digest-off = Режим дайджеста выключен. Каждое напоминание придёт отдельным сообщением.
+
+## Dialogues
+
+cancelled = Текущее действие отменено.
+invalid-message = Не удалось обработать сообщение. Попробуйте ещё раз или используйте /cancel, чтобы прервать текущее действие.
+invalid-query = Не удалось обработать нажатие. Попробуйте ещё раз или используйте /cancel, чтобы прервать текущее действие.
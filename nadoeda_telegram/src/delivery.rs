@@ -1,15 +1,31 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use nadoeda_models::{reminder::Reminder, user::UserId};
+use nadoeda_models::{
+    agenda::{AgendaTime, DailyMessage, SummaryOutcome},
+    buddy::{BuddyTarget, ReminderBuddy},
+    reminder::{ChecklistItemId, Reminder, ReminderId, ReminderVerification},
+    template::{MessageTemplate, TemplateKind, pick_template},
+    user::{Language, User, UserId},
+};
 use nadoeda_scheduler::delivery::{ReminderDeliveryChannel, ReminderMessageType};
-use nadoeda_storage::{UserInfoStorage, sqlite::user_storage::SqliteUserInfoStorage};
+use nadoeda_storage::{
+    DynMessageTemplateStorage, DynReminderBuddyStorage, DynReminderStatsStorage,
+    DynReminderStorage, DynUserInfoStorage, Storages,
+};
 use teloxide::{
     prelude::*,
     types::{InlineKeyboardButton, InlineKeyboardMarkup, ParseMode},
+    utils::markdown,
 };
 use thiserror::Error;
 
+use crate::i18n::t;
+use crate::template::{TemplateValues, render_template, uses_placeholder};
+
+#[cfg(test)]
+mod tests;
+
 #[derive(Debug, Error)]
 pub enum TelegramDeliveryChannelError {
     #[error(transparent)]
@@ -26,13 +42,199 @@ pub enum TelegramDeliveryChannelError {
 }
 
 pub struct TelegramDeliveryChannel {
-    user_store: Arc<SqliteUserInfoStorage>,
+    user_store: Arc<DynUserInfoStorage>,
+    buddy_store: Arc<DynReminderBuddyStorage>,
+    reminder_store: Arc<DynReminderStorage>,
+    template_store: Arc<DynMessageTemplateStorage>,
+    stats_store: Arc<DynReminderStatsStorage>,
     bot: Bot,
 }
 
 impl TelegramDeliveryChannel {
-    pub fn new(user_store: Arc<SqliteUserInfoStorage>, bot: Bot) -> Self {
-        Self { user_store, bot }
+    pub fn new(storages: &Storages, bot: Bot) -> Self {
+        Self {
+            user_store: storages.users.clone(),
+            buddy_store: storages.buddies.clone(),
+            reminder_store: storages.reminders.clone(),
+            template_store: storages.templates.clone(),
+            stats_store: storages.stats.clone(),
+            bot,
+        }
+    }
+
+    /// The user together with the chat to send their messages to.
+    async fn recipient(
+        &self,
+        user_id: &UserId,
+    ) -> Result<(i64, User), TelegramDeliveryChannelError> {
+        let user = self
+            .user_store
+            .get(user_id)
+            .await?
+            .ok_or(TelegramDeliveryChannelError::InvalidUser(*user_id))?;
+
+        let chat_id = user
+            .tg_chat_id
+            .ok_or(TelegramDeliveryChannelError::NoTelegramConfigured(*user_id))?;
+
+        Ok((chat_id, user))
+    }
+
+    /// The user's own text for the notification, if they wrote one.
+    async fn custom_text(
+        &self,
+        reminder: &Reminder,
+        message: ReminderMessageType,
+        templates: &[MessageTemplate],
+        user: &User,
+    ) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        let Some(kind) = template_kind(message) else {
+            return Ok(None);
+        };
+        let attempt = match message {
+            ReminderMessageType::Nag | ReminderMessageType::Confirmation => {
+                u32::from(reminder.state.attempts_made()) + 1
+            }
+            _ => 1,
+        };
+        let Some(template) = pick_template(templates, kind, attempt) else {
+            return Ok(None);
+        };
+
+        let now = chrono::Utc::now();
+        let streak = if uses_placeholder(template, "streak") {
+            self.stats_store
+                .get_for_reminder(&reminder.id, &reminder.user_id, now)
+                .await?
+                .current_streak
+        } else {
+            0
+        };
+
+        let values = TemplateValues {
+            text: reminder.text.clone(),
+            attempt,
+            streak,
+            local_time: now
+                .with_timezone(&user.timezone)
+                .format("%H:%M")
+                .to_string(),
+        };
+        let text = render_template(template, &values);
+
+        // The proof prompt tells what to send, so it stays under custom confirmation texts.
+        let prompt = match message {
+            ReminderMessageType::Confirmation => {
+                proof_prompt(&reminder.verification, user.language)
+            }
+            _ => None,
+        };
+
+        Ok(Some(match prompt {
+            Some(prompt) => format!("{text}\n{prompt}"),
+            None => text,
+        }))
+    }
+
+    /// Loads the current checklist. Ticks from the previous day are cleared when the reminder fires.
+    async fn refresh_checklist(
+        &self,
+        reminder: &Reminder,
+        message: ReminderMessageType,
+    ) -> Result<Reminder, Box<dyn std::error::Error>> {
+        let refreshed = if message == ReminderMessageType::Fired {
+            Some(
+                self.reminder_store
+                    .reset_checklist(&reminder.id, &reminder.user_id)
+                    .await?,
+            )
+        } else {
+            self.reminder_store
+                .get(&reminder.id, &reminder.user_id)
+                .await?
+        };
+
+        Ok(refreshed.unwrap_or_else(|| reminder.clone()))
+    }
+
+    async fn escalate_to_buddy(
+        &self,
+        reminder: &Reminder,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(buddy) = self
+            .buddy_store
+            .get_for_reminder(&reminder.id)
+            .await?
+            .filter(ReminderBuddy::is_accepted)
+        else {
+            return Ok(());
+        };
+
+        // Buddies without an account are addressed in English.
+        let language = match &buddy.target {
+            BuddyTarget::User(user_id) => self
+                .user_store
+                .get(user_id)
+                .await?
+                .map(|user| user.language)
+                .unwrap_or_default(),
+            BuddyTarget::TelegramChat(_) => Language::default(),
+        };
+
+        let Some(chat_id) = buddy_chat_id(&buddy.target, self.user_store.as_ref()).await? else {
+            log::warn!(
+                "Buddy of reminder {} does not have Telegram chat id configured",
+                reminder.id
+            );
+            return Ok(());
+        };
+
+        self.bot
+            .send_message(ChatId(chat_id), get_buddy_message_text(reminder, language))
+            .parse_mode(ParseMode::MarkdownV2)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Sends the notification to the owner of the reminder.
+    async fn notify_owner(
+        &self,
+        reminder: &Reminder,
+        message: ReminderMessageType,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (chat_id, user) = self.recipient(&reminder.user_id).await?;
+        let language = user.language;
+
+        let refreshed;
+        let reminder = match message {
+            ReminderMessageType::Fired | ReminderMessageType::Nag => {
+                refreshed = self.refresh_checklist(reminder, message).await?;
+                &refreshed
+            }
+            _ => reminder,
+        };
+
+        let templates = match template_kind(message) {
+            Some(_) => self.template_store.get_for_reminder(&reminder.id).await?,
+            None => Vec::new(),
+        };
+        let message_text = match self
+            .custom_text(reminder, message, &templates, &user)
+            .await?
+        {
+            Some(text) => text,
+            None => get_message_text(reminder, message, language),
+        };
+        let keyboard_markup = get_keyboard_markup(reminder, message, language);
+
+        self.bot
+            .send_message(ChatId(chat_id), message_text)
+            .parse_mode(ParseMode::MarkdownV2)
+            .reply_markup(keyboard_markup)
+            .await?;
+
+        Ok(())
     }
 }
 
@@ -43,52 +245,333 @@ impl ReminderDeliveryChannel for TelegramDeliveryChannel {
         reminder: &Reminder,
         message: ReminderMessageType,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        let user = self
-            .user_store
-            .get(&reminder.user_id)
-            .await?
-            .ok_or(TelegramDeliveryChannelError::InvalidUser(reminder.user_id))?;
+        let result = self.notify_owner(reminder, message).await;
+        if message != ReminderMessageType::Timeout {
+            return result;
+        }
+
+        // The buddy is the fallback when the owner does not react, so an owner that can not
+        // be reached is no reason to keep quiet.
+        if let Err(err) = result.map_err(|err| err.to_string()) {
+            log::error!(
+                "Failed to notify the owner about timed out reminder {}: {}",
+                reminder.id,
+                err
+            );
+        }
+        self.escalate_to_buddy(reminder).await
+    }
 
+    async fn send_daily_message(
+        &self,
+        user: &User,
+        message: &DailyMessage,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         let chat_id = user
             .tg_chat_id
-            .ok_or(TelegramDeliveryChannelError::NoTelegramConfigured(
-                reminder.user_id,
-            ))?;
+            .ok_or(TelegramDeliveryChannelError::NoTelegramConfigured(user.id))?;
 
-        let message_text = get_message_text(reminder, message);
-        let keyboard_markup = get_keyboard_markup(reminder, message);
+        self.bot
+            .send_message(ChatId(chat_id), get_daily_message_text(user, message))
+            .parse_mode(ParseMode::MarkdownV2)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn send_digest_notification(
+        &self,
+        reminders: &[Reminder],
+        message: ReminderMessageType,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        // Checklists need their own toggle buttons and custom texts their own message,
+        // so they are never merged.
+        let mut merged = Vec::new();
+        for reminder in reminders {
+            let has_template = match template_kind(message) {
+                Some(kind) => self
+                    .template_store
+                    .get_for_reminder(&reminder.id)
+                    .await?
+                    .iter()
+                    .any(|template| template.kind == kind),
+                None => false,
+            };
+
+            if reminder.checklist.is_empty() && !has_template {
+                merged.push(reminder);
+            } else {
+                self.send_reminder_notification(reminder, message).await?;
+            }
+        }
+
+        let Some(user_id) = merged.first().map(|reminder| reminder.user_id) else {
+            return Ok(());
+        };
+        let (chat_id, user) = self.recipient(&user_id).await?;
+        let language = user.language;
+
+        let Some(digest_text) = get_digest_text(&merged, message, language) else {
+            for reminder in merged {
+                self.send_reminder_notification(reminder, message).await?;
+            }
+            return Ok(());
+        };
 
         self.bot
-            .send_message(ChatId(chat_id), message_text)
+            .send_message(ChatId(chat_id), digest_text)
             .parse_mode(ParseMode::MarkdownV2)
-            .reply_markup(keyboard_markup)
+            .reply_markup(get_digest_markup(&merged, language))
             .await?;
 
         Ok(())
     }
 }
 
-fn get_keyboard_markup(reminder: &Reminder, message: ReminderMessageType) -> InlineKeyboardMarkup {
+async fn buddy_chat_id(
+    target: &BuddyTarget,
+    user_store: &DynUserInfoStorage,
+) -> anyhow::Result<Option<i64>> {
+    match target {
+        BuddyTarget::User(user_id) => Ok(user_store.get(user_id).await?.and_then(|u| u.tg_chat_id)),
+        BuddyTarget::TelegramChat(chat_id) => Ok(Some(*chat_id)),
+    }
+}
+
+/// Reminder buttons pressed by the user, encoded in callback data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReminderAction {
+    Acknowledge(ReminderId),
+    Confirm(ReminderId),
+}
+
+const ACKNOWLEDGE_PREFIX: &str = "ack_";
+const CONFIRM_PREFIX: &str = "confirm_";
+
+impl ReminderAction {
+    pub fn to_callback_data(self) -> String {
+        match self {
+            Self::Acknowledge(id) => format!("{ACKNOWLEDGE_PREFIX}{id}"),
+            Self::Confirm(id) => format!("{CONFIRM_PREFIX}{id}"),
+        }
+    }
+
+    pub fn parse(data: &str) -> Option<Self> {
+        if let Some(id) = data.strip_prefix(ACKNOWLEDGE_PREFIX) {
+            id.parse().ok().map(Self::Acknowledge)
+        } else {
+            data.strip_prefix(CONFIRM_PREFIX)?
+                .parse()
+                .ok()
+                .map(Self::Confirm)
+        }
+    }
+}
+
+/// Checklist item ticked or unticked by the user, encoded in callback data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ChecklistToggle(pub ChecklistItemId);
+
+const CHECKLIST_PREFIX: &str = "check_";
+
+impl ChecklistToggle {
+    pub fn to_callback_data(self) -> String {
+        format!("{CHECKLIST_PREFIX}{}", self.0)
+    }
+
+    pub fn parse(data: &str) -> Option<Self> {
+        data.strip_prefix(CHECKLIST_PREFIX)?.parse().ok().map(Self)
+    }
+}
+
+/// One toggle button per checklist item, in order.
+pub(crate) fn get_checklist_markup(reminder: &Reminder) -> InlineKeyboardMarkup {
+    let rows = reminder.checklist.iter().map(|item| {
+        let mark = if item.checked { "✅" } else { "⬜" };
+        vec![InlineKeyboardButton::callback(
+            format!("{mark} {}", item.text),
+            ChecklistToggle(item.id).to_callback_data(),
+        )]
+    });
+
+    InlineKeyboardMarkup::new(rows)
+}
+
+fn get_keyboard_markup(
+    reminder: &Reminder,
+    message: ReminderMessageType,
+    language: Language,
+) -> InlineKeyboardMarkup {
     match message {
-        ReminderMessageType::Fired
-        | ReminderMessageType::Nag
-        | ReminderMessageType::Confirmation => {
-            let confirm_button = InlineKeyboardButton::callback("Confirm", reminder.id.to_string());
+        ReminderMessageType::Fired | ReminderMessageType::Nag if !reminder.checklist.is_empty() => {
+            get_checklist_markup(reminder)
+        }
+        ReminderMessageType::Fired | ReminderMessageType::Nag => {
+            let acknowledge_button = InlineKeyboardButton::callback(
+                t!(language, "reminder-acknowledge-button"),
+                ReminderAction::Acknowledge(reminder.id).to_callback_data(),
+            );
+            InlineKeyboardMarkup::new(vec![vec![acknowledge_button]])
+        }
+        ReminderMessageType::Confirmation => {
+            let label = if reminder.verification.requires_evidence() {
+                t!(language, "reminder-proof-button")
+            } else {
+                t!(language, "reminder-confirm-button")
+            };
+            let confirm_button = InlineKeyboardButton::callback(
+                label,
+                ReminderAction::Confirm(reminder.id).to_callback_data(),
+            );
             InlineKeyboardMarkup::new(vec![vec![confirm_button]])
         }
         _ => InlineKeyboardMarkup::new(vec![vec![]]),
     }
 }
 
-fn get_message_text(reminder: &Reminder, message: ReminderMessageType) -> String {
+fn template_kind(message: ReminderMessageType) -> Option<TemplateKind> {
     match message {
-        ReminderMessageType::Scheduled => format!("⏱️ Scheduled *{}*", reminder.text),
-        ReminderMessageType::Fired => format!("🚨 {}", reminder.text),
-        ReminderMessageType::Nag => format!("🚨 {}", reminder.text),
-        ReminderMessageType::Confirmation => format!("⁉️ {}", reminder.text),
-        ReminderMessageType::Acknowledge => format!("☑️ {}", reminder.text),
-        ReminderMessageType::Timeout => "No reaction\\! Stopping\\.".to_string(),
-        ReminderMessageType::Finished => format!("✅ {}", reminder.text),
-        ReminderMessageType::Cancelled => format!("❌ Cancelled {}", reminder.text),
+        ReminderMessageType::Fired => Some(TemplateKind::Fired),
+        ReminderMessageType::Nag => Some(TemplateKind::Nag),
+        ReminderMessageType::Confirmation => Some(TemplateKind::Confirmation),
+        ReminderMessageType::Timeout => Some(TemplateKind::Timeout),
+        _ => None,
+    }
+}
+
+fn get_message_text(
+    reminder: &Reminder,
+    message: ReminderMessageType,
+    language: Language,
+) -> String {
+    let text = markdown::escape(&reminder.text);
+
+    match message {
+        ReminderMessageType::Scheduled => t!(language, "reminder-scheduled", text = text),
+        ReminderMessageType::Fired | ReminderMessageType::Nag => {
+            t!(language, "reminder-fired", text = text)
+        }
+        ReminderMessageType::Confirmation => {
+            let question = t!(language, "reminder-confirmation", text = text);
+            match proof_prompt(&reminder.verification, language) {
+                Some(prompt) => format!("{question}\n{prompt}"),
+                None => question,
+            }
+        }
+        ReminderMessageType::Acknowledge => t!(language, "reminder-acknowledged", text = text),
+        ReminderMessageType::Timeout => t!(language, "reminder-timeout"),
+        ReminderMessageType::Finished => t!(language, "reminder-finished", text = text),
+        ReminderMessageType::Cancelled => t!(language, "reminder-cancelled", text = text),
+    }
+}
+
+/// Lists merged reminders, if the message type can be merged and there is more than one.
+fn get_digest_text(
+    reminders: &[&Reminder],
+    message: ReminderMessageType,
+    language: Language,
+) -> Option<String> {
+    let header = match message {
+        ReminderMessageType::Fired => t!(language, "digest-fired"),
+        ReminderMessageType::Nag => t!(language, "digest-nag"),
+        _ => return None,
+    };
+
+    if reminders.len() < 2 {
+        return None;
+    }
+
+    let items = reminders
+        .iter()
+        .map(|reminder| format!("• {}", markdown::escape(&reminder.text)))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Some(format!("{header}\n{items}"))
+}
+
+/// One acknowledge button per merged reminder.
+fn get_digest_markup(reminders: &[&Reminder], language: Language) -> InlineKeyboardMarkup {
+    let rows = reminders.iter().map(|reminder| {
+        vec![InlineKeyboardButton::callback(
+            t!(
+                language,
+                "digest-acknowledge-button",
+                text = reminder.text.as_str()
+            ),
+            ReminderAction::Acknowledge(reminder.id).to_callback_data(),
+        )]
+    });
+
+    InlineKeyboardMarkup::new(rows)
+}
+
+pub(crate) fn get_daily_message_text(user: &User, message: &DailyMessage) -> String {
+    let local_time = |time: &chrono::DateTime<chrono::Utc>| {
+        time.with_timezone(&user.timezone)
+            .format("%H:%M")
+            .to_string()
+    };
+
+    match message {
+        DailyMessage::Agenda(items) if items.is_empty() => {
+            t!(user.language, "agenda-empty")
+        }
+        DailyMessage::Agenda(items) => {
+            let lines = items.iter().map(|item| {
+                let time = match &item.time {
+                    AgendaTime::At(at) => local_time(at),
+                    AgendaTime::Between(start, end) => {
+                        format!("{}\\-{}", local_time(start), local_time(end))
+                    }
+                    AgendaTime::AfterPredecessor => t!(user.language, "agenda-after-predecessor"),
+                };
+                format!("*{time}* {}", markdown::escape(&item.text))
+            });
+
+            std::iter::once(t!(user.language, "agenda-title"))
+                .chain(lines)
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        DailyMessage::Summary(items) if items.is_empty() => {
+            t!(user.language, "summary-empty")
+        }
+        DailyMessage::Summary(items) => {
+            let lines = items.iter().map(|item| {
+                let mark = match item.outcome {
+                    SummaryOutcome::Completed => "✅",
+                    SummaryOutcome::TimedOut => "⌛",
+                    SummaryOutcome::Skipped => "⏭️",
+                };
+                format!("{mark} {}", markdown::escape(&item.text))
+            });
+
+            std::iter::once(t!(user.language, "summary-title"))
+                .chain(lines)
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    }
+}
+
+fn get_buddy_message_text(reminder: &Reminder, language: Language) -> String {
+    t!(
+        language,
+        "buddy-no-reaction",
+        text = markdown::escape(&reminder.text)
+    )
+}
+
+/// Explains what has to be sent to confirm the reminder, already escaped for MarkdownV2.
+pub(crate) fn proof_prompt(
+    verification: &ReminderVerification,
+    language: Language,
+) -> Option<String> {
+    match verification {
+        ReminderVerification::None => None,
+        ReminderVerification::Photo => Some(t!(language, "proof-photo")),
+        ReminderVerification::Text => Some(t!(language, "proof-text")),
+        ReminderVerification::Keyword(_) => Some(t!(language, "proof-keyword")),
     }
 }
@@ -0,0 +1,138 @@
+use nadoeda_models::buddy::{BuddyInvitationStatus, BuddyTarget};
+use nadoeda_models::chrono::NaiveTime;
+use nadoeda_models::chrono_tz;
+use nadoeda_models::reminder::ReminderFireTime;
+use nadoeda_storage::{NewReminder, NewReminderBuddy, NewUser, Storages};
+use teloxide::dispatching::UpdateHandler;
+use teloxide::dptree::deps;
+use teloxide_tests::{MockBot, MockMessageText};
+
+use super::*;
+
+const BUDDY_CHAT_ID: i64 = 424242;
+
+/// Sends the timeout notification of the reminder with the bot of the mocked dispatch.
+fn timeout_schema() -> UpdateHandler<anyhow::Error> {
+    dptree::endpoint(
+        |bot: Bot, storages: Storages, reminder: Reminder| async move {
+            let delivery = TelegramDeliveryChannel::new(&storages, bot);
+            delivery
+                .send_reminder_notification(&reminder, ReminderMessageType::Timeout)
+                .await
+                .map_err(|err| anyhow::anyhow!(err.to_string()))
+        },
+    )
+}
+
+/// A reminder whose buddy answered the invitation with `status`.
+async fn reminder_with_buddy(
+    storages: &Storages,
+    owner_chat_id: Option<i64>,
+    status: BuddyInvitationStatus,
+) -> Reminder {
+    let user = storages
+        .users
+        .create(NewUser {
+            timezone: chrono_tz::Tz::Europe__Prague,
+            tg_chat_id: owner_chat_id,
+            language: Language::English,
+        })
+        .await
+        .unwrap();
+
+    let reminder = storages
+        .reminders
+        .insert(NewReminder {
+            text: "Take out trash".to_string(),
+            fire_at: ReminderFireTime::new(NaiveTime::from_hms_opt(12, 0, 0).unwrap()),
+            user_id: user.id,
+        })
+        .await
+        .unwrap();
+
+    let buddy_store = &storages.buddies;
+    let buddy = buddy_store
+        .invite(NewReminderBuddy {
+            reminder_id: reminder.id,
+            user_id: user.id,
+            target: BuddyTarget::TelegramChat(BUDDY_CHAT_ID),
+        })
+        .await
+        .unwrap();
+    if status != BuddyInvitationStatus::Pending {
+        buddy_store
+            .set_status(&buddy.id, BUDDY_CHAT_ID, status)
+            .await
+            .unwrap();
+    }
+
+    reminder
+}
+
+async fn chats_notified_on_timeout(
+    storages: Storages,
+    owner_chat_id: Option<i64>,
+    status: BuddyInvitationStatus,
+) -> Vec<i64> {
+    let message = MockMessageText::new();
+    let reminder = reminder_with_buddy(&storages, owner_chat_id, status).await;
+
+    let mut bot = MockBot::new(message, timeout_schema());
+    bot.dependencies(deps![storages, reminder]);
+    bot.dispatch().await;
+
+    bot.get_responses()
+        .sent_messages
+        .iter()
+        .map(|message| message.chat.id.0)
+        .collect()
+}
+
+#[tokio::test]
+async fn given_accepted_buddy_timeout_should_notify_buddy() {
+    let storages = Storages::in_memory();
+    let chats = chats_notified_on_timeout(storages, Some(1), BuddyInvitationStatus::Accepted).await;
+
+    assert_eq!(chats.len(), 2);
+    assert_eq!(chats.last(), Some(&BUDDY_CHAT_ID));
+}
+
+#[tokio::test]
+async fn given_pending_buddy_timeout_should_only_notify_owner() {
+    let storages = Storages::in_memory();
+    let chats = chats_notified_on_timeout(storages, Some(1), BuddyInvitationStatus::Pending).await;
+
+    assert_eq!(chats.len(), 1);
+    assert!(!chats.contains(&BUDDY_CHAT_ID));
+}
+
+#[tokio::test]
+async fn given_declined_buddy_timeout_should_only_notify_owner() {
+    let storages = Storages::in_memory();
+    let chats = chats_notified_on_timeout(storages, Some(1), BuddyInvitationStatus::Declined).await;
+
+    assert_eq!(chats.len(), 1);
+    assert!(!chats.contains(&BUDDY_CHAT_ID));
+}
+
+#[tokio::test]
+async fn given_unreachable_owner_timeout_should_still_notify_buddy() {
+    let storages = Storages::in_memory();
+    let chats = chats_notified_on_timeout(storages, None, BuddyInvitationStatus::Accepted).await;
+
+    assert_eq!(chats, vec![BUDDY_CHAT_ID]);
+}
+
+#[tokio::test]
+async fn messages_escape_reminder_text() {
+    let storages = Storages::in_memory();
+    let mut reminder =
+        reminder_with_buddy(&storages, Some(1), BuddyInvitationStatus::Accepted).await;
+    reminder.text = "Pay 5.00 (rent)".to_string();
+
+    assert!(get_buddy_message_text(&reminder, Language::English).contains("Pay 5\\.00 \\(rent\\)"));
+    assert_eq!(
+        get_message_text(&reminder, ReminderMessageType::Fired, Language::English),
+        "🚨 Pay 5\\.00 \\(rent\\)"
+    );
+}
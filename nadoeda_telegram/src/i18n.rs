@@ -0,0 +1,83 @@
+//! Bot messages in every supported language, bundled from the Fluent files in `locales/`.
+//!
+//! Messages sent with MarkdownV2 keep their escaping in the Fluent files, arguments are
+//! inserted as they are and have to be escaped by the caller.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use nadoeda_models::user::Language;
+
+#[cfg(test)]
+mod tests;
+
+static BUNDLES: LazyLock<HashMap<Language, FluentBundle<FluentResource>>> = LazyLock::new(|| {
+    Language::ALL
+        .into_iter()
+        .map(|language| (language, bundle(language)))
+        .collect()
+});
+
+fn source(language: Language) -> &'static str {
+    match language {
+        Language::English => include_str!("../locales/en.ftl"),
+        Language::Russian => include_str!("../locales/ru.ftl"),
+    }
+}
+
+fn bundle(language: Language) -> FluentBundle<FluentResource> {
+    let resource = FluentResource::try_new(source(language).to_string())
+        .unwrap_or_else(|(_, errors)| panic!("Invalid {} messages: {errors:?}", language.code()));
+    let id = language
+        .code()
+        .parse()
+        .expect("Language codes are valid identifiers");
+
+    let mut bundle = FluentBundle::new_concurrent(vec![id]);
+    // Unicode isolation marks around arguments would end up in Telegram messages.
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(resource)
+        .unwrap_or_else(|errors| panic!("Duplicate {} messages: {errors:?}", language.code()));
+
+    bundle
+}
+
+/// Formats a message, falling back to English and then to the key itself.
+pub fn translate(language: Language, key: &str, args: Option<&FluentArgs>) -> String {
+    let translated = [language, Language::English]
+        .into_iter()
+        .find_map(|language| {
+            let bundle = &BUNDLES[&language];
+            let pattern = bundle.get_message(key)?.value()?;
+
+            let mut errors = Vec::new();
+            let text = bundle.format_pattern(pattern, args, &mut errors);
+            if !errors.is_empty() {
+                log::error!("Failed to format {key} in {}: {errors:?}", language.code());
+            }
+
+            Some(text.into_owned())
+        });
+
+    translated.unwrap_or_else(|| {
+        log::error!("Missing message {key}");
+        key.to_string()
+    })
+}
+
+/// `t!(language, "key")` or `t!(language, "key", name = value, ...)`.
+macro_rules! t {
+    ($language:expr, $key:literal) => {
+        $crate::i18n::translate($language, $key, None)
+    };
+    ($language:expr, $key:literal, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent_bundle::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::translate($language, $key, Some(&args))
+    }};
+}
+
+pub(crate) use t;
@@ -0,0 +1,92 @@
+use std::collections::BTreeSet;
+
+use fluent_syntax::ast::Entry;
+
+use super::*;
+
+fn message_ids(language: Language) -> BTreeSet<String> {
+    let resource = FluentResource::try_new(source(language).to_string()).unwrap();
+
+    resource
+        .entries()
+        .filter_map(|entry| match entry {
+            Entry::Message(message) => Some(message.id.name.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn every_message_exists_in_every_language() {
+    let english = message_ids(Language::English);
+
+    for language in Language::ALL {
+        let ids = message_ids(language);
+        assert_eq!(
+            english.difference(&ids).collect::<Vec<_>>(),
+            Vec::<&String>::new(),
+            "missing in {}",
+            language.code()
+        );
+        assert_eq!(
+            ids.difference(&english).collect::<Vec<_>>(),
+            Vec::<&String>::new(),
+            "unknown in {}",
+            language.code()
+        );
+    }
+}
+
+#[test]
+fn translates_with_arguments() {
+    assert_eq!(
+        t!(Language::English, "reminder-scheduled", text = "Water"),
+        "⏱️ Scheduled *Water*"
+    );
+    assert_eq!(
+        t!(Language::Russian, "reminder-scheduled", text = "Полить"),
+        "⏱️ Запланировано *Полить*"
+    );
+    assert_eq!(
+        t!(Language::Russian, "time-in-past"),
+        "Это время уже прошло\\. Пришлите время, например *13:00*, *в 7 вечера* или *каждый день в 8*"
+    );
+}
+
+#[test]
+fn selects_plural_forms() {
+    let saved = |language, minutes: u32| {
+        t!(
+            language,
+            "link-saved",
+            text = "B",
+            minutes = minutes,
+            predecessor = "A"
+        )
+    };
+
+    assert_eq!(
+        saved(Language::English, 0),
+        "*B* will fire right after *A* is confirmed\\."
+    );
+    assert_eq!(
+        saved(Language::English, 1),
+        "*B* will fire 1 minute after *A* is confirmed\\."
+    );
+    assert_eq!(
+        saved(Language::Russian, 3),
+        "*B* сработает через 3 минуты после подтверждения *A*\\."
+    );
+    assert_eq!(
+        saved(Language::Russian, 25),
+        "*B* сработает через 25 минут после подтверждения *A*\\."
+    );
+}
+
+#[test]
+fn falls_back_to_the_key() {
+    assert_eq!(
+        translate(Language::Russian, "no-such-key", None),
+        "no-such-key"
+    );
+}
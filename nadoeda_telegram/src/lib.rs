@@ -1,4 +1,7 @@
 pub mod delivery;
+mod i18n;
+mod template;
+pub mod time_parser;
 pub mod ui;
 
 pub use teloxide;
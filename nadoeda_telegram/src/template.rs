@@ -0,0 +1,70 @@
+//! User-written notification texts with placeholders.
+
+use teloxide::utils::markdown;
+
+#[cfg(test)]
+mod tests;
+
+/// Placeholders that can be used in templates, without the braces.
+pub const PLACEHOLDERS: [&str; 4] = ["text", "attempt", "streak", "local_time"];
+
+/// Values filled into the placeholders of a template.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TemplateValues {
+    pub text: String,
+    /// Which nag or confirmation request this is, counting from 1.
+    pub attempt: u32,
+    /// Confirmed occurrences in a row.
+    pub streak: u32,
+    /// Current time of day of the user.
+    pub local_time: String,
+}
+
+impl TemplateValues {
+    fn get(&self, placeholder: &str) -> Option<String> {
+        match placeholder {
+            "text" => Some(self.text.clone()),
+            "attempt" => Some(self.attempt.to_string()),
+            "streak" => Some(self.streak.to_string()),
+            "local_time" => Some(self.local_time.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// Returns `true` if the template refers to the placeholder.
+pub fn uses_placeholder(template: &str, placeholder: &str) -> bool {
+    template.contains(&format!("{{{placeholder}}}"))
+}
+
+/// Fills in the placeholders and escapes everything for MarkdownV2.
+///
+/// The template is plain text, so formatting characters typed by the user show up as they are.
+/// Unknown placeholders are kept verbatim.
+pub fn render_template(template: &str, values: &TemplateValues) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&markdown::escape(&rest[..start]));
+        let placeholder = &rest[start..];
+
+        let value = placeholder
+            .find('}')
+            .and_then(|end| Some((values.get(&placeholder[1..end])?, end)));
+
+        match value {
+            Some((value, end)) => {
+                rendered.push_str(&markdown::escape(&value));
+                rest = &placeholder[end + 1..];
+            }
+            None => {
+                rendered.push_str(&markdown::escape("{"));
+                rest = &placeholder[1..];
+            }
+        }
+    }
+
+    rendered.push_str(&markdown::escape(rest));
+    rendered
+}
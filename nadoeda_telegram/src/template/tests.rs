@@ -0,0 +1,37 @@
+use super::*;
+
+fn values() -> TemplateValues {
+    TemplateValues {
+        text: "Take pills (2x)".to_string(),
+        attempt: 3,
+        streak: 12,
+        local_time: "08:30".to_string(),
+    }
+}
+
+#[test]
+fn renders_templates() {
+    let cases = [
+        ("{text}!", "Take pills \\(2x\\)\\!"),
+        ("Nag #{attempt} at {local_time}", "Nag \\#3 at 08:30"),
+        (
+            "Don't break a {streak} day streak",
+            "Don't break a 12 day streak",
+        ),
+        ("*bold* _on purpose_", "\\*bold\\* \\_on purpose\\_"),
+        ("{unknown} and {text", "\\{unknown\\} and \\{text"),
+        ("{{text}}", "\\{Take pills \\(2x\\)\\}"),
+        ("}{", "\\}\\{"),
+        ("", ""),
+    ];
+
+    for (template, expected) in cases {
+        assert_eq!(render_template(template, &values()), expected, "{template}");
+    }
+}
+
+#[test]
+fn finds_placeholders() {
+    assert!(uses_placeholder("{streak} days", "streak"));
+    assert!(!uses_placeholder("streak", "streak"));
+}
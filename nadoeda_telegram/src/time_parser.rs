@@ -0,0 +1,596 @@
+//! Understands phrases like "tomorrow 9am", "in 20 minutes" or "каждый день в 8".
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, TimeDelta, Timelike, Weekday};
+use nadoeda_models::chrono_tz::Tz;
+use nadoeda_models::user::Language;
+use thiserror::Error;
+
+use crate::i18n::t;
+
+#[cfg(test)]
+mod tests;
+
+/// Relative times further away than this are most likely typos.
+const MAX_RELATIVE_DAYS: i64 = 366;
+
+const WEEKDAYS: [Weekday; 5] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+];
+const WEEKEND: [Weekday; 2] = [Weekday::Sat, Weekday::Sun];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Recurrence {
+    Once(NaiveDate),
+    Daily,
+    /// Days of the week, starting from Monday.
+    Weekly(Vec<Weekday>),
+}
+
+/// Local time of day and how often it repeats.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedSchedule {
+    pub time: NaiveTime,
+    pub recurrence: Recurrence,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum TimeParseError {
+    #[error("I could not understand the time")]
+    Unrecognized,
+    #[error("Please tell me the time of day too")]
+    MissingTime,
+    #[error("This time has already passed")]
+    InPast,
+}
+
+impl ParsedSchedule {
+    /// Interpretation to echo back to the user, not escaped.
+    pub fn describe(&self, today: NaiveDate, language: Language) -> String {
+        let time = self.time.format("%H:%M").to_string();
+
+        match &self.recurrence {
+            Recurrence::Daily => t!(language, "schedule-daily", time = time),
+            Recurrence::Weekly(days) if days[..] == WEEKDAYS => {
+                t!(language, "schedule-weekdays", time = time)
+            }
+            Recurrence::Weekly(days) if days[..] == WEEKEND => {
+                t!(language, "schedule-weekend", time = time)
+            }
+            Recurrence::Weekly(days) => {
+                let names = days
+                    .iter()
+                    .map(|day| weekday_name(*day, language))
+                    .collect::<Vec<_>>();
+                let days = match names.split_last() {
+                    Some((last, rest)) if !rest.is_empty() => t!(
+                        language,
+                        "schedule-days",
+                        first = rest.join(", "),
+                        last = last.as_str()
+                    ),
+                    _ => names.concat(),
+                };
+                t!(language, "schedule-weekly", days = days, time = time)
+            }
+            Recurrence::Once(date) if *date == today => {
+                t!(language, "schedule-today", time = time)
+            }
+            Recurrence::Once(date) if Some(*date) == today.succ_opt() => {
+                t!(language, "schedule-tomorrow", time = time)
+            }
+            Recurrence::Once(date) => {
+                let format = if date.year() == today.year() {
+                    t!(language, "schedule-date-format")
+                } else {
+                    t!(language, "schedule-date-with-year-format")
+                };
+                let date = date.format(&format).to_string();
+                t!(language, "schedule-on", date = date, time = time)
+            }
+        }
+    }
+}
+
+fn weekday_name(day: Weekday, language: Language) -> String {
+    match day {
+        Weekday::Mon => t!(language, "schedule-monday"),
+        Weekday::Tue => t!(language, "schedule-tuesday"),
+        Weekday::Wed => t!(language, "schedule-wednesday"),
+        Weekday::Thu => t!(language, "schedule-thursday"),
+        Weekday::Fri => t!(language, "schedule-friday"),
+        Weekday::Sat => t!(language, "schedule-saturday"),
+        Weekday::Sun => t!(language, "schedule-sunday"),
+    }
+}
+
+/// Parses the whole text. A time without a day repeats daily.
+pub fn parse_schedule(text: &str, now: DateTime<Tz>) -> Result<ParsedSchedule, TimeParseError> {
+    let tokens = tokenize(text);
+    let scan = scan(&tokens);
+
+    if scan.consumed < tokens.len() {
+        return Err(TimeParseError::Unrecognized);
+    }
+
+    assemble(&scan.items, now)
+}
+
+/// Parses the longest leading schedule and returns it with the remaining text.
+pub fn parse_schedule_prefix(text: &str, now: DateTime<Tz>) -> Option<(ParsedSchedule, &str)> {
+    let tokens = tokenize(text);
+    let scan = scan(&tokens);
+
+    scan.steps.iter().rev().find_map(|&(consumed, items)| {
+        let at_word_end = tokens
+            .get(consumed)
+            .is_none_or(|next| next.word != tokens[consumed - 1].word);
+        if !at_word_end {
+            return None;
+        }
+
+        let schedule = assemble(&scan.items[..items], now).ok()?;
+        let rest = &text[tokens[consumed - 1].end..];
+
+        Some((schedule, rest.trim()))
+    })
+}
+
+#[derive(Debug)]
+struct Token {
+    text: String,
+    /// Index of the whitespace separated word the token comes from.
+    word: usize,
+    /// Byte offset right after the word in the original text.
+    end: usize,
+}
+
+fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let words = text.split_whitespace().map(|word| {
+        (
+            word,
+            word.as_ptr() as usize - text.as_ptr() as usize + word.len(),
+        )
+    });
+
+    for (index, (word, end)) in words.enumerate() {
+        let normalized = word
+            .to_lowercase()
+            .replace("a.m", "am")
+            .replace("p.m", "pm")
+            .trim_matches(|c: char| matches!(c, ',' | '.' | '!' | '?' | ';'))
+            .to_string();
+
+        // "9am", "7:30pm" or "20min" are a number and a word glued together.
+        let split = normalized
+            .find(|c: char| !(c.is_ascii_digit() || c == ':' || c == '.'))
+            .filter(|&at| at > 0);
+
+        let parts = match split {
+            Some(at) => vec![normalized[..at].to_string(), normalized[at..].to_string()],
+            None if normalized.is_empty() => vec![],
+            None => vec![normalized],
+        };
+
+        tokens.extend(parts.into_iter().map(|text| Token {
+            text,
+            word: index,
+            end,
+        }));
+    }
+
+    tokens
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Item {
+    Time(NaiveTime),
+    Relative(TimeDelta),
+    /// Days from today.
+    DayOffset(i64),
+    Weekday(Weekday),
+    Repeat(Vec<Weekday>),
+}
+
+struct Scan {
+    items: Vec<Item>,
+    consumed: usize,
+    /// Tokens consumed and items found after every recognized phrase.
+    steps: Vec<(usize, usize)>,
+}
+
+fn scan(tokens: &[Token]) -> Scan {
+    let words = tokens
+        .iter()
+        .map(|token| token.text.as_str())
+        .collect::<Vec<_>>();
+    let mut items = Vec::new();
+    let mut steps = Vec::new();
+    let mut pos = 0;
+    let mut after_at = false;
+
+    while pos < words.len() {
+        let rest = &words[pos..];
+
+        if matches!(rest[0], "at" | "в" | "во" | "к") {
+            after_at = true;
+            pos += 1;
+            continue;
+        }
+
+        let found = match_relative(rest)
+            .or_else(|| match_repeat(rest))
+            .or_else(|| match_day(rest))
+            .or_else(|| match_time(rest, after_at))
+            .map(|(length, item)| (length, Some(item)))
+            .or_else(|| match_filler(rest).map(|length| (length, None)));
+
+        let Some((length, item)) = found else {
+            break;
+        };
+
+        pos += length;
+        after_at = false;
+
+        // A schedule never ends with a filler word, "at 8 and feed the cat" keeps the "and".
+        if let Some(item) = item {
+            items.push(item);
+            steps.push((pos, items.len()));
+        }
+    }
+
+    Scan {
+        items,
+        consumed: pos,
+        steps,
+    }
+}
+
+fn match_filler(words: &[&str]) -> Option<usize> {
+    matches!(
+        words[0],
+        "on" | "next" | "this" | "the" | "and" | "и" | "на"
+    )
+    .then_some(1)
+}
+
+fn match_relative(words: &[&str]) -> Option<(usize, Item)> {
+    if !matches!(words[0], "in" | "через") {
+        return None;
+    }
+
+    let mut pos = 1;
+    let mut total = TimeDelta::zero();
+
+    while let Some((length, delta)) = match_duration(&words[pos..]) {
+        total = total.checked_add(&delta)?;
+        pos += length;
+
+        match words.get(pos) {
+            Some(&("and" | "и")) if match_duration(&words[pos + 1..]).is_some() => pos += 1,
+            _ => {}
+        }
+    }
+
+    (pos > 1 && total <= TimeDelta::days(MAX_RELATIVE_DAYS)).then_some((pos, Item::Relative(total)))
+}
+
+fn match_duration(words: &[&str]) -> Option<(usize, TimeDelta)> {
+    match words {
+        ["half", "an" | "a", "hour", ..] => return Some((3, TimeDelta::minutes(30))),
+        ["half", "hour", ..] | ["пол", "часа", ..] => {
+            return Some((2, TimeDelta::minutes(30)));
+        }
+        ["полчаса", ..] => return Some((1, TimeDelta::minutes(30))),
+        ["полтора", "часа", ..] => return Some((2, TimeDelta::minutes(90))),
+        _ => {}
+    }
+
+    if let Some(unit) = words.first().and_then(|word| duration_unit(word)) {
+        // "через час", "через минуту"
+        return Some((1, unit));
+    }
+
+    let amount = amount(words.first()?)?;
+    let unit = duration_unit(words.get(1)?)?;
+
+    Some((2, unit.checked_mul(amount)?))
+}
+
+fn amount(word: &str) -> Option<i32> {
+    let amount = match word {
+        "a" | "an" | "one" | "один" | "одну" | "одна" => 1,
+        "two" | "два" | "две" => 2,
+        "three" | "три" => 3,
+        "four" | "четыре" => 4,
+        "five" | "пять" => 5,
+        "ten" | "десять" => 10,
+        "fifteen" | "пятнадцать" => 15,
+        "twenty" | "двадцать" => 20,
+        "thirty" | "тридцать" => 30,
+        number => number.parse().ok().filter(|&n| n > 0)?,
+    };
+
+    Some(amount)
+}
+
+fn duration_unit(word: &str) -> Option<TimeDelta> {
+    let unit = match word {
+        "minute" | "minutes" | "min" | "mins" | "m" | "минута" | "минуту" | "минуты" | "минут"
+        | "мин" => TimeDelta::minutes(1),
+        "hour" | "hours" | "hr" | "hrs" | "h" | "час" | "часа" | "часов" | "ч" => {
+            TimeDelta::hours(1)
+        }
+        "day" | "days" | "день" | "дня" | "дней" => TimeDelta::days(1),
+        _ => return None,
+    };
+
+    Some(unit)
+}
+
+fn match_repeat(words: &[&str]) -> Option<(usize, Item)> {
+    match words {
+        ["daily" | "ежедневно", ..] => return Some((1, Item::Repeat(Vec::new()))),
+        ["weekdays" | "workdays" | "будни", ..] | ["по", "будням", ..] => {
+            let length = if words[0] == "по" { 2 } else { 1 };
+            return Some((length, Item::Repeat(WEEKDAYS.to_vec())));
+        }
+        ["weekends", ..] => return Some((1, Item::Repeat(WEEKEND.to_vec()))),
+        ["по", "выходным", ..] => return Some((2, Item::Repeat(WEEKEND.to_vec()))),
+        ["по", ..] => {
+            let (length, days) = match_weekday_list(&words[1..])?;
+            return Some((length + 1, Item::Repeat(days)));
+        }
+        [word, ..] if is_plural_weekday(word) => {
+            let (length, days) = match_weekday_list(words)?;
+            return Some((length, Item::Repeat(days)));
+        }
+        _ => {}
+    }
+
+    if !matches!(
+        words[0],
+        "every" | "each" | "каждый" | "каждую" | "каждое" | "каждые" | "каждая"
+    ) {
+        return None;
+    }
+
+    match &words[1..] {
+        ["day" | "день", ..] => Some((2, Item::Repeat(Vec::new()))),
+        [
+            "weekday" | "weekdays" | "workday" | "workdays" | "будний" | "будни",
+            rest @ ..,
+        ] => {
+            let length = if rest.first() == Some(&"день") {
+                3
+            } else {
+                2
+            };
+            Some((length, Item::Repeat(WEEKDAYS.to_vec())))
+        }
+        ["weekend" | "weekends" | "выходные", ..] => {
+            Some((2, Item::Repeat(WEEKEND.to_vec())))
+        }
+        rest => {
+            let (length, days) = match_weekday_list(rest)?;
+            Some((length + 1, Item::Repeat(days)))
+        }
+    }
+}
+
+/// "monday and friday", "mon, wed fri", "понедельник и среду".
+fn match_weekday_list(words: &[&str]) -> Option<(usize, Vec<Weekday>)> {
+    let mut days = vec![weekday(words.first()?)?];
+    let mut pos = 1;
+
+    loop {
+        match words.get(pos..) {
+            Some([word, ..]) if weekday(word).is_some() => {
+                days.extend(weekday(word));
+                pos += 1;
+            }
+            Some([separator, word, ..])
+                if matches!(*separator, "and" | "и") && weekday(word).is_some() =>
+            {
+                days.extend(weekday(word));
+                pos += 2;
+            }
+            _ => break,
+        }
+    }
+
+    days.sort_by_key(Weekday::num_days_from_monday);
+    days.dedup();
+
+    Some((pos, days))
+}
+
+/// "mondays" repeats every week, "monday" is just the next one.
+fn is_plural_weekday(word: &str) -> bool {
+    word.strip_suffix('s')
+        .is_some_and(|singular| singular.ends_with("day") && weekday(singular).is_some())
+}
+
+fn weekday(word: &str) -> Option<Weekday> {
+    let day = match word {
+        "monday" | "mondays" | "mon" | "понедельник" | "понедельникам" | "пн" => {
+            Weekday::Mon
+        }
+        "tuesday" | "tuesdays" | "tue" | "tues" | "вторник" | "вторникам" | "вт" => {
+            Weekday::Tue
+        }
+        "wednesday" | "wednesdays" | "wed" | "среда" | "среду" | "средам" | "ср" => {
+            Weekday::Wed
+        }
+        "thursday" | "thursdays" | "thu" | "thur" | "thurs" | "четверг" | "четвергам" | "чт" => {
+            Weekday::Thu
+        }
+        "friday" | "fridays" | "fri" | "пятница" | "пятницу" | "пятницам" | "пт" => {
+            Weekday::Fri
+        }
+        "saturday" | "saturdays" | "sat" | "суббота" | "субботу" | "субботам" | "сб" => {
+            Weekday::Sat
+        }
+        "sunday" | "sundays" | "sun" | "воскресенье" | "воскресеньям" | "вс" => {
+            Weekday::Sun
+        }
+        _ => return None,
+    };
+
+    Some(day)
+}
+
+fn match_day(words: &[&str]) -> Option<(usize, Item)> {
+    match words {
+        ["today" | "сегодня", ..] => Some((1, Item::DayOffset(0))),
+        ["tomorrow" | "завтра", ..] => Some((1, Item::DayOffset(1))),
+        ["послезавтра", ..] => Some((1, Item::DayOffset(2))),
+        ["day", "after", "tomorrow", ..] => Some((3, Item::DayOffset(2))),
+        [word, ..] => weekday(word).map(|day| (1, Item::Weekday(day))),
+        [] => None,
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Meridiem {
+    Am,
+    Pm,
+}
+
+fn match_time(words: &[&str], after_at: bool) -> Option<(usize, Item)> {
+    match words[0] {
+        "noon" | "полдень" => {
+            return Some((1, Item::Time(NaiveTime::from_hms_opt(12, 0, 0)?)));
+        }
+        "midnight" | "полночь" => return Some((1, Item::Time(NaiveTime::MIN))),
+        _ => {}
+    }
+
+    let (hour, minute, has_minutes) = clock(words[0])?;
+    let mut pos = 1;
+
+    let has_hour_word = words
+        .get(pos)
+        .is_some_and(|word| matches!(*word, "o'clock" | "oclock" | "час" | "часа" | "часов" | "ч"));
+    if has_hour_word {
+        pos += 1;
+    }
+
+    let meridiem = words.get(pos).and_then(|word| match *word {
+        "am" | "утра" | "ночи" => Some(Meridiem::Am),
+        "pm" | "дня" | "вечера" => Some(Meridiem::Pm),
+        _ => None,
+    });
+    if meridiem.is_some() {
+        pos += 1;
+    }
+
+    // A lone number is only a time when something says so, "2 apples" is not.
+    if !(has_minutes || has_hour_word || meridiem.is_some() || after_at) {
+        return None;
+    }
+
+    let hour = match (meridiem, hour) {
+        (None, hour) => hour,
+        (Some(_), 0) | (Some(_), 13..) => return None,
+        (Some(Meridiem::Am), 12) => 0,
+        (Some(Meridiem::Am), hour) => hour,
+        (Some(Meridiem::Pm), 12) => 12,
+        (Some(Meridiem::Pm), hour) => hour + 12,
+    };
+
+    Some((pos, Item::Time(NaiveTime::from_hms_opt(hour, minute, 0)?)))
+}
+
+/// "8", "08:30" or "8.30" as hour, minute and whether minutes were given.
+fn clock(word: &str) -> Option<(u32, u32, bool)> {
+    let (hour, minute) = match word.split_once([':', '.']) {
+        Some((hour, minute)) if minute.len() == 2 => (hour, Some(minute)),
+        Some(_) => return None,
+        None => (word, None),
+    };
+
+    if hour.is_empty() || hour.len() > 2 {
+        return None;
+    }
+
+    let hour = hour.parse().ok().filter(|&hour| hour < 24)?;
+    let has_minutes = minute.is_some();
+    let minute = match minute {
+        Some(minute) => minute.parse().ok().filter(|&minute| minute < 60)?,
+        None => 0,
+    };
+
+    Some((hour, minute, has_minutes))
+}
+
+fn assemble(items: &[Item], now: DateTime<Tz>) -> Result<ParsedSchedule, TimeParseError> {
+    let mut time = None;
+    let mut relative = None;
+    let mut day = None;
+    let mut repeat = None;
+
+    for item in items {
+        let duplicate = match item {
+            Item::Time(value) => time.replace(*value).is_some(),
+            Item::Relative(value) => relative.replace(*value).is_some(),
+            Item::DayOffset(_) | Item::Weekday(_) => day.replace(item.clone()).is_some(),
+            Item::Repeat(days) => repeat.replace(days.clone()).is_some(),
+        };
+
+        if duplicate {
+            return Err(TimeParseError::Unrecognized);
+        }
+    }
+
+    if let Some(relative) = relative {
+        if time.is_some() || day.is_some() || repeat.is_some() {
+            return Err(TimeParseError::Unrecognized);
+        }
+
+        let at = now + relative;
+        return Ok(ParsedSchedule {
+            time: at
+                .time()
+                .with_second(0)
+                .and_then(|t| t.with_nanosecond(0))
+                .unwrap(),
+            recurrence: Recurrence::Once(at.date_naive()),
+        });
+    }
+
+    let Some(time) = time else {
+        return Err(if items.is_empty() {
+            TimeParseError::Unrecognized
+        } else {
+            TimeParseError::MissingTime
+        });
+    };
+
+    let today = now.date_naive();
+    let passed_today = time <= now.time();
+
+    let recurrence = match (day, repeat) {
+        (Some(_), Some(_)) => return Err(TimeParseError::Unrecognized),
+        (None, None) => Recurrence::Daily,
+        (None, Some(days)) if days.is_empty() || days.len() == 7 => Recurrence::Daily,
+        (None, Some(days)) => Recurrence::Weekly(days),
+        (Some(Item::DayOffset(0)), None) if passed_today => return Err(TimeParseError::InPast),
+        (Some(Item::DayOffset(offset)), None) => Recurrence::Once(today + TimeDelta::days(offset)),
+        (Some(Item::Weekday(weekday)), None) => {
+            let mut days_ahead =
+                (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+            if days_ahead == 0 && passed_today {
+                days_ahead = 7;
+            }
+            Recurrence::Once(today + TimeDelta::days(days_ahead as i64))
+        }
+        (Some(_), None) => unreachable!("Only days are stored in `day`"),
+    };
+
+    Ok(ParsedSchedule { time, recurrence })
+}
@@ -0,0 +1,247 @@
+use chrono::TimeZone;
+use nadoeda_models::chrono_tz::Europe::Prague;
+
+use super::*;
+
+/// Wednesday afternoon.
+fn now() -> DateTime<Tz> {
+    Prague.with_ymd_and_hms(2025, 6, 4, 14, 0, 0).unwrap()
+}
+
+fn time(hour: u32, minute: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+}
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 6, day).unwrap()
+}
+
+fn once(day: u32) -> Recurrence {
+    Recurrence::Once(date(day))
+}
+
+fn weekly(days: &[Weekday]) -> Recurrence {
+    Recurrence::Weekly(days.to_vec())
+}
+
+#[test]
+fn parses_schedules() {
+    use Weekday::*;
+
+    let cases = [
+        ("08:30", time(8, 30), Recurrence::Daily),
+        ("8:30", time(8, 30), Recurrence::Daily),
+        ("8.30", time(8, 30), Recurrence::Daily),
+        ("at 8", time(8, 0), Recurrence::Daily),
+        ("7:30pm", time(19, 30), Recurrence::Daily),
+        ("7:30 PM", time(19, 30), Recurrence::Daily),
+        ("9am", time(9, 0), Recurrence::Daily),
+        ("9 a.m.", time(9, 0), Recurrence::Daily),
+        ("12am", time(0, 0), Recurrence::Daily),
+        ("12pm", time(12, 0), Recurrence::Daily),
+        ("noon", time(12, 0), Recurrence::Daily),
+        ("midnight", time(0, 0), Recurrence::Daily),
+        ("6 o'clock", time(6, 0), Recurrence::Daily),
+        ("daily at 7", time(7, 0), Recurrence::Daily),
+        ("every day at 21:15", time(21, 15), Recurrence::Daily),
+        (
+            "every weekday at 8",
+            time(8, 0),
+            weekly(&[Mon, Tue, Wed, Thu, Fri]),
+        ),
+        (
+            "weekdays 8am",
+            time(8, 0),
+            weekly(&[Mon, Tue, Wed, Thu, Fri]),
+        ),
+        ("every weekend at 10", time(10, 0), weekly(&[Sat, Sun])),
+        (
+            "every monday and friday at 9am",
+            time(9, 0),
+            weekly(&[Mon, Fri]),
+        ),
+        ("every fri, mon at 9am", time(9, 0), weekly(&[Mon, Fri])),
+        ("tuesdays at 18:00", time(18, 0), weekly(&[Tue])),
+        ("mondays and fridays at 9", time(9, 0), weekly(&[Mon, Fri])),
+        ("today at 18:00", time(18, 0), once(4)),
+        ("tomorrow 9am", time(9, 0), once(5)),
+        ("tomorrow at 9", time(9, 0), once(5)),
+        ("9am tomorrow", time(9, 0), once(5)),
+        ("day after tomorrow at noon", time(12, 0), once(6)),
+        ("on friday at 10:15", time(10, 15), once(6)),
+        ("next monday 8am", time(8, 0), once(9)),
+        ("wednesday at 15:00", time(15, 0), once(4)),
+        ("wednesday at 13:00", time(13, 0), once(11)),
+        ("in 20 minutes", time(14, 20), once(4)),
+        ("in 20min", time(14, 20), once(4)),
+        ("in an hour", time(15, 0), once(4)),
+        ("in half an hour", time(14, 30), once(4)),
+        ("in 2 hours and 15 minutes", time(16, 15), once(4)),
+        ("in 12 hours", time(2, 0), once(5)),
+        ("in 3 days", time(14, 0), once(7)),
+        ("в 8", time(8, 0), Recurrence::Daily),
+        ("в 9 утра", time(9, 0), Recurrence::Daily),
+        ("в 7 вечера", time(19, 0), Recurrence::Daily),
+        ("в 2 часа дня", time(14, 0), Recurrence::Daily),
+        ("в полдень", time(12, 0), Recurrence::Daily),
+        ("ежедневно в 7:00", time(7, 0), Recurrence::Daily),
+        ("каждый день в 8", time(8, 0), Recurrence::Daily),
+        (
+            "по будням в 8",
+            time(8, 0),
+            weekly(&[Mon, Tue, Wed, Thu, Fri]),
+        ),
+        (
+            "каждый будний день в 8",
+            time(8, 0),
+            weekly(&[Mon, Tue, Wed, Thu, Fri]),
+        ),
+        (
+            "по понедельникам и пятницам в 9",
+            time(9, 0),
+            weekly(&[Mon, Fri]),
+        ),
+        ("каждую субботу в 11", time(11, 0), weekly(&[Sat])),
+        ("сегодня в 20:00", time(20, 0), once(4)),
+        ("завтра в 9", time(9, 0), once(5)),
+        ("послезавтра в 10:30", time(10, 30), once(6)),
+        ("в пятницу в 10", time(10, 0), once(6)),
+        ("через 20 минут", time(14, 20), once(4)),
+        ("через час", time(15, 0), once(4)),
+        ("через полчаса", time(14, 30), once(4)),
+        ("через 2 часа", time(16, 0), once(4)),
+    ];
+
+    for (text, time, recurrence) in cases {
+        assert_eq!(
+            parse_schedule(text, now()),
+            Ok(ParsedSchedule { time, recurrence }),
+            "{text}"
+        );
+    }
+}
+
+#[test]
+fn rejects_what_it_does_not_understand() {
+    let cases = [
+        ("", TimeParseError::Unrecognized),
+        ("soon", TimeParseError::Unrecognized),
+        ("8", TimeParseError::Unrecognized),
+        ("25:00", TimeParseError::Unrecognized),
+        ("8:75", TimeParseError::Unrecognized),
+        ("13pm", TimeParseError::Unrecognized),
+        ("in", TimeParseError::Unrecognized),
+        ("in 5", TimeParseError::Unrecognized),
+        ("in 1000 days", TimeParseError::Unrecognized),
+        ("8:00 9:00", TimeParseError::Unrecognized),
+        ("tomorrow every day at 8", TimeParseError::Unrecognized),
+        ("in 5 minutes at 8", TimeParseError::Unrecognized),
+        ("at 8 buy milk", TimeParseError::Unrecognized),
+        ("tomorrow", TimeParseError::MissingTime),
+        ("every weekday", TimeParseError::MissingTime),
+        ("today at 9", TimeParseError::InPast),
+        ("сегодня в 14:00", TimeParseError::InPast),
+    ];
+
+    for (text, error) in cases {
+        assert_eq!(parse_schedule(text, now()), Err(error), "{text}");
+    }
+}
+
+#[test]
+fn splits_schedule_from_the_rest() {
+    let cases = [
+        (
+            "08:30 take pills",
+            time(8, 30),
+            Recurrence::Daily,
+            "take pills",
+        ),
+        ("tomorrow 9:00 call mom", time(9, 0), once(5), "call mom"),
+        (
+            "in 20 minutes check the oven",
+            time(14, 20),
+            once(4),
+            "check the oven",
+        ),
+        (
+            "завтра в 9 позвонить маме",
+            time(9, 0),
+            once(5),
+            "позвонить маме",
+        ),
+        (
+            "at 8 and feed the cat",
+            time(8, 0),
+            Recurrence::Daily,
+            "and feed the cat",
+        ),
+        ("9am", time(9, 0), Recurrence::Daily, ""),
+    ];
+
+    for (text, time, recurrence, rest) in cases {
+        assert_eq!(
+            parse_schedule_prefix(text, now()),
+            Some((ParsedSchedule { time, recurrence }, rest)),
+            "{text}"
+        );
+    }
+
+    assert_eq!(parse_schedule_prefix("take pills at 8", now()), None);
+    assert_eq!(parse_schedule_prefix("2 apples", now()), None);
+}
+
+#[test]
+fn describes_schedules() {
+    use Weekday::*;
+
+    let cases = [
+        (Recurrence::Daily, "every day at 08:05"),
+        (weekly(&[Mon, Tue, Wed, Thu, Fri]), "every weekday at 08:05"),
+        (weekly(&[Sat, Sun]), "every weekend at 08:05"),
+        (weekly(&[Tue]), "every Tuesday at 08:05"),
+        (
+            weekly(&[Mon, Wed, Fri]),
+            "every Monday, Wednesday and Friday at 08:05",
+        ),
+        (once(4), "today at 08:05"),
+        (once(5), "tomorrow at 08:05"),
+        (once(13), "on Fri, 13 Jun at 08:05"),
+        (
+            Recurrence::Once(NaiveDate::from_ymd_opt(2026, 1, 2).unwrap()),
+            "on Fri, 2 Jan 2026 at 08:05",
+        ),
+    ];
+
+    for (recurrence, expected) in cases {
+        let schedule = ParsedSchedule {
+            time: time(8, 5),
+            recurrence,
+        };
+        assert_eq!(schedule.describe(date(4), Language::English), expected);
+    }
+}
+
+#[test]
+fn describes_schedules_in_russian() {
+    use Weekday::*;
+
+    let cases = [
+        (Recurrence::Daily, "каждый день в 08:05"),
+        (weekly(&[Mon, Tue, Wed, Thu, Fri]), "по будням в 08:05"),
+        (
+            weekly(&[Mon, Wed, Fri]),
+            "по понедельникам, средам и пятницам в 08:05",
+        ),
+        (once(5), "завтра в 08:05"),
+        (once(13), "13.06 в 08:05"),
+    ];
+
+    for (recurrence, expected) in cases {
+        let schedule = ParsedSchedule {
+            time: time(8, 5),
+            recurrence,
+        };
+        assert_eq!(schedule.describe(date(4), Language::Russian), expected);
+    }
+}
@@ -1,29 +1,48 @@
 mod authenticate_user;
+mod confirm_reminder;
 mod create_daily_reminder;
+mod daily_messages;
+mod delete_reminder;
+mod digest_mode;
 mod edit_reminders;
+mod exclusion_dates;
+mod link_reminders;
+mod message_templates;
+mod quick_reminder;
+mod reminder_archive;
+mod reminder_buddy;
+mod reminder_calendar;
+mod reminder_chart;
+mod reminder_stats;
+mod user_language;
 mod util;
 
 #[cfg(test)]
 mod tests;
 
 use authenticate_user::AuthenticationState;
+use confirm_reminder::ConfirmingReminderState;
+use delete_reminder::DeletingReminderState;
 use edit_reminders::EditingRemindersState;
+use exclusion_dates::ExcludingDatesState;
+use link_reminders::LinkingRemindersState;
+use message_templates::CustomizingTextsState;
 use nadoeda_models::user::User;
+use reminder_buddy::AssigningBuddyState;
+use reminder_calendar::ImportingRemindersState;
 
+use crate::i18n::t;
 use create_daily_reminder::CreatingDailyReminderState;
 use dptree::case;
 use nadoeda_scheduler::ReminderScheduler;
-use nadoeda_storage::{
-    ReminderStorage,
-    sqlite::{reminder_storage::SqliteReminderStorage, user_storage::SqliteUserInfoStorage},
-};
+use nadoeda_storage::{DynUserInfoStorage, Storages};
 use std::sync::Arc;
 use teloxide::{
     dispatching::dialogue::{self, InMemStorage},
     macros::BotCommands,
     prelude::*,
 };
-use util::HandlerExtensions;
+use util::{HandlerExtensions, chat_language};
 
 type GlobalDialogue = Dialogue<GlobalState, InMemStorage<GlobalState>>;
 type AuthenticatedDialogue =
@@ -48,6 +67,13 @@ enum AuthenticatedActionState {
     Idle,
     CreatingDailyReminder(CreatingDailyReminderState),
     EditingReminder(EditingRemindersState),
+    AssigningBuddy(AssigningBuddyState),
+    ConfirmingReminder(ConfirmingReminderState),
+    LinkingReminders(LinkingRemindersState),
+    ExcludingDates(ExcludingDatesState),
+    ImportingReminders(ImportingRemindersState),
+    CustomizingTexts(CustomizingTextsState),
+    DeletingReminder(DeletingReminderState),
 }
 
 pub struct TelegramInteractionInterface;
@@ -56,8 +82,7 @@ impl TelegramInteractionInterface {
     pub async fn start(
         bot: teloxide::Bot,
         scheduler: Arc<dyn ReminderScheduler>,
-        reminder_storage: Arc<SqliteReminderStorage>,
-        user_storage: Arc<SqliteUserInfoStorage>,
+        storages: Storages,
     ) {
         log::info!("Starting Telegram UI.");
 
@@ -66,13 +91,28 @@ impl TelegramInteractionInterface {
 
         let schema = dialogue::enter::<Update, InMemStorage<GlobalState>, GlobalState, _>()
         .chain(authenticate_user::schema())
+        .branch(reminder_buddy::invitation_schema())
         .branch(
             case![GlobalState::AuthenticatedV2(auth, state)]
                 .inject_auth_and_state::<AuthenticatedActionState>()
                 .enter_dialogue::<Update, InMemStorage<AuthenticatedActionState>, AuthenticatedActionState>()
                 .branch(get_cancel_handler::<AuthenticatedActionState>())
+                .branch(confirm_reminder::schema())
                 .branch(create_daily_reminder::schema())
+                .branch(daily_messages::schema())
+                .branch(delete_reminder::schema())
+                .branch(digest_mode::schema())
                 .branch(edit_reminders::schema())
+                .branch(exclusion_dates::schema())
+                .branch(link_reminders::schema())
+                .branch(message_templates::schema())
+                .branch(quick_reminder::schema())
+                .branch(reminder_archive::schema())
+                .branch(reminder_buddy::schema())
+                .branch(reminder_calendar::schema())
+                .branch(reminder_chart::schema())
+                .branch(reminder_stats::schema())
+                .branch(user_language::schema())
                 .branch(get_invalid_callback_handler::<AuthenticatedActionState>())
         )
         .branch(get_cancel_handler::<GlobalState>())
@@ -84,8 +124,15 @@ impl TelegramInteractionInterface {
                 InMemStorage::<GlobalState>::new(),
                 InMemStorage::<AuthenticatedActionState>::new(),
                 scheduler,
-                reminder_storage,
-                user_storage
+                storages.reminders,
+                storages.users,
+                storages.buddies,
+                storages.evidence,
+                storages.challenges,
+                storages.occurrences,
+                storages.stats,
+                storages.exclusions,
+                storages.templates
             ])
             .enable_ctrlc_handler()
             .build()
@@ -116,22 +163,26 @@ async fn cancel<S>(
     bot: Bot,
     dialogue: Dialogue<S, InMemStorage<S>>,
     msg: Message,
+    user_store: Arc<DynUserInfoStorage>,
 ) -> HandlerResult
 where
     S: Send + Sync + Clone + 'static,
 {
-    bot.send_message(msg.chat.id, "Cancelled current operation.")
+    let language = chat_language(user_store.as_ref(), msg.chat.id, msg.from.as_ref()).await?;
+    bot.send_message(msg.chat.id, t!(language, "cancelled"))
         .await?;
     dialogue.exit().await?;
     Ok(())
 }
 
-async fn invalid_state(bot: Bot, msg: Message) -> HandlerResult {
-    bot.send_message(
-    msg.chat.id,
-    "Unable to handle the message. Please try again or use /cancel to stop current operation.",
-)
-.await?;
+async fn invalid_state(
+    bot: Bot,
+    msg: Message,
+    user_store: Arc<DynUserInfoStorage>,
+) -> HandlerResult {
+    let language = chat_language(user_store.as_ref(), msg.chat.id, msg.from.as_ref()).await?;
+    bot.send_message(msg.chat.id, t!(language, "invalid-message"))
+        .await?;
     Ok(())
 }
 
@@ -139,15 +190,16 @@ async fn invalid_query<S>(
     bot: Bot,
     dialogue: Dialogue<S, InMemStorage<S>>,
     query: CallbackQuery,
+    user_store: Arc<DynUserInfoStorage>,
 ) -> HandlerResult
 where
     S: Send + Clone + 'static,
 {
     bot.answer_callback_query(query.id).await?;
-    bot.send_message(
-    dialogue.chat_id(),
-    "Unable to handle the query result. Please try again or use /cancel to stop current operation.",
-).await?;
+    let language =
+        chat_language(user_store.as_ref(), dialogue.chat_id(), Some(&query.from)).await?;
+    bot.send_message(dialogue.chat_id(), t!(language, "invalid-query"))
+        .await?;
 
     Ok(())
 }
@@ -160,5 +212,9 @@ where
 enum GlobalCommand {
     ListReminders,
     CreateReminder,
+    Stats,
+    Chart,
+    Digest,
+    Archive,
     Cancel,
 }
@@ -1,8 +1,7 @@
 use std::sync::Arc;
 
 use nadoeda_models::chrono_tz;
-use nadoeda_storage::sqlite::user_storage::SqliteUserInfoStorage;
-use nadoeda_storage::{NewUser, UserInfoStorage};
+use nadoeda_storage::{DynUserInfoStorage, NewUser};
 use sqlx::Result;
 use teloxide::dispatching::dialogue::InMemStorageError;
 use teloxide::prelude::*;
@@ -11,6 +10,9 @@ use teloxide::{dispatching::UpdateHandler, dptree, types::Update};
 
 use anyhow::anyhow;
 
+use crate::i18n::t;
+
+use super::util::client_language;
 use super::{
     AuthenticatedActionState, AuthenticationInfo, GlobalDialogue, GlobalState, HandlerResult,
 };
@@ -49,12 +51,12 @@ async fn try_authenticate(
     bot: Bot,
     dialogue: &GlobalDialogue,
     msg: Message,
-    user_store: Arc<SqliteUserInfoStorage>,
+    user_store: Arc<DynUserInfoStorage>,
 ) -> Result<bool, AuthError> {
     let user = user_store.get_by_tg_chat(msg.chat.id.0).await?;
 
     if let Some(user) = user {
-        bot.send_message(msg.chat.id, "I've seen you before. Proceed.")
+        bot.send_message(msg.chat.id, t!(user.language, "auth-welcome-back"))
             .await?;
 
         dialogue
@@ -65,8 +67,11 @@ async fn try_authenticate(
             .await?;
         Ok(true)
     } else {
-        bot.send_message(msg.chat.id, "Not seen before. Send me your timezone.")
-            .await?;
+        bot.send_message(
+            msg.chat.id,
+            t!(client_language(msg.from.as_ref()), "auth-ask-timezone"),
+        )
+        .await?;
 
         dialogue
             .update(GlobalState::Authenticating(
@@ -82,8 +87,10 @@ async fn get_user_timezone(
     bot: Bot,
     dialogue: &GlobalDialogue,
     msg: Message,
-    user_store: Arc<SqliteUserInfoStorage>,
+    user_store: Arc<DynUserInfoStorage>,
 ) -> HandlerResult {
+    let language = client_language(msg.from.as_ref());
+
     if let Some(tz_str) = msg.text() {
         match tz_str.parse::<chrono_tz::Tz>() {
             Ok(timezone) => {
@@ -91,10 +98,11 @@ async fn get_user_timezone(
                     .create(NewUser {
                         timezone,
                         tg_chat_id: Some(msg.chat.id.0),
+                        language,
                     })
                     .await?;
 
-                bot.send_message(msg.chat.id, "Timezone received. Welcome aboard.")
+                bot.send_message(msg.chat.id, t!(language, "auth-registered"))
                     .await?;
 
                 dialogue
@@ -105,12 +113,13 @@ async fn get_user_timezone(
                     .await?;
             }
             Err(_) => {
-                bot.send_message(msg.chat.id, "Invalid timezone. Please try again.")
+                bot.send_message(msg.chat.id, t!(language, "auth-invalid-timezone"))
                     .await?;
             }
         }
     } else {
-        bot.send_message(msg.chat.id, "Invalid.").await?;
+        bot.send_message(msg.chat.id, t!(language, "auth-invalid"))
+            .await?;
     };
 
     Ok(())
@@ -121,7 +130,7 @@ async fn middleware(
     dialogue: GlobalDialogue,
     state: GlobalState,
     update: Update,
-    user_store: Arc<SqliteUserInfoStorage>,
+    user_store: Arc<DynUserInfoStorage>,
 ) -> Result<Option<GlobalState>, AuthError> {
     let UpdateKind::Message(msg) = update.kind else {
         return Ok(Some(state));
@@ -0,0 +1,533 @@
+mod challenge;
+
+use std::sync::Arc;
+
+pub(super) use challenge::Challenge;
+use chrono::Utc;
+use dptree::case;
+use nadoeda_models::challenge::ChallengeAttempt;
+use nadoeda_models::occurrence::ReminderOccurrence;
+use nadoeda_models::reminder::{Reminder, ReminderId, ReminderVerification};
+use nadoeda_models::user::Language;
+use nadoeda_scheduler::{ReminderScheduler, ScheduledReminder};
+use nadoeda_storage::{
+    DynChallengeAttemptStorage, DynReminderEvidenceStorage, DynReminderOccurrenceStorage,
+    DynReminderStorage, NewReminderEvidence, ReminderStorageError,
+};
+use teloxide::types::{FileId, InlineKeyboardButton, InlineKeyboardMarkup, InputFile, ParseMode};
+use teloxide::utils::markdown;
+use teloxide::{dispatching::UpdateHandler, macros::BotCommands};
+use teloxide::{filter_command, prelude::*};
+
+use crate::delivery::{ChecklistToggle, ReminderAction, get_checklist_markup, proof_prompt};
+use crate::i18n::t;
+
+use super::util::{
+    clear_message_buttons, remove_message_button, replace_message_buttons,
+    try_get_message_from_query,
+};
+use super::{AuthenticatedActionState, AuthenticatedDialogue, AuthenticationInfo, HandlerResult};
+
+const EVIDENCE_REVIEW_LIMIT: u32 = 10;
+const CHALLENGE_OPTION_PREFIX: &str = "challenge_";
+
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub(super) enum ConfirmingReminderState {
+    #[default]
+    Start,
+    SolvingChallenge {
+        reminder: Arc<Reminder>,
+        challenge: Challenge,
+    },
+    WaitingForEvidence(Arc<Reminder>),
+}
+
+#[derive(BotCommands, Clone)]
+#[command(
+    rename_rule = "lowercase",
+    parse_with = "split",
+    command_separator = "_"
+)]
+enum ProofCommand {
+    Proof(ReminderId),
+}
+
+async fn handle_reminder_action(
+    bot: Bot,
+    query: CallbackQuery,
+    action: ReminderAction,
+    dialogue: AuthenticatedDialogue,
+    auth: AuthenticationInfo,
+    store: Arc<DynReminderStorage>,
+    scheduler: Arc<dyn ReminderScheduler>,
+) -> HandlerResult {
+    bot.answer_callback_query(query.id.clone()).await?;
+
+    if let Some(message) = try_get_message_from_query(&query) {
+        // Digests carry buttons for other reminders too, only the pressed one goes away.
+        remove_message_button(&bot, message, &action.to_callback_data()).await?;
+    }
+
+    let id = match action {
+        ReminderAction::Acknowledge(id) | ReminderAction::Confirm(id) => id,
+    };
+
+    let Some(reminder) = store.get(&id, &auth.0.id).await? else {
+        bot.send_message(dialogue.chat_id(), t!(auth.0.language, "confirm-gone"))
+            .await?;
+        return Ok(());
+    };
+
+    let scheduled_reminder = ScheduledReminder { id: reminder.id };
+
+    match action {
+        ReminderAction::Acknowledge(_) => {
+            scheduler.acknowledge_reminder(&scheduled_reminder).await?;
+        }
+        ReminderAction::Confirm(_) if !reminder.is_checklist_done() => {
+            bot.send_message(
+                dialogue.chat_id(),
+                t!(auth.0.language, "confirm-checklist-first"),
+            )
+            .await?;
+        }
+        ReminderAction::Confirm(_) => {
+            let challenge =
+                Challenge::generate(reminder.challenge, auth.0.language, &mut rand::rng());
+            match challenge {
+                Some(challenge) => {
+                    ask_challenge(&bot, &dialogue, Arc::new(reminder), challenge).await?
+                }
+                None => {
+                    finish_confirmation(
+                        &bot,
+                        &dialogue,
+                        Arc::new(reminder),
+                        auth.0.language,
+                        scheduler,
+                    )
+                    .await?
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Edits the checklist in place and acknowledges the reminder once every item is ticked.
+async fn toggle_checklist_item(
+    bot: Bot,
+    query: CallbackQuery,
+    toggle: ChecklistToggle,
+    auth: AuthenticationInfo,
+    store: Arc<DynReminderStorage>,
+    scheduler: Arc<dyn ReminderScheduler>,
+) -> HandlerResult {
+    bot.answer_callback_query(query.id.clone()).await?;
+
+    let message = try_get_message_from_query(&query);
+
+    let reminder = match store.toggle_checklist_item(&toggle.0, &auth.0.id).await {
+        Ok(reminder) => reminder,
+        Err(ReminderStorageError::ChecklistItemNotFound(_)) => {
+            if let Some(message) = message {
+                clear_message_buttons(&bot, message).await?;
+            }
+            return Ok(());
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    if !reminder.is_checklist_done() {
+        if let Some(message) = message {
+            replace_message_buttons(&bot, message, get_checklist_markup(&reminder)).await?;
+        }
+        return Ok(());
+    }
+
+    if let Some(message) = message {
+        clear_message_buttons(&bot, message).await?;
+    }
+
+    scheduler
+        .acknowledge_reminder(&ScheduledReminder { id: reminder.id })
+        .await?;
+
+    Ok(())
+}
+
+async fn ask_challenge(
+    bot: &Bot,
+    dialogue: &AuthenticatedDialogue,
+    reminder: Arc<Reminder>,
+    challenge: Challenge,
+) -> HandlerResult {
+    let request = bot.send_message(dialogue.chat_id(), &challenge.question);
+
+    if challenge.options.is_empty() {
+        request.await?;
+    } else {
+        let buttons = challenge
+            .options
+            .iter()
+            .enumerate()
+            .map(|(i, option)| {
+                InlineKeyboardButton::callback(option, format!("{CHALLENGE_OPTION_PREFIX}{i}"))
+            })
+            .collect::<Vec<_>>();
+
+        request
+            .reply_markup(InlineKeyboardMarkup::new(vec![buttons]))
+            .await?;
+    }
+
+    dialogue
+        .update(AuthenticatedActionState::ConfirmingReminder(
+            ConfirmingReminderState::SolvingChallenge {
+                reminder,
+                challenge,
+            },
+        ))
+        .await?;
+
+    Ok(())
+}
+
+/// Asks for proof if the reminder requires it, otherwise confirms the reminder right away.
+async fn finish_confirmation(
+    bot: &Bot,
+    dialogue: &AuthenticatedDialogue,
+    reminder: Arc<Reminder>,
+    language: Language,
+    scheduler: Arc<dyn ReminderScheduler>,
+) -> HandlerResult {
+    match proof_prompt(&reminder.verification, language) {
+        Some(prompt) => {
+            bot.send_message(dialogue.chat_id(), prompt)
+                .parse_mode(ParseMode::MarkdownV2)
+                .await?;
+
+            dialogue
+                .update(AuthenticatedActionState::ConfirmingReminder(
+                    ConfirmingReminderState::WaitingForEvidence(reminder),
+                ))
+                .await?;
+        }
+        None => {
+            scheduler
+                .confirm_reminder(&ScheduledReminder { id: reminder.id })
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn check_challenge_answer(
+    answer: &str,
+    bot: &Bot,
+    dialogue: &AuthenticatedDialogue,
+    (reminder, challenge): (Arc<Reminder>, Challenge),
+    language: Language,
+    challenge_store: Arc<DynChallengeAttemptStorage>,
+    scheduler: Arc<dyn ReminderScheduler>,
+) -> HandlerResult {
+    let correct = challenge.check(answer);
+
+    challenge_store
+        .record(ChallengeAttempt {
+            reminder_id: reminder.id,
+            user_id: reminder.user_id,
+            correct,
+            answered_at: Utc::now(),
+        })
+        .await?;
+
+    if correct {
+        dialogue.exit().await?;
+        return finish_confirmation(bot, dialogue, reminder, language, scheduler).await;
+    }
+
+    bot.send_message(dialogue.chat_id(), t!(language, "challenge-wrong"))
+        .await?;
+
+    let next =
+        Challenge::generate(reminder.challenge, language, &mut rand::rng()).unwrap_or(challenge);
+    ask_challenge(bot, dialogue, reminder, next).await
+}
+
+async fn receive_challenge_answer(
+    state: (Arc<Reminder>, Challenge),
+    msg: Message,
+    bot: Bot,
+    dialogue: AuthenticatedDialogue,
+    auth: AuthenticationInfo,
+    challenge_store: Arc<DynChallengeAttemptStorage>,
+    scheduler: Arc<dyn ReminderScheduler>,
+) -> HandlerResult {
+    let Some(answer) = msg.text() else {
+        bot.send_message(msg.chat.id, t!(auth.0.language, "challenge-ask-answer"))
+            .await?;
+        return Ok(());
+    };
+
+    check_challenge_answer(
+        answer,
+        &bot,
+        &dialogue,
+        state,
+        auth.0.language,
+        challenge_store,
+        scheduler,
+    )
+    .await
+}
+
+async fn receive_challenge_option(
+    state: (Arc<Reminder>, Challenge),
+    query: CallbackQuery,
+    bot: Bot,
+    dialogue: AuthenticatedDialogue,
+    auth: AuthenticationInfo,
+    challenge_store: Arc<DynChallengeAttemptStorage>,
+    scheduler: Arc<dyn ReminderScheduler>,
+) -> HandlerResult {
+    bot.answer_callback_query(query.id.clone()).await?;
+
+    let option = query
+        .data
+        .as_deref()
+        .and_then(|data| data.strip_prefix(CHALLENGE_OPTION_PREFIX))
+        .and_then(|i| i.parse::<usize>().ok())
+        .and_then(|i| state.1.options.get(i).cloned());
+
+    let Some(option) = option else {
+        return Ok(());
+    };
+
+    if let Some(message) = try_get_message_from_query(&query) {
+        clear_message_buttons(&bot, message).await?;
+    }
+
+    check_challenge_answer(
+        &option,
+        &bot,
+        &dialogue,
+        state,
+        auth.0.language,
+        challenge_store,
+        scheduler,
+    )
+    .await
+}
+
+/// Extracts the photo file id and text from the message, if it is valid proof for the reminder.
+fn extract_evidence(
+    verification: &ReminderVerification,
+    msg: &Message,
+) -> Option<(Option<String>, Option<String>)> {
+    match verification {
+        ReminderVerification::None => Some((None, None)),
+        ReminderVerification::Photo => {
+            let photo = msg.photo()?.last()?;
+            Some((
+                Some(photo.file.id.to_string()),
+                msg.caption().map(str::to_string),
+            ))
+        }
+        ReminderVerification::Text => {
+            let text = msg.text().map(str::trim).filter(|t| !t.is_empty())?;
+            Some((None, Some(text.to_string())))
+        }
+        ReminderVerification::Keyword(keyword) => {
+            let text = msg.text().map(str::trim)?;
+            text.eq_ignore_ascii_case(keyword.trim())
+                .then(|| (None, Some(text.to_string())))
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn receive_evidence(
+    reminder: Arc<Reminder>,
+    msg: Message,
+    bot: Bot,
+    dialogue: AuthenticatedDialogue,
+    auth: AuthenticationInfo,
+    evidence_store: Arc<DynReminderEvidenceStorage>,
+    occurrence_store: Arc<DynReminderOccurrenceStorage>,
+    scheduler: Arc<dyn ReminderScheduler>,
+) -> HandlerResult {
+    let language = auth.0.language;
+
+    // The scheduler records the acknowledgement in the occurrence and closes it when the
+    // reminder times out or gets confirmed, so late proof has nothing to belong to.
+    let occurrence = occurrence_store
+        .get_for_reminder(&reminder.id, &auth.0.id, 1)
+        .await?
+        .into_iter()
+        .next()
+        .filter(ReminderOccurrence::is_confirming);
+    let Some(occurrence) = occurrence else {
+        bot.send_message(msg.chat.id, t!(language, "proof-expired"))
+            .await?;
+        dialogue.exit().await?;
+        return Ok(());
+    };
+
+    let Some((photo_file_id, text)) = extract_evidence(&reminder.verification, &msg) else {
+        let prompt = proof_prompt(&reminder.verification, language).unwrap_or_default();
+        bot.send_message(msg.chat.id, t!(language, "proof-invalid", prompt = prompt))
+            .parse_mode(ParseMode::MarkdownV2)
+            .await?;
+        return Ok(());
+    };
+
+    evidence_store
+        .insert(NewReminderEvidence {
+            reminder_id: reminder.id,
+            occurrence_id: occurrence.id,
+            user_id: reminder.user_id,
+            photo_file_id,
+            text,
+            submitted_at: Utc::now(),
+        })
+        .await?;
+
+    scheduler
+        .confirm_reminder(&ScheduledReminder { id: reminder.id })
+        .await?;
+
+    bot.send_message(msg.chat.id, t!(language, "proof-saved"))
+        .await?;
+    dialogue.exit().await?;
+
+    Ok(())
+}
+
+async fn review_evidence(
+    id: ReminderId,
+    msg: Message,
+    bot: Bot,
+    auth: AuthenticationInfo,
+    store: Arc<DynReminderStorage>,
+    evidence_store: Arc<DynReminderEvidenceStorage>,
+    occurrence_store: Arc<DynReminderOccurrenceStorage>,
+) -> HandlerResult {
+    let Some(reminder) = store.get(&id, &auth.0.id).await? else {
+        bot.send_message(msg.chat.id, t!(auth.0.language, "proof-invalid-command"))
+            .await?;
+        return Ok(());
+    };
+
+    let occurrences = occurrence_store
+        .get_for_reminder(&reminder.id, &auth.0.id, EVIDENCE_REVIEW_LIMIT)
+        .await?;
+
+    let mut reviewed = Vec::new();
+    for occurrence in occurrences {
+        let evidence = evidence_store
+            .get_for_occurrence(&occurrence.id, &auth.0.id)
+            .await?;
+        if !evidence.is_empty() {
+            reviewed.push((occurrence, evidence));
+        }
+    }
+
+    if reviewed.is_empty() {
+        bot.send_message(msg.chat.id, t!(auth.0.language, "proof-none"))
+            .await?;
+        return Ok(());
+    }
+
+    bot.send_message(
+        msg.chat.id,
+        t!(
+            auth.0.language,
+            "proof-latest",
+            text = markdown::escape(&reminder.text)
+        ),
+    )
+    .parse_mode(ParseMode::MarkdownV2)
+    .await?;
+
+    for (occurrence, evidence) in reviewed {
+        let scheduled_at = occurrence
+            .scheduled_at
+            .with_timezone(&auth.0.timezone)
+            .format("%Y-%m-%d %H:%M");
+
+        for item in evidence {
+            let caption = match &item.text {
+                Some(text) => format!("{scheduled_at}: {text}"),
+                None => scheduled_at.to_string(),
+            };
+
+            match item.photo_file_id {
+                Some(file_id) => {
+                    bot.send_photo(msg.chat.id, InputFile::file_id(FileId(file_id)))
+                        .caption(caption)
+                        .await?;
+                }
+                None => {
+                    bot.send_message(msg.chat.id, caption).await?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub(super) fn schema() -> UpdateHandler<anyhow::Error> {
+    dptree::entry()
+        .branch(
+            Update::filter_callback_query()
+                .filter_map(|query: CallbackQuery| {
+                    query.data.as_deref().and_then(ReminderAction::parse)
+                })
+                .endpoint(handle_reminder_action),
+        )
+        .branch(
+            Update::filter_callback_query()
+                .filter_map(|query: CallbackQuery| {
+                    query.data.as_deref().and_then(ChecklistToggle::parse)
+                })
+                .endpoint(toggle_checklist_item),
+        )
+        .branch(
+            case![AuthenticatedActionState::Idle].branch(
+                Update::filter_message().branch(
+                    filter_command::<ProofCommand, _>()
+                        .branch(case![ProofCommand::Proof(id)].endpoint(review_evidence)),
+                ),
+            ),
+        )
+        .branch(
+            case![AuthenticatedActionState::ConfirmingReminder(x)]
+                .branch(
+                    Update::filter_callback_query().branch(
+                        case![ConfirmingReminderState::SolvingChallenge {
+                            reminder,
+                            challenge
+                        }]
+                        .endpoint(receive_challenge_option),
+                    ),
+                )
+                .branch(
+                    Update::filter_message()
+                        .branch(
+                            case![ConfirmingReminderState::SolvingChallenge {
+                                reminder,
+                                challenge
+                            }]
+                            .endpoint(receive_challenge_answer),
+                        )
+                        .branch(
+                            case![ConfirmingReminderState::WaitingForEvidence(reminder)]
+                                .endpoint(receive_evidence),
+                        ),
+                ),
+        )
+}
@@ -0,0 +1,171 @@
+use nadoeda_models::reminder::ConfirmationChallenge;
+use nadoeda_models::user::Language;
+use rand::Rng;
+use rand::seq::{IndexedRandom, SliceRandom};
+
+use crate::i18n::t;
+
+const WORDS: &[&str] = &[
+    "anchor", "banana", "candle", "dragon", "eclipse", "falcon", "garden", "harbor", "island",
+    "jungle", "kettle", "lantern", "meadow", "nectar", "orbit", "pepper", "quartz", "ribbon",
+    "saddle", "tundra", "umbrella", "velvet", "walnut", "yonder", "zephyr",
+];
+
+const PICK_OPTIONS_COUNT: usize = 4;
+
+/// A generated puzzle that has to be solved before the reminder is confirmed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Challenge {
+    pub question: String,
+    pub answer: String,
+    /// Shuffled answer options, empty when the answer has to be typed.
+    pub options: Vec<String>,
+}
+
+impl Challenge {
+    pub fn generate(
+        kind: ConfirmationChallenge,
+        language: Language,
+        rng: &mut impl Rng,
+    ) -> Option<Self> {
+        match kind {
+            ConfirmationChallenge::None => None,
+            ConfirmationChallenge::Arithmetic => Some(Self::arithmetic(language, rng)),
+            ConfirmationChallenge::RetypeWord => Some(Self::retype_word(language, rng)),
+            ConfirmationChallenge::PickOption => Some(Self::pick_option(language, rng)),
+        }
+    }
+
+    pub fn check(&self, answer: &str) -> bool {
+        answer.trim().eq_ignore_ascii_case(&self.answer)
+    }
+
+    fn arithmetic(language: Language, rng: &mut impl Rng) -> Self {
+        let a: i32 = rng.random_range(2..=20);
+        let b: i32 = rng.random_range(2..=20);
+        let (symbol, result) = match rng.random_range(0..3) {
+            0 => ("+", a + b),
+            1 => ("-", a - b),
+            _ => ("×", a * b),
+        };
+
+        Self {
+            question: t!(
+                language,
+                "challenge-arithmetic",
+                a = a.to_string(),
+                symbol = symbol,
+                b = b.to_string()
+            ),
+            answer: result.to_string(),
+            options: Vec::new(),
+        }
+    }
+
+    fn retype_word(language: Language, rng: &mut impl Rng) -> Self {
+        let word = random_word(rng);
+
+        Self {
+            question: t!(language, "challenge-retype", word = word),
+            answer: word.chars().rev().collect(),
+            options: Vec::new(),
+        }
+    }
+
+    fn pick_option(language: Language, rng: &mut impl Rng) -> Self {
+        let mut options: Vec<String> = WORDS
+            .choose_multiple(rng, PICK_OPTIONS_COUNT)
+            .map(|word| word.to_string())
+            .collect();
+        let answer = options[0].clone();
+        options.shuffle(rng);
+
+        Self {
+            question: t!(language, "challenge-pick", word = answer.to_uppercase()),
+            answer,
+            options,
+        }
+    }
+}
+
+fn random_word(rng: &mut impl Rng) -> &'static str {
+    WORDS.choose(rng).expect("Word list is never empty.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    #[test]
+    fn no_challenge_is_generated_when_disabled() {
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(
+            Challenge::generate(ConfirmationChallenge::None, Language::English, &mut rng),
+            None
+        );
+    }
+
+    #[test]
+    fn arithmetic_answer_matches_question() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..100 {
+            let challenge = Challenge::generate(
+                ConfirmationChallenge::Arithmetic,
+                Language::English,
+                &mut rng,
+            )
+            .unwrap();
+            let expression = challenge
+                .question
+                .trim_start_matches("How much is ")
+                .trim_end_matches('?');
+            let parts: Vec<&str> = expression.split(' ').collect();
+            let (a, b): (i32, i32) = (parts[0].parse().unwrap(), parts[2].parse().unwrap());
+            let expected = match parts[1] {
+                "+" => a + b,
+                "-" => a - b,
+                _ => a * b,
+            };
+
+            assert!(challenge.check(&expected.to_string()));
+            assert!(!challenge.check(&(expected + 1).to_string()));
+        }
+    }
+
+    #[test]
+    fn retype_word_expects_reversed_word() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let challenge = Challenge::generate(
+            ConfirmationChallenge::RetypeWord,
+            Language::English,
+            &mut rng,
+        )
+        .unwrap();
+        let word = challenge.question.rsplit(' ').next().unwrap();
+
+        assert!(challenge.check(&word.chars().rev().collect::<String>()));
+        assert!(!challenge.check(word));
+    }
+
+    #[test]
+    fn pick_option_contains_answer_once() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let challenge = Challenge::generate(
+            ConfirmationChallenge::PickOption,
+            Language::English,
+            &mut rng,
+        )
+        .unwrap();
+
+        assert_eq!(challenge.options.len(), PICK_OPTIONS_COUNT);
+        assert_eq!(
+            challenge
+                .options
+                .iter()
+                .filter(|option| challenge.check(option))
+                .count(),
+            1
+        );
+    }
+}
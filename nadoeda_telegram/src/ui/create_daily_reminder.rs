@@ -1,19 +1,18 @@
 use std::sync::Arc;
 
-use chrono::NaiveTime;
 use dptree::case;
 use nadoeda_scheduler::{ReminderScheduler, ScheduleRequest};
-use nadoeda_storage::sqlite::reminder_storage::SqliteReminderStorage;
-use nadoeda_storage::{NewReminder, ReminderStorage};
+use nadoeda_storage::{DynReminderStorage, NewReminder};
 use teloxide::dispatching::UpdateHandler;
 use teloxide::prelude::*;
 use teloxide::sugar::bot::BotMessagesExt;
 use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+use teloxide::utils::markdown;
 use teloxide::{Bot, types::Message};
 
-use nadoeda_models::reminder::ReminderFireTime;
+use crate::i18n::t;
 
-use super::util::try_get_message_from_query;
+use super::util::{DailySchedule, parse_daily_schedule, try_get_message_from_query};
 use super::{AuthenticatedActionState, AuthenticatedDialogue, AuthenticationInfo};
 
 use super::{GlobalCommand, HandlerResult};
@@ -28,15 +27,16 @@ pub(super) enum CreatingDailyReminderState {
     },
     WaitingForConfirmation {
         text: String,
-        firing_time: NaiveTime,
+        schedule: DailySchedule,
     },
 }
 
-async fn create_daily_reminder_start(bot: Bot, dialogue: AuthenticatedDialogue) -> HandlerResult {
-    bot.send_message(
-        dialogue.chat_id(),
-            "Creating a new daily reminder! Please input reminder text. If you want to cancel, use the /cancel command.",
-        )
+async fn create_daily_reminder_start(
+    bot: Bot,
+    dialogue: AuthenticatedDialogue,
+    auth: AuthenticationInfo,
+) -> HandlerResult {
+    bot.send_message(dialogue.chat_id(), t!(auth.0.language, "create-start"))
         .await?;
 
     dialogue
@@ -51,25 +51,24 @@ async fn receive_reminder_text(
     bot: Bot,
     dialogue: AuthenticatedDialogue,
     msg: Message,
+    auth: AuthenticationInfo,
 ) -> HandlerResult {
+    let language = auth.0.language;
+
     match msg.text() {
         Some(text) => {
-            let escaped_text = teloxide::utils::markdown::escape(text);
-            let message = format!(
-                "Great! You will be reminded about \"{}\"\nNow, please enter time when reminder is going to be fired (e.g. 13:00)",
-                escaped_text
-            );
+            let message = t!(language, "create-ask-time", text = text);
             bot.send_message(msg.chat.id, message).await?;
             dialogue
                 .update(AuthenticatedActionState::CreatingDailyReminder(
                     CreatingDailyReminderState::WaitingForFiringTime {
-                        text: escaped_text.to_string(),
+                        text: text.to_string(),
                     },
                 ))
                 .await?;
         }
         None => {
-            bot.send_message(msg.chat.id, "Please send me reminder text.")
+            bot.send_message(msg.chat.id, t!(language, "create-ask-text"))
                 .await?;
         }
     }
@@ -81,30 +80,32 @@ async fn receive_firing_time(
     dialogue: AuthenticatedDialogue,
     text: String,
     msg: Message,
+    auth: AuthenticationInfo,
 ) -> HandlerResult {
-    match msg
-        .text()
-        .map(|text| NaiveTime::parse_from_str(text, "%H:%M"))
-    {
-        Some(Ok(time)) => {
-            let message_text = format!(
-                "You will be reminded every day at *\"{}\"*
-Reminder text is *\"{}\"*
-If it's okay, please press *Confirm*
-If you want to change something, please type /cancel and start over",
-                time.format("%H:%M"),
-                text
+    let language = auth.0.language;
+
+    let Some(time_text) = msg.text() else {
+        bot.send_message(msg.chat.id, t!(language, "create-ask-time-again"))
+            .await?;
+        return Ok(());
+    };
+
+    match parse_daily_schedule(time_text, auth.0.timezone, language) {
+        Ok(schedule) => {
+            let message_text = t!(
+                language,
+                "create-confirm",
+                schedule = markdown::escape(&schedule.description),
+                text = markdown::escape(&text)
             );
 
-            let ok_button = InlineKeyboardButton::callback("Confirm", "Confirm");
+            let ok_button =
+                InlineKeyboardButton::callback(t!(language, "create-confirm-button"), "Confirm");
             let keyboard = InlineKeyboardMarkup::new(vec![vec![ok_button]]);
 
             dialogue
                 .update(AuthenticatedActionState::CreatingDailyReminder(
-                    CreatingDailyReminderState::WaitingForConfirmation {
-                        text,
-                        firing_time: time,
-                    },
+                    CreatingDailyReminderState::WaitingForConfirmation { text, schedule },
                 ))
                 .await?;
 
@@ -113,32 +114,27 @@ If you want to change something, please type /cancel and start over",
                 .parse_mode(teloxide::types::ParseMode::MarkdownV2)
                 .await?;
         }
-        _ => {
-            bot.send_message(
-                msg.chat.id,
-                "Could not parse time. Please send time in the following format: *13:00*",
-            )
-            .parse_mode(teloxide::types::ParseMode::MarkdownV2)
-            .await?;
+        Err(reason) => {
+            bot.send_message(msg.chat.id, reason)
+                .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                .await?;
         }
     }
     Ok(())
 }
 
 async fn confirm_reminder(
-    storage: Arc<SqliteReminderStorage>,
+    storage: Arc<DynReminderStorage>,
     bot: Bot,
     dialogue: AuthenticatedDialogue,
-    (text, firing_time): (String, NaiveTime),
+    (text, schedule): (String, DailySchedule),
     auth: AuthenticationInfo,
     query: CallbackQuery,
     scheduler: Arc<dyn ReminderScheduler>,
 ) -> HandlerResult {
-    let fire_at = ReminderFireTime::new_utc_from_local(firing_time, auth.0.timezone).unwrap();
-
     let reminder = NewReminder {
         text,
-        fire_at,
+        fire_at: schedule.fire_at,
         user_id: auth.0.id,
     };
 
@@ -156,11 +152,13 @@ async fn confirm_reminder(
 
     log::info!("Created reminder with id {}", reminder.id);
 
-    scheduler
-        .schedule_reminder(ScheduleRequest::new(reminder))
-        .await?;
+    let mut request = ScheduleRequest::new(reminder);
+    if let Some(not_before) = schedule.not_before(auth.0.timezone) {
+        request = request.not_before(not_before);
+    }
+    scheduler.schedule_reminder(request).await?;
 
-    bot.send_message(dialogue.chat_id(), "Reminder saved and scheduled.")
+    bot.send_message(dialogue.chat_id(), t!(auth.0.language, "create-saved"))
         .await?;
 
     dialogue.exit().await?;
@@ -195,7 +193,7 @@ pub(super) fn schema() -> UpdateHandler<anyhow::Error> {
                     Update::filter_callback_query().branch(
                         case![CreatingDailyReminderState::WaitingForConfirmation {
                             text,
-                            firing_time
+                            schedule
                         }]
                         .endpoint(confirm_reminder),
                     ),
@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use chrono::NaiveTime;
+use dptree::case;
+use nadoeda_models::reminder::ReminderFireTime;
+use nadoeda_models::user::Language;
+use nadoeda_storage::DynUserInfoStorage;
+use teloxide::types::ParseMode;
+use teloxide::{dispatching::UpdateHandler, filter_command, macros::BotCommands, prelude::*};
+
+use crate::i18n::t;
+
+use super::{AuthenticatedActionState, AuthenticationInfo, HandlerResult};
+
+#[derive(BotCommands, Clone)]
+#[command(rename_rule = "lowercase")]
+enum DailyMessageCommand {
+    Agenda(String),
+    Summary(String),
+}
+
+#[derive(Clone, Copy)]
+enum DailyMessageKind {
+    Agenda,
+    Summary,
+}
+
+impl DailyMessageKind {
+    fn usage(self, language: Language) -> String {
+        match self {
+            Self::Agenda => t!(language, "agenda-usage"),
+            Self::Summary => t!(language, "summary-usage"),
+        }
+    }
+
+    /// MarkdownV2 reply to the new setting.
+    fn reply(self, setting: &DailyMessageSetting, language: Language) -> String {
+        match (self, setting) {
+            (Self::Agenda, DailyMessageSetting::Off) => t!(language, "agenda-off"),
+            (Self::Summary, DailyMessageSetting::Off) => t!(language, "summary-off"),
+            (Self::Agenda, DailyMessageSetting::At(time)) => {
+                t!(
+                    language,
+                    "agenda-on",
+                    time = time.format("%H:%M").to_string()
+                )
+            }
+            (Self::Summary, DailyMessageSetting::At(time)) => {
+                t!(
+                    language,
+                    "summary-on",
+                    time = time.format("%H:%M").to_string()
+                )
+            }
+        }
+    }
+}
+
+enum DailyMessageSetting {
+    Off,
+    At(NaiveTime),
+}
+
+fn parse_setting(text: &str) -> Option<DailyMessageSetting> {
+    match text.trim() {
+        "off" => Some(DailyMessageSetting::Off),
+        time => NaiveTime::parse_from_str(time, "%H:%M")
+            .ok()
+            .map(DailyMessageSetting::At),
+    }
+}
+
+async fn set_daily_message(
+    kind: DailyMessageKind,
+    argument: String,
+    msg: Message,
+    bot: Bot,
+    auth: AuthenticationInfo,
+    user_store: Arc<DynUserInfoStorage>,
+) -> HandlerResult {
+    let language = auth.0.language;
+
+    let Some(setting) = parse_setting(&argument) else {
+        bot.send_message(msg.chat.id, kind.usage(language)).await?;
+        return Ok(());
+    };
+
+    let Some(mut user) = user_store.get(&auth.0.id).await? else {
+        bot.send_message(msg.chat.id, t!(language, "auth-register-first"))
+            .await?;
+        return Ok(());
+    };
+
+    let at = match setting {
+        DailyMessageSetting::Off => None,
+        DailyMessageSetting::At(time) => {
+            Some(ReminderFireTime::new_utc_from_local(time, user.timezone).unwrap())
+        }
+    };
+
+    match kind {
+        DailyMessageKind::Agenda => user.agenda_at = at,
+        DailyMessageKind::Summary => user.summary_at = at,
+    }
+    user_store.update(user).await?;
+
+    bot.send_message(msg.chat.id, kind.reply(&setting, language))
+        .parse_mode(ParseMode::MarkdownV2)
+        .await?;
+
+    Ok(())
+}
+
+pub(super) fn schema() -> UpdateHandler<anyhow::Error> {
+    case![AuthenticatedActionState::Idle].branch(
+        Update::filter_message().branch(
+            filter_command::<DailyMessageCommand, _>()
+                .branch(
+                    case![DailyMessageCommand::Agenda(argument)]
+                        .map(|| DailyMessageKind::Agenda)
+                        .endpoint(set_daily_message),
+                )
+                .branch(
+                    case![DailyMessageCommand::Summary(argument)]
+                        .map(|| DailyMessageKind::Summary)
+                        .endpoint(set_daily_message),
+                ),
+        ),
+    )
+}
@@ -0,0 +1,180 @@
+use std::sync::Arc;
+
+use dptree::case;
+use nadoeda_models::reminder::{Reminder, ReminderId};
+use nadoeda_models::user::Language;
+use nadoeda_scheduler::{ReminderScheduler, ScheduledReminder};
+use nadoeda_storage::DynReminderStorage;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup, ParseMode};
+use teloxide::utils::markdown;
+use teloxide::{dispatching::UpdateHandler, filter_command, macros::BotCommands, prelude::*};
+
+use crate::i18n::{t, translate};
+
+use super::link_reminders::reschedule;
+use super::util::{clear_message_buttons, try_get_message_from_query};
+use super::{AuthenticatedActionState, AuthenticatedDialogue, AuthenticationInfo, HandlerResult};
+
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub(super) enum DeletingReminderState {
+    #[default]
+    Start,
+    WaitingForConfirmation(Arc<Reminder>),
+}
+
+#[derive(BotCommands, Clone)]
+#[command(
+    rename_rule = "lowercase",
+    parse_with = "split",
+    command_separator = "_"
+)]
+enum DeleteReminderCommand {
+    Delete(ReminderId),
+}
+
+/// Asks whether the reminder should really be deleted. Also used by the edit menu.
+pub(super) async fn ask_for_confirmation(
+    bot: &Bot,
+    dialogue: &AuthenticatedDialogue,
+    reminder: Arc<Reminder>,
+    language: Language,
+) -> HandlerResult {
+    let button = |key, data| InlineKeyboardButton::callback(translate(language, key, None), data);
+    let keyboard = InlineKeyboardMarkup::new(vec![
+        vec![
+            button("delete-archive-button", "archive"),
+            button("delete-confirm-button", "delete"),
+        ],
+        vec![button("delete-keep-button", "keep")],
+    ]);
+
+    bot.send_message(
+        dialogue.chat_id(),
+        t!(
+            language,
+            "delete-ask",
+            text = markdown::escape(&reminder.text)
+        ),
+    )
+    .parse_mode(ParseMode::MarkdownV2)
+    .reply_markup(keyboard)
+    .await?;
+
+    dialogue
+        .update(AuthenticatedActionState::DeletingReminder(
+            DeletingReminderState::WaitingForConfirmation(reminder),
+        ))
+        .await?;
+
+    Ok(())
+}
+
+async fn delete_reminder_start(
+    id: ReminderId,
+    msg: Message,
+    bot: Bot,
+    dialogue: AuthenticatedDialogue,
+    auth: AuthenticationInfo,
+    store: Arc<DynReminderStorage>,
+) -> HandlerResult {
+    let Some(reminder) = store.get(&id, &auth.0.id).await? else {
+        bot.send_message(msg.chat.id, t!(auth.0.language, "delete-invalid-command"))
+            .await?;
+        return Ok(());
+    };
+
+    ask_for_confirmation(&bot, &dialogue, Arc::new(reminder), auth.0.language).await
+}
+
+async fn handle_confirmation(
+    reminder: Arc<Reminder>,
+    query: CallbackQuery,
+    bot: Bot,
+    dialogue: AuthenticatedDialogue,
+    auth: AuthenticationInfo,
+    store: Arc<DynReminderStorage>,
+    scheduler: Arc<dyn ReminderScheduler>,
+) -> HandlerResult {
+    bot.answer_callback_query(query.id.clone()).await?;
+
+    let language = auth.0.language;
+    let action = query.data.clone().unwrap_or_default();
+    if !["archive", "delete", "keep"].contains(&action.as_str()) {
+        return Ok(());
+    }
+
+    if let Some(message) = try_get_message_from_query(&query) {
+        clear_message_buttons(&bot, message).await?;
+    }
+    dialogue.exit().await?;
+
+    if action == "keep" {
+        bot.send_message(dialogue.chat_id(), t!(language, "delete-kept"))
+            .await?;
+        return Ok(());
+    }
+
+    let dependents: Vec<ReminderId> = store
+        .get_all_user_reminders(&auth.0.id)
+        .await?
+        .into_iter()
+        .filter(|other| {
+            other
+                .dependency
+                .as_ref()
+                .is_some_and(|dependency| dependency.predecessor_id == reminder.id)
+        })
+        .map(|other| other.id)
+        .collect();
+
+    let removed = match action.as_str() {
+        "archive" => store.archive(&reminder.id, &auth.0.id).await?,
+        _ => store.delete(&reminder.id, &auth.0.id).await?,
+    };
+    if !removed {
+        bot.send_message(dialogue.chat_id(), t!(language, "delete-gone"))
+            .await?;
+        return Ok(());
+    }
+
+    if let Err(err) = scheduler
+        .cancel_reminder(&ScheduledReminder { id: reminder.id })
+        .await
+    {
+        log::debug!("Reminder {} was not scheduled: {err}", reminder.id);
+    }
+
+    // Dependents lost their predecessor and would otherwise wait for it forever.
+    for id in dependents {
+        if let Some(dependent) = store.get(&id, &auth.0.id).await? {
+            reschedule(scheduler.as_ref(), dependent).await?;
+        }
+    }
+
+    let reply = match action.as_str() {
+        "archive" => t!(language, "delete-archived"),
+        _ => t!(language, "delete-done"),
+    };
+    bot.send_message(dialogue.chat_id(), reply).await?;
+
+    Ok(())
+}
+
+pub(super) fn schema() -> UpdateHandler<anyhow::Error> {
+    dptree::entry()
+        .branch(case![AuthenticatedActionState::Idle].branch(
+            Update::filter_message().branch(
+                filter_command::<DeleteReminderCommand, _>().branch(
+                    case![DeleteReminderCommand::Delete(id)].endpoint(delete_reminder_start),
+                ),
+            ),
+        ))
+        .branch(
+            case![AuthenticatedActionState::DeletingReminder(x)].branch(
+                Update::filter_callback_query().branch(
+                    case![DeletingReminderState::WaitingForConfirmation(reminder)]
+                        .endpoint(handle_confirmation),
+                ),
+            ),
+        )
+}
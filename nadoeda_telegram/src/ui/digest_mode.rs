@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use dptree::case;
+use nadoeda_storage::DynUserInfoStorage;
+use teloxide::{dispatching::UpdateHandler, filter_command, prelude::*};
+
+use crate::i18n::t;
+
+use super::{AuthenticatedActionState, AuthenticationInfo, GlobalCommand, HandlerResult};
+
+async fn toggle_digest_mode(
+    msg: Message,
+    bot: Bot,
+    auth: AuthenticationInfo,
+    user_store: Arc<DynUserInfoStorage>,
+) -> HandlerResult {
+    let Some(mut user) = user_store.get(&auth.0.id).await? else {
+        bot.send_message(msg.chat.id, t!(auth.0.language, "auth-register-first"))
+            .await?;
+        return Ok(());
+    };
+
+    user.digest_mode = !user.digest_mode;
+    let user = user_store.update(user).await?;
+
+    let reply = if user.digest_mode {
+        t!(user.language, "digest-on")
+    } else {
+        t!(user.language, "digest-off")
+    };
+    bot.send_message(msg.chat.id, reply).await?;
+
+    Ok(())
+}
+
+pub(super) fn schema() -> UpdateHandler<anyhow::Error> {
+    case![AuthenticatedActionState::Idle].branch(
+        Update::filter_message().branch(
+            filter_command::<GlobalCommand, _>()
+                .branch(case![GlobalCommand::Digest].endpoint(toggle_digest_mode)),
+        ),
+    )
+}
@@ -2,16 +2,23 @@ use std::sync::Arc;
 
 use chrono::NaiveTime;
 use dptree::case;
-use nadoeda_models::user::User;
-use nadoeda_storage::{ReminderStorage, sqlite::reminder_storage::SqliteReminderStorage};
+use nadoeda_models::chrono_tz::Tz;
+use nadoeda_models::user::{Language, User};
+use nadoeda_storage::{DynExclusionDateStorage, DynReminderStorage, ReminderStorageError};
 use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup, ParseMode};
 use teloxide::utils::markdown;
 use teloxide::{dispatching::UpdateHandler, macros::BotCommands};
 use teloxide::{filter_command, prelude::*};
 
-use nadoeda_models::reminder::{Reminder, ReminderFireTime, ReminderId};
+use nadoeda_models::reminder::{ConfirmationChallenge, Reminder, ReminderId, ReminderVerification};
 
-use super::util::{clear_message_buttons, try_get_message_from_query};
+use crate::i18n::{t, translate};
+
+use super::delete_reminder::ask_for_confirmation;
+use super::exclusion_dates::format_upcoming_exclusions;
+use super::util::{
+    clear_message_buttons, local_fire_time, parse_daily_schedule, try_get_message_from_query,
+};
 use super::{AuthenticatedActionState, AuthenticatedDialogue, AuthenticationInfo};
 
 use super::{GlobalCommand, HandlerResult};
@@ -23,6 +30,10 @@ pub(super) enum EditingRemindersState {
     WaitingForFieldSelection(Arc<Reminder>),
     WaitingForText(Arc<Reminder>),
     WaitingForTime(Arc<Reminder>),
+    WaitingForVerification(Arc<Reminder>),
+    WaitingForKeyword(Arc<Reminder>),
+    WaitingForChallenge(Arc<Reminder>),
+    WaitingForChecklist(Arc<Reminder>),
 }
 
 #[derive(BotCommands, Clone)]
@@ -36,14 +47,15 @@ enum EditReminderCommand {
 }
 
 async fn list_reminders(
-    storage: Arc<SqliteReminderStorage>,
+    storage: Arc<DynReminderStorage>,
+    exclusion_store: Arc<DynExclusionDateStorage>,
     bot: Bot,
     auth: AuthenticationInfo,
     msg: Message,
 ) -> HandlerResult {
     let reminders = storage.get_all_user_reminders(&auth.0.id).await?;
-    let message = if reminders.is_empty() {
-        "You have to create at least one reminder\\!".to_string()
+    let mut message = if reminders.is_empty() {
+        t!(auth.0.language, "edit-no-reminders")
     } else {
         reminders
             .iter()
@@ -53,6 +65,12 @@ async fn list_reminders(
             .join("\n\n")
     };
 
+    let exclusions = format_upcoming_exclusions(&auth.0, exclusion_store.as_ref()).await?;
+    if !exclusions.is_empty() {
+        message.push_str("\n\n");
+        message.push_str(&exclusions);
+    }
+
     bot.send_message(msg.chat.id, message)
         .parse_mode(ParseMode::MarkdownV2)
         .await?;
@@ -60,22 +78,72 @@ async fn list_reminders(
     Ok(())
 }
 
+fn field_keyboard(language: Language) -> InlineKeyboardMarkup {
+    let button = |key, data| InlineKeyboardButton::callback(translate(language, key, None), data);
+    let text_button = button("edit-text-button", "text");
+    let time_button = button("edit-time-button", "time");
+    let proof_button = button("edit-proof-button", "proof");
+    let challenge_button = button("edit-challenge-button", "challenge");
+    let checklist_button = button("edit-checklist-button", "checklist");
+    let delete_button = button("edit-delete-button", "delete");
+    InlineKeyboardMarkup::new(vec![
+        vec![text_button, time_button],
+        vec![proof_button, challenge_button],
+        vec![checklist_button, delete_button],
+    ])
+}
+
+/// Saves the edited copy of the reminder and returns whether it worked. When the reminder was
+/// changed since the dialog loaded it, the fresh one is loaded and the user picks the field again.
+async fn save_edited(
+    bot: &Bot,
+    dialogue: &AuthenticatedDialogue,
+    store: &DynReminderStorage,
+    auth: &AuthenticationInfo,
+    reminder: Reminder,
+) -> anyhow::Result<bool> {
+    let id = match store.update(reminder, &auth.0.id).await {
+        Ok(_) => return Ok(true),
+        Err(ReminderStorageError::Conflict(id)) => id,
+        Err(err) => return Err(err.into()),
+    };
+
+    let language = auth.0.language;
+    match store.get(&id, &auth.0.id).await? {
+        Some(reminder) => {
+            bot.send_message(dialogue.chat_id(), t!(language, "edit-conflict"))
+                .reply_markup(field_keyboard(language))
+                .await?;
+            dialogue
+                .update(AuthenticatedActionState::EditingReminder(
+                    EditingRemindersState::WaitingForFieldSelection(Arc::new(reminder)),
+                ))
+                .await?;
+        }
+        None => {
+            bot.send_message(dialogue.chat_id(), t!(language, "edit-gone"))
+                .await?;
+            dialogue.exit().await?;
+        }
+    }
+
+    Ok(false)
+}
+
 async fn edit_reminder(
     id: ReminderId,
     msg: Message,
     dialogue: AuthenticatedDialogue,
     auth: AuthenticationInfo,
-    store: Arc<SqliteReminderStorage>,
+    store: Arc<DynReminderStorage>,
     bot: Bot,
 ) -> HandlerResult {
     let reminder = store.get(&id, &auth.0.id).await?;
-    if let Some(reminder) = reminder {
-        let text_button = InlineKeyboardButton::callback("Text", "text");
-        let time_button = InlineKeyboardButton::callback("Time", "time");
-        let keyboard = InlineKeyboardMarkup::new(vec![vec![text_button, time_button]]);
+    let language = auth.0.language;
 
-        bot.send_message(msg.chat.id, "What do you want to update?")
-            .reply_markup(keyboard)
+    if let Some(reminder) = reminder {
+        bot.send_message(msg.chat.id, t!(language, "edit-what"))
+            .reply_markup(field_keyboard(language))
             .await?;
 
         dialogue
@@ -84,11 +152,8 @@ async fn edit_reminder(
             ))
             .await?;
     } else {
-        bot.send_message(
-            msg.chat.id,
-            "Invalid Edit Reminder command. Please try again.",
-        )
-        .await?;
+        bot.send_message(msg.chat.id, t!(language, "edit-invalid-command"))
+            .await?;
     }
 
     Ok(())
@@ -96,11 +161,13 @@ async fn edit_reminder(
 
 async fn handle_selected_field(
     dialogue: AuthenticatedDialogue,
-    store: Arc<SqliteReminderStorage>,
     bot: Bot,
     query: CallbackQuery,
     reminder: Arc<Reminder>,
+    auth: AuthenticationInfo,
 ) -> HandlerResult {
+    let language = auth.0.language;
+    let button = |key, data| InlineKeyboardButton::callback(translate(language, key, None), data);
     let message = try_get_message_from_query(&query);
 
     match query.data.as_deref().unwrap_or("") {
@@ -108,7 +175,7 @@ async fn handle_selected_field(
             if let Some(message) = message {
                 clear_message_buttons(&bot, message).await?;
 
-                bot.send_message(dialogue.chat_id(), "Please enter reminder text.")
+                bot.send_message(dialogue.chat_id(), t!(language, "edit-ask-text"))
                     .await?;
 
                 dialogue
@@ -122,7 +189,7 @@ async fn handle_selected_field(
             if let Some(message) = message {
                 clear_message_buttons(&bot, message).await?;
 
-                bot.send_message(dialogue.chat_id(), "Please enter the time. Example: 13:00")
+                bot.send_message(dialogue.chat_id(), t!(language, "edit-ask-time"))
                     .await?;
 
                 dialogue
@@ -132,6 +199,79 @@ async fn handle_selected_field(
                     .await?;
             }
         }
+        "proof" => {
+            if let Some(message) = message {
+                clear_message_buttons(&bot, message).await?;
+
+                let keyboard = InlineKeyboardMarkup::new(vec![
+                    vec![
+                        button("edit-proof-none-button", "none"),
+                        button("edit-proof-photo-button", "photo"),
+                    ],
+                    vec![
+                        button("edit-proof-text-button", "text"),
+                        button("edit-proof-keyword-button", "keyword"),
+                    ],
+                ]);
+
+                bot.send_message(dialogue.chat_id(), t!(language, "edit-ask-proof"))
+                    .reply_markup(keyboard)
+                    .await?;
+
+                dialogue
+                    .update(AuthenticatedActionState::EditingReminder(
+                        EditingRemindersState::WaitingForVerification(reminder),
+                    ))
+                    .await?;
+            }
+        }
+        "challenge" => {
+            if let Some(message) = message {
+                clear_message_buttons(&bot, message).await?;
+
+                let keyboard = InlineKeyboardMarkup::new(vec![
+                    vec![
+                        button("edit-challenge-none-button", "none"),
+                        button("edit-challenge-math-button", "math"),
+                    ],
+                    vec![
+                        button("edit-challenge-word-button", "word"),
+                        button("edit-challenge-pick-button", "pick"),
+                    ],
+                ]);
+
+                bot.send_message(dialogue.chat_id(), t!(language, "edit-ask-challenge"))
+                    .reply_markup(keyboard)
+                    .await?;
+
+                dialogue
+                    .update(AuthenticatedActionState::EditingReminder(
+                        EditingRemindersState::WaitingForChallenge(reminder),
+                    ))
+                    .await?;
+            }
+        }
+        "checklist" => {
+            if let Some(message) = message {
+                clear_message_buttons(&bot, message).await?;
+
+                bot.send_message(dialogue.chat_id(), t!(language, "edit-ask-checklist"))
+                    .await?;
+
+                dialogue
+                    .update(AuthenticatedActionState::EditingReminder(
+                        EditingRemindersState::WaitingForChecklist(reminder),
+                    ))
+                    .await?;
+            }
+        }
+        "delete" => {
+            if let Some(message) = message {
+                clear_message_buttons(&bot, message).await?;
+
+                ask_for_confirmation(&bot, &dialogue, reminder, language).await?;
+            }
+        }
         _ => {}
     }
 
@@ -139,22 +279,154 @@ async fn handle_selected_field(
     Ok(())
 }
 
+async fn handle_selected_verification(
+    dialogue: AuthenticatedDialogue,
+    store: Arc<DynReminderStorage>,
+    bot: Bot,
+    query: CallbackQuery,
+    reminder: Arc<Reminder>,
+    auth: AuthenticationInfo,
+) -> HandlerResult {
+    let language = auth.0.language;
+    let verification = match query.data.as_deref().unwrap_or("") {
+        "none" => Some(ReminderVerification::None),
+        "photo" => Some(ReminderVerification::Photo),
+        "text" => Some(ReminderVerification::Text),
+        _ => None,
+    };
+
+    if let Some(message) = try_get_message_from_query(&query) {
+        clear_message_buttons(&bot, message).await?;
+    }
+    bot.answer_callback_query(query.id).await?;
+
+    match verification {
+        Some(verification) => {
+            let mut new_reminder = Reminder::clone(&reminder);
+            new_reminder.verification = verification;
+            if !save_edited(&bot, &dialogue, store.as_ref(), &auth, new_reminder).await? {
+                return Ok(());
+            }
+
+            bot.send_message(dialogue.chat_id(), t!(language, "edit-updated"))
+                .await?;
+            dialogue.exit().await?;
+        }
+        None => {
+            bot.send_message(dialogue.chat_id(), t!(language, "edit-ask-keyword"))
+                .await?;
+
+            dialogue
+                .update(AuthenticatedActionState::EditingReminder(
+                    EditingRemindersState::WaitingForKeyword(reminder),
+                ))
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn handle_selected_challenge(
+    dialogue: AuthenticatedDialogue,
+    store: Arc<DynReminderStorage>,
+    bot: Bot,
+    query: CallbackQuery,
+    reminder: Arc<Reminder>,
+    auth: AuthenticationInfo,
+) -> HandlerResult {
+    let challenge = match query.data.as_deref().unwrap_or("") {
+        "none" => Some(ConfirmationChallenge::None),
+        "math" => Some(ConfirmationChallenge::Arithmetic),
+        "word" => Some(ConfirmationChallenge::RetypeWord),
+        "pick" => Some(ConfirmationChallenge::PickOption),
+        _ => None,
+    };
+
+    bot.answer_callback_query(query.id.clone()).await?;
+
+    let Some(challenge) = challenge else {
+        return Ok(());
+    };
+
+    if let Some(message) = try_get_message_from_query(&query) {
+        clear_message_buttons(&bot, message).await?;
+    }
+
+    let mut new_reminder = Reminder::clone(&reminder);
+    new_reminder.challenge = challenge;
+    if !save_edited(&bot, &dialogue, store.as_ref(), &auth, new_reminder).await? {
+        return Ok(());
+    }
+
+    bot.send_message(dialogue.chat_id(), t!(auth.0.language, "edit-updated"))
+        .await?;
+    dialogue.exit().await?;
+
+    Ok(())
+}
+
+async fn save_reminder_keyword(
+    msg: Message,
+    bot: Bot,
+    reminder: Arc<Reminder>,
+    store: Arc<DynReminderStorage>,
+    auth: AuthenticationInfo,
+    dialogue: AuthenticatedDialogue,
+) -> HandlerResult {
+    let language = auth.0.language;
+
+    match msg.text().map(str::trim).filter(|text| !text.is_empty()) {
+        Some(keyword) => {
+            let mut new_reminder = Reminder::clone(&reminder);
+            new_reminder.verification = ReminderVerification::Keyword(keyword.to_string());
+            if !save_edited(&bot, &dialogue, store.as_ref(), &auth, new_reminder).await? {
+                return Ok(());
+            }
+
+            let message = t!(
+                language,
+                "edit-updated-keyword",
+                keyword = markdown::escape(keyword)
+            );
+
+            bot.send_message(msg.chat.id, message)
+                .parse_mode(ParseMode::MarkdownV2)
+                .await?;
+
+            dialogue.exit().await?;
+        }
+        None => {
+            bot.send_message(msg.chat.id, t!(language, "edit-ask-keyword-again"))
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
 async fn save_reminder_text(
     msg: Message,
     bot: Bot,
     reminder: Arc<Reminder>,
-    store: Arc<SqliteReminderStorage>,
+    store: Arc<DynReminderStorage>,
+    auth: AuthenticationInfo,
     dialogue: AuthenticatedDialogue,
 ) -> HandlerResult {
+    let language = auth.0.language;
+
     match msg.text() {
         Some(text) => {
             let mut new_reminder = Reminder::clone(&reminder);
             new_reminder.text = text.to_string();
-            store.update(new_reminder).await?;
+            if !save_edited(&bot, &dialogue, store.as_ref(), &auth, new_reminder).await? {
+                return Ok(());
+            }
 
-            let message = format!(
-                "Reminder updated, new text: *\"{}\"*",
-                teloxide::utils::markdown::escape(text)
+            let message = t!(
+                language,
+                "edit-updated-text",
+                text = teloxide::utils::markdown::escape(text)
             );
 
             bot.send_message(msg.chat.id, message)
@@ -164,7 +436,7 @@ async fn save_reminder_text(
             dialogue.exit().await?;
         }
         None => {
-            bot.send_message(msg.chat.id, "Please send me reminder text.")
+            bot.send_message(msg.chat.id, t!(language, "edit-ask-text"))
                 .await?;
         }
     }
@@ -172,29 +444,123 @@ async fn save_reminder_text(
     Ok(())
 }
 
+async fn save_reminder_checklist(
+    msg: Message,
+    bot: Bot,
+    reminder: Arc<Reminder>,
+    store: Arc<DynReminderStorage>,
+    auth: AuthenticationInfo,
+    dialogue: AuthenticatedDialogue,
+) -> HandlerResult {
+    let language = auth.0.language;
+
+    let Some(text) = msg.text() else {
+        bot.send_message(msg.chat.id, t!(language, "edit-ask-checklist-again"))
+            .await?;
+        return Ok(());
+    };
+
+    let items = if text.trim() == "-" {
+        Vec::new()
+    } else {
+        text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+
+    let updated = store.set_checklist(&reminder.id, &auth.0.id, items).await?;
+
+    let message = if updated.checklist.is_empty() {
+        t!(language, "edit-checklist-removed")
+    } else {
+        let items = updated
+            .checklist
+            .iter()
+            .map(|item| format!("⬜ {}", markdown::escape(&item.text)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        t!(language, "edit-updated-checklist", items = items)
+    };
+
+    bot.send_message(msg.chat.id, message)
+        .parse_mode(ParseMode::MarkdownV2)
+        .await?;
+
+    dialogue.exit().await?;
+
+    Ok(())
+}
+
+/// Parses a fire window like `14:00-17:00`, or a single time understood by [`parse_daily_schedule`].
+/// Edits keep the reminder repeating daily, so only the time of a one-off schedule is used.
+fn parse_fire_time(
+    text: &str,
+    timezone: Tz,
+    language: Language,
+) -> Result<(NaiveTime, Option<NaiveTime>), String> {
+    let parse = |time: &str| NaiveTime::parse_from_str(time.trim(), "%H:%M").ok();
+
+    let window = text
+        .split_once('-')
+        .and_then(|(start, end)| Some((parse(start)?, parse(end)?)));
+
+    match window {
+        Some((start, end)) if start != end => Ok((start, Some(end))),
+        Some(_) => Err(t!(language, "time-empty-window")),
+        None => Ok((parse_daily_schedule(text, timezone, language)?.time, None)),
+    }
+}
+
 async fn save_reminder_time(
     msg: Message,
     bot: Bot,
     reminder: Arc<Reminder>,
-    store: Arc<SqliteReminderStorage>,
+    store: Arc<DynReminderStorage>,
     auth: AuthenticationInfo,
     dialogue: AuthenticatedDialogue,
 ) -> HandlerResult {
-    match msg
-        .text()
-        .map(|text| NaiveTime::parse_from_str(text, "%H:%M"))
-    {
-        Some(Ok(time)) => {
+    let language = auth.0.language;
+
+    let Some(text) = msg.text() else {
+        bot.send_message(msg.chat.id, t!(language, "edit-ask-time"))
+            .await?;
+        return Ok(());
+    };
+
+    let timezone = auth.0.timezone;
+    let parsed = parse_fire_time(text, timezone, language).and_then(|(time, window_end)| {
+        let fire_at = local_fire_time(time, timezone, language)?;
+        let fire_window_end = window_end
+            .map(|end| local_fire_time(end, timezone, language))
+            .transpose()?;
+        Ok((time, window_end, fire_at, fire_window_end))
+    });
+
+    match parsed {
+        Ok((time, window_end, fire_at, fire_window_end)) => {
             let mut new_reminder = Reminder::clone(&reminder);
-            new_reminder.fire_at =
-                ReminderFireTime::new_utc_from_local(time, auth.0.timezone).unwrap();
+            new_reminder.fire_at = fire_at;
+            new_reminder.fire_window_end = fire_window_end;
 
-            store.update(new_reminder).await?;
+            if !save_edited(&bot, &dialogue, store.as_ref(), &auth, new_reminder).await? {
+                return Ok(());
+            }
 
-            let message = format!(
-                "Reminder updated, new time: *{}*",
-                teloxide::utils::markdown::escape(&time.format("%H:%M").to_string())
-            );
+            let message = match window_end {
+                Some(end) => t!(
+                    language,
+                    "edit-updated-window",
+                    start = time.format("%H:%M").to_string(),
+                    end = end.format("%H:%M").to_string()
+                ),
+                None => t!(
+                    language,
+                    "edit-updated-time",
+                    time = time.format("%H:%M").to_string()
+                ),
+            };
 
             bot.send_message(msg.chat.id, message)
                 .parse_mode(ParseMode::MarkdownV2)
@@ -202,8 +568,9 @@ async fn save_reminder_time(
 
             dialogue.exit().await?;
         }
-        _ => {
-            bot.send_message(msg.chat.id, "Please enter the time. Example: 13:00")
+        Err(reason) => {
+            bot.send_message(msg.chat.id, reason)
+                .parse_mode(ParseMode::MarkdownV2)
                 .await?;
         }
     }
@@ -213,12 +580,27 @@ async fn save_reminder_time(
 
 fn format_reminder(order: usize, reminder: &Reminder, user: &User) -> String {
     let adjusted_fire_time = reminder.fire_at.to_local_time(user.timezone);
-    format!(
-        "{order}: *{0}* \\(remind every day at *{1}*\\)
-Edit \\- /edit\\_{2}",
-        markdown::escape(&reminder.text),
-        adjusted_fire_time.format("%H:%M"),
-        reminder.id
+    let schedule = match &reminder.fire_window_end {
+        Some(end) => t!(
+            user.language,
+            "edit-reminder-between",
+            start = adjusted_fire_time.format("%H:%M").to_string(),
+            end = end.to_local_time(user.timezone).format("%H:%M").to_string()
+        ),
+        None => t!(
+            user.language,
+            "edit-reminder-at",
+            time = adjusted_fire_time.format("%H:%M").to_string()
+        ),
+    };
+
+    t!(
+        user.language,
+        "edit-reminder",
+        order = order.to_string(),
+        text = markdown::escape(&reminder.text),
+        schedule = schedule,
+        id = reminder.id.to_string()
     )
 }
 
@@ -240,10 +622,19 @@ pub(super) fn schema() -> UpdateHandler<anyhow::Error> {
         .branch(
             case![AuthenticatedActionState::EditingReminder(x)]
                 .branch(
-                    Update::filter_callback_query().branch(
-                        case![EditingRemindersState::WaitingForFieldSelection(rem)]
-                            .endpoint(handle_selected_field),
-                    ),
+                    Update::filter_callback_query()
+                        .branch(
+                            case![EditingRemindersState::WaitingForFieldSelection(rem)]
+                                .endpoint(handle_selected_field),
+                        )
+                        .branch(
+                            case![EditingRemindersState::WaitingForVerification(rem)]
+                                .endpoint(handle_selected_verification),
+                        )
+                        .branch(
+                            case![EditingRemindersState::WaitingForChallenge(rem)]
+                                .endpoint(handle_selected_challenge),
+                        ),
                 )
                 .branch(
                     Update::filter_message()
@@ -254,6 +645,14 @@ pub(super) fn schema() -> UpdateHandler<anyhow::Error> {
                         .branch(
                             case![EditingRemindersState::WaitingForTime(reminder)]
                                 .endpoint(save_reminder_time),
+                        )
+                        .branch(
+                            case![EditingRemindersState::WaitingForKeyword(reminder)]
+                                .endpoint(save_reminder_keyword),
+                        )
+                        .branch(
+                            case![EditingRemindersState::WaitingForChecklist(reminder)]
+                                .endpoint(save_reminder_checklist),
                         ),
                 ),
         )
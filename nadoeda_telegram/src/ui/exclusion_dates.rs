@@ -0,0 +1,202 @@
+use std::sync::Arc;
+
+use chrono::{NaiveDate, Utc};
+use dptree::case;
+use nadoeda_models::user::User;
+use nadoeda_storage::{DynExclusionDateStorage, NewExclusionDate, ics};
+use teloxide::utils::markdown;
+use teloxide::{dispatching::UpdateHandler, filter_command, macros::BotCommands, prelude::*};
+
+use crate::i18n::t;
+
+use super::util::download_calendar;
+use super::{AuthenticatedActionState, AuthenticatedDialogue, AuthenticationInfo, HandlerResult};
+
+const UPCOMING_EXCLUSIONS: u32 = 5;
+
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub(super) enum ExcludingDatesState {
+    #[default]
+    WaitingForDates,
+}
+
+#[derive(BotCommands, Clone)]
+#[command(rename_rule = "lowercase")]
+enum ExclusionCommand {
+    Exclude,
+    Include(String),
+}
+
+async fn exclude_dates_start(
+    msg: Message,
+    bot: Bot,
+    dialogue: AuthenticatedDialogue,
+    auth: AuthenticationInfo,
+) -> HandlerResult {
+    bot.send_message(msg.chat.id, t!(auth.0.language, "exclude-start"))
+        .await?;
+
+    dialogue
+        .update(AuthenticatedActionState::ExcludingDates(
+            ExcludingDatesState::WaitingForDates,
+        ))
+        .await?;
+
+    Ok(())
+}
+
+fn parse_dates(text: &str) -> Result<Vec<NewExclusionDate>, String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let (date, label) = line.split_once(' ').unwrap_or((line, ""));
+            let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| line.to_string())?;
+            let label = Some(label.trim().to_string()).filter(|label| !label.is_empty());
+
+            Ok(NewExclusionDate { date, label })
+        })
+        .collect()
+}
+
+async fn receive_dates(
+    msg: Message,
+    bot: Bot,
+    dialogue: AuthenticatedDialogue,
+    auth: AuthenticationInfo,
+    exclusion_store: Arc<DynExclusionDateStorage>,
+) -> HandlerResult {
+    let language = auth.0.language;
+
+    let dates = match (msg.document(), msg.text()) {
+        (Some(document), _) => {
+            let parsed = download_calendar(&bot, document, language)
+                .await?
+                .and_then(|text| {
+                    ics::parse_exclusion_dates(&text)
+                        .map_err(|err| t!(language, "calendar-unreadable", error = err.to_string()))
+                });
+
+            match parsed {
+                Ok(dates) => dates,
+                Err(reason) => {
+                    bot.send_message(msg.chat.id, reason).await?;
+                    return Ok(());
+                }
+            }
+        }
+        (None, Some(text)) => match parse_dates(text) {
+            Ok(dates) => dates,
+            Err(line) => {
+                bot.send_message(
+                    msg.chat.id,
+                    t!(language, "exclude-invalid-date", line = line),
+                )
+                .await?;
+                return Ok(());
+            }
+        },
+        (None, None) => {
+            bot.send_message(msg.chat.id, t!(language, "exclude-ask-dates"))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    if dates.is_empty() {
+        bot.send_message(msg.chat.id, t!(language, "exclude-no-dates"))
+            .await?;
+        return Ok(());
+    }
+
+    let total = dates.len();
+    let added = exclusion_store.add(&auth.0.id, dates).await?;
+
+    let reply = match total as u64 - added {
+        0 => t!(language, "exclude-added", added = added),
+        known => t!(
+            language,
+            "exclude-added-known",
+            added = added,
+            known = known
+        ),
+    };
+    bot.send_message(msg.chat.id, reply).await?;
+    dialogue.exit().await?;
+
+    Ok(())
+}
+
+async fn include_date(
+    argument: String,
+    msg: Message,
+    bot: Bot,
+    auth: AuthenticationInfo,
+    exclusion_store: Arc<DynExclusionDateStorage>,
+) -> HandlerResult {
+    let language = auth.0.language;
+
+    let Ok(date) = NaiveDate::parse_from_str(argument.trim(), "%Y-%m-%d") else {
+        bot.send_message(msg.chat.id, t!(language, "include-usage"))
+            .await?;
+        return Ok(());
+    };
+
+    let reply = if exclusion_store.remove(&auth.0.id, date).await? {
+        t!(language, "include-done", date = date.to_string())
+    } else {
+        t!(language, "include-not-skipped", date = date.to_string())
+    };
+    bot.send_message(msg.chat.id, reply).await?;
+
+    Ok(())
+}
+
+/// MarkdownV2 list of the next skipped days, empty when there are none.
+pub(super) async fn format_upcoming_exclusions(
+    user: &User,
+    exclusion_store: &DynExclusionDateStorage,
+) -> anyhow::Result<String> {
+    let today = Utc::now().with_timezone(&user.timezone).date_naive();
+    let upcoming = exclusion_store
+        .get_upcoming(&user.id, today, UPCOMING_EXCLUSIONS)
+        .await?;
+
+    if upcoming.is_empty() {
+        return Ok(String::new());
+    }
+
+    let lines = upcoming
+        .iter()
+        .map(|exclusion| match &exclusion.label {
+            Some(label) => format!(
+                "• *{}* {}",
+                markdown::escape(&exclusion.date.to_string()),
+                markdown::escape(label)
+            ),
+            None => format!("• *{}*", markdown::escape(&exclusion.date.to_string())),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Ok(t!(user.language, "exclude-upcoming", days = lines))
+}
+
+pub(super) fn schema() -> UpdateHandler<anyhow::Error> {
+    dptree::entry()
+        .branch(
+            case![AuthenticatedActionState::Idle].branch(
+                Update::filter_message().branch(
+                    filter_command::<ExclusionCommand, _>()
+                        .branch(case![ExclusionCommand::Exclude].endpoint(exclude_dates_start))
+                        .branch(case![ExclusionCommand::Include(argument)].endpoint(include_date)),
+                ),
+            ),
+        )
+        .branch(
+            case![AuthenticatedActionState::ExcludingDates(x)].branch(
+                Update::filter_message()
+                    .branch(case![ExcludingDatesState::WaitingForDates].endpoint(receive_dates)),
+            ),
+        )
+}
@@ -0,0 +1,255 @@
+use std::sync::Arc;
+
+use chrono::TimeDelta;
+use dptree::case;
+use nadoeda_models::reminder::{Reminder, ReminderDependency, ReminderId};
+use nadoeda_scheduler::{ReminderScheduler, ScheduleRequest, ScheduledReminder};
+use nadoeda_storage::{DynReminderStorage, ReminderStorageError};
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup, ParseMode};
+use teloxide::utils::markdown;
+use teloxide::{dispatching::UpdateHandler, macros::BotCommands};
+use teloxide::{filter_command, prelude::*};
+
+use crate::i18n::t;
+
+use super::util::{clear_message_buttons, try_get_message_from_query};
+use super::{AuthenticatedActionState, AuthenticatedDialogue, AuthenticationInfo, HandlerResult};
+
+const NO_PREDECESSOR: &str = "none";
+
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub(super) enum LinkingRemindersState {
+    #[default]
+    Start,
+    WaitingForPredecessor(Arc<Reminder>),
+    WaitingForDelay {
+        reminder: Arc<Reminder>,
+        predecessor_id: ReminderId,
+    },
+}
+
+#[derive(BotCommands, Clone)]
+#[command(
+    rename_rule = "lowercase",
+    parse_with = "split",
+    command_separator = "_"
+)]
+enum LinkRemindersCommand {
+    After(ReminderId),
+}
+
+async fn link_reminder_start(
+    id: ReminderId,
+    msg: Message,
+    dialogue: AuthenticatedDialogue,
+    auth: AuthenticationInfo,
+    store: Arc<DynReminderStorage>,
+    bot: Bot,
+) -> HandlerResult {
+    let language = auth.0.language;
+
+    let Some(reminder) = store.get(&id, &auth.0.id).await? else {
+        bot.send_message(msg.chat.id, t!(language, "link-invalid-command"))
+            .await?;
+        return Ok(());
+    };
+
+    let mut buttons: Vec<Vec<InlineKeyboardButton>> = store
+        .get_all_user_reminders(&auth.0.id)
+        .await?
+        .into_iter()
+        .filter(|other| other.id != reminder.id)
+        .map(|other| {
+            vec![InlineKeyboardButton::callback(
+                other.text,
+                other.id.to_string(),
+            )]
+        })
+        .collect();
+    buttons.push(vec![InlineKeyboardButton::callback(
+        t!(language, "link-none-button"),
+        NO_PREDECESSOR,
+    )]);
+
+    bot.send_message(
+        msg.chat.id,
+        t!(
+            language,
+            "link-ask-predecessor",
+            text = markdown::escape(&reminder.text)
+        ),
+    )
+    .parse_mode(ParseMode::MarkdownV2)
+    .reply_markup(InlineKeyboardMarkup::new(buttons))
+    .await?;
+
+    dialogue
+        .update(AuthenticatedActionState::LinkingReminders(
+            LinkingRemindersState::WaitingForPredecessor(Arc::new(reminder)),
+        ))
+        .await?;
+
+    Ok(())
+}
+
+/// Restarts the scheduled task so that it picks up the new dependency.
+pub(super) async fn reschedule(
+    scheduler: &dyn ReminderScheduler,
+    reminder: Reminder,
+) -> HandlerResult {
+    if let Err(err) = scheduler
+        .cancel_reminder(&ScheduledReminder { id: reminder.id })
+        .await
+    {
+        log::debug!("Reminder {} was not scheduled: {err}", reminder.id);
+    }
+
+    scheduler
+        .schedule_reminder(ScheduleRequest::new(reminder))
+        .await?;
+
+    Ok(())
+}
+
+async fn handle_selected_predecessor(
+    reminder: Arc<Reminder>,
+    query: CallbackQuery,
+    bot: Bot,
+    dialogue: AuthenticatedDialogue,
+    auth: AuthenticationInfo,
+    store: Arc<DynReminderStorage>,
+    scheduler: Arc<dyn ReminderScheduler>,
+) -> HandlerResult {
+    bot.answer_callback_query(query.id.clone()).await?;
+
+    let selection = query.data.as_deref().unwrap_or_default();
+    let predecessor_id = match selection.parse::<ReminderId>() {
+        Ok(predecessor_id) => Some(predecessor_id),
+        Err(_) if selection == NO_PREDECESSOR => None,
+        Err(_) => return Ok(()),
+    };
+
+    if let Some(message) = try_get_message_from_query(&query) {
+        clear_message_buttons(&bot, message).await?;
+    }
+
+    match predecessor_id {
+        Some(predecessor_id) => {
+            bot.send_message(dialogue.chat_id(), t!(auth.0.language, "link-ask-delay"))
+                .await?;
+
+            dialogue
+                .update(AuthenticatedActionState::LinkingReminders(
+                    LinkingRemindersState::WaitingForDelay {
+                        reminder,
+                        predecessor_id,
+                    },
+                ))
+                .await?;
+        }
+        None => {
+            let reminder = store.set_dependency(&reminder.id, &auth.0.id, None).await?;
+            reschedule(scheduler.as_ref(), reminder).await?;
+
+            bot.send_message(dialogue.chat_id(), t!(auth.0.language, "link-removed"))
+                .await?;
+            dialogue.exit().await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn receive_delay(
+    (reminder, predecessor_id): (Arc<Reminder>, ReminderId),
+    msg: Message,
+    bot: Bot,
+    dialogue: AuthenticatedDialogue,
+    auth: AuthenticationInfo,
+    store: Arc<DynReminderStorage>,
+    scheduler: Arc<dyn ReminderScheduler>,
+) -> HandlerResult {
+    let language = auth.0.language;
+
+    let Some(minutes) = msg.text().and_then(|text| text.trim().parse::<u32>().ok()) else {
+        bot.send_message(msg.chat.id, t!(language, "link-ask-delay-again"))
+            .await?;
+        return Ok(());
+    };
+
+    let dependency = ReminderDependency {
+        predecessor_id,
+        delay: TimeDelta::minutes(minutes as i64),
+    };
+
+    let reminder = match store
+        .set_dependency(&reminder.id, &auth.0.id, Some(dependency))
+        .await
+    {
+        Ok(reminder) => reminder,
+        Err(ReminderStorageError::DependencyCycle(..)) => {
+            bot.send_message(msg.chat.id, t!(language, "link-cycle"))
+                .await?;
+            dialogue.exit().await?;
+            return Ok(());
+        }
+        Err(ReminderStorageError::NotFound(_)) => {
+            bot.send_message(msg.chat.id, t!(language, "link-gone"))
+                .await?;
+            dialogue.exit().await?;
+            return Ok(());
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    let predecessor = store.get(&predecessor_id, &auth.0.id).await?;
+    let predecessor_text = predecessor.map(|p| p.text).unwrap_or_default();
+    let message = t!(
+        language,
+        "link-saved",
+        text = markdown::escape(&reminder.text),
+        minutes = minutes,
+        predecessor = markdown::escape(&predecessor_text)
+    );
+
+    reschedule(scheduler.as_ref(), reminder).await?;
+
+    bot.send_message(msg.chat.id, message)
+        .parse_mode(ParseMode::MarkdownV2)
+        .await?;
+    dialogue.exit().await?;
+
+    Ok(())
+}
+
+pub(super) fn schema() -> UpdateHandler<anyhow::Error> {
+    dptree::entry()
+        .branch(
+            case![AuthenticatedActionState::Idle].branch(
+                Update::filter_message().branch(
+                    filter_command::<LinkRemindersCommand, _>().branch(
+                        case![LinkRemindersCommand::After(id)].endpoint(link_reminder_start),
+                    ),
+                ),
+            ),
+        )
+        .branch(
+            case![AuthenticatedActionState::LinkingReminders(x)]
+                .branch(
+                    Update::filter_callback_query().branch(
+                        case![LinkingRemindersState::WaitingForPredecessor(reminder)]
+                            .endpoint(handle_selected_predecessor),
+                    ),
+                )
+                .branch(
+                    Update::filter_message().branch(
+                        case![LinkingRemindersState::WaitingForDelay {
+                            reminder,
+                            predecessor_id
+                        }]
+                        .endpoint(receive_delay),
+                    ),
+                ),
+        )
+}
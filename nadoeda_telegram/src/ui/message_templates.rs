@@ -0,0 +1,210 @@
+use std::sync::Arc;
+
+use dptree::case;
+use nadoeda_models::reminder::{Reminder, ReminderId};
+use nadoeda_models::template::TemplateKind;
+use nadoeda_storage::{DynMessageTemplateStorage, DynReminderStorage};
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup, ParseMode};
+use teloxide::utils::markdown;
+use teloxide::{dispatching::UpdateHandler, filter_command, macros::BotCommands, prelude::*};
+
+use crate::i18n::{t, translate};
+use crate::template::PLACEHOLDERS;
+
+use super::util::{clear_message_buttons, try_get_message_from_query};
+use super::{AuthenticatedActionState, AuthenticatedDialogue, AuthenticationInfo, HandlerResult};
+
+/// Sent instead of the texts to go back to the default one.
+const RESET: &str = "-";
+
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub(super) enum CustomizingTextsState {
+    #[default]
+    Start,
+    WaitingForKind(Arc<Reminder>),
+    WaitingForTexts {
+        reminder: Arc<Reminder>,
+        kind: TemplateKind,
+    },
+}
+
+#[derive(BotCommands, Clone)]
+#[command(
+    rename_rule = "lowercase",
+    parse_with = "split",
+    command_separator = "_"
+)]
+enum TemplatesCommand {
+    Texts(ReminderId),
+}
+
+fn kind_button_key(kind: TemplateKind) -> &'static str {
+    match kind {
+        TemplateKind::Fired => "texts-fired-button",
+        TemplateKind::Nag => "texts-nag-button",
+        TemplateKind::Confirmation => "texts-confirmation-button",
+        TemplateKind::Timeout => "texts-timeout-button",
+    }
+}
+
+async fn customize_texts_start(
+    id: ReminderId,
+    msg: Message,
+    bot: Bot,
+    dialogue: AuthenticatedDialogue,
+    auth: AuthenticationInfo,
+    store: Arc<DynReminderStorage>,
+) -> HandlerResult {
+    let language = auth.0.language;
+    let Some(reminder) = store.get(&id, &auth.0.id).await? else {
+        bot.send_message(msg.chat.id, t!(language, "texts-invalid-command"))
+            .await?;
+        return Ok(());
+    };
+
+    let buttons = TemplateKind::ALL.map(|kind| {
+        vec![InlineKeyboardButton::callback(
+            translate(language, kind_button_key(kind), None),
+            kind.code(),
+        )]
+    });
+
+    bot.send_message(
+        msg.chat.id,
+        t!(
+            language,
+            "texts-which",
+            text = markdown::escape(&reminder.text)
+        ),
+    )
+    .parse_mode(ParseMode::MarkdownV2)
+    .reply_markup(InlineKeyboardMarkup::new(buttons))
+    .await?;
+
+    dialogue
+        .update(AuthenticatedActionState::CustomizingTexts(
+            CustomizingTextsState::WaitingForKind(Arc::new(reminder)),
+        ))
+        .await?;
+
+    Ok(())
+}
+
+async fn handle_selected_kind(
+    reminder: Arc<Reminder>,
+    query: CallbackQuery,
+    bot: Bot,
+    dialogue: AuthenticatedDialogue,
+    auth: AuthenticationInfo,
+    template_store: Arc<DynMessageTemplateStorage>,
+) -> HandlerResult {
+    bot.answer_callback_query(query.id.clone()).await?;
+
+    let Some(kind) = query.data.as_deref().and_then(TemplateKind::from_code) else {
+        return Ok(());
+    };
+
+    if let Some(message) = try_get_message_from_query(&query) {
+        clear_message_buttons(&bot, message).await?;
+    }
+
+    let language = auth.0.language;
+    let placeholders = PLACEHOLDERS
+        .map(|placeholder| markdown::code_inline(&format!("{{{placeholder}}}")))
+        .join(", ");
+    let mut prompt = t!(language, "texts-ask", placeholders = placeholders);
+
+    let current: Vec<String> = template_store
+        .get_for_reminder(&reminder.id)
+        .await?
+        .into_iter()
+        .filter(|template| template.kind == kind)
+        .map(|template| markdown::escape(&template.text))
+        .collect();
+    if !current.is_empty() {
+        prompt.push_str("\n\n");
+        prompt.push_str(&t!(language, "texts-current", texts = current.join("\n")));
+    }
+
+    bot.send_message(dialogue.chat_id(), prompt)
+        .parse_mode(ParseMode::MarkdownV2)
+        .await?;
+
+    dialogue
+        .update(AuthenticatedActionState::CustomizingTexts(
+            CustomizingTextsState::WaitingForTexts { reminder, kind },
+        ))
+        .await?;
+
+    Ok(())
+}
+
+async fn receive_texts(
+    (reminder, kind): (Arc<Reminder>, TemplateKind),
+    msg: Message,
+    bot: Bot,
+    dialogue: AuthenticatedDialogue,
+    auth: AuthenticationInfo,
+    template_store: Arc<DynMessageTemplateStorage>,
+) -> HandlerResult {
+    let language = auth.0.language;
+    let Some(text) = msg.text().map(str::trim).filter(|text| !text.is_empty()) else {
+        bot.send_message(msg.chat.id, t!(language, "texts-empty"))
+            .await?;
+        return Ok(());
+    };
+
+    let texts: Vec<String> = match text {
+        RESET => Vec::new(),
+        text => text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect(),
+    };
+    let count = texts.len();
+
+    let reply = if !template_store
+        .set(&reminder.id, &auth.0.id, kind, texts)
+        .await?
+    {
+        t!(language, "texts-gone")
+    } else if count == 0 {
+        t!(language, "texts-reset")
+    } else {
+        t!(language, "texts-saved", count = count.to_string())
+    };
+
+    bot.send_message(msg.chat.id, reply).await?;
+    dialogue.exit().await?;
+
+    Ok(())
+}
+
+pub(super) fn schema() -> UpdateHandler<anyhow::Error> {
+    dptree::entry()
+        .branch(
+            case![AuthenticatedActionState::Idle].branch(
+                Update::filter_message()
+                    .branch(filter_command::<TemplatesCommand, _>().branch(
+                        case![TemplatesCommand::Texts(id)].endpoint(customize_texts_start),
+                    )),
+            ),
+        )
+        .branch(
+            case![AuthenticatedActionState::CustomizingTexts(x)]
+                .branch(
+                    Update::filter_callback_query().branch(
+                        case![CustomizingTextsState::WaitingForKind(reminder)]
+                            .endpoint(handle_selected_kind),
+                    ),
+                )
+                .branch(
+                    Update::filter_message().branch(
+                        case![CustomizingTextsState::WaitingForTexts { reminder, kind }]
+                            .endpoint(receive_texts),
+                    ),
+                ),
+        )
+}
@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use dptree::case;
+use nadoeda_models::reminder::ReminderId;
+use nadoeda_scheduler::{ReminderScheduler, ScheduleRequest, ScheduledReminder};
+use nadoeda_storage::{DynReminderStorage, NewReminder};
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup, ParseMode};
+use teloxide::utils::markdown;
+use teloxide::{dispatching::UpdateHandler, filter_command, macros::BotCommands, prelude::*};
+
+use crate::i18n::t;
+use crate::time_parser;
+
+use super::util::{clear_message_buttons, daily_schedule, try_get_message_from_query};
+use super::{AuthenticatedActionState, AuthenticatedDialogue, AuthenticationInfo, HandlerResult};
+
+const UNDO_PREFIX: &str = "remind_undo_";
+
+#[derive(BotCommands, Clone)]
+#[command(rename_rule = "lowercase")]
+enum QuickReminderCommand {
+    Remind(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct UndoReminder(ReminderId);
+
+impl UndoReminder {
+    fn parse(data: &str) -> Option<Self> {
+        Some(Self(data.strip_prefix(UNDO_PREFIX)?.parse().ok()?))
+    }
+
+    fn to_callback_data(self) -> String {
+        format!("{UNDO_PREFIX}{}", self.0)
+    }
+}
+
+async fn create_quick_reminder(
+    argument: String,
+    msg: Message,
+    bot: Bot,
+    auth: AuthenticationInfo,
+    store: Arc<DynReminderStorage>,
+    scheduler: Arc<dyn ReminderScheduler>,
+) -> HandlerResult {
+    let timezone = auth.0.timezone;
+    let language = auth.0.language;
+    let now = Utc::now().with_timezone(&timezone);
+
+    let Some((schedule, text)) =
+        time_parser::parse_schedule_prefix(&argument, now).filter(|(_, text)| !text.is_empty())
+    else {
+        bot.send_message(msg.chat.id, t!(language, "remind-usage"))
+            .await?;
+        return Ok(());
+    };
+
+    let schedule = match daily_schedule(&schedule, timezone, language) {
+        Ok(schedule) => schedule,
+        Err(reason) => {
+            bot.send_message(msg.chat.id, reason)
+                .parse_mode(ParseMode::MarkdownV2)
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let reminder = store
+        .insert(NewReminder {
+            text: text.to_string(),
+            fire_at: schedule.fire_at,
+            user_id: auth.0.id,
+        })
+        .await?;
+
+    log::info!("Created reminder with id {}", reminder.id);
+
+    let reminder_id = reminder.id;
+    let mut request = ScheduleRequest::new(reminder);
+    if let Some(not_before) = schedule.not_before(timezone) {
+        request = request.not_before(not_before);
+    }
+    scheduler.schedule_reminder(request).await?;
+
+    let keyboard = InlineKeyboardMarkup::new(vec![vec![InlineKeyboardButton::callback(
+        t!(language, "remind-undo-button"),
+        UndoReminder(reminder_id).to_callback_data(),
+    )]]);
+
+    bot.send_message(
+        msg.chat.id,
+        t!(
+            language,
+            "remind-set",
+            text = markdown::escape(text),
+            schedule = markdown::escape(&schedule.description)
+        ),
+    )
+    .parse_mode(ParseMode::MarkdownV2)
+    .reply_markup(keyboard)
+    .await?;
+
+    Ok(())
+}
+
+async fn undo_quick_reminder(
+    undo: UndoReminder,
+    query: CallbackQuery,
+    bot: Bot,
+    dialogue: AuthenticatedDialogue,
+    auth: AuthenticationInfo,
+    store: Arc<DynReminderStorage>,
+    scheduler: Arc<dyn ReminderScheduler>,
+) -> HandlerResult {
+    bot.answer_callback_query(query.id.clone()).await?;
+
+    if let Some(message) = try_get_message_from_query(&query) {
+        clear_message_buttons(&bot, message).await?;
+    }
+
+    // Deleting first checks that the reminder belongs to the user.
+    if !store.delete(&undo.0, &auth.0.id).await? {
+        bot.send_message(dialogue.chat_id(), t!(auth.0.language, "remind-gone"))
+            .await?;
+        return Ok(());
+    }
+
+    if let Err(err) = scheduler
+        .cancel_reminder(&ScheduledReminder { id: undo.0 })
+        .await
+    {
+        log::debug!("Reminder {} was not scheduled: {err}", undo.0);
+    }
+
+    bot.send_message(dialogue.chat_id(), t!(auth.0.language, "remind-removed"))
+        .await?;
+
+    Ok(())
+}
+
+pub(super) fn schema() -> UpdateHandler<anyhow::Error> {
+    dptree::entry()
+        .branch(
+            case![AuthenticatedActionState::Idle].branch(Update::filter_message().branch(
+                filter_command::<QuickReminderCommand, _>().branch(
+                    case![QuickReminderCommand::Remind(argument)].endpoint(create_quick_reminder),
+                ),
+            )),
+        )
+        .branch(
+            Update::filter_callback_query()
+                .filter_map(|query: CallbackQuery| {
+                    query.data.as_deref().and_then(UndoReminder::parse)
+                })
+                .endpoint(undo_quick_reminder),
+        )
+}
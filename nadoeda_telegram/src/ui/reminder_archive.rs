@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use dptree::case;
+use nadoeda_models::reminder::ReminderId;
+use nadoeda_scheduler::{ReminderScheduler, ScheduleRequest};
+use nadoeda_storage::DynReminderStorage;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup, ParseMode};
+use teloxide::utils::markdown;
+use teloxide::{dispatching::UpdateHandler, filter_command, prelude::*};
+
+use crate::i18n::t;
+
+use super::util::{clear_message_buttons, try_get_message_from_query};
+use super::{
+    AuthenticatedActionState, AuthenticatedDialogue, AuthenticationInfo, GlobalCommand,
+    HandlerResult,
+};
+
+const RESTORE_PREFIX: &str = "archive_restore_";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct RestoreReminder(ReminderId);
+
+impl RestoreReminder {
+    fn parse(data: &str) -> Option<Self> {
+        Some(Self(data.strip_prefix(RESTORE_PREFIX)?.parse().ok()?))
+    }
+
+    fn to_callback_data(self) -> String {
+        format!("{RESTORE_PREFIX}{}", self.0)
+    }
+}
+
+async fn show_archive(
+    msg: Message,
+    bot: Bot,
+    auth: AuthenticationInfo,
+    store: Arc<DynReminderStorage>,
+) -> HandlerResult {
+    let language = auth.0.language;
+    let reminders = store.get_archived_user_reminders(&auth.0.id).await?;
+    if reminders.is_empty() {
+        bot.send_message(msg.chat.id, t!(language, "archive-empty"))
+            .await?;
+        return Ok(());
+    }
+
+    let buttons = reminders.into_iter().map(|reminder| {
+        vec![InlineKeyboardButton::callback(
+            t!(language, "archive-restore-button", text = reminder.text),
+            RestoreReminder(reminder.id).to_callback_data(),
+        )]
+    });
+
+    bot.send_message(msg.chat.id, t!(language, "archive-title"))
+        .reply_markup(InlineKeyboardMarkup::new(buttons))
+        .await?;
+
+    Ok(())
+}
+
+async fn restore_reminder(
+    restore: RestoreReminder,
+    query: CallbackQuery,
+    bot: Bot,
+    dialogue: AuthenticatedDialogue,
+    auth: AuthenticationInfo,
+    store: Arc<DynReminderStorage>,
+    scheduler: Arc<dyn ReminderScheduler>,
+) -> HandlerResult {
+    bot.answer_callback_query(query.id.clone()).await?;
+
+    let language = auth.0.language;
+    if let Some(message) = try_get_message_from_query(&query) {
+        clear_message_buttons(&bot, message).await?;
+    }
+
+    let Some(reminder) = store.restore(&restore.0, &auth.0.id).await? else {
+        bot.send_message(dialogue.chat_id(), t!(language, "archive-gone"))
+            .await?;
+        return Ok(());
+    };
+
+    let text = markdown::escape(&reminder.text);
+    scheduler
+        .schedule_reminder(ScheduleRequest::new(reminder))
+        .await?;
+
+    bot.send_message(
+        dialogue.chat_id(),
+        t!(language, "archive-restored", text = text),
+    )
+    .parse_mode(ParseMode::MarkdownV2)
+    .await?;
+
+    Ok(())
+}
+
+pub(super) fn schema() -> UpdateHandler<anyhow::Error> {
+    case![AuthenticatedActionState::Idle]
+        .branch(
+            Update::filter_message().branch(
+                filter_command::<GlobalCommand, _>()
+                    .branch(case![GlobalCommand::Archive].endpoint(show_archive)),
+            ),
+        )
+        .branch(
+            Update::filter_callback_query()
+                .filter_map(|query: CallbackQuery| {
+                    query.data.as_deref().and_then(RestoreReminder::parse)
+                })
+                .endpoint(restore_reminder),
+        )
+}
@@ -0,0 +1,297 @@
+use std::sync::Arc;
+
+use dptree::case;
+use nadoeda_models::buddy::{BuddyInvitationStatus, BuddyTarget, ReminderBuddyId};
+use nadoeda_models::reminder::ReminderId;
+use nadoeda_models::user::Language;
+use nadoeda_storage::{
+    DynReminderBuddyStorage, DynReminderStorage, DynUserInfoStorage, NewReminderBuddy, StorageError,
+};
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup, ParseMode};
+use teloxide::utils::markdown;
+use teloxide::{dispatching::UpdateHandler, macros::BotCommands};
+use teloxide::{filter_command, prelude::*};
+
+use crate::i18n::t;
+
+use super::util::{clear_message_buttons, try_get_message_from_query};
+use super::{AuthenticatedActionState, AuthenticatedDialogue, AuthenticationInfo, HandlerResult};
+
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub(super) enum AssigningBuddyState {
+    #[default]
+    Start,
+    WaitingForBuddyChat(ReminderId),
+}
+
+#[derive(BotCommands, Clone)]
+#[command(
+    rename_rule = "lowercase",
+    parse_with = "split",
+    command_separator = "_"
+)]
+enum ReminderBuddyCommand {
+    Buddy(ReminderId),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct InvitationAnswer {
+    pub buddy_id: ReminderBuddyId,
+    pub accepted: bool,
+}
+
+const ACCEPT_PREFIX: &str = "buddy_accept_";
+const DECLINE_PREFIX: &str = "buddy_decline_";
+
+impl InvitationAnswer {
+    pub fn parse(data: &str) -> Option<Self> {
+        let (accepted, id) = if let Some(id) = data.strip_prefix(ACCEPT_PREFIX) {
+            (true, id)
+        } else {
+            (false, data.strip_prefix(DECLINE_PREFIX)?)
+        };
+
+        Some(Self {
+            buddy_id: id.parse().ok()?,
+            accepted,
+        })
+    }
+}
+
+async fn assign_buddy_start(
+    id: ReminderId,
+    msg: Message,
+    dialogue: AuthenticatedDialogue,
+    auth: AuthenticationInfo,
+    store: Arc<DynReminderStorage>,
+    bot: Bot,
+) -> HandlerResult {
+    let language = auth.0.language;
+
+    let Some(reminder) = store.get(&id, &auth.0.id).await? else {
+        bot.send_message(msg.chat.id, t!(language, "buddy-invalid-command"))
+            .await?;
+        return Ok(());
+    };
+
+    let message = t!(
+        language,
+        "buddy-ask",
+        text = markdown::escape(&reminder.text)
+    );
+
+    bot.send_message(msg.chat.id, message)
+        .parse_mode(ParseMode::MarkdownV2)
+        .await?;
+
+    dialogue
+        .update(AuthenticatedActionState::AssigningBuddy(
+            AssigningBuddyState::WaitingForBuddyChat(reminder.id),
+        ))
+        .await?;
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn receive_buddy_chat(
+    reminder_id: ReminderId,
+    msg: Message,
+    bot: Bot,
+    dialogue: AuthenticatedDialogue,
+    auth: AuthenticationInfo,
+    store: Arc<DynReminderStorage>,
+    user_store: Arc<DynUserInfoStorage>,
+    buddy_store: Arc<DynReminderBuddyStorage>,
+) -> HandlerResult {
+    let language = auth.0.language;
+
+    let chat_id = msg
+        .contact()
+        .and_then(|contact| contact.user_id)
+        .map(|user_id| user_id.0 as i64)
+        .or_else(|| msg.text().and_then(|text| text.trim().parse().ok()));
+
+    let Some(chat_id) = chat_id else {
+        bot.send_message(msg.chat.id, t!(language, "buddy-ask-chat"))
+            .await?;
+        return Ok(());
+    };
+
+    if chat_id == msg.chat.id.0 {
+        bot.send_message(msg.chat.id, t!(language, "buddy-self"))
+            .await?;
+        return Ok(());
+    }
+
+    let Some(reminder) = store.get(&reminder_id, &auth.0.id).await? else {
+        bot.send_message(msg.chat.id, t!(language, "buddy-gone"))
+            .await?;
+        dialogue.exit().await?;
+        return Ok(());
+    };
+
+    // Buddies without an account are addressed in English.
+    let (target, buddy_language) = match user_store.get_by_tg_chat(chat_id).await? {
+        Some(user) => (BuddyTarget::User(user.id), user.language),
+        None => (BuddyTarget::TelegramChat(chat_id), Language::default()),
+    };
+
+    let buddy = buddy_store
+        .invite(NewReminderBuddy {
+            reminder_id: reminder.id,
+            user_id: auth.0.id,
+            target,
+        })
+        .await?;
+
+    let inviter = msg
+        .from
+        .as_ref()
+        .map(|user| user.full_name())
+        .unwrap_or_else(|| t!(buddy_language, "buddy-someone"));
+
+    let invitation = t!(
+        buddy_language,
+        "buddy-invitation",
+        inviter = markdown::escape(&inviter),
+        text = markdown::escape(&reminder.text)
+    );
+
+    let keyboard = InlineKeyboardMarkup::new(vec![vec![
+        InlineKeyboardButton::callback(
+            t!(buddy_language, "buddy-accept-button"),
+            format!("{ACCEPT_PREFIX}{}", buddy.id),
+        ),
+        InlineKeyboardButton::callback(
+            t!(buddy_language, "buddy-decline-button"),
+            format!("{DECLINE_PREFIX}{}", buddy.id),
+        ),
+    ]]);
+
+    let sent = bot
+        .send_message(ChatId(chat_id), invitation)
+        .parse_mode(ParseMode::MarkdownV2)
+        .reply_markup(keyboard)
+        .await;
+
+    let reply = match sent {
+        Ok(_) => t!(language, "buddy-invited"),
+        Err(err) => {
+            log::warn!("Could not deliver buddy invitation {}: {err}", buddy.id);
+            t!(language, "buddy-unreachable")
+        }
+    };
+
+    bot.send_message(msg.chat.id, reply).await?;
+    dialogue.exit().await?;
+
+    Ok(())
+}
+
+async fn answer_invitation(
+    bot: Bot,
+    query: CallbackQuery,
+    answer: InvitationAnswer,
+    store: Arc<DynReminderStorage>,
+    user_store: Arc<DynUserInfoStorage>,
+    buddy_store: Arc<DynReminderBuddyStorage>,
+) -> HandlerResult {
+    bot.answer_callback_query(query.id.clone()).await?;
+
+    let Some(message) = try_get_message_from_query(&query) else {
+        return Ok(());
+    };
+
+    clear_message_buttons(&bot, message).await?;
+
+    // Buddies without an account are addressed in English.
+    let language = user_store
+        .get_by_tg_chat(message.chat.id.0)
+        .await?
+        .map(|user| user.language)
+        .unwrap_or_default();
+
+    let buddy = match buddy_store.get(&answer.buddy_id).await? {
+        Some(buddy) if buddy.status == BuddyInvitationStatus::Pending => buddy,
+        _ => {
+            bot.send_message(message.chat.id, t!(language, "buddy-invitation-invalid"))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let status = if answer.accepted {
+        BuddyInvitationStatus::Accepted
+    } else {
+        BuddyInvitationStatus::Declined
+    };
+    // Only the invited chat may answer, anyone else is told the invitation is invalid.
+    let buddy = match buddy_store
+        .set_status(&buddy.id, message.chat.id.0, status)
+        .await
+    {
+        Ok(buddy) => buddy,
+        Err(StorageError::NotFound) => {
+            bot.send_message(message.chat.id, t!(language, "buddy-invitation-invalid"))
+                .await?;
+            return Ok(());
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    let reply = if answer.accepted {
+        t!(language, "buddy-accepted")
+    } else {
+        t!(language, "buddy-declined")
+    };
+    bot.send_message(message.chat.id, reply).await?;
+
+    let owner = user_store.get(&buddy.user_id).await?;
+    let reminder = store.get(&buddy.reminder_id, &buddy.user_id).await?;
+
+    let owner = owner.and_then(|user| Some((user.tg_chat_id?, user.language)));
+
+    if let (Some((owner_chat_id, owner_language)), Some(reminder)) = (owner, reminder) {
+        let text = markdown::escape(&reminder.text);
+        let notification = if answer.accepted {
+            t!(owner_language, "buddy-owner-accepted", text = text)
+        } else {
+            t!(owner_language, "buddy-owner-declined", text = text)
+        };
+
+        bot.send_message(ChatId(owner_chat_id), notification)
+            .parse_mode(ParseMode::MarkdownV2)
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Handles invitation answers. Buddies may be in any dialogue state, or not registered at all.
+pub(super) fn invitation_schema() -> UpdateHandler<anyhow::Error> {
+    Update::filter_callback_query()
+        .filter_map(|query: CallbackQuery| query.data.as_deref().and_then(InvitationAnswer::parse))
+        .endpoint(answer_invitation)
+}
+
+pub(super) fn schema() -> UpdateHandler<anyhow::Error> {
+    dptree::entry()
+        .branch(
+            case![AuthenticatedActionState::Idle].branch(
+                Update::filter_message().branch(
+                    filter_command::<ReminderBuddyCommand, _>().branch(
+                        case![ReminderBuddyCommand::Buddy(id)].endpoint(assign_buddy_start),
+                    ),
+                ),
+            ),
+        )
+        .branch(
+            case![AuthenticatedActionState::AssigningBuddy(x)].branch(
+                Update::filter_message().branch(
+                    case![AssigningBuddyState::WaitingForBuddyChat(reminder_id)]
+                        .endpoint(receive_buddy_chat),
+                ),
+            ),
+        )
+}
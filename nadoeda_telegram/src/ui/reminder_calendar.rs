@@ -0,0 +1,220 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use dptree::case;
+use nadoeda_scheduler::{ReminderScheduler, ScheduleRequest};
+use nadoeda_storage::{DynReminderStorage, NewReminder, ics};
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup, InputFile, ParseMode};
+use teloxide::utils::markdown;
+use teloxide::{dispatching::UpdateHandler, filter_command, macros::BotCommands, prelude::*};
+
+use crate::i18n::t;
+
+use super::util::{clear_message_buttons, download_calendar, try_get_message_from_query};
+use super::{AuthenticatedActionState, AuthenticatedDialogue, AuthenticationInfo, HandlerResult};
+
+const CONFIRM_IMPORT: &str = "import_confirm";
+const CANCEL_IMPORT: &str = "import_cancel";
+
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub(super) enum ImportingRemindersState {
+    #[default]
+    WaitingForCalendar,
+    WaitingForConfirmation(Arc<Vec<NewReminder>>),
+}
+
+#[derive(BotCommands, Clone)]
+#[command(rename_rule = "lowercase")]
+enum CalendarCommand {
+    Export,
+    Import,
+}
+
+async fn export_reminders(
+    msg: Message,
+    bot: Bot,
+    auth: AuthenticationInfo,
+    store: Arc<DynReminderStorage>,
+) -> HandlerResult {
+    let reminders = store.get_all_user_reminders(&auth.0.id).await?;
+    if reminders.is_empty() {
+        bot.send_message(msg.chat.id, t!(auth.0.language, "calendar-export-empty"))
+            .await?;
+        return Ok(());
+    }
+
+    let calendar = ics::write_reminders(&reminders, Utc::now());
+    bot.send_document(
+        msg.chat.id,
+        InputFile::memory(calendar.into_bytes()).file_name("nadoeda.ics"),
+    )
+    .await?;
+
+    Ok(())
+}
+
+async fn import_reminders_start(
+    msg: Message,
+    bot: Bot,
+    dialogue: AuthenticatedDialogue,
+    auth: AuthenticationInfo,
+) -> HandlerResult {
+    bot.send_message(msg.chat.id, t!(auth.0.language, "calendar-import-start"))
+        .await?;
+
+    dialogue
+        .update(AuthenticatedActionState::ImportingReminders(
+            ImportingRemindersState::WaitingForCalendar,
+        ))
+        .await?;
+
+    Ok(())
+}
+
+async fn receive_calendar(
+    msg: Message,
+    bot: Bot,
+    dialogue: AuthenticatedDialogue,
+    auth: AuthenticationInfo,
+) -> HandlerResult {
+    let language = auth.0.language;
+
+    let Some(document) = msg.document() else {
+        bot.send_message(msg.chat.id, t!(language, "calendar-ask-file"))
+            .await?;
+        return Ok(());
+    };
+
+    let parsed = download_calendar(&bot, document, language)
+        .await?
+        .and_then(|text| {
+            ics::parse_reminders(&text, auth.0.id, auth.0.timezone)
+                .map_err(|err| t!(language, "calendar-unreadable", error = err.to_string()))
+        });
+
+    let import = match parsed {
+        Ok(import) if !import.reminders.is_empty() => import,
+        Ok(_) => {
+            bot.send_message(msg.chat.id, t!(language, "calendar-no-reminders"))
+                .await?;
+            return Ok(());
+        }
+        Err(reason) => {
+            bot.send_message(msg.chat.id, reason).await?;
+            return Ok(());
+        }
+    };
+
+    let mut preview = import
+        .reminders
+        .iter()
+        .map(|reminder| {
+            format!(
+                "• *{}* {}",
+                reminder
+                    .fire_at
+                    .to_local_time(auth.0.timezone)
+                    .format("%H:%M"),
+                markdown::escape(&reminder.text)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if import.skipped > 0 {
+        preview.push_str("\n\n");
+        preview.push_str(&t!(language, "calendar-skipped", count = import.skipped));
+    }
+
+    let keyboard = InlineKeyboardMarkup::new(vec![vec![
+        InlineKeyboardButton::callback(t!(language, "calendar-import-button"), CONFIRM_IMPORT),
+        InlineKeyboardButton::callback(t!(language, "calendar-cancel-button"), CANCEL_IMPORT),
+    ]]);
+
+    bot.send_message(
+        msg.chat.id,
+        t!(language, "calendar-preview", preview = preview),
+    )
+    .parse_mode(ParseMode::MarkdownV2)
+    .reply_markup(keyboard)
+    .await?;
+
+    dialogue
+        .update(AuthenticatedActionState::ImportingReminders(
+            ImportingRemindersState::WaitingForConfirmation(Arc::new(import.reminders)),
+        ))
+        .await?;
+
+    Ok(())
+}
+
+async fn confirm_import(
+    reminders: Arc<Vec<NewReminder>>,
+    query: CallbackQuery,
+    bot: Bot,
+    dialogue: AuthenticatedDialogue,
+    auth: AuthenticationInfo,
+    store: Arc<DynReminderStorage>,
+    scheduler: Arc<dyn ReminderScheduler>,
+) -> HandlerResult {
+    let language = auth.0.language;
+
+    bot.answer_callback_query(query.id.clone()).await?;
+
+    if let Some(message) = try_get_message_from_query(&query) {
+        clear_message_buttons(&bot, message).await?;
+    }
+
+    if query.data.as_deref() != Some(CONFIRM_IMPORT) {
+        bot.send_message(
+            dialogue.chat_id(),
+            t!(language, "calendar-import-cancelled"),
+        )
+        .await?;
+        dialogue.exit().await?;
+        return Ok(());
+    }
+
+    for new_reminder in reminders.iter().cloned() {
+        let reminder = store.insert(new_reminder).await?;
+        log::info!("Imported reminder with id {}", reminder.id);
+
+        scheduler
+            .schedule_reminder(ScheduleRequest::new(reminder))
+            .await?;
+    }
+
+    bot.send_message(
+        dialogue.chat_id(),
+        t!(language, "calendar-imported", count = reminders.len()),
+    )
+    .await?;
+    dialogue.exit().await?;
+
+    Ok(())
+}
+
+pub(super) fn schema() -> UpdateHandler<anyhow::Error> {
+    dptree::entry()
+        .branch(
+            case![AuthenticatedActionState::Idle].branch(
+                Update::filter_message().branch(
+                    filter_command::<CalendarCommand, _>()
+                        .branch(case![CalendarCommand::Export].endpoint(export_reminders))
+                        .branch(case![CalendarCommand::Import].endpoint(import_reminders_start)),
+                ),
+            ),
+        )
+        .branch(
+            case![AuthenticatedActionState::ImportingReminders(x)]
+                .branch(Update::filter_message().branch(
+                    case![ImportingRemindersState::WaitingForCalendar].endpoint(receive_calendar),
+                ))
+                .branch(
+                    Update::filter_callback_query().branch(
+                        case![ImportingRemindersState::WaitingForConfirmation(reminders)]
+                            .endpoint(confirm_import),
+                    ),
+                ),
+        )
+}
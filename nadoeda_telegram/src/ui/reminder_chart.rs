@@ -0,0 +1,173 @@
+mod render;
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use chrono::{TimeDelta, Utc};
+use dptree::case;
+use nadoeda_models::occurrence::{OccurrenceOutcome, ReminderOccurrence};
+use nadoeda_models::reminder::ReminderId;
+use nadoeda_models::stats::CompletionRate;
+use nadoeda_models::user::User;
+use nadoeda_storage::{DynReminderOccurrenceStorage, DynReminderStorage};
+use render::{HEATMAP_WEEKS, render_ack_times, render_heatmap};
+use teloxide::types::{InputFile, ParseMode};
+use teloxide::utils::markdown;
+use teloxide::{dispatching::UpdateHandler, macros::BotCommands};
+use teloxide::{filter_command, prelude::*};
+
+use crate::i18n::t;
+
+use super::{AuthenticatedActionState, AuthenticationInfo, GlobalCommand, HandlerResult};
+
+const ACK_CHART_OCCURRENCES: u32 = 30;
+
+#[derive(BotCommands, Clone)]
+#[command(
+    rename_rule = "lowercase",
+    parse_with = "split",
+    command_separator = "_"
+)]
+enum ReminderChartCommand {
+    Chart(ReminderId),
+}
+
+/// Groups finished occurrences by the day they were scheduled for, in the user's timezone.
+fn completion_by_day<'a>(
+    occurrences: impl IntoIterator<Item = &'a ReminderOccurrence>,
+    user: &User,
+) -> BTreeMap<chrono::NaiveDate, CompletionRate> {
+    let mut days: BTreeMap<_, CompletionRate> = BTreeMap::new();
+
+    for occurrence in occurrences {
+        let day = occurrence
+            .scheduled_at
+            .with_timezone(&user.timezone)
+            .date_naive();
+
+        match occurrence.outcome {
+            Some(OccurrenceOutcome::Confirmed) => {
+                let rate = days.entry(day).or_default();
+                rate.confirmed += 1;
+                rate.total += 1;
+            }
+            Some(OccurrenceOutcome::TimedOut) => days.entry(day).or_default().total += 1,
+            _ => {}
+        }
+    }
+
+    days
+}
+
+async fn send_heatmap<'a>(
+    bot: &Bot,
+    chat_id: ChatId,
+    user: &User,
+    occurrences: impl IntoIterator<Item = &'a ReminderOccurrence>,
+    caption: String,
+) -> HandlerResult {
+    let today = Utc::now().with_timezone(&user.timezone).date_naive();
+    let heatmap = render_heatmap(&completion_by_day(occurrences, user), today)?;
+
+    bot.send_photo(chat_id, InputFile::memory(heatmap).file_name("heatmap.png"))
+        .caption(caption)
+        .parse_mode(ParseMode::MarkdownV2)
+        .await?;
+
+    Ok(())
+}
+
+async fn chart_all_reminders(
+    msg: Message,
+    bot: Bot,
+    auth: AuthenticationInfo,
+    occurrence_store: Arc<DynReminderOccurrenceStorage>,
+) -> HandlerResult {
+    let now = Utc::now();
+    let occurrences = occurrence_store
+        .get_for_user_between(&auth.0.id, now - TimeDelta::weeks(HEATMAP_WEEKS), now)
+        .await?;
+
+    send_heatmap(
+        &bot,
+        msg.chat.id,
+        &auth.0,
+        &occurrences,
+        t!(auth.0.language, "chart-all-caption"),
+    )
+    .await
+}
+
+async fn chart_reminder(
+    id: ReminderId,
+    msg: Message,
+    bot: Bot,
+    auth: AuthenticationInfo,
+    store: Arc<DynReminderStorage>,
+    occurrence_store: Arc<DynReminderOccurrenceStorage>,
+) -> HandlerResult {
+    let language = auth.0.language;
+
+    let Some(reminder) = store.get(&id, &auth.0.id).await? else {
+        bot.send_message(msg.chat.id, t!(language, "chart-invalid-command"))
+            .await?;
+        return Ok(());
+    };
+
+    let now = Utc::now();
+    let occurrences = occurrence_store
+        .get_for_user_between(&auth.0.id, now - TimeDelta::weeks(HEATMAP_WEEKS), now)
+        .await?;
+
+    send_heatmap(
+        &bot,
+        msg.chat.id,
+        &auth.0,
+        occurrences.iter().filter(|o| o.reminder_id == reminder.id),
+        t!(
+            language,
+            "chart-caption",
+            text = markdown::escape(&reminder.text)
+        ),
+    )
+    .await?;
+
+    let mut recent = occurrence_store
+        .get_for_reminder(&reminder.id, &auth.0.id, ACK_CHART_OCCURRENCES)
+        .await?;
+    recent.reverse();
+
+    let ack_times: Vec<TimeDelta> = recent
+        .iter()
+        .filter_map(|occurrence| Some(occurrence.acknowledged_at? - occurrence.fired_at?))
+        .collect();
+
+    if ack_times.is_empty() {
+        bot.send_message(msg.chat.id, t!(language, "chart-no-acknowledgements"))
+            .await?;
+        return Ok(());
+    }
+
+    bot.send_photo(
+        msg.chat.id,
+        InputFile::memory(render_ack_times(&ack_times)?).file_name("ack_times.png"),
+    )
+    .caption(t!(language, "chart-ack-caption"))
+    .await?;
+
+    Ok(())
+}
+
+pub(super) fn schema() -> UpdateHandler<anyhow::Error> {
+    case![AuthenticatedActionState::Idle].branch(
+        Update::filter_message()
+            .branch(
+                filter_command::<GlobalCommand, _>()
+                    .branch(case![GlobalCommand::Chart].endpoint(chart_all_reminders)),
+            )
+            .branch(
+                filter_command::<ReminderChartCommand, _>()
+                    .branch(case![ReminderChartCommand::Chart(id)].endpoint(chart_reminder)),
+            ),
+    )
+}
@@ -0,0 +1,212 @@
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, NaiveDate, TimeDelta};
+use nadoeda_models::stats::CompletionRate;
+
+pub(crate) const HEATMAP_WEEKS: i64 = 26;
+
+const CELL_SIZE: u32 = 14;
+const CELL_GAP: u32 = 3;
+const MARGIN: u32 = 12;
+
+const BAR_WIDTH: u32 = 12;
+const BAR_GAP: u32 = 4;
+const BAR_CHART_HEIGHT: u32 = 200;
+
+type Color = [u8; 3];
+
+const BACKGROUND: Color = [255, 255, 255];
+const AXIS: Color = [140, 140, 140];
+const EMPTY: Color = [235, 237, 240];
+const MISSED: Color = [250, 180, 170];
+const LOW: Color = [198, 228, 139];
+const MEDIUM: Color = [123, 201, 111];
+const DONE: Color = [35, 154, 59];
+const BAR: Color = [64, 132, 214];
+
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: BACKGROUND.repeat((width * height) as usize),
+        }
+    }
+
+    fn fill_rect(&mut self, x: u32, y: u32, width: u32, height: u32, color: Color) {
+        for row in y..(y + height).min(self.height) {
+            for column in x..(x + width).min(self.width) {
+                let offset = ((row * self.width + column) * 3) as usize;
+                self.pixels[offset..offset + 3].copy_from_slice(&color);
+            }
+        }
+    }
+
+    fn encode(self) -> Result<Vec<u8>, png::EncodingError> {
+        let mut data = Vec::new();
+        let mut encoder = png::Encoder::new(&mut data, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        writer.finish()?;
+
+        Ok(data)
+    }
+}
+
+fn completion_color(rate: Option<&CompletionRate>) -> Color {
+    match rate.and_then(CompletionRate::percent) {
+        None => EMPTY,
+        Some(0) => MISSED,
+        Some(percent) if percent < 50 => LOW,
+        Some(percent) if percent < 100 => MEDIUM,
+        Some(_) => DONE,
+    }
+}
+
+/// Renders a contributions-like heatmap of the last weeks, one column per week starting on Monday.
+pub(crate) fn render_heatmap(
+    days: &BTreeMap<NaiveDate, CompletionRate>,
+    today: NaiveDate,
+) -> Result<Vec<u8>, png::EncodingError> {
+    let step = CELL_SIZE + CELL_GAP;
+    let width = MARGIN * 2 + step * HEATMAP_WEEKS as u32 - CELL_GAP;
+    let height = MARGIN * 2 + step * 7 - CELL_GAP;
+    let mut canvas = Canvas::new(width, height);
+
+    let this_monday = today - TimeDelta::days(today.weekday().num_days_from_monday() as i64);
+    let first_monday = this_monday - TimeDelta::weeks(HEATMAP_WEEKS - 1);
+
+    for week in 0..HEATMAP_WEEKS {
+        for weekday in 0..7 {
+            let date = first_monday + TimeDelta::days(week * 7 + weekday);
+            if date > today {
+                break;
+            }
+
+            canvas.fill_rect(
+                MARGIN + week as u32 * step,
+                MARGIN + weekday as u32 * step,
+                CELL_SIZE,
+                CELL_SIZE,
+                completion_color(days.get(&date)),
+            );
+        }
+    }
+
+    canvas.encode()
+}
+
+/// Renders a bar per occurrence, oldest first, with height relative to the slowest acknowledgement.
+pub(crate) fn render_ack_times(ack_times: &[TimeDelta]) -> Result<Vec<u8>, png::EncodingError> {
+    let step = BAR_WIDTH + BAR_GAP;
+    let width = MARGIN * 2 + (step * ack_times.len() as u32).max(step);
+    let height = MARGIN * 2 + BAR_CHART_HEIGHT;
+    let mut canvas = Canvas::new(width, height);
+
+    let slowest = ack_times
+        .iter()
+        .map(TimeDelta::num_seconds)
+        .max()
+        .unwrap_or_default()
+        .max(1);
+
+    for (i, ack_time) in ack_times.iter().enumerate() {
+        let seconds = ack_time.num_seconds().clamp(0, slowest);
+        let bar_height = ((seconds * BAR_CHART_HEIGHT as i64) / slowest).max(1) as u32;
+
+        canvas.fill_rect(
+            MARGIN + i as u32 * step + BAR_GAP / 2,
+            MARGIN + BAR_CHART_HEIGHT - bar_height,
+            BAR_WIDTH,
+            bar_height,
+            BAR,
+        );
+    }
+
+    canvas.fill_rect(
+        MARGIN,
+        MARGIN + BAR_CHART_HEIGHT,
+        width - MARGIN * 2,
+        1,
+        AXIS,
+    );
+
+    canvas.encode()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(data: &[u8]) -> (png::OutputInfo, Vec<u8>) {
+        let decoder = png::Decoder::new(data);
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        pixels.truncate(info.buffer_size());
+
+        (info, pixels)
+    }
+
+    fn pixel(info: &png::OutputInfo, pixels: &[u8], x: u32, y: u32) -> Color {
+        let offset = ((y * info.width + x) * 3) as usize;
+        [pixels[offset], pixels[offset + 1], pixels[offset + 2]]
+    }
+
+    #[test]
+    fn heatmap_colors_days_by_completion() {
+        // 2025-11-24 is a Monday, so it is the first row of the last column.
+        let today = NaiveDate::from_ymd_opt(2025, 11, 25).unwrap();
+        let monday = NaiveDate::from_ymd_opt(2025, 11, 24).unwrap();
+        let days = BTreeMap::from([
+            (
+                monday,
+                CompletionRate {
+                    confirmed: 0,
+                    total: 1,
+                },
+            ),
+            (
+                today,
+                CompletionRate {
+                    confirmed: 1,
+                    total: 1,
+                },
+            ),
+        ]);
+
+        let (info, pixels) = decode(&render_heatmap(&days, today).unwrap());
+        let last_column = MARGIN + (HEATMAP_WEEKS as u32 - 1) * (CELL_SIZE + CELL_GAP);
+        let second_row = MARGIN + CELL_SIZE + CELL_GAP;
+        let third_row = second_row + CELL_SIZE + CELL_GAP;
+
+        assert_eq!(pixel(&info, &pixels, last_column, MARGIN), MISSED);
+        assert_eq!(pixel(&info, &pixels, last_column, second_row), DONE);
+        assert_eq!(pixel(&info, &pixels, last_column, third_row), BACKGROUND);
+        assert_eq!(pixel(&info, &pixels, MARGIN, MARGIN), EMPTY);
+    }
+
+    #[test]
+    fn ack_time_bars_scale_to_slowest() {
+        let ack_times = [TimeDelta::seconds(30), TimeDelta::seconds(60)];
+
+        let (info, pixels) = decode(&render_ack_times(&ack_times).unwrap());
+        let bar_x = |i: u32| MARGIN + i * (BAR_WIDTH + BAR_GAP) + BAR_GAP / 2;
+        let top = MARGIN;
+        let middle = MARGIN + BAR_CHART_HEIGHT / 2;
+
+        assert_eq!(info.width, MARGIN * 2 + 2 * (BAR_WIDTH + BAR_GAP));
+        assert_eq!(pixel(&info, &pixels, bar_x(0), top), BACKGROUND);
+        assert_eq!(pixel(&info, &pixels, bar_x(0), middle), BAR);
+        assert_eq!(pixel(&info, &pixels, bar_x(1), top), BAR);
+    }
+}
@@ -0,0 +1,126 @@
+use std::sync::Arc;
+
+use chrono::{TimeDelta, Utc};
+use dptree::case;
+use nadoeda_models::reminder::{ConfirmationChallenge, Reminder};
+use nadoeda_models::stats::{CompletionRate, ReminderStats};
+use nadoeda_models::user::Language;
+use nadoeda_storage::{DynReminderStatsStorage, DynReminderStorage};
+use teloxide::types::ParseMode;
+use teloxide::utils::markdown;
+use teloxide::{dispatching::UpdateHandler, filter_command, prelude::*};
+
+use crate::i18n::t;
+
+use super::{AuthenticatedActionState, AuthenticationInfo, GlobalCommand, HandlerResult};
+
+async fn show_stats(
+    msg: Message,
+    bot: Bot,
+    auth: AuthenticationInfo,
+    store: Arc<DynReminderStorage>,
+    stats_store: Arc<DynReminderStatsStorage>,
+) -> HandlerResult {
+    let language = auth.0.language;
+
+    let reminders = store.get_all_user_reminders(&auth.0.id).await?;
+    if reminders.is_empty() {
+        bot.send_message(msg.chat.id, t!(language, "stats-no-reminders"))
+            .parse_mode(ParseMode::MarkdownV2)
+            .await?;
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    let mut sections = Vec::with_capacity(reminders.len());
+    for (i, reminder) in reminders.iter().enumerate() {
+        let stats = stats_store
+            .get_for_reminder(&reminder.id, &auth.0.id, now)
+            .await?;
+        sections.push(format_stats(i + 1, reminder, &stats, language));
+    }
+
+    bot.send_message(msg.chat.id, sections.join("\n\n"))
+        .parse_mode(ParseMode::MarkdownV2)
+        .await?;
+
+    Ok(())
+}
+
+fn format_stats(
+    order: usize,
+    reminder: &Reminder,
+    stats: &ReminderStats,
+    language: Language,
+) -> String {
+    let mut lines = vec![
+        format!("{order}: *{}*", markdown::escape(&reminder.text)),
+        t!(
+            language,
+            "stats-streak",
+            current = stats.current_streak.to_string(),
+            longest = stats.longest_streak.to_string()
+        ),
+        t!(
+            language,
+            "stats-done",
+            week = format_rate(&stats.last_7_days),
+            month = format_rate(&stats.last_30_days)
+        ),
+    ];
+
+    if let Some(time_to_ack) = stats.average_time_to_acknowledge {
+        lines.push(t!(
+            language,
+            "stats-time-to-acknowledge",
+            duration = format_duration(time_to_ack, language)
+        ));
+    }
+
+    if reminder.challenge != ConfirmationChallenge::None || stats.wrong_challenge_answers > 0 {
+        lines.push(t!(
+            language,
+            "stats-wrong-answers",
+            count = stats.wrong_challenge_answers.to_string()
+        ));
+    }
+
+    lines.join("\n")
+}
+
+fn format_rate(rate: &CompletionRate) -> String {
+    match rate.percent() {
+        Some(percent) => format!("{percent}% \\({}/{}\\)", rate.confirmed, rate.total),
+        None => "\\-".to_string(),
+    }
+}
+
+fn format_duration(duration: TimeDelta, language: Language) -> String {
+    let seconds = duration.num_seconds().max(0);
+    let (hours, minutes, seconds) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+
+    match (hours, minutes) {
+        (0, 0) => t!(language, "stats-seconds", seconds = seconds.to_string()),
+        (0, _) => t!(
+            language,
+            "stats-minutes",
+            minutes = minutes.to_string(),
+            seconds = seconds.to_string()
+        ),
+        _ => t!(
+            language,
+            "stats-hours",
+            hours = hours.to_string(),
+            minutes = minutes.to_string()
+        ),
+    }
+}
+
+pub(super) fn schema() -> UpdateHandler<anyhow::Error> {
+    case![AuthenticatedActionState::Idle].branch(
+        Update::filter_message().branch(
+            filter_command::<GlobalCommand, _>()
+                .branch(case![GlobalCommand::Stats].endpoint(show_stats)),
+        ),
+    )
+}
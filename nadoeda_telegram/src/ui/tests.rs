@@ -1,3 +1,18 @@
 mod authenticate_user_tests;
+mod confirm_reminder_tests;
 mod create_reminder_tests;
+mod daily_messages_tests;
+mod delete_reminder_tests;
+mod digest_mode_tests;
+mod edit_reminders_tests;
+mod exclusion_dates_tests;
+mod link_reminders_tests;
+mod message_templates_tests;
+mod quick_reminder_tests;
+mod reminder_archive_tests;
+mod reminder_buddy_tests;
+mod reminder_calendar_tests;
+mod reminder_chart_tests;
+mod reminder_stats_tests;
 mod test_utils;
+mod user_language_tests;
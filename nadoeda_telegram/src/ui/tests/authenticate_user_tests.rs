@@ -4,9 +4,11 @@ use crate::ui::{
 };
 
 use crate::ui::authenticate_user::schema;
-use nadoeda_models::{chrono_tz, user::User};
-use nadoeda_storage::{NewUser, UserInfoStorage};
-use sqlx::{Pool, Sqlite};
+use nadoeda_models::{
+    chrono_tz,
+    user::{Language, User},
+};
+use nadoeda_storage::{NewUser, Storages};
 use teloxide::{
     dispatching::{
         UpdateFilterExt,
@@ -15,12 +17,13 @@ use teloxide::{
     dptree::deps,
     types::Update,
 };
-use teloxide_tests::{MockBot, MockMessageText};
+use teloxide_tests::{MockBot, MockMessageText, MockUser};
 
-#[sqlx::test(migrations = "../nadoeda_storage/migrations")]
-async fn given_user_not_exist_should_ask_for_info(pool: Pool<Sqlite>) {
-    let storage = storage(pool.clone());
-    let user_storage = user_storage(pool.clone());
+#[tokio::test]
+async fn given_user_not_exist_should_ask_for_info() {
+    let storages = Storages::in_memory();
+    let storage = storages.reminders.clone();
+    let user_storage = storages.users.clone();
     let schema =
         dialogue::enter::<Update, InMemStorage<GlobalState>, GlobalState, _>().branch(schema());
     let mut bot = MockBot::new(MockMessageText::new().text("Random Text"), schema);
@@ -40,10 +43,11 @@ async fn given_user_not_exist_should_ask_for_info(pool: Pool<Sqlite>) {
     .await;
 }
 
-#[sqlx::test(migrations = "../nadoeda_storage/migrations")]
-async fn given_user_exists_should_not_ask_for_info(pool: Pool<Sqlite>) {
-    let storage = storage(pool.clone());
-    let user_storage = user_storage(pool.clone());
+#[tokio::test]
+async fn given_user_exists_should_not_ask_for_info() {
+    let storages = Storages::in_memory();
+    let storage = storages.reminders.clone();
+    let user_storage = storages.users.clone();
     let schema =
         dialogue::enter::<Update, InMemStorage<GlobalState>, GlobalState, _>().branch(schema());
     let mock_message = MockMessageText::new().text("Random Text");
@@ -53,6 +57,7 @@ async fn given_user_exists_should_not_ask_for_info(pool: Pool<Sqlite>) {
         .create(NewUser {
             timezone: chrono_tz::Tz::Europe__Prague,
             tg_chat_id: Some(chat_id.0),
+            language: Language::English,
         })
         .await
         .unwrap();
@@ -74,10 +79,11 @@ async fn given_user_exists_should_not_ask_for_info(pool: Pool<Sqlite>) {
     .await
 }
 
-#[sqlx::test(migrations = "../nadoeda_storage/migrations")]
-async fn given_provided_correct_timezone_should_set_authenticated_state(pool: Pool<Sqlite>) {
-    let storage = storage(pool.clone());
-    let user_storage = user_storage(pool.clone());
+#[tokio::test]
+async fn given_provided_correct_timezone_should_set_authenticated_state() {
+    let storages = Storages::in_memory();
+    let storage = storages.reminders.clone();
+    let user_storage = storages.users.clone();
     let schema =
         dialogue::enter::<Update, InMemStorage<GlobalState>, GlobalState, _>().branch(schema());
     let mock_message = MockMessageText::new().text("Europe/Prague");
@@ -101,18 +107,22 @@ async fn given_provided_correct_timezone_should_set_authenticated_state(pool: Po
             id: 1,
             timezone: chrono_tz::Tz::Europe__Prague,
             tg_chat_id: Some(chat_id.0),
+            language: Language::English,
+            digest_mode: false,
+            agenda_at: None,
+            summary_at: None,
         }),
         AuthenticatedActionState::Idle,
     ))
     .await;
 }
 
-#[sqlx::test(migrations = "../nadoeda_storage/migrations")]
-async fn given_unauthenticated_state_when_user_does_not_exist_should_not_pass_message_to_handlers(
-    pool: Pool<Sqlite>,
-) {
-    let storage = storage(pool.clone());
-    let user_storage = user_storage(pool.clone());
+#[tokio::test]
+async fn given_unauthenticated_state_when_user_does_not_exist_should_not_pass_message_to_handlers()
+{
+    let storages = Storages::in_memory();
+    let storage = storages.reminders.clone();
+    let user_storage = storages.users.clone();
     let marker = CallMarker::new();
     let schema = dialogue::enter::<Update, InMemStorage<GlobalState>, GlobalState, _>()
         .chain(schema())
@@ -137,12 +147,11 @@ async fn given_unauthenticated_state_when_user_does_not_exist_should_not_pass_me
     assert!(!marker.was_called());
 }
 
-#[sqlx::test(migrations = "../nadoeda_storage/migrations")]
-async fn given_unauthenticated_state_when_user_exists_should_pass_message_to_handlers(
-    pool: Pool<Sqlite>,
-) {
-    let storage = storage(pool.clone());
-    let user_storage = user_storage(pool.clone());
+#[tokio::test]
+async fn given_unauthenticated_state_when_user_exists_should_pass_message_to_handlers() {
+    let storages = Storages::in_memory();
+    let storage = storages.reminders.clone();
+    let user_storage = storages.users.clone();
     let marker = CallMarker::new();
     let schema = dialogue::enter::<Update, InMemStorage<GlobalState>, GlobalState, _>()
         .chain(schema())
@@ -154,6 +163,7 @@ async fn given_unauthenticated_state_when_user_exists_should_pass_message_to_han
         .create(NewUser {
             timezone: chrono_tz::Tz::Europe__Prague,
             tg_chat_id: Some(chat_id.0),
+            language: Language::English,
         })
         .await
         .expect("Error creating user");
@@ -175,3 +185,32 @@ async fn given_unauthenticated_state_when_user_exists_should_pass_message_to_han
 
     assert!(marker.was_called());
 }
+
+#[tokio::test]
+async fn given_telegram_language_should_register_user_with_it() {
+    let storages = Storages::in_memory();
+    let user_storage = storages.users.clone();
+    let schema =
+        dialogue::enter::<Update, InMemStorage<GlobalState>, GlobalState, _>().branch(schema());
+    let mock_message = MockMessageText::new()
+        .text("Europe/Prague")
+        .from(MockUser::new().language_code("ru-RU").build());
+    let chat_id = mock_message.chat.id;
+    let mut bot = MockBot::new(mock_message, schema);
+
+    bot.dependencies(deps![
+        user_storage.clone(),
+        InMemStorage::<GlobalState>::new()
+    ]);
+
+    bot.set_state(GlobalState::Authenticating(
+        AuthenticationState::WaitingForTimezone,
+    ))
+    .await;
+
+    bot.dispatch_and_check_last_text("Часовой пояс сохранён. Добро пожаловать!")
+        .await;
+
+    let user = user_storage.get_by_tg_chat(chat_id.0).await.unwrap();
+    assert_eq!(user.unwrap().language, Language::Russian);
+}
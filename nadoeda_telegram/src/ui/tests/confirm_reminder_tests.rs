@@ -0,0 +1,549 @@
+use std::sync::Arc;
+
+use nadoeda_models::chrono::{NaiveTime, Utc};
+use nadoeda_models::occurrence::{OccurrenceOutcome, ReminderOccurrence};
+use nadoeda_models::reminder::{
+    ConfirmationChallenge, Reminder, ReminderFireTime, ReminderVerification,
+};
+use nadoeda_models::{
+    chrono_tz,
+    user::{Language, User},
+};
+use nadoeda_scheduler::ReminderScheduler;
+use nadoeda_storage::{NewReminder, NewReminderOccurrence, NewUser, Storages};
+use teloxide::{
+    dispatching::{
+        UpdateHandler,
+        dialogue::{self, InMemStorage},
+    },
+    dptree::deps,
+};
+use teloxide_tests::{MockBot, MockCallbackQuery, MockMessageText};
+
+use crate::ui::confirm_reminder::{Challenge, schema};
+use crate::ui::*;
+
+use crate::ui::tests::test_utils::*;
+
+async fn create_reminder(
+    storages: &Storages,
+    chat_id: i64,
+    verification: ReminderVerification,
+) -> (User, Reminder) {
+    let user = storages
+        .users
+        .create(NewUser {
+            timezone: chrono_tz::Tz::Europe__Prague,
+            tg_chat_id: Some(chat_id),
+            language: Language::English,
+        })
+        .await
+        .unwrap();
+
+    let mut reminder = storages
+        .reminders
+        .insert(NewReminder {
+            text: "Water the plants".to_string(),
+            fire_at: ReminderFireTime::new(NaiveTime::from_hms_opt(12, 0, 0).unwrap()),
+            user_id: user.id,
+        })
+        .await
+        .unwrap();
+
+    reminder.verification = verification;
+    let reminder = storages.reminders.update(reminder, &user.id).await.unwrap();
+
+    (user, reminder)
+}
+
+async fn with_challenge(
+    storages: &Storages,
+    mut reminder: Reminder,
+    challenge: ConfirmationChallenge,
+) -> Reminder {
+    reminder.challenge = challenge;
+    let user_id = reminder.user_id;
+    storages.reminders.update(reminder, &user_id).await.unwrap()
+}
+
+/// The occurrence the scheduler is going through after the reminder was acknowledged.
+async fn acknowledged_occurrence(storages: &Storages, reminder: &Reminder) -> ReminderOccurrence {
+    let mut occurrence = storages
+        .occurrences
+        .insert(NewReminderOccurrence {
+            reminder_id: reminder.id,
+            user_id: reminder.user_id,
+            scheduled_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+
+    occurrence.fired_at = Some(Utc::now());
+    occurrence.acknowledged_at = Some(Utc::now());
+    storages
+        .occurrences
+        .update(occurrence, &reminder.user_id)
+        .await
+        .unwrap()
+}
+
+fn math_challenge() -> Challenge {
+    Challenge {
+        question: "How much is 2 + 3?".to_string(),
+        answer: "5".to_string(),
+        options: Vec::new(),
+    }
+}
+
+fn confirm_schema() -> UpdateHandler<anyhow::Error> {
+    dialogue::enter::<Update, InMemStorage<AuthenticatedActionState>, AuthenticatedActionState, _>()
+        .branch(schema())
+}
+
+fn dependencies(
+    bot: &mut MockBot<anyhow::Error, teloxide_tests::mock_bot::DistributionKey>,
+    storages: &Storages,
+    user: User,
+    scheduler: &RecordingReminderScheduler,
+) {
+    let scheduler: Arc<dyn ReminderScheduler> = Arc::new(scheduler.clone());
+    bot.dependencies(deps![
+        storages.reminders.clone(),
+        storages.evidence.clone(),
+        storages.occurrences.clone(),
+        storages.challenges.clone(),
+        scheduler,
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+}
+
+#[tokio::test]
+async fn given_no_verification_confirm_button_should_confirm_reminder() {
+    let storages = Storages::in_memory();
+    let query = MockCallbackQuery::new();
+    let (user, reminder) = create_reminder(
+        &storages,
+        query.from.id.0 as i64,
+        ReminderVerification::None,
+    )
+    .await;
+    let query = query.data(format!("confirm_{}", reminder.id));
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(query, confirm_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_state(AuthenticatedActionState::Idle)
+        .await;
+
+    assert_eq!(scheduler.calls(), vec![SchedulerCall::Confirm(reminder.id)]);
+}
+
+#[tokio::test]
+async fn given_acknowledge_button_should_acknowledge_reminder() {
+    let storages = Storages::in_memory();
+    let query = MockCallbackQuery::new();
+    let (user, reminder) = create_reminder(
+        &storages,
+        query.from.id.0 as i64,
+        ReminderVerification::Photo,
+    )
+    .await;
+    let query = query.data(format!("ack_{}", reminder.id));
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(query, confirm_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_state(AuthenticatedActionState::Idle)
+        .await;
+
+    assert_eq!(
+        scheduler.calls(),
+        vec![SchedulerCall::Acknowledge(reminder.id)]
+    );
+}
+
+#[tokio::test]
+async fn given_photo_verification_confirm_button_should_wait_for_evidence() {
+    let storages = Storages::in_memory();
+    let query = MockCallbackQuery::new();
+    let (user, reminder) = create_reminder(
+        &storages,
+        query.from.id.0 as i64,
+        ReminderVerification::Photo,
+    )
+    .await;
+    let query = query.data(format!("confirm_{}", reminder.id));
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(query, confirm_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_state(AuthenticatedActionState::ConfirmingReminder(
+        ConfirmingReminderState::WaitingForEvidence(Arc::new(reminder)),
+    ))
+    .await;
+
+    assert!(scheduler.calls().is_empty());
+}
+
+#[tokio::test]
+async fn given_correct_keyword_should_store_evidence_and_confirm() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("  Done ");
+    let (user, reminder) = create_reminder(
+        &storages,
+        message.chat.id.0,
+        ReminderVerification::Keyword("done".to_string()),
+    )
+    .await;
+    let occurrence = acknowledged_occurrence(&storages, &reminder).await;
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(message, confirm_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::ConfirmingReminder(
+        ConfirmingReminderState::WaitingForEvidence(Arc::new(reminder.clone())),
+    ))
+    .await;
+
+    bot.dispatch_and_check_last_text("Proof saved. Well done!")
+        .await;
+
+    let evidence = storages
+        .evidence
+        .get_for_occurrence(&occurrence.id, &user.id)
+        .await
+        .unwrap();
+
+    assert_eq!(evidence.len(), 1);
+    assert_eq!(evidence[0].text.as_deref(), Some("Done"));
+    assert_eq!(evidence[0].occurrence_id, Some(occurrence.id));
+    assert_eq!(scheduler.calls(), vec![SchedulerCall::Confirm(reminder.id)]);
+}
+
+#[tokio::test]
+async fn given_proof_after_timeout_should_not_store_evidence() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("done");
+    let (user, reminder) = create_reminder(
+        &storages,
+        message.chat.id.0,
+        ReminderVerification::Keyword("done".to_string()),
+    )
+    .await;
+    let mut occurrence = acknowledged_occurrence(&storages, &reminder).await;
+    occurrence.outcome = Some(OccurrenceOutcome::TimedOut);
+    let occurrence = storages
+        .occurrences
+        .update(occurrence, &user.id)
+        .await
+        .unwrap();
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(message, confirm_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::ConfirmingReminder(
+        ConfirmingReminderState::WaitingForEvidence(Arc::new(reminder)),
+    ))
+    .await;
+
+    bot.dispatch_and_check_last_text(
+        "This reminder is not waiting for confirmation anymore, the proof was not saved.",
+    )
+    .await;
+
+    let evidence = storages
+        .evidence
+        .get_for_occurrence(&occurrence.id, &user.id)
+        .await
+        .unwrap();
+
+    assert!(evidence.is_empty());
+    assert!(scheduler.calls().is_empty());
+    assert!(
+        bot.try_get_state::<AuthenticatedActionState>()
+            .await
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn given_text_for_photo_verification_should_keep_waiting() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("I did it, trust me");
+    let (user, reminder) =
+        create_reminder(&storages, message.chat.id.0, ReminderVerification::Photo).await;
+    acknowledged_occurrence(&storages, &reminder).await;
+    let scheduler = RecordingReminderScheduler::default();
+    let state = AuthenticatedActionState::ConfirmingReminder(
+        ConfirmingReminderState::WaitingForEvidence(Arc::new(reminder)),
+    );
+
+    let mut bot = MockBot::new(message, confirm_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(state.clone()).await;
+
+    bot.dispatch_and_check_state(state).await;
+
+    assert!(scheduler.calls().is_empty());
+}
+
+#[tokio::test]
+async fn given_challenge_confirm_button_should_ask_question() {
+    let storages = Storages::in_memory();
+    let query = MockCallbackQuery::new();
+    let (user, reminder) = create_reminder(
+        &storages,
+        query.from.id.0 as i64,
+        ReminderVerification::None,
+    )
+    .await;
+    let reminder = with_challenge(&storages, reminder, ConfirmationChallenge::PickOption).await;
+    let query = query.data(format!("confirm_{}", reminder.id));
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(query, confirm_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch().await;
+
+    let state: AuthenticatedActionState = bot.get_state().await;
+    let AuthenticatedActionState::ConfirmingReminder(ConfirmingReminderState::SolvingChallenge {
+        reminder: state_reminder,
+        challenge,
+    }) = state
+    else {
+        panic!("Unexpected state {state:?}");
+    };
+
+    assert_eq!(*state_reminder, reminder);
+    assert_eq!(challenge.options.len(), 4);
+    assert!(scheduler.calls().is_empty());
+}
+
+#[tokio::test]
+async fn given_correct_challenge_answer_should_confirm_reminder() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text(" 5 ");
+    let (user, reminder) =
+        create_reminder(&storages, message.chat.id.0, ReminderVerification::None).await;
+    let reminder = with_challenge(&storages, reminder, ConfirmationChallenge::Arithmetic).await;
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(message, confirm_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::ConfirmingReminder(
+        ConfirmingReminderState::SolvingChallenge {
+            reminder: Arc::new(reminder.clone()),
+            challenge: math_challenge(),
+        },
+    ))
+    .await;
+
+    bot.dispatch_and_check_state(AuthenticatedActionState::Idle)
+        .await;
+
+    assert_eq!(scheduler.calls(), vec![SchedulerCall::Confirm(reminder.id)]);
+}
+
+#[tokio::test]
+async fn given_correct_challenge_answer_should_ask_for_proof() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("5");
+    let (user, reminder) =
+        create_reminder(&storages, message.chat.id.0, ReminderVerification::Photo).await;
+    let reminder = with_challenge(&storages, reminder, ConfirmationChallenge::Arithmetic).await;
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(message, confirm_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::ConfirmingReminder(
+        ConfirmingReminderState::SolvingChallenge {
+            reminder: Arc::new(reminder.clone()),
+            challenge: math_challenge(),
+        },
+    ))
+    .await;
+
+    bot.dispatch_and_check_state(AuthenticatedActionState::ConfirmingReminder(
+        ConfirmingReminderState::WaitingForEvidence(Arc::new(reminder)),
+    ))
+    .await;
+
+    assert!(scheduler.calls().is_empty());
+}
+
+#[tokio::test]
+async fn given_wrong_challenge_answer_should_not_confirm_and_count_it() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("6");
+    let (user, reminder) =
+        create_reminder(&storages, message.chat.id.0, ReminderVerification::None).await;
+    let reminder = with_challenge(&storages, reminder, ConfirmationChallenge::Arithmetic).await;
+    let scheduler = RecordingReminderScheduler::default();
+    let started_at = Utc::now();
+
+    let mut bot = MockBot::new(message, confirm_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::ConfirmingReminder(
+        ConfirmingReminderState::SolvingChallenge {
+            reminder: Arc::new(reminder.clone()),
+            challenge: math_challenge(),
+        },
+    ))
+    .await;
+
+    bot.dispatch().await;
+
+    let state: AuthenticatedActionState = bot.get_state().await;
+    assert!(matches!(
+        state,
+        AuthenticatedActionState::ConfirmingReminder(
+            ConfirmingReminderState::SolvingChallenge { .. }
+        )
+    ));
+
+    let wrong_answers = storages
+        .challenges
+        .count_wrong_answers(&reminder.id, &user.id, started_at)
+        .await
+        .unwrap();
+
+    assert_eq!(wrong_answers, 1);
+    assert!(scheduler.calls().is_empty());
+}
+
+async fn with_checklist(storages: &Storages, reminder: Reminder, items: &[&str]) -> Reminder {
+    storages
+        .reminders
+        .set_checklist(
+            &reminder.id,
+            &reminder.user_id,
+            items.iter().map(|item| item.to_string()).collect(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn given_unfinished_checklist_toggle_should_only_tick_item() {
+    let storages = Storages::in_memory();
+    let query = MockCallbackQuery::new();
+    let (user, reminder) = create_reminder(
+        &storages,
+        query.from.id.0 as i64,
+        ReminderVerification::None,
+    )
+    .await;
+    let reminder = with_checklist(&storages, reminder, &["Brush teeth", "Lock door"]).await;
+    let query = query.data(format!("check_{}", reminder.checklist[0].id));
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(query, confirm_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_state(AuthenticatedActionState::Idle)
+        .await;
+
+    let reminder = storages
+        .reminders
+        .get(&reminder.id, &user.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(reminder.checklist[0].checked);
+    assert!(!reminder.checklist[1].checked);
+    assert!(scheduler.calls().is_empty());
+}
+
+#[tokio::test]
+async fn given_last_checklist_item_toggle_should_acknowledge_reminder() {
+    let storages = Storages::in_memory();
+    let query = MockCallbackQuery::new();
+    let (user, reminder) = create_reminder(
+        &storages,
+        query.from.id.0 as i64,
+        ReminderVerification::None,
+    )
+    .await;
+    let reminder = with_checklist(&storages, reminder, &["Brush teeth", "Lock door"]).await;
+    storages
+        .reminders
+        .toggle_checklist_item(&reminder.checklist[0].id, &user.id)
+        .await
+        .unwrap();
+    let query = query.data(format!("check_{}", reminder.checklist[1].id));
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(query, confirm_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_state(AuthenticatedActionState::Idle)
+        .await;
+
+    assert_eq!(
+        scheduler.calls(),
+        vec![SchedulerCall::Acknowledge(reminder.id)]
+    );
+}
+
+#[tokio::test]
+async fn given_unfinished_checklist_confirm_button_should_not_confirm() {
+    let storages = Storages::in_memory();
+    let query = MockCallbackQuery::new();
+    let (user, reminder) = create_reminder(
+        &storages,
+        query.from.id.0 as i64,
+        ReminderVerification::None,
+    )
+    .await;
+    let reminder = with_checklist(&storages, reminder, &["Brush teeth", "Lock door"]).await;
+    let query = query.data(format!("confirm_{}", reminder.id));
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(query, confirm_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_last_text("Tick every item on the checklist first.")
+        .await;
+
+    assert!(scheduler.calls().is_empty());
+}
+
+#[tokio::test]
+async fn given_new_checklist_should_replace_items_in_order() {
+    let storages = Storages::in_memory();
+    let (user, reminder) = create_reminder(&storages, 1, ReminderVerification::None).await;
+    let reminder = with_checklist(&storages, reminder, &["Brush teeth", "Lock door"]).await;
+    let store = storages.reminders.clone();
+    store
+        .toggle_checklist_item(&reminder.checklist[0].id, &user.id)
+        .await
+        .unwrap();
+
+    let reminder = with_checklist(&storages, reminder, &["Set alarm", "Brush teeth"]).await;
+    let items = reminder
+        .checklist
+        .iter()
+        .map(|item| (item.text.as_str(), item.checked))
+        .collect::<Vec<_>>();
+    assert_eq!(items, vec![("Set alarm", false), ("Brush teeth", false)]);
+
+    store
+        .toggle_checklist_item(&reminder.checklist[1].id, &user.id)
+        .await
+        .unwrap();
+    let reminder = store.reset_checklist(&reminder.id, &user.id).await.unwrap();
+    assert!(reminder.checklist.iter().all(|item| !item.checked));
+}
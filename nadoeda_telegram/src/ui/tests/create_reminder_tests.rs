@@ -1,7 +1,9 @@
-use nadoeda_models::{chrono_tz, user::User};
+use nadoeda_models::{
+    chrono_tz,
+    user::{Language, User},
+};
 use nadoeda_scheduler::ReminderScheduler;
-use nadoeda_storage::sqlite;
-use sqlx::{Pool, Sqlite};
+use nadoeda_storage::Storages;
 use std::sync::Arc;
 use teloxide::{
     dispatching::dialogue::{self, InMemStorage},
@@ -13,10 +15,11 @@ use crate::ui::{create_daily_reminder::schema, *};
 
 use crate::ui::tests::test_utils::*;
 
-#[sqlite::sqlx::test]
-async fn test(pool: Pool<Sqlite>) {
-    let reminder_storage = storage(pool.clone());
-    let user_storage = storage(pool.clone());
+#[tokio::test]
+async fn test() {
+    let storages = Storages::in_memory();
+    let reminder_storage = storages.reminders.clone();
+    let user_storage = storages.reminders.clone();
 
     let scheduler: Arc<dyn ReminderScheduler> = Arc::new(NoopReminderScheduler);
     let schema = dialogue::enter::<
@@ -36,7 +39,11 @@ async fn test(pool: Pool<Sqlite>) {
         AuthenticationInfo(User {
             id: 0,
             tg_chat_id: None,
+            language: Language::English,
             timezone: chrono_tz::Tz::Europe__Prague,
+            digest_mode: false,
+            agenda_at: None,
+            summary_at: None,
         }),
         AuthenticatedActionState::CreatingDailyReminder(
             CreatingDailyReminderState::WaitingForReminderText
@@ -55,3 +62,48 @@ async fn test(pool: Pool<Sqlite>) {
     ))
     .await;
 }
+
+#[tokio::test]
+async fn given_date_should_confirm_reminder_repeats_daily() {
+    let storages = Storages::in_memory();
+    let scheduler: Arc<dyn ReminderScheduler> = Arc::new(NoopReminderScheduler);
+    let schema = dialogue::enter::<
+        Update,
+        InMemStorage<AuthenticatedActionState>,
+        AuthenticatedActionState,
+        _,
+    >()
+    .branch(schema());
+    let mut bot = MockBot::new(MockMessageText::new().text("tomorrow 9am"), schema);
+
+    bot.dependencies(deps![
+        storages.reminders.clone(),
+        scheduler,
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(User {
+            id: 0,
+            tg_chat_id: None,
+            language: Language::English,
+            timezone: chrono_tz::Tz::Europe__Prague,
+            digest_mode: false,
+            agenda_at: None,
+            summary_at: None,
+        })
+    ]);
+
+    bot.set_state(AuthenticatedActionState::CreatingDailyReminder(
+        CreatingDailyReminderState::WaitingForFiringTime {
+            text: "New Reminder".to_string(),
+        },
+    ))
+    .await;
+
+    bot.dispatch().await;
+
+    let responses = bot.get_responses();
+    let text = responses.sent_messages.last().unwrap().text().unwrap();
+    assert!(
+        text.starts_with("You will be reminded *\"tomorrow at 09:00, then every day\"*"),
+        "Unexpected confirmation: {text}"
+    );
+}
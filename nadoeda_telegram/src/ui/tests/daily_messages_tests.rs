@@ -0,0 +1,115 @@
+use chrono::NaiveTime;
+use nadoeda_models::chrono_tz;
+use nadoeda_models::user::Language;
+use nadoeda_storage::{NewUser, Storages};
+use teloxide::{
+    dispatching::{
+        UpdateHandler,
+        dialogue::{self, InMemStorage},
+    },
+    dptree::deps,
+};
+use teloxide_tests::{MockBot, MockMessageText};
+
+use crate::ui::daily_messages::schema;
+use crate::ui::*;
+
+fn daily_messages_schema() -> UpdateHandler<anyhow::Error> {
+    dialogue::enter::<Update, InMemStorage<AuthenticatedActionState>, AuthenticatedActionState, _>()
+        .branch(schema())
+}
+
+#[tokio::test]
+async fn given_agenda_time_should_store_it_in_utc() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("/agenda 07:30");
+    let user = storages
+        .users
+        .create(NewUser {
+            timezone: chrono_tz::Tz::Asia__Tokyo,
+            tg_chat_id: Some(message.chat.id.0),
+            language: Language::English,
+        })
+        .await
+        .unwrap();
+
+    let mut bot = MockBot::new(message, daily_messages_schema());
+    bot.dependencies(deps![
+        storages.users.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_last_text("You will get the daily agenda every day at *07:30*\\.")
+        .await;
+
+    let stored = storages.users.get(&user.id).await.unwrap().unwrap();
+    assert_eq!(
+        stored.agenda_at.map(|at| *at.time()),
+        NaiveTime::from_hms_opt(22, 30, 0)
+    );
+    assert_eq!(stored.summary_at, None);
+}
+
+#[tokio::test]
+async fn given_summary_off_should_disable_it() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("/summary 21:00");
+    let user = storages
+        .users
+        .create(NewUser {
+            timezone: chrono_tz::Tz::UTC,
+            tg_chat_id: Some(message.chat.id.0),
+            language: Language::English,
+        })
+        .await
+        .unwrap();
+
+    let mut bot = MockBot::new(message, daily_messages_schema());
+    bot.dependencies(deps![
+        storages.users.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch().await;
+    let stored = storages.users.get(&user.id).await.unwrap().unwrap();
+    assert!(stored.summary_at.is_some());
+
+    bot.update(MockMessageText::new().text("/summary off"));
+    bot.dispatch_and_check_last_text("You will not get the daily summary anymore\\.")
+        .await;
+
+    let stored = storages.users.get(&user.id).await.unwrap().unwrap();
+    assert_eq!(stored.summary_at, None);
+}
+
+#[tokio::test]
+async fn given_invalid_time_should_explain_usage() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("/agenda soon");
+    let user = storages
+        .users
+        .create(NewUser {
+            timezone: chrono_tz::Tz::UTC,
+            tg_chat_id: Some(message.chat.id.0),
+            language: Language::English,
+        })
+        .await
+        .unwrap();
+
+    let mut bot = MockBot::new(message, daily_messages_schema());
+    bot.dependencies(deps![
+        storages.users.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_last_text(
+        "Please use /agenda HH:MM to get it every day at that time or /agenda off to stop it.",
+    )
+    .await;
+}
@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use nadoeda_models::chrono::TimeDelta;
+use nadoeda_models::reminder::ReminderDependency;
+use nadoeda_storage::Storages;
+use teloxide::dispatching::{
+    UpdateHandler,
+    dialogue::{self, InMemStorage},
+};
+use teloxide_tests::{MockBot, MockCallbackQuery, MockMessageText};
+
+use crate::ui::delete_reminder::{DeletingReminderState, schema};
+use crate::ui::*;
+
+use crate::ui::tests::test_utils::*;
+
+fn delete_schema() -> UpdateHandler<anyhow::Error> {
+    dialogue::enter::<Update, InMemStorage<AuthenticatedActionState>, AuthenticatedActionState, _>()
+        .branch(schema())
+}
+
+#[tokio::test]
+async fn given_delete_command_should_ask_for_confirmation() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("/delete_1");
+    let user = create_user(&storages, message.chat.id.0).await;
+    let reminder = create_reminder(&storages, &user, "Take out trash").await;
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(message, delete_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_last_text_and_state(
+        "Delete *Take out trash*? This cannot be undone, archive it instead to keep its history\\.",
+        AuthenticatedActionState::DeletingReminder(DeletingReminderState::WaitingForConfirmation(
+            Arc::new(reminder),
+        )),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn given_confirmation_should_delete_and_cancel_reminder() {
+    let storages = Storages::in_memory();
+    let query = MockCallbackQuery::new().data("delete");
+    let user = create_user(&storages, query.from.id.0 as i64).await;
+    let reminder = create_reminder(&storages, &user, "Collect trash").await;
+    let dependent = create_reminder(&storages, &user, "Take out trash").await;
+    let store = storages.reminders.clone();
+    store
+        .set_dependency(
+            &dependent.id,
+            &user.id,
+            Some(ReminderDependency {
+                predecessor_id: reminder.id,
+                delay: TimeDelta::zero(),
+            }),
+        )
+        .await
+        .unwrap();
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(query, delete_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::DeletingReminder(
+        DeletingReminderState::WaitingForConfirmation(Arc::new(reminder.clone())),
+    ))
+    .await;
+
+    bot.dispatch_and_check_last_text_and_state("Reminder deleted.", AuthenticatedActionState::Idle)
+        .await;
+
+    assert_eq!(store.get(&reminder.id, &user.id).await.unwrap(), None);
+    let dependent = store.get(&dependent.id, &user.id).await.unwrap().unwrap();
+    assert_eq!(dependent.dependency, None);
+    assert_eq!(
+        scheduler.calls(),
+        vec![
+            SchedulerCall::Cancel(reminder.id),
+            SchedulerCall::Cancel(dependent.id),
+            SchedulerCall::Schedule(dependent.id)
+        ]
+    );
+}
+
+#[tokio::test]
+async fn given_keep_should_leave_reminder() {
+    let storages = Storages::in_memory();
+    let query = MockCallbackQuery::new().data("keep");
+    let user = create_user(&storages, query.from.id.0 as i64).await;
+    let reminder = create_reminder(&storages, &user, "Take out trash").await;
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(query, delete_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::DeletingReminder(
+        DeletingReminderState::WaitingForConfirmation(Arc::new(reminder.clone())),
+    ))
+    .await;
+
+    bot.dispatch_and_check_last_text_and_state("Reminder kept.", AuthenticatedActionState::Idle)
+        .await;
+
+    assert!(
+        storages
+            .reminders
+            .get(&reminder.id, &user.id)
+            .await
+            .unwrap()
+            .is_some()
+    );
+    assert!(scheduler.calls().is_empty());
+}
+
+#[tokio::test]
+async fn given_archive_should_hide_and_cancel_reminder() {
+    let storages = Storages::in_memory();
+    let query = MockCallbackQuery::new().data("archive");
+    let user = create_user(&storages, query.from.id.0 as i64).await;
+    let reminder = create_reminder(&storages, &user, "Take out trash").await;
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(query, delete_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::DeletingReminder(
+        DeletingReminderState::WaitingForConfirmation(Arc::new(reminder.clone())),
+    ))
+    .await;
+
+    bot.dispatch_and_check_last_text_and_state(
+        "Reminder archived. Use /archive to bring it back.",
+        AuthenticatedActionState::Idle,
+    )
+    .await;
+
+    let store = storages.reminders;
+    assert_eq!(store.get(&reminder.id, &user.id).await.unwrap(), None);
+    let archived = store.get_archived_user_reminders(&user.id).await.unwrap();
+    assert_eq!(archived, vec![reminder.clone()]);
+    assert_eq!(scheduler.calls(), vec![SchedulerCall::Cancel(reminder.id)]);
+}
@@ -0,0 +1,58 @@
+use nadoeda_models::chrono_tz;
+use nadoeda_models::user::Language;
+use nadoeda_storage::{NewUser, Storages};
+use teloxide::{
+    dispatching::{
+        UpdateHandler,
+        dialogue::{self, InMemStorage},
+    },
+    dptree::deps,
+};
+use teloxide_tests::{MockBot, MockMessageText};
+
+use crate::ui::digest_mode::schema;
+use crate::ui::*;
+
+fn digest_schema() -> UpdateHandler<anyhow::Error> {
+    dialogue::enter::<Update, InMemStorage<AuthenticatedActionState>, AuthenticatedActionState, _>()
+        .branch(schema())
+}
+
+#[tokio::test]
+async fn given_digest_command_should_toggle_digest_mode() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("/digest");
+    let user = storages
+        .users
+        .create(NewUser {
+            timezone: chrono_tz::Tz::Europe__Prague,
+            tg_chat_id: Some(message.chat.id.0),
+            language: Language::English,
+        })
+        .await
+        .unwrap();
+
+    let mut bot = MockBot::new(message, digest_schema());
+    bot.dependencies(deps![
+        storages.users.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_last_text(
+        "Digest mode is on. Reminders firing together will arrive in one message.",
+    )
+    .await;
+
+    let stored = storages.users.get(&user.id).await.unwrap();
+    assert!(stored.unwrap().digest_mode);
+
+    bot.dispatch_and_check_last_text(
+        "Digest mode is off. Every reminder will arrive in its own message.",
+    )
+    .await;
+
+    let stored = storages.users.get(&user.id).await.unwrap();
+    assert!(!stored.unwrap().digest_mode);
+}
@@ -0,0 +1,212 @@
+use std::sync::Arc;
+
+use nadoeda_models::chrono::{NaiveTime, TimeDelta};
+use nadoeda_models::reminder::{Reminder, ReminderFireTime};
+use nadoeda_models::{
+    chrono_tz,
+    user::{Language, User},
+};
+use nadoeda_storage::{NewReminder, NewUser, Storages};
+use teloxide::{
+    dispatching::{
+        UpdateHandler,
+        dialogue::{self, InMemStorage},
+    },
+    dptree::deps,
+};
+use teloxide_tests::{MockBot, MockMessageText};
+
+use crate::ui::edit_reminders::{EditingRemindersState, schema};
+use crate::ui::*;
+
+async fn create_reminder(storages: &Storages, chat_id: i64) -> (User, Reminder) {
+    let user = storages
+        .users
+        .create(NewUser {
+            timezone: chrono_tz::Tz::Europe__Prague,
+            tg_chat_id: Some(chat_id),
+            language: Language::English,
+        })
+        .await
+        .unwrap();
+
+    let reminder = storages
+        .reminders
+        .insert(NewReminder {
+            text: "Stretch".to_string(),
+            fire_at: ReminderFireTime::new(NaiveTime::from_hms_opt(12, 0, 0).unwrap()),
+            user_id: user.id,
+        })
+        .await
+        .unwrap();
+
+    (user, reminder)
+}
+
+fn edit_schema() -> UpdateHandler<anyhow::Error> {
+    dialogue::enter::<Update, InMemStorage<AuthenticatedActionState>, AuthenticatedActionState, _>()
+        .branch(schema())
+}
+
+#[tokio::test]
+async fn given_time_window_should_save_window() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("14:00-17:00");
+    let (user, reminder) = create_reminder(&storages, message.chat.id.0).await;
+
+    let mut bot = MockBot::new(message, edit_schema());
+    bot.dependencies(deps![
+        storages.reminders.clone(),
+        storages.exclusions.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    bot.set_state(AuthenticatedActionState::EditingReminder(
+        EditingRemindersState::WaitingForTime(Arc::new(reminder.clone())),
+    ))
+    .await;
+
+    bot.dispatch_and_check_last_text_and_state(
+        "Reminder updated, new window: *14:00* \\- *17:00*",
+        AuthenticatedActionState::Idle,
+    )
+    .await;
+
+    let reminder = storages
+        .reminders
+        .get(&reminder.id, &user.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(reminder.fire_window_end.is_some());
+    assert_eq!(reminder.fire_window(), TimeDelta::hours(3));
+}
+
+#[tokio::test]
+async fn given_windowed_reminder_list_should_show_window() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("/listreminders");
+    let (user, mut reminder) = create_reminder(&storages, message.chat.id.0).await;
+    let timezone = user.timezone;
+    let local = |h| {
+        ReminderFireTime::new_utc_from_local(NaiveTime::from_hms_opt(h, 0, 0).unwrap(), timezone)
+            .unwrap()
+    };
+    reminder.fire_at = local(14);
+    reminder.fire_window_end = Some(local(17));
+    storages.reminders.update(reminder, &user.id).await.unwrap();
+
+    let mut bot = MockBot::new(message, edit_schema());
+    bot.dependencies(deps![
+        storages.reminders.clone(),
+        storages.exclusions.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch().await;
+
+    let responses = bot.get_responses();
+    let text = responses.sent_messages.last().unwrap().text().unwrap();
+    assert!(
+        text.contains("remind every day between *14:00* and *17:00*"),
+        "Unexpected list: {text}"
+    );
+}
+
+#[tokio::test]
+async fn given_natural_time_should_save_time() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("every day at 7:30pm");
+    let (user, reminder) = create_reminder(&storages, message.chat.id.0).await;
+
+    let mut bot = MockBot::new(message, edit_schema());
+    bot.dependencies(deps![
+        storages.reminders.clone(),
+        storages.exclusions.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    bot.set_state(AuthenticatedActionState::EditingReminder(
+        EditingRemindersState::WaitingForTime(Arc::new(reminder.clone())),
+    ))
+    .await;
+
+    bot.dispatch_and_check_last_text_and_state(
+        "Reminder updated, new time: every day at *19:30*",
+        AuthenticatedActionState::Idle,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn given_weekly_time_should_explain_interpretation() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("every weekday at 8");
+    let (user, reminder) = create_reminder(&storages, message.chat.id.0).await;
+
+    let mut bot = MockBot::new(message, edit_schema());
+    bot.dependencies(deps![
+        storages.reminders.clone(),
+        storages.exclusions.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    let state = AuthenticatedActionState::EditingReminder(EditingRemindersState::WaitingForTime(
+        Arc::new(reminder.clone()),
+    ));
+    bot.set_state(state.clone()).await;
+
+    bot.dispatch_and_check_last_text_and_state(
+        "I understood *every weekday at 08:00*, but only daily reminders are supported yet\\. Please send a time of day, for example *13:00*, *7:30pm* or *every day at 8*",
+        state,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn given_reminder_changed_meanwhile_should_reload_and_ask_again() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("Stretch twice");
+    let (user, outdated) = create_reminder(&storages, message.chat.id.0).await;
+    let current = storages
+        .reminders
+        .update(
+            Reminder {
+                text: "Stretch and breathe".to_string(),
+                ..outdated.clone()
+            },
+            &user.id,
+        )
+        .await
+        .unwrap();
+
+    let mut bot = MockBot::new(message, edit_schema());
+    bot.dependencies(deps![
+        storages.reminders.clone(),
+        storages.exclusions.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    bot.set_state(AuthenticatedActionState::EditingReminder(
+        EditingRemindersState::WaitingForText(Arc::new(outdated.clone())),
+    ))
+    .await;
+
+    bot.dispatch_and_check_last_text_and_state(
+        "This reminder was changed while you were editing it, so nothing was saved. What do you want to update?",
+        AuthenticatedActionState::EditingReminder(EditingRemindersState::WaitingForFieldSelection(
+            Arc::new(current.clone()),
+        )),
+    )
+    .await;
+
+    let stored = storages
+        .reminders
+        .get(&outdated.id, &user.id)
+        .await
+        .unwrap();
+    assert_eq!(stored, Some(current));
+}
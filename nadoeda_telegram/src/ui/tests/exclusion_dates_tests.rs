@@ -0,0 +1,172 @@
+use chrono::NaiveDate;
+use nadoeda_storage::{NewExclusionDate, Storages};
+use teloxide::{
+    dispatching::{
+        UpdateHandler,
+        dialogue::{self, InMemStorage},
+    },
+    dptree::deps,
+};
+use teloxide_tests::{MockBot, MockMessageText};
+
+use crate::ui::exclusion_dates::ExcludingDatesState;
+use crate::ui::*;
+
+use crate::ui::tests::test_utils::create_user;
+
+fn exclusion_schema() -> UpdateHandler<anyhow::Error> {
+    dialogue::enter::<Update, InMemStorage<AuthenticatedActionState>, AuthenticatedActionState, _>()
+        .branch(exclusion_dates::schema())
+        .branch(edit_reminders::schema())
+}
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+#[tokio::test]
+async fn given_exclude_command_should_wait_for_dates() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("/exclude");
+    let user = create_user(&storages, message.chat.id.0).await;
+
+    let mut bot = MockBot::new(message, exclusion_schema());
+    bot.dependencies(deps![
+        storages.reminders.clone(),
+        storages.exclusions.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_state(AuthenticatedActionState::ExcludingDates(
+        ExcludingDatesState::WaitingForDates,
+    ))
+    .await;
+}
+
+#[tokio::test]
+async fn given_dates_should_store_them_and_list_them() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("2099-12-24 Christmas Eve\n2099-12-25");
+    let user = create_user(&storages, message.chat.id.0).await;
+
+    let mut bot = MockBot::new(message, exclusion_schema());
+    bot.dependencies(deps![
+        storages.reminders.clone(),
+        storages.exclusions.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    bot.set_state(AuthenticatedActionState::ExcludingDates(
+        ExcludingDatesState::WaitingForDates,
+    ))
+    .await;
+
+    bot.dispatch_and_check_last_text_and_state(
+        "Reminders will skip 2 more day(s).",
+        AuthenticatedActionState::Idle,
+    )
+    .await;
+
+    let store = storages.exclusions.clone();
+    assert!(
+        store
+            .is_excluded(&user.id, date(2099, 12, 24))
+            .await
+            .unwrap()
+    );
+    assert!(
+        store
+            .is_excluded(&user.id, date(2099, 12, 25))
+            .await
+            .unwrap()
+    );
+    assert!(
+        !store
+            .is_excluded(&user.id, date(2099, 12, 26))
+            .await
+            .unwrap()
+    );
+
+    bot.update(MockMessageText::new().text("/listreminders"));
+    bot.dispatch().await;
+
+    let responses = bot.get_responses();
+    let text = responses.sent_messages.last().unwrap().text().unwrap();
+    assert!(
+        text.contains("Skipped days:\n• *2099\\-12\\-24* Christmas Eve\n• *2099\\-12\\-25*"),
+        "Unexpected list: {text}"
+    );
+}
+
+#[tokio::test]
+async fn given_invalid_date_should_keep_waiting() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("2099-12-24\nnext friday");
+    let user = create_user(&storages, message.chat.id.0).await;
+
+    let mut bot = MockBot::new(message, exclusion_schema());
+    bot.dependencies(deps![
+        storages.reminders.clone(),
+        storages.exclusions.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    bot.set_state(AuthenticatedActionState::ExcludingDates(
+        ExcludingDatesState::WaitingForDates,
+    ))
+    .await;
+
+    bot.dispatch_and_check_last_text_and_state(
+        "I don't understand \"next friday\". Please use YYYY-MM-DD, for example 2025-12-24.",
+        AuthenticatedActionState::ExcludingDates(ExcludingDatesState::WaitingForDates),
+    )
+    .await;
+
+    let store = storages.exclusions.clone();
+    assert!(
+        !store
+            .is_excluded(&user.id, date(2099, 12, 24))
+            .await
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn given_include_command_should_remove_date() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("/include 2099-12-24");
+    let user = create_user(&storages, message.chat.id.0).await;
+    storages
+        .exclusions
+        .add(
+            &user.id,
+            vec![NewExclusionDate {
+                date: date(2099, 12, 24),
+                label: None,
+            }],
+        )
+        .await
+        .unwrap();
+
+    let mut bot = MockBot::new(message, exclusion_schema());
+    bot.dependencies(deps![
+        storages.reminders.clone(),
+        storages.exclusions.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_last_text("Reminders will fire on 2099-12-24 again.")
+        .await;
+
+    let store = storages.exclusions.clone();
+    assert!(
+        !store
+            .is_excluded(&user.id, date(2099, 12, 24))
+            .await
+            .unwrap()
+    );
+}
@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use nadoeda_models::chrono::TimeDelta;
+use nadoeda_models::reminder::ReminderDependency;
+use nadoeda_storage::Storages;
+use teloxide::dispatching::{
+    UpdateHandler,
+    dialogue::{self, InMemStorage},
+};
+use teloxide_tests::{MockBot, MockCallbackQuery, MockMessageText};
+
+use crate::ui::link_reminders::{LinkingRemindersState, schema};
+use crate::ui::*;
+
+use crate::ui::tests::test_utils::*;
+
+fn link_schema() -> UpdateHandler<anyhow::Error> {
+    dialogue::enter::<Update, InMemStorage<AuthenticatedActionState>, AuthenticatedActionState, _>()
+        .branch(schema())
+}
+
+#[tokio::test]
+async fn given_after_command_should_ask_for_predecessor() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("/after_2");
+    let user = create_user(&storages, message.chat.id.0).await;
+    create_reminder(&storages, &user, "Collect trash").await;
+    let reminder = create_reminder(&storages, &user, "Take out trash").await;
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(message, link_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_state(AuthenticatedActionState::LinkingReminders(
+        LinkingRemindersState::WaitingForPredecessor(Arc::new(reminder)),
+    ))
+    .await;
+}
+
+#[tokio::test]
+async fn given_predecessor_should_ask_for_delay() {
+    let storages = Storages::in_memory();
+    let query = MockCallbackQuery::new();
+    let user = create_user(&storages, query.from.id.0 as i64).await;
+    let predecessor = create_reminder(&storages, &user, "Collect trash").await;
+    let reminder = Arc::new(create_reminder(&storages, &user, "Take out trash").await);
+    let query = query.data(predecessor.id.to_string());
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(query, link_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::LinkingReminders(
+        LinkingRemindersState::WaitingForPredecessor(reminder.clone()),
+    ))
+    .await;
+
+    bot.dispatch_and_check_state(AuthenticatedActionState::LinkingReminders(
+        LinkingRemindersState::WaitingForDelay {
+            reminder,
+            predecessor_id: predecessor.id,
+        },
+    ))
+    .await;
+}
+
+#[tokio::test]
+async fn given_delay_should_link_and_reschedule_reminder() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("15");
+    let user = create_user(&storages, message.chat.id.0).await;
+    let predecessor = create_reminder(&storages, &user, "Collect trash").await;
+    let reminder = create_reminder(&storages, &user, "Take out trash").await;
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(message, link_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::LinkingReminders(
+        LinkingRemindersState::WaitingForDelay {
+            reminder: Arc::new(reminder.clone()),
+            predecessor_id: predecessor.id,
+        },
+    ))
+    .await;
+
+    bot.dispatch_and_check_last_text_and_state(
+        "*Take out trash* will fire 15 minutes after *Collect trash* is confirmed\\.",
+        AuthenticatedActionState::Idle,
+    )
+    .await;
+
+    let linked = storages
+        .reminders
+        .get(&reminder.id, &user.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        linked.dependency,
+        Some(ReminderDependency {
+            predecessor_id: predecessor.id,
+            delay: TimeDelta::minutes(15),
+        })
+    );
+    assert_eq!(
+        scheduler.calls(),
+        vec![
+            SchedulerCall::Cancel(reminder.id),
+            SchedulerCall::Schedule(reminder.id)
+        ]
+    );
+}
+
+#[tokio::test]
+async fn given_cycle_should_refuse_to_link() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("0");
+    let user = create_user(&storages, message.chat.id.0).await;
+    let first = create_reminder(&storages, &user, "Collect trash").await;
+    let second = create_reminder(&storages, &user, "Take out trash").await;
+    let third = create_reminder(&storages, &user, "Wash the bin").await;
+    let store = storages.reminders.clone();
+    let dependency = |predecessor_id| {
+        Some(ReminderDependency {
+            predecessor_id,
+            delay: TimeDelta::zero(),
+        })
+    };
+    store
+        .set_dependency(&second.id, &user.id, dependency(first.id))
+        .await
+        .unwrap();
+    store
+        .set_dependency(&third.id, &user.id, dependency(second.id))
+        .await
+        .unwrap();
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(message, link_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::LinkingReminders(
+        LinkingRemindersState::WaitingForDelay {
+            reminder: Arc::new(first.clone()),
+            predecessor_id: third.id,
+        },
+    ))
+    .await;
+
+    bot.dispatch_and_check_last_text(
+        "These reminders already wait for each other. Pick another one.",
+    )
+    .await;
+
+    let first = store.get(&first.id, &user.id).await.unwrap().unwrap();
+    assert_eq!(first.dependency, None);
+    assert!(scheduler.calls().is_empty());
+}
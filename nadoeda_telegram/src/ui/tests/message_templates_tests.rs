@@ -0,0 +1,136 @@
+use std::sync::Arc;
+
+use nadoeda_models::template::TemplateKind;
+use nadoeda_models::user::User;
+use nadoeda_storage::Storages;
+use teloxide::{
+    dispatching::{
+        UpdateHandler,
+        dialogue::{self, InMemStorage},
+    },
+    dptree::deps,
+};
+use teloxide_tests::{MockBot, MockCallbackQuery, MockMessageText};
+
+use crate::ui::message_templates::{CustomizingTextsState, schema};
+use crate::ui::*;
+
+use crate::ui::tests::test_utils::{create_reminder, create_user};
+
+fn templates_schema() -> UpdateHandler<anyhow::Error> {
+    dialogue::enter::<Update, InMemStorage<AuthenticatedActionState>, AuthenticatedActionState, _>()
+        .branch(schema())
+}
+
+fn dependencies(
+    bot: &mut MockBot<anyhow::Error, teloxide_tests::mock_bot::DistributionKey>,
+    storages: &Storages,
+    user: User,
+) {
+    bot.dependencies(deps![
+        storages.reminders.clone(),
+        storages.templates.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+}
+
+#[tokio::test]
+async fn given_kind_should_ask_for_texts() {
+    let storages = Storages::in_memory();
+    let query = MockCallbackQuery::new().data("nag");
+    let user = create_user(&storages, query.from.id.0 as i64).await;
+    let reminder = Arc::new(create_reminder(&storages, &user, "Take pills").await);
+
+    let mut bot = MockBot::new(query, templates_schema());
+    dependencies(&mut bot, &storages, user);
+    bot.set_state(AuthenticatedActionState::CustomizingTexts(
+        CustomizingTextsState::WaitingForKind(reminder.clone()),
+    ))
+    .await;
+
+    bot.dispatch_and_check_state(AuthenticatedActionState::CustomizingTexts(
+        CustomizingTextsState::WaitingForTexts {
+            reminder,
+            kind: TemplateKind::Nag,
+        },
+    ))
+    .await;
+}
+
+#[tokio::test]
+async fn given_texts_should_save_one_variant_per_line() {
+    let storages = Storages::in_memory();
+    let message =
+        MockMessageText::new().text("Time for {text}\n\nStill waiting, attempt {attempt}");
+    let user = create_user(&storages, message.chat.id.0).await;
+    let reminder = create_reminder(&storages, &user, "Take pills").await;
+
+    let mut bot = MockBot::new(message, templates_schema());
+    dependencies(&mut bot, &storages, user);
+    bot.set_state(AuthenticatedActionState::CustomizingTexts(
+        CustomizingTextsState::WaitingForTexts {
+            reminder: Arc::new(reminder.clone()),
+            kind: TemplateKind::Nag,
+        },
+    ))
+    .await;
+
+    bot.dispatch_and_check_last_text_and_state("Saved 2 text(s).", AuthenticatedActionState::Idle)
+        .await;
+
+    let texts: Vec<String> = storages
+        .templates
+        .get_for_reminder(&reminder.id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|template| template.text)
+        .collect();
+    assert_eq!(
+        texts,
+        vec!["Time for {text}", "Still waiting, attempt {attempt}"]
+    );
+}
+
+#[tokio::test]
+async fn given_dash_should_reset_texts() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("-");
+    let user = create_user(&storages, message.chat.id.0).await;
+    let reminder = create_reminder(&storages, &user, "Take pills").await;
+    let store = storages.templates.clone();
+    store
+        .set(
+            &reminder.id,
+            &user.id,
+            TemplateKind::Fired,
+            vec!["Pills!".to_string()],
+        )
+        .await
+        .unwrap();
+
+    let mut bot = MockBot::new(message, templates_schema());
+    dependencies(&mut bot, &storages, user);
+    bot.set_state(AuthenticatedActionState::CustomizingTexts(
+        CustomizingTextsState::WaitingForTexts {
+            reminder: Arc::new(reminder.clone()),
+            kind: TemplateKind::Fired,
+        },
+    ))
+    .await;
+
+    bot.dispatch_and_check_last_text_and_state(
+        "The default text is back.",
+        AuthenticatedActionState::Idle,
+    )
+    .await;
+
+    assert!(
+        store
+            .get_for_reminder(&reminder.id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
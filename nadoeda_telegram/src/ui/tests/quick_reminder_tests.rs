@@ -0,0 +1,204 @@
+use nadoeda_models::chrono::NaiveTime;
+use nadoeda_models::reminder::ReminderFireTime;
+use nadoeda_models::{ chrono_tz};
+use nadoeda_storage::{NewReminder, Storages};
+use teloxide::dispatching::{
+    UpdateHandler,
+    dialogue::{self, InMemStorage},
+};
+use teloxide_tests::{MockBot, MockCallbackQuery, MockMessageText};
+
+use crate::ui::quick_reminder::schema;
+use crate::ui::*;
+
+use crate::ui::tests::test_utils::*;
+
+fn quick_schema() -> UpdateHandler<anyhow::Error> {
+    dialogue::enter::<Update, InMemStorage<AuthenticatedActionState>, AuthenticatedActionState, _>()
+        .branch(schema())
+}
+
+#[tokio::test]
+async fn given_time_and_text_should_create_and_schedule_reminder() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("/remind 08:30 take pills");
+    let user = create_user_in(&storages, message.chat.id.0, chrono_tz::Tz::UTC).await;
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(message, quick_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_last_text_and_state(
+        "Reminder *take pills* is set for every day at 08:30\\.",
+        AuthenticatedActionState::Idle,
+    )
+    .await;
+
+    let reminders = storages
+        .reminders
+        .get_all_user_reminders(&user.id)
+        .await
+        .unwrap();
+    assert_eq!(reminders.len(), 1);
+    assert_eq!(reminders[0].text, "take pills");
+    assert_eq!(
+        *reminders[0].fire_at.time(),
+        NaiveTime::from_hms_opt(8, 30, 0).unwrap()
+    );
+    assert_eq!(
+        scheduler.calls(),
+        vec![SchedulerCall::Schedule(reminders[0].id)]
+    );
+}
+
+#[tokio::test]
+async fn given_date_should_say_reminder_repeats_daily() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("/remind tomorrow 9:00 call mom");
+    let user = create_user_in(&storages, message.chat.id.0, chrono_tz::Tz::UTC).await;
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(message, quick_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_last_text(
+        "Reminder *call mom* is set for tomorrow at 09:00, then every day\\.",
+    )
+    .await;
+
+    let reminders = storages
+        .reminders
+        .get_all_user_reminders(&user.id)
+        .await
+        .unwrap();
+    assert_eq!(reminders.len(), 1);
+    assert_eq!(
+        scheduler.calls(),
+        vec![SchedulerCall::Schedule(reminders[0].id)]
+    );
+}
+
+#[tokio::test]
+async fn given_weekly_schedule_should_not_create_reminder() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("/remind every weekday at 8 stretch");
+    let user = create_user_in(&storages, message.chat.id.0, chrono_tz::Tz::UTC).await;
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(message, quick_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_last_text(
+        "I understood *every weekday at 08:00*, but only daily reminders are supported yet\\. Please send a time of day, for example *13:00*, *7:30pm* or *every day at 8*",
+    )
+    .await;
+
+    let reminders = storages
+        .reminders
+        .get_all_user_reminders(&user.id)
+        .await
+        .unwrap();
+    assert!(reminders.is_empty());
+    assert!(scheduler.calls().is_empty());
+}
+
+#[tokio::test]
+async fn given_text_without_time_should_explain_usage() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("/remind take pills");
+    let user = create_user_in(&storages, message.chat.id.0, chrono_tz::Tz::UTC).await;
+
+    let mut bot = MockBot::new(message, quick_schema());
+    dependencies(
+        &mut bot,
+        &storages,
+        user,
+        &RecordingReminderScheduler::default(),
+    );
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_last_text(
+        "Please use /remind TIME TEXT, for example /remind 08:30 take pills or /remind tomorrow 9:00 call mom.",
+    )
+    .await;
+
+    let reminders = storages
+        .reminders
+        .get_all_user_reminders(&user.id)
+        .await
+        .unwrap();
+    assert!(reminders.is_empty());
+}
+
+#[tokio::test]
+async fn given_undo_should_remove_reminder() {
+    let storages = Storages::in_memory();
+    let query = MockCallbackQuery::new();
+    let user = create_user_in(&storages, query.from.id.0 as i64, chrono_tz::Tz::UTC).await;
+    let reminder = storages
+        .reminders
+        .insert(NewReminder {
+            text: "take pills".to_string(),
+            fire_at: ReminderFireTime::new(NaiveTime::from_hms_opt(8, 30, 0).unwrap()),
+            user_id: user.id,
+        })
+        .await
+        .unwrap();
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(
+        query.data(format!("remind_undo_{}", reminder.id)),
+        quick_schema(),
+    );
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_last_text("Reminder removed.").await;
+
+    let stored = storages
+        .reminders
+        .get(&reminder.id, &user.id)
+        .await
+        .unwrap();
+    assert!(stored.is_none());
+    assert_eq!(scheduler.calls(), vec![SchedulerCall::Cancel(reminder.id)]);
+}
+
+#[tokio::test]
+async fn given_undo_for_foreign_reminder_should_keep_it() {
+    let storages = Storages::in_memory();
+    let query = MockCallbackQuery::new();
+    let user = create_user_in(&storages, query.from.id.0 as i64, chrono_tz::Tz::UTC).await;
+    let owner = create_user_in(&storages, query.from.id.0 as i64 + 1, chrono_tz::Tz::UTC).await;
+    let reminder = storages
+        .reminders
+        .insert(NewReminder {
+            text: "take pills".to_string(),
+            fire_at: ReminderFireTime::new(NaiveTime::from_hms_opt(8, 30, 0).unwrap()),
+            user_id: owner.id,
+        })
+        .await
+        .unwrap();
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(
+        query.data(format!("remind_undo_{}", reminder.id)),
+        quick_schema(),
+    );
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_last_text("This reminder is already gone.")
+        .await;
+
+    let stored = storages
+        .reminders
+        .get(&reminder.id, &owner.id)
+        .await
+        .unwrap();
+    assert!(stored.is_some());
+    assert!(scheduler.calls().is_empty());
+}
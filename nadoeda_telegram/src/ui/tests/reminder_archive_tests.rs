@@ -0,0 +1,113 @@
+use nadoeda_models::chrono::{NaiveTime, TimeDelta, Utc};
+use nadoeda_models::reminder::{Reminder, ReminderFireTime};
+use nadoeda_models::user::User;
+use nadoeda_storage::{NewReminder, Storages};
+use teloxide::dispatching::{
+    UpdateHandler,
+    dialogue::{self, InMemStorage},
+};
+use teloxide_tests::{MockBot, MockCallbackQuery, MockMessageText};
+
+use crate::ui::reminder_archive::schema;
+use crate::ui::*;
+
+use crate::ui::tests::test_utils::*;
+
+async fn create_archived_reminder(storages: &Storages, user: &User) -> Reminder {
+    let store = storages.reminders.clone();
+    let reminder = store
+        .insert(NewReminder {
+            text: "Water the plants".to_string(),
+            fire_at: ReminderFireTime::new(NaiveTime::from_hms_opt(18, 0, 0).unwrap()),
+            user_id: user.id,
+        })
+        .await
+        .unwrap();
+    assert!(store.archive(&reminder.id, &user.id).await.unwrap());
+
+    reminder
+}
+
+fn archive_schema() -> UpdateHandler<anyhow::Error> {
+    dialogue::enter::<Update, InMemStorage<AuthenticatedActionState>, AuthenticatedActionState, _>()
+        .branch(schema())
+}
+
+#[tokio::test]
+async fn given_archive_command_should_list_archived_reminders() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("/archive");
+    let user = create_user(&storages, message.chat.id.0).await;
+    create_archived_reminder(&storages, &user).await;
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(message, archive_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch().await;
+
+    let responses = bot.get_responses();
+    let sent = responses.sent_messages.last().unwrap();
+    assert_eq!(
+        sent.text(),
+        Some("Archived reminders, tap one to bring it back:")
+    );
+    let buttons = sent.reply_markup().unwrap();
+    assert_eq!(
+        buttons.inline_keyboard[0][0].text,
+        "Restore: Water the plants"
+    );
+}
+
+#[tokio::test]
+async fn given_restore_should_bring_reminder_back_and_schedule_it() {
+    let storages = Storages::in_memory();
+    let query = MockCallbackQuery::new();
+    let user = create_user(&storages, query.from.id.0 as i64).await;
+    let reminder = create_archived_reminder(&storages, &user).await;
+    let query = query.data(format!("archive_restore_{}", reminder.id));
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(query, archive_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_last_text("Reminder *Water the plants* is back\\.")
+        .await;
+
+    let store = storages.reminders;
+    assert!(store.get(&reminder.id, &user.id).await.unwrap().is_some());
+    assert_eq!(
+        scheduler.calls(),
+        vec![SchedulerCall::Schedule(reminder.id)]
+    );
+}
+
+#[tokio::test]
+async fn given_old_archive_should_purge_it() {
+    let storages = Storages::in_memory();
+    let user = create_user(&storages, 1).await;
+    let reminder = create_archived_reminder(&storages, &user).await;
+    let store = storages.reminders;
+
+    let purged = store
+        .purge_archived(Utc::now() - TimeDelta::days(1))
+        .await
+        .unwrap();
+    assert_eq!(purged, 0);
+
+    let purged = store
+        .purge_archived(Utc::now() + TimeDelta::seconds(1))
+        .await
+        .unwrap();
+    assert_eq!(purged, 1);
+    assert!(
+        store
+            .get_archived_user_reminders(&user.id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(store.get(&reminder.id, &user.id).await.unwrap(), None);
+}
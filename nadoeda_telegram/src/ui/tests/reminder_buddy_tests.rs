@@ -0,0 +1,156 @@
+use nadoeda_models::buddy::{BuddyInvitationStatus, BuddyTarget};
+use nadoeda_models::chrono::NaiveTime;
+use nadoeda_models::reminder::{ReminderFireTime, ReminderId};
+use nadoeda_models::{
+    chrono_tz,
+    user::{Language, User},
+};
+use nadoeda_storage::{NewReminder, NewUser, Storages};
+use teloxide::{
+    dispatching::{
+        UpdateHandler,
+        dialogue::{self, InMemStorage},
+    },
+    dptree::deps,
+};
+use teloxide_tests::{MockBot, MockMessageText};
+
+use crate::ui::reminder_buddy::{InvitationAnswer, schema};
+use crate::ui::*;
+
+#[test]
+fn invitation_answers_are_parsed_from_callback_data() {
+    assert_eq!(
+        InvitationAnswer::parse("buddy_accept_42"),
+        Some(InvitationAnswer {
+            buddy_id: 42,
+            accepted: true
+        })
+    );
+    assert_eq!(
+        InvitationAnswer::parse("buddy_decline_7"),
+        Some(InvitationAnswer {
+            buddy_id: 7,
+            accepted: false
+        })
+    );
+    assert_eq!(InvitationAnswer::parse("42"), None);
+    assert_eq!(InvitationAnswer::parse("buddy_accept_"), None);
+}
+
+async fn create_user_with_reminder(storages: &Storages, chat_id: i64) -> (User, ReminderId) {
+    let user = storages
+        .users
+        .create(NewUser {
+            timezone: chrono_tz::Tz::Europe__Prague,
+            tg_chat_id: Some(chat_id),
+            language: Language::English,
+        })
+        .await
+        .unwrap();
+
+    let reminder = storages
+        .reminders
+        .insert(NewReminder {
+            text: "Take out trash".to_string(),
+            fire_at: ReminderFireTime::new(NaiveTime::from_hms_opt(12, 0, 0).unwrap()),
+            user_id: user.id,
+        })
+        .await
+        .unwrap();
+
+    (user, reminder.id)
+}
+
+fn buddy_schema() -> UpdateHandler<anyhow::Error> {
+    dialogue::enter::<Update, InMemStorage<AuthenticatedActionState>, AuthenticatedActionState, _>()
+        .branch(schema())
+}
+
+#[tokio::test]
+async fn given_buddy_command_should_ask_for_buddy_chat() {
+    let storages = Storages::in_memory();
+    let mock_message = MockMessageText::new().text("/buddy_1");
+    let (user, reminder_id) = create_user_with_reminder(&storages, mock_message.chat.id.0).await;
+
+    let mut bot = MockBot::new(mock_message, buddy_schema());
+    bot.dependencies(deps![
+        storages.reminders.clone(),
+        storages.users.clone(),
+        storages.buddies.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_state(AuthenticatedActionState::AssigningBuddy(
+        AssigningBuddyState::WaitingForBuddyChat(reminder_id),
+    ))
+    .await;
+}
+
+#[tokio::test]
+async fn given_buddy_chat_id_should_store_pending_invitation() {
+    let storages = Storages::in_memory();
+    let buddy_chat_id = 424242;
+    let mock_message = MockMessageText::new().text(buddy_chat_id.to_string());
+    let (user, reminder_id) = create_user_with_reminder(&storages, mock_message.chat.id.0).await;
+
+    let mut bot = MockBot::new(mock_message, buddy_schema());
+    bot.dependencies(deps![
+        storages.reminders.clone(),
+        storages.users.clone(),
+        storages.buddies.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    bot.set_state(AuthenticatedActionState::AssigningBuddy(
+        AssigningBuddyState::WaitingForBuddyChat(reminder_id),
+    ))
+    .await;
+
+    bot.dispatch().await;
+
+    let buddy = storages
+        .buddies
+        .get_for_reminder(&reminder_id)
+        .await
+        .unwrap()
+        .expect("Invitation should be stored");
+
+    assert_eq!(buddy.target, BuddyTarget::TelegramChat(buddy_chat_id));
+    assert_eq!(buddy.status, BuddyInvitationStatus::Pending);
+    assert!(
+        bot.try_get_state::<AuthenticatedActionState>()
+            .await
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn given_own_chat_id_should_keep_waiting_for_buddy() {
+    let storages = Storages::in_memory();
+    let mock_message = MockMessageText::new();
+    let own_chat_id = mock_message.chat.id.0;
+    let mock_message = mock_message.text(own_chat_id.to_string());
+    let (user, reminder_id) = create_user_with_reminder(&storages, own_chat_id).await;
+
+    let mut bot = MockBot::new(mock_message, buddy_schema());
+    bot.dependencies(deps![
+        storages.reminders.clone(),
+        storages.users.clone(),
+        storages.buddies.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    let state = AuthenticatedActionState::AssigningBuddy(AssigningBuddyState::WaitingForBuddyChat(
+        reminder_id,
+    ));
+    bot.set_state(state.clone()).await;
+
+    bot.dispatch_and_check_last_text_and_state(
+        "You can't be your own buddy. Please try again.",
+        state,
+    )
+    .await;
+}
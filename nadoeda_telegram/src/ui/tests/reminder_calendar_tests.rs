@@ -0,0 +1,159 @@
+use std::sync::Arc;
+
+use nadoeda_models::chrono::NaiveTime;
+use nadoeda_models::reminder::ReminderFireTime;
+use nadoeda_models::{chrono_tz, user::User};
+use nadoeda_storage::{NewReminder, Storages};
+use teloxide::dispatching::{
+    UpdateHandler,
+    dialogue::{self, InMemStorage},
+};
+use teloxide_tests::{MockBot, MockCallbackQuery, MockMessageDocument, MockMessageText};
+
+use crate::ui::reminder_calendar::{ImportingRemindersState, schema};
+use crate::ui::*;
+
+use crate::ui::tests::test_utils::*;
+
+fn calendar_schema() -> UpdateHandler<anyhow::Error> {
+    dialogue::enter::<Update, InMemStorage<AuthenticatedActionState>, AuthenticatedActionState, _>()
+        .branch(schema())
+}
+
+fn new_reminder(user: &User, text: &str) -> NewReminder {
+    NewReminder {
+        text: text.to_string(),
+        fire_at: ReminderFireTime::new(NaiveTime::from_hms_opt(8, 0, 0).unwrap()),
+        user_id: user.id,
+    }
+}
+
+#[tokio::test]
+async fn given_export_command_should_send_calendar() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("/export");
+    let user = create_user_in(&storages, message.chat.id.0, chrono_tz::Tz::UTC).await;
+    storages
+        .reminders
+        .insert(new_reminder(&user, "Stretch"))
+        .await
+        .unwrap();
+
+    let mut bot = MockBot::new(message, calendar_schema());
+    dependencies(
+        &mut bot,
+        &storages,
+        user,
+        &RecordingReminderScheduler::default(),
+    );
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch().await;
+
+    let responses = bot.get_responses();
+    let document = &responses.sent_messages_document.last().unwrap().bot_request;
+    assert_eq!(document.file_name, "nadoeda.ics");
+    assert!(document.file_data.contains("SUMMARY:Stretch"));
+    assert!(document.file_data.contains("RRULE:FREQ=DAILY"));
+}
+
+#[tokio::test]
+async fn given_other_document_should_ask_for_calendar() {
+    let storages = Storages::in_memory();
+    let message = MockMessageDocument::new().file_name("notes.txt");
+    let user = create_user_in(&storages, message.chat.id.0, chrono_tz::Tz::UTC).await;
+
+    let mut bot = MockBot::new(message, calendar_schema());
+    dependencies(
+        &mut bot,
+        &storages,
+        user,
+        &RecordingReminderScheduler::default(),
+    );
+    bot.set_state(AuthenticatedActionState::ImportingReminders(
+        ImportingRemindersState::WaitingForCalendar,
+    ))
+    .await;
+
+    bot.dispatch_and_check_last_text_and_state(
+        "Please upload an .ics calendar file.",
+        AuthenticatedActionState::ImportingReminders(ImportingRemindersState::WaitingForCalendar),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn given_confirmed_import_should_create_and_schedule_reminders() {
+    let storages = Storages::in_memory();
+    let query = MockCallbackQuery::new().data("import_confirm");
+    let user = create_user_in(&storages, query.from.id.0 as i64, chrono_tz::Tz::UTC).await;
+    let scheduler = RecordingReminderScheduler::default();
+
+    let mut bot = MockBot::new(query, calendar_schema());
+    dependencies(&mut bot, &storages, user, &scheduler);
+    bot.set_state(AuthenticatedActionState::ImportingReminders(
+        ImportingRemindersState::WaitingForConfirmation(Arc::new(vec![
+            new_reminder(&user, "Vitamins"),
+            new_reminder(&user, "Plan the day"),
+        ])),
+    ))
+    .await;
+
+    bot.dispatch_and_check_last_text_and_state(
+        "Imported 2 reminder(s).",
+        AuthenticatedActionState::Idle,
+    )
+    .await;
+
+    let mut reminders = storages
+        .reminders
+        .get_all_user_reminders(&user.id)
+        .await
+        .unwrap();
+    reminders.sort_by_key(|reminder| reminder.id);
+    assert_eq!(
+        reminders
+            .iter()
+            .map(|reminder| reminder.text.as_str())
+            .collect::<Vec<_>>(),
+        vec!["Vitamins", "Plan the day"]
+    );
+    assert_eq!(
+        scheduler.calls(),
+        reminders
+            .iter()
+            .map(|reminder| SchedulerCall::Schedule(reminder.id))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[tokio::test]
+async fn given_cancelled_import_should_create_nothing() {
+    let storages = Storages::in_memory();
+    let query = MockCallbackQuery::new().data("import_cancel");
+    let user = create_user_in(&storages, query.from.id.0 as i64, chrono_tz::Tz::UTC).await;
+
+    let mut bot = MockBot::new(query, calendar_schema());
+    dependencies(
+        &mut bot,
+        &storages,
+        user,
+        &RecordingReminderScheduler::default(),
+    );
+    bot.set_state(AuthenticatedActionState::ImportingReminders(
+        ImportingRemindersState::WaitingForConfirmation(Arc::new(vec![new_reminder(
+            &user, "Vitamins",
+        )])),
+    ))
+    .await;
+
+    bot.dispatch_and_check_last_text_and_state("Import cancelled.", AuthenticatedActionState::Idle)
+        .await;
+
+    let reminders = storages
+        .reminders
+        .get_all_user_reminders(&user.id)
+        .await
+        .unwrap();
+    assert!(reminders.is_empty());
+}
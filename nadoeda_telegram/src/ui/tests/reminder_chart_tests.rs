@@ -0,0 +1,143 @@
+use nadoeda_models::chrono::{NaiveTime, TimeDelta, Utc};
+use nadoeda_models::occurrence::OccurrenceOutcome;
+use nadoeda_models::reminder::{Reminder, ReminderFireTime};
+use nadoeda_models::{
+    chrono_tz,
+    user::{Language, User},
+};
+use nadoeda_storage::{NewReminder, NewReminderOccurrence, NewUser, Storages};
+use teloxide::{
+    dispatching::{
+        UpdateHandler,
+        dialogue::{self, InMemStorage},
+    },
+    dptree::deps,
+};
+use teloxide_tests::{MockBot, MockMessageText};
+
+use crate::ui::reminder_chart::schema;
+use crate::ui::*;
+
+async fn create_user_with_reminder(storages: &Storages, chat_id: i64) -> (User, Reminder) {
+    let user = storages
+        .users
+        .create(NewUser {
+            timezone: chrono_tz::Tz::Europe__Prague,
+            tg_chat_id: Some(chat_id),
+            language: Language::English,
+        })
+        .await
+        .unwrap();
+
+    let reminder = storages
+        .reminders
+        .insert(NewReminder {
+            text: "Water the plants".to_string(),
+            fire_at: ReminderFireTime::new(NaiveTime::from_hms_opt(12, 0, 0).unwrap()),
+            user_id: user.id,
+        })
+        .await
+        .unwrap();
+
+    (user, reminder)
+}
+
+async fn record_acknowledged_occurrence(storages: &Storages, reminder: &Reminder, days_ago: i64) {
+    let occurrences = storages.occurrences.clone();
+    let scheduled_at = Utc::now() - TimeDelta::days(days_ago);
+    let mut occurrence = occurrences
+        .insert(NewReminderOccurrence {
+            reminder_id: reminder.id,
+            user_id: reminder.user_id,
+            scheduled_at,
+        })
+        .await
+        .unwrap();
+
+    occurrence.fired_at = Some(scheduled_at);
+    occurrence.acknowledged_at = Some(scheduled_at + TimeDelta::minutes(days_ago));
+    occurrence.outcome = Some(OccurrenceOutcome::Confirmed);
+    occurrences
+        .update(occurrence, &reminder.user_id)
+        .await
+        .unwrap();
+}
+
+fn chart_schema() -> UpdateHandler<anyhow::Error> {
+    dialogue::enter::<Update, InMemStorage<AuthenticatedActionState>, AuthenticatedActionState, _>()
+        .branch(schema())
+}
+
+#[tokio::test]
+async fn given_chart_command_should_send_heatmap_of_all_reminders() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("/chart");
+    let (user, reminder) = create_user_with_reminder(&storages, message.chat.id.0).await;
+    record_acknowledged_occurrence(&storages, &reminder, 1).await;
+
+    let mut bot = MockBot::new(message, chart_schema());
+    bot.dependencies(deps![
+        storages.reminders.clone(),
+        storages.occurrences.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch().await;
+
+    let photos = bot.get_responses().sent_messages_photo;
+    assert_eq!(photos.len(), 1);
+    assert_eq!(photos[0].bot_request.file_name, "heatmap.png");
+    assert_eq!(
+        photos[0].message.caption(),
+        Some("Completion of all reminders")
+    );
+}
+
+#[tokio::test]
+async fn given_reminder_chart_command_should_send_heatmap_and_ack_times() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("/chart_1");
+    let (user, reminder) = create_user_with_reminder(&storages, message.chat.id.0).await;
+    record_acknowledged_occurrence(&storages, &reminder, 2).await;
+    record_acknowledged_occurrence(&storages, &reminder, 1).await;
+
+    let mut bot = MockBot::new(message, chart_schema());
+    bot.dependencies(deps![
+        storages.reminders.clone(),
+        storages.occurrences.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch().await;
+
+    let file_names: Vec<String> = bot
+        .get_responses()
+        .sent_messages_photo
+        .into_iter()
+        .map(|photo| photo.bot_request.file_name)
+        .collect();
+    assert_eq!(file_names, vec!["heatmap.png", "ack_times.png"]);
+}
+
+#[tokio::test]
+async fn given_unknown_reminder_chart_command_should_reply_with_error() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("/chart_42");
+    let (user, _) = create_user_with_reminder(&storages, message.chat.id.0).await;
+
+    let mut bot = MockBot::new(message, chart_schema());
+    bot.dependencies(deps![
+        storages.reminders.clone(),
+        storages.occurrences.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_last_text("Invalid Chart command. Please try again.")
+        .await;
+}
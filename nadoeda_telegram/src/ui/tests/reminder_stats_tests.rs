@@ -0,0 +1,97 @@
+use nadoeda_models::chrono::{TimeDelta, Utc};
+use nadoeda_models::occurrence::OccurrenceOutcome;
+use nadoeda_models::reminder::Reminder;
+use nadoeda_storage::{NewReminderOccurrence, Storages};
+use teloxide::{
+    dispatching::{
+        UpdateHandler,
+        dialogue::{self, InMemStorage},
+    },
+    dptree::deps,
+};
+use teloxide_tests::{MockBot, MockMessageText};
+
+use crate::ui::reminder_stats::schema;
+use crate::ui::*;
+
+use crate::ui::tests::test_utils::{create_reminder, create_user};
+
+async fn record_occurrence(
+    storages: &Storages,
+    reminder: &Reminder,
+    days_ago: i64,
+    outcome: OccurrenceOutcome,
+) {
+    let occurrences = storages.occurrences.clone();
+    let scheduled_at = Utc::now() - TimeDelta::days(days_ago);
+    let mut occurrence = occurrences
+        .insert(NewReminderOccurrence {
+            reminder_id: reminder.id,
+            user_id: reminder.user_id,
+            scheduled_at,
+        })
+        .await
+        .unwrap();
+
+    occurrence.fired_at = Some(scheduled_at);
+    occurrence.acknowledged_at = Some(scheduled_at + TimeDelta::seconds(90));
+    occurrence.outcome = Some(outcome);
+    occurrences
+        .update(occurrence, &reminder.user_id)
+        .await
+        .unwrap();
+}
+
+fn stats_schema() -> UpdateHandler<anyhow::Error> {
+    dialogue::enter::<Update, InMemStorage<AuthenticatedActionState>, AuthenticatedActionState, _>()
+        .branch(schema())
+}
+
+#[tokio::test]
+async fn given_no_reminders_should_ask_to_create_one() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("/stats");
+    let user = create_user(&storages, message.chat.id.0).await;
+
+    let mut bot = MockBot::new(message, stats_schema());
+    bot.dependencies(deps![
+        storages.reminders.clone(),
+        storages.stats.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_last_text("You have to create at least one reminder\\!")
+        .await;
+}
+
+#[tokio::test]
+async fn given_history_should_show_streaks_and_rates() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("/stats");
+    let user = create_user(&storages, message.chat.id.0).await;
+    let reminder = create_reminder(&storages, &user, "Water the plants").await;
+
+    record_occurrence(&storages, &reminder, 10, OccurrenceOutcome::Confirmed).await;
+    record_occurrence(&storages, &reminder, 9, OccurrenceOutcome::Confirmed).await;
+    record_occurrence(&storages, &reminder, 3, OccurrenceOutcome::TimedOut).await;
+    record_occurrence(&storages, &reminder, 2, OccurrenceOutcome::Confirmed).await;
+
+    let mut bot = MockBot::new(message, stats_schema());
+    bot.dependencies(deps![
+        storages.reminders.clone(),
+        storages.stats.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_last_text(
+        "1: *Water the plants*
+Streak: *1* \\(best *2*\\)
+Done: *50% \\(1/2\\)* last 7 days, *75% \\(3/4\\)* last 30 days
+Average time to acknowledge: *1m 30s*",
+    )
+    .await;
+}
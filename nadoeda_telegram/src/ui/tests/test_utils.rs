@@ -1,31 +1,90 @@
 use std::sync::{
-    Arc,
+    Arc, Mutex,
     atomic::{AtomicBool, Ordering},
 };
 
 use anyhow::Error;
 use async_trait::async_trait;
+use nadoeda_models::{
+    chrono::NaiveTime,
+    chrono_tz,
+    reminder::{Reminder, ReminderFireTime, ReminderId},
+    user::{Language, User},
+};
 use nadoeda_scheduler::{ReminderScheduler, ScheduleRequest, ScheduledReminder};
-use nadoeda_storage::sqlite::{
-    reminder_storage::SqliteReminderStorage, user_storage::SqliteUserInfoStorage,
+use nadoeda_storage::{NewReminder, NewUser, Storages};
+use teloxide::{
+    dispatching::{DpHandlerDescription, dialogue::InMemStorage},
+    dptree::{Handler, deps},
+    types::ChatId,
 };
-use sqlx::{Pool, Sqlite};
-use teloxide::{dispatching::DpHandlerDescription, dptree::Handler, types::ChatId};
 use teloxide_tests::{MockBot, MockMessageText, mock_bot::DistributionKey};
 
-use crate::ui::HandlerResult;
+use crate::ui::{AuthenticatedActionState, AuthenticationInfo, HandlerResult};
 
 pub struct NoopReminderScheduler;
 #[async_trait]
 impl ReminderScheduler for NoopReminderScheduler {
     async fn schedule_reminder(
         &self,
-        schedule_request: ScheduleRequest,
+        _schedule_request: ScheduleRequest,
     ) -> anyhow::Result<ScheduledReminder> {
         Ok(ScheduledReminder::new(1))
     }
 
+    async fn cancel_reminder(&self, _scheduled_reminder: &ScheduledReminder) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn acknowledge_reminder(
+        &self,
+        _scheduled_reminder: &ScheduledReminder,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn confirm_reminder(
+        &self,
+        _scheduled_reminder: &ScheduledReminder,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchedulerCall {
+    Schedule(ReminderId),
+    Cancel(ReminderId),
+    Acknowledge(ReminderId),
+    Confirm(ReminderId),
+}
+
+#[derive(Clone, Default)]
+pub struct RecordingReminderScheduler(Arc<Mutex<Vec<SchedulerCall>>>);
+
+impl RecordingReminderScheduler {
+    pub fn calls(&self) -> Vec<SchedulerCall> {
+        self.0.lock().unwrap().clone()
+    }
+
+    fn record(&self, call: SchedulerCall) {
+        self.0.lock().unwrap().push(call);
+    }
+}
+
+#[async_trait]
+impl ReminderScheduler for RecordingReminderScheduler {
+    async fn schedule_reminder(
+        &self,
+        schedule_request: ScheduleRequest,
+    ) -> anyhow::Result<ScheduledReminder> {
+        let id = schedule_request.reminder.id;
+        self.record(SchedulerCall::Schedule(id));
+        Ok(ScheduledReminder::new(id))
+    }
+
     async fn cancel_reminder(&self, scheduled_reminder: &ScheduledReminder) -> anyhow::Result<()> {
+        self.record(SchedulerCall::Cancel(scheduled_reminder.id));
         Ok(())
     }
 
@@ -33,10 +92,12 @@ impl ReminderScheduler for NoopReminderScheduler {
         &self,
         scheduled_reminder: &ScheduledReminder,
     ) -> anyhow::Result<()> {
+        self.record(SchedulerCall::Acknowledge(scheduled_reminder.id));
         Ok(())
     }
 
     async fn confirm_reminder(&self, scheduled_reminder: &ScheduledReminder) -> anyhow::Result<()> {
+        self.record(SchedulerCall::Confirm(scheduled_reminder.id));
         Ok(())
     }
 }
@@ -63,14 +124,6 @@ pub async fn call_marker_endpoint(called: CallMarker) -> HandlerResult {
     Ok(())
 }
 
-pub fn storage(pool: Pool<Sqlite>) -> Arc<SqliteReminderStorage> {
-    Arc::new(SqliteReminderStorage::new(pool))
-}
-
-pub fn user_storage(pool: Pool<Sqlite>) -> Arc<SqliteUserInfoStorage> {
-    Arc::new(SqliteUserInfoStorage::new(pool))
-}
-
 pub fn bot(
     msg_text: &str,
     schema: Handler<'static, Result<(), Error>, DpHandlerDescription>,
@@ -81,3 +134,47 @@ pub fn bot(
 
     (bot, chat_id)
 }
+
+pub async fn create_user(storages: &Storages, chat_id: i64) -> User {
+    create_user_in(storages, chat_id, chrono_tz::Tz::Europe__Prague).await
+}
+
+pub async fn create_user_in(storages: &Storages, chat_id: i64, timezone: chrono_tz::Tz) -> User {
+    storages
+        .users
+        .create(NewUser {
+            timezone,
+            tg_chat_id: Some(chat_id),
+            language: Language::English,
+        })
+        .await
+        .unwrap()
+}
+
+pub async fn create_reminder(storages: &Storages, user: &User, text: &str) -> Reminder {
+    storages
+        .reminders
+        .insert(NewReminder {
+            text: text.to_string(),
+            fire_at: ReminderFireTime::new(NaiveTime::from_hms_opt(12, 0, 0).unwrap()),
+            user_id: user.id,
+        })
+        .await
+        .unwrap()
+}
+
+/// Provides the reminder storage and the scheduler to handlers run by an authenticated user.
+pub fn dependencies(
+    bot: &mut MockBot<anyhow::Error, DistributionKey>,
+    storages: &Storages,
+    user: User,
+    scheduler: &RecordingReminderScheduler,
+) {
+    let scheduler: Arc<dyn ReminderScheduler> = Arc::new(scheduler.clone());
+    bot.dependencies(deps![
+        storages.reminders.clone(),
+        scheduler,
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+}
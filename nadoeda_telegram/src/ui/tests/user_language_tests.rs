@@ -0,0 +1,78 @@
+use nadoeda_models::user::{Language, User};
+use nadoeda_storage::Storages;
+use teloxide::{
+    dispatching::{
+        UpdateHandler,
+        dialogue::{self, InMemStorage, Storage},
+    },
+    dptree::deps,
+};
+use teloxide_tests::{MockBot, MockMessageText};
+
+use crate::ui::user_language::schema;
+use crate::ui::*;
+
+use crate::ui::tests::test_utils::create_user;
+
+fn language_schema() -> UpdateHandler<anyhow::Error> {
+    dialogue::enter::<Update, InMemStorage<AuthenticatedActionState>, AuthenticatedActionState, _>()
+        .branch(schema())
+}
+
+#[tokio::test]
+async fn given_language_command_should_store_language_and_refresh_state() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("/language ru");
+    let chat_id = message.chat.id;
+    let user = create_user(&storages, chat_id.0).await;
+    let global_storage = InMemStorage::<GlobalState>::new();
+
+    let mut bot = MockBot::new(message, language_schema());
+    bot.dependencies(deps![
+        storages.users.clone(),
+        global_storage.clone(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_last_text("Теперь я говорю по-русски.")
+        .await;
+
+    let stored = storages.users.get(&user.id).await.unwrap();
+    assert_eq!(stored.unwrap().language, Language::Russian);
+
+    let state = global_storage.get_dialogue(chat_id).await.unwrap();
+    assert_eq!(
+        state,
+        Some(GlobalState::AuthenticatedV2(
+            AuthenticationInfo(User {
+                language: Language::Russian,
+                ..user
+            }),
+            AuthenticatedActionState::Idle,
+        ))
+    );
+}
+
+#[tokio::test]
+async fn given_unknown_language_should_show_usage() {
+    let storages = Storages::in_memory();
+    let message = MockMessageText::new().text("/language klingon");
+    let user = create_user(&storages, message.chat.id.0).await;
+
+    let mut bot = MockBot::new(message, language_schema());
+    bot.dependencies(deps![
+        storages.users.clone(),
+        InMemStorage::<GlobalState>::new(),
+        InMemStorage::<AuthenticatedActionState>::new(),
+        AuthenticationInfo(user)
+    ]);
+    bot.set_state(AuthenticatedActionState::Idle).await;
+
+    bot.dispatch_and_check_last_text("Please use /language en or /language ru.")
+        .await;
+
+    let stored = storages.users.get(&user.id).await.unwrap();
+    assert_eq!(stored.unwrap().language, Language::English);
+}
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use dptree::case;
+use nadoeda_models::user::Language;
+use nadoeda_storage::DynUserInfoStorage;
+use teloxide::dispatching::dialogue::InMemStorage;
+use teloxide::{dispatching::UpdateHandler, filter_command, macros::BotCommands, prelude::*};
+
+use crate::i18n::t;
+
+use super::{
+    AuthenticatedActionState, AuthenticationInfo, GlobalDialogue, GlobalState, HandlerResult,
+};
+
+#[derive(BotCommands, Clone)]
+#[command(rename_rule = "lowercase")]
+enum LanguageCommand {
+    Language(String),
+}
+
+async fn change_language(
+    code: String,
+    msg: Message,
+    bot: Bot,
+    auth: AuthenticationInfo,
+    user_store: Arc<DynUserInfoStorage>,
+    global_storage: Arc<InMemStorage<GlobalState>>,
+) -> HandlerResult {
+    let Some(language) = Language::from_code(code.trim()) else {
+        bot.send_message(msg.chat.id, t!(auth.0.language, "language-usage"))
+            .await?;
+        return Ok(());
+    };
+
+    let Some(mut user) = user_store.get(&auth.0.id).await? else {
+        return Ok(());
+    };
+
+    user.language = language;
+    let user = user_store.update(user).await?;
+
+    // Handlers read the language from the authenticated state, which still holds the old user.
+    GlobalDialogue::new(global_storage, msg.chat.id)
+        .update(GlobalState::AuthenticatedV2(
+            AuthenticationInfo(user),
+            AuthenticatedActionState::Idle,
+        ))
+        .await?;
+
+    bot.send_message(msg.chat.id, t!(language, "language-changed"))
+        .await?;
+
+    Ok(())
+}
+
+pub(super) fn schema() -> UpdateHandler<anyhow::Error> {
+    case![AuthenticatedActionState::Idle].branch(
+        Update::filter_message().branch(
+            filter_command::<LanguageCommand, _>()
+                .branch(case![LanguageCommand::Language(code)].endpoint(change_language)),
+        ),
+    )
+}
@@ -1,12 +1,28 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc};
+use nadoeda_models::chrono_tz::Tz;
+use nadoeda_models::reminder::ReminderFireTime;
+use nadoeda_models::user::Language;
+use nadoeda_storage::DynUserInfoStorage;
 use teloxide::{
     Bot,
     dptree::{Handler, HandlerDescription},
+    net::Download,
     payloads::EditMessageReplyMarkupSetters,
+    prelude::Requester,
     sugar::bot::BotMessagesExt,
-    types::{CallbackQuery, InlineKeyboardMarkup, MaybeInaccessibleMessage, Message},
+    types::{
+        CallbackQuery, ChatId, Document, InlineKeyboardButtonKind, InlineKeyboardMarkup,
+        MaybeInaccessibleMessage, Message,
+    },
+    utils::markdown,
 };
 
 use super::AuthenticationInfo;
+use crate::i18n::t;
+use crate::time_parser::{self, ParsedSchedule, Recurrence, TimeParseError};
+
+/// Calendars larger than this are rejected before downloading.
+const MAX_CALENDAR_SIZE: u32 = 1024 * 1024;
 
 pub trait HandlerExtensions<'a, Output, Descr>
 where
@@ -40,9 +56,174 @@ pub fn try_get_message_from_query(query: &CallbackQuery) -> Option<&Message> {
 }
 
 pub async fn clear_message_buttons(bot: &Bot, message: &Message) -> Result<(), anyhow::Error> {
-    bot.edit_reply_markup(message)
-        .reply_markup(InlineKeyboardMarkup::default())
-        .await?;
+    replace_message_buttons(bot, message, InlineKeyboardMarkup::default()).await
+}
+
+pub async fn replace_message_buttons(
+    bot: &Bot,
+    message: &Message,
+    markup: InlineKeyboardMarkup,
+) -> Result<(), anyhow::Error> {
+    bot.edit_reply_markup(message).reply_markup(markup).await?;
 
     Ok(())
 }
+
+/// Removes the button that sent `data`, keeping the rest of the keyboard.
+pub async fn remove_message_button(
+    bot: &Bot,
+    message: &Message,
+    data: &str,
+) -> Result<(), anyhow::Error> {
+    let rows = message
+        .reply_markup()
+        .map(|markup| {
+            markup
+                .inline_keyboard
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .filter(|button| {
+                            !matches!(&button.kind, InlineKeyboardButtonKind::CallbackData(d) if d == data)
+                        })
+                        .cloned()
+                        .collect::<Vec<_>>()
+                })
+                .filter(|row| !row.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    replace_message_buttons(bot, message, InlineKeyboardMarkup::new(rows)).await
+}
+
+/// Language set in the Telegram client of a user that has not registered yet.
+pub fn client_language(from: Option<&teloxide::types::User>) -> Language {
+    from.and_then(|user| user.language_code.as_deref())
+        .and_then(Language::from_code)
+        .unwrap_or_default()
+}
+
+/// Language to answer in for handlers that also run before the user registers.
+pub async fn chat_language(
+    user_store: &DynUserInfoStorage,
+    chat_id: ChatId,
+    from: Option<&teloxide::types::User>,
+) -> anyhow::Result<Language> {
+    let user = user_store.get_by_tg_chat(chat_id.0).await?;
+
+    Ok(user.map_or_else(|| client_language(from), |user| user.language))
+}
+
+/// Downloads an uploaded `.ics` file. The inner error is a message for the user.
+pub async fn download_calendar(
+    bot: &Bot,
+    document: &Document,
+    language: Language,
+) -> Result<Result<String, String>, anyhow::Error> {
+    let is_calendar = document
+        .file_name
+        .as_deref()
+        .is_some_and(|name| name.to_lowercase().ends_with(".ics"));
+    if !is_calendar {
+        return Ok(Err(t!(language, "calendar-ask-file")));
+    }
+
+    if document.file.size > MAX_CALENDAR_SIZE {
+        return Ok(Err(t!(language, "calendar-too-large")));
+    }
+
+    let file = bot.get_file(document.file.id.clone()).await?;
+    let mut content = Vec::new();
+    bot.download_file(&file.path, &mut content).await?;
+
+    Ok(String::from_utf8(content).map_err(|_| t!(language, "calendar-not-utf8")))
+}
+
+/// A daily schedule understood from the user's text. Reminders repeat daily, so a one-off
+/// schedule like `tomorrow 9am` or `in 20 minutes` only postpones the first firing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DailySchedule {
+    pub time: NaiveTime,
+    pub fire_at: ReminderFireTime,
+    pub starts_on: Option<NaiveDate>,
+    /// How the schedule was understood, not escaped.
+    pub description: String,
+}
+
+impl DailySchedule {
+    /// Local midnight of the first day, the reminder must not fire before it.
+    pub fn not_before(&self, timezone: Tz) -> Option<DateTime<Utc>> {
+        let midnight = timezone
+            .from_local_datetime(&self.starts_on?.into())
+            .earliest()?;
+
+        Some(midnight.with_timezone(&Utc))
+    }
+}
+
+/// Converts a local time of day to the stored fire time.
+/// The error is a MarkdownV2 message for the user.
+pub fn local_fire_time(
+    time: NaiveTime,
+    timezone: Tz,
+    language: Language,
+) -> Result<ReminderFireTime, String> {
+    ReminderFireTime::new_utc_from_local(time, timezone).map_err(|_| {
+        t!(
+            language,
+            "time-skipped",
+            time = time.format("%H:%M").to_string()
+        )
+    })
+}
+
+/// Turns a parsed schedule into a daily one, rejecting schedules that repeat on some days only.
+/// The error is a MarkdownV2 message for the user.
+pub fn daily_schedule(
+    schedule: &ParsedSchedule,
+    timezone: Tz,
+    language: Language,
+) -> Result<DailySchedule, String> {
+    let today = Utc::now().with_timezone(&timezone).date_naive();
+    let describe = || schedule.describe(today, language);
+
+    let (starts_on, description) = match &schedule.recurrence {
+        Recurrence::Daily => (None, describe()),
+        Recurrence::Once(date) => (
+            Some(*date),
+            t!(language, "schedule-then-daily", first = describe()),
+        ),
+        Recurrence::Weekly(_) => {
+            return Err(t!(
+                language,
+                "time-not-daily",
+                schedule = markdown::escape(&describe())
+            ));
+        }
+    };
+
+    Ok(DailySchedule {
+        time: schedule.time,
+        fire_at: local_fire_time(schedule.time, timezone, language)?,
+        starts_on,
+        description,
+    })
+}
+
+/// Parses a daily schedule like `13:00`, `7:30pm`, `every day at 8` or `tomorrow 9am`.
+/// The error is a MarkdownV2 message for the user.
+pub fn parse_daily_schedule(
+    text: &str,
+    timezone: Tz,
+    language: Language,
+) -> Result<DailySchedule, String> {
+    let now = Utc::now().with_timezone(&timezone);
+
+    match time_parser::parse_schedule(text, now) {
+        Ok(schedule) => daily_schedule(&schedule, timezone, language),
+        Err(TimeParseError::Unrecognized) => Err(t!(language, "time-unrecognized")),
+        Err(TimeParseError::MissingTime) => Err(t!(language, "time-missing")),
+        Err(TimeParseError::InPast) => Err(t!(language, "time-in-past")),
+    }
+}
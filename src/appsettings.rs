@@ -1,9 +1,8 @@
 use nadoeda_models::settings::Settings;
-use std::sync::OnceLock;
 
 use config::{Config, ConfigError, Environment, File};
 
-fn load_settings() -> Result<Settings, ConfigError> {
+pub fn load() -> Result<Settings, ConfigError> {
     let settings = Config::builder()
         .add_source(File::with_name("appsettings").required(true))
         .add_source(File::with_name("appsettings.local").required(false))
@@ -12,8 +11,3 @@ fn load_settings() -> Result<Settings, ConfigError> {
 
     settings.try_deserialize()
 }
-
-pub fn get() -> &'static Settings {
-    static APPSETTINGS: OnceLock<Settings> = OnceLock::new();
-    APPSETTINGS.get_or_init(|| load_settings().unwrap())
-}
@@ -1,59 +1,95 @@
 mod appsettings;
 
-use std::{error::Error, sync::Arc};
-
-use async_trait::async_trait;
-use nadoeda_delivery_scheduler::DeliveryReminderScheduler;
-use nadoeda_models::reminder::Reminder;
-use nadoeda_scheduler::delivery::{ReminderDeliveryChannel, ReminderMessageType};
-use nadoeda_storage::sqlite::{
-    reminder_storage::SqliteReminderStorage, sqlx::SqlitePool, user_storage::SqliteUserInfoStorage,
+use std::sync::Arc;
+
+use anyhow::Context;
+use nadoeda_delivery_scheduler::{
+    ArchiveRetentionJob, DailyMessageScheduler, DeliveryReminderScheduler, DigestDeliveryChannel,
+    ExclusionCalendar, StorageDailyMessageSource, StorageDigestPreferences,
+    StorageExclusionCalendar, StorageOccurrenceLog,
 };
+use nadoeda_models::{chrono::TimeDelta, settings::DatabaseBackend};
+use nadoeda_scheduler::delivery::ReminderDeliveryChannel;
+#[cfg(feature = "postgres")]
+use nadoeda_storage::postgres;
+use nadoeda_storage::{Storages, sqlite};
 use nadoeda_telegram::delivery::TelegramDeliveryChannel;
 use nadoeda_telegram::{teloxide};
 use nadoeda_telegram::ui::TelegramInteractionInterface;
 
-struct PrinterDeliveryChannel;
-
-#[async_trait]
-impl ReminderDeliveryChannel for PrinterDeliveryChannel {
-    async fn send_reminder_notification(
-        &self,
-        reminder: &Reminder,
-        message: ReminderMessageType,
-    ) -> Result<(), Box<dyn Error>> {
-        println!("{:?} - {:?}", reminder, message);
-        Ok(())
-    }
-}
-
 #[tokio::main]
-async fn main() {
+async fn main() -> anyhow::Result<()> {
     pretty_env_logger::init();
+    let settings = appsettings::load().context("Failed to load the application settings")?;
+
+    let storages = match settings.database.backend() {
+        Some(DatabaseBackend::Sqlite) => {
+            let sqlite_pool = sqlite::connect_and_migrate(&settings.database)
+                .await
+                .context("Failed to prepare the database")?;
+            Storages::sqlite(sqlite_pool)
+        }
+        #[cfg(feature = "postgres")]
+        Some(DatabaseBackend::Postgres) => {
+            let postgres_pool = postgres::connect_and_migrate(&settings.database)
+                .await
+                .context("Failed to prepare the database")?;
+            Storages::postgres(postgres_pool)
+        }
+        #[cfg(not(feature = "postgres"))]
+        Some(DatabaseBackend::Postgres) => {
+            anyhow::bail!("Built without PostgreSQL support, enable the postgres feature")
+        }
+        Some(DatabaseBackend::Memory) => Storages::in_memory(),
+        None => anyhow::bail!("Unsupported database URL {:?}", settings.database.url),
+    };
 
-    let sqlite_pool = SqlitePool::connect("sqlite:///tmp/nadoeda.db")
-        .await
-        .expect("Error creating SQLite pool");
-    let storage: Arc<SqliteReminderStorage> =
-        Arc::new(SqliteReminderStorage::new(sqlite_pool.clone()));
-    let user_storage: Arc<SqliteUserInfoStorage> =
-        Arc::new(SqliteUserInfoStorage::new(sqlite_pool.clone()));
-
-    let bot = teloxide::Bot::new(appsettings::get().telegram.token.clone());
-    let tg_delivery: Arc<dyn ReminderDeliveryChannel> = Arc::new(TelegramDeliveryChannel::new(
-        Arc::clone(&user_storage),
-        bot.clone(),
+    let bot = teloxide::Bot::new(settings.telegram.token.clone());
+    let tg_delivery: Arc<dyn ReminderDeliveryChannel> =
+        Arc::new(TelegramDeliveryChannel::new(&storages, bot.clone()));
+    let digest_delivery: Arc<dyn ReminderDeliveryChannel> = Arc::new(DigestDeliveryChannel::new(
+        tg_delivery,
+        Arc::new(StorageDigestPreferences::new(storages.users.clone())),
     ));
 
-    let scheduler = Arc::new(DeliveryReminderScheduler::new(Arc::clone(&tg_delivery)));
+    let exclusion_calendar: Arc<dyn ExclusionCalendar> = Arc::new(StorageExclusionCalendar::new(
+        storages.users.clone(),
+        storages.exclusions.clone(),
+    ));
+    let scheduler = Arc::new(
+        DeliveryReminderScheduler::new(
+            Arc::clone(&digest_delivery),
+            Arc::new(StorageOccurrenceLog::new(storages.occurrences.clone())),
+        )
+        .with_exclusions(Arc::clone(&exclusion_calendar)),
+    );
+
+    let daily_message_task = DailyMessageScheduler::new(
+        Arc::new(StorageDailyMessageSource::new(
+            storages.users.clone(),
+            storages.reminders.clone(),
+            storages.occurrences.clone(),
+        )),
+        Arc::clone(&digest_delivery),
+    )
+    .with_exclusions(exclusion_calendar)
+    .spawn();
+
+    let retention_task = ArchiveRetentionJob::new(
+        storages.reminders.clone(),
+        TimeDelta::days(settings.archive.retention_days as i64),
+    )
+    .spawn();
 
     let interface_task = tokio::spawn({
-        let storage = storage.clone();
-        let user_storage = user_storage.clone();
         let scheduler = scheduler.clone();
         let bot = bot.clone();
-        async move { TelegramInteractionInterface::start(bot, scheduler, storage, user_storage).await }
+        async move { TelegramInteractionInterface::start(bot, scheduler, storages).await }
     });
 
     interface_task.await.expect("Error in the interface task");
+    daily_message_task.abort();
+    retention_task.abort();
+
+    Ok(())
 }